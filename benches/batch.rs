@@ -0,0 +1,38 @@
+//! Compares classifying a batch of evidence strings on one thread with classifying it on a rayon pool, as
+//! `acmg batch --threads` does.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rayon::prelude::*;
+
+use acmg::{AcmgAssessment, AcmgClassification};
+
+const CODES: [&str; 12] = ["PVS1", "PS1", "PS3", "PM1", "PM2_Supporting", "PM5", "PP1", "PP3", "BS1", "BS3", "BP4", "BP7"];
+
+/// Evidence strings of three codes each, varied deterministically.
+fn batch(rows: usize) -> Vec<String> {
+    (0..rows).map(|row| {
+        (0..3).map(|i| CODES[(row * 7 + i * 5) % CODES.len()]).collect::<Vec<_>>().join(", ")
+    }).collect()
+}
+
+fn classify(evidence: &str) -> Option<AcmgClassification> {
+    evidence.parse::<AcmgAssessment>().ok().map(|assessment| assessment.classify())
+}
+
+fn bench_batch(c: &mut Criterion) {
+    let rows = batch(100_000);
+    let mut group = c.benchmark_group("batch");
+    group.throughput(Throughput::Elements(rows.len() as u64));
+    group.sample_size(10);
+    group.bench_function("sequential", |b| b.iter(|| rows.iter().map(|row| classify(row)).collect::<Vec<_>>()));
+    for threads in [2, 4, 8] {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+        group.bench_with_input(BenchmarkId::new("rayon", threads), &threads, |b, _| {
+            b.iter(|| pool.install(|| rows.par_iter().map(|row| classify(row)).collect::<Vec<_>>()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch);
+criterion_main!(benches);
@@ -0,0 +1,30 @@
+//! Measures the throughput of tokenizing and parsing evidence strings, the per-row hot path of `acmg batch`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use acmg::{normalize_input, parse_evidence, parse_evidence_set};
+
+const EVIDENCE: [&str; 4] = [
+    "PVS1, PM2_Supporting, PP3",
+    "[PS3(PMID:12345; MAVE assay), PM1, pm5_strong, BP7]",
+    "PVS1 PS1 PM2{gnomAD v4 AF=0} PP1_Moderate PP3",
+    "BA1",
+];
+
+fn bench_parse(c: &mut Criterion) {
+    let bytes = EVIDENCE.iter().map(|evidence| evidence.len() as u64).sum();
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(bytes));
+    group.bench_function("normalize_input", |b| {
+        b.iter(|| EVIDENCE.iter().map(|evidence| normalize_input(black_box(evidence)).count()).sum::<usize>())
+    });
+    group.bench_function("parse_evidence_set", |b| {
+        b.iter(|| EVIDENCE.iter().map(|evidence| parse_evidence_set(black_box(evidence)).map(|set| set.len())).collect::<Vec<_>>())
+    });
+    group.throughput(Throughput::Bytes("PM2_Supporting".len() as u64));
+    group.bench_function("parse_evidence", |b| b.iter(|| parse_evidence(black_box("PM2_Supporting"))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+#ifndef ACMG_H
+#define ACMG_H
+
+#include <stdint.h>
+
+/*
+ The outcome of a call to the C API.
+ */
+typedef enum {
+  ACMG_STATUS_OK = 0,
+  /*
+   The evidence could not be parsed or scored, see `acmg_last_error`.
+   */
+  ACMG_STATUS_INVALID_EVIDENCE = 1,
+  /*
+   An argument was null or not UTF-8.
+   */
+  ACMG_STATUS_INVALID_ARGUMENT = 2,
+} AcmgStatus;
+
+/*
+ The five-tier ACMG/AMP variant classification.
+ */
+typedef enum {
+  ACMG_CLASSIFICATION_PATHOGENIC,
+  ACMG_CLASSIFICATION_LIKELY_PATHOGENIC,
+  ACMG_CLASSIFICATION_UNCERTAIN_SIGNIFICANCE,
+  ACMG_CLASSIFICATION_LIKELY_BENIGN,
+  ACMG_CLASSIFICATION_BENIGN,
+} AcmgClassification;
+
+/*
+ The score and classification of an evidence string.
+ */
+typedef struct {
+  int32_t points;
+  AcmgClassification classification;
+  double post_prob_path;
+} AcmgResult;
+
+/*
+ Classifies an evidence string such as `"PVS1, PM2_Supporting"` with the points-based guideline, writing the
+ score and classification to `result`.
+
+ # Safety
+
+ `evidence` must be null or a NUL-terminated string, and `result` null or valid for writes.
+ */
+AcmgStatus acmg_classify(const char *evidence,
+                         AcmgResult *result);
+
+/*
+ The message of the last error on this thread, or null if there hasn't been one. The message is valid until the
+ next failed call on the thread.
+ */
+const char *acmg_last_error(void);
+
+/*
+ The term for a classification, e.g. `"Likely pathogenic"`, as a static string.
+ */
+const char *acmg_classification_term(AcmgClassification classification);
+
+#endif  /* ACMG_H */
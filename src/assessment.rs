@@ -0,0 +1,741 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::guidance::disabled_reason;
+#[cfg(feature = "serde")]
+use crate::evidence::ScoredEvidence;
+use crate::{AlleleClassification, AlleleModel, secondary_findings_gene, SecondaryFindingsGene, spliceai_evidence, GeneCuration, SPLICEAI_BP4, SPLICEAI_PP3, Ba1Exceptions, Regions, check_evidence_with_spec, check_inheritance, Inheritance, PointSystem, AlleleFrequency, GenomicVariant, HgvsVariant, CriteriaSpec, FrequencyCriteria, posterior_classification, Predictor, CaseControl, BayesianModel, Classifier, CodeRegistry, parse_evidence_set, AcmgClassification, AcmgError, Category, Code, Diagnostic, DuplicatePolicy, Evidence, EvidenceSet, EvidenceStrength, Guideline, Rule, VusTemperature};
+
+/// A set of applied evidence which can be scored and classified.
+#[derive(Debug, Default, PartialEq)]
+pub struct AcmgAssessment {
+    evidence: EvidenceSet,
+    guideline: Guideline,
+    classifier: Classifier,
+    model: BayesianModel,
+    point_system: PointSystem,
+    spec: Option<CriteriaSpec>,
+    inheritance: Option<Inheritance>,
+    frequency: Option<AlleleFrequency>,
+    hgvs: Option<HgvsVariant>,
+    variant: Option<GenomicVariant>,
+    spliceai: Option<f64>,
+    gene_curation: Option<GeneCuration>,
+    allele_model: AlleleModel,
+    case_control: Option<CaseControl>,
+    replications: u32,
+    resolved: Vec<Diagnostic>,
+}
+
+impl AcmgAssessment {
+    pub fn new(evidence: EvidenceSet) -> AcmgAssessment {
+        AcmgAssessment { evidence, guideline: Guideline::default(), classifier: Classifier::default(), model: BayesianModel::default(), point_system: PointSystem::default(), spec: None, inheritance: None, frequency: None, hgvs: None, variant: None, spliceai: None, gene_curation: None, allele_model: AlleleModel::default(), case_control: None, replications: 0, resolved: vec![] }
+    }
+
+    /// Parses an evidence string, resolving any criterion given at different strengths with the policy, see
+    /// [`parse_evidence_set_with_policy`]. The warnings explaining each resolution are kept in the diagnostics.
+    pub fn parse_with_policy(acmg_evidence: &str, policy: DuplicatePolicy) -> Result<AcmgAssessment, AcmgError> {
+        AcmgAssessment::parse_with_registry(acmg_evidence, &CodeRegistry::new(), Some(policy))
+    }
+
+    /// Parses an evidence string which may apply the codes registered in the registry, resolving any criterion given
+    /// at different strengths with the policy if given, as [`AcmgAssessment::parse_with_policy`].
+    pub fn parse_with_registry(acmg_evidence: &str, registry: &CodeRegistry, policy: Option<DuplicatePolicy>) -> Result<AcmgAssessment, AcmgError> {
+        match policy {
+            Some(policy) => {
+                let (evidence, resolved) = registry.parse_evidence_set_with_policy(acmg_evidence, policy)?;
+                Ok(AcmgAssessment { resolved, ..AcmgAssessment::new(evidence) })
+            }
+            None => registry.parse_evidence_set(acmg_evidence).map(AcmgAssessment::new),
+        }
+    }
+
+    /// Classifies using the given guideline rather than the default points-based system.
+    pub fn with_guideline(mut self, guideline: Guideline) -> AcmgAssessment {
+        self.guideline = guideline;
+        self
+    }
+
+    pub fn guideline(&self) -> Guideline {
+        self.guideline
+    }
+
+    /// Selects whether the points-based guideline classifies from the points or the posterior probability.
+    pub fn with_classifier(mut self, classifier: Classifier) -> AcmgAssessment {
+        self.classifier = classifier;
+        self
+    }
+
+    pub fn classifier(&self) -> Classifier {
+        self.classifier
+    }
+
+    /// Adjusts the evidence according to a gene-specific criteria specification, see [`CriteriaSpec::apply`].
+    pub fn apply_spec(mut self, spec: &CriteriaSpec) -> Result<AcmgAssessment, AcmgError> {
+        self.evidence = spec.apply(&self.evidence)?;
+        self.spec = Some(spec.clone());
+        Ok(self)
+    }
+
+    /// Fails with an [`AcmgError::DisabledCode`] if any evidence uses one of the codes, explaining why if the SVI
+    /// retired it.
+    pub fn check_disabled(&self, codes: &[Code]) -> Result<(), AcmgError> {
+        match codes.iter().find(|code| self.evidence.iter().any(|evidence| evidence.evidence_code == code.evidence_code())) {
+            Some(code) => Err(AcmgError::DisabledCode { code: code.to_string(), reason: disabled_reason(*code).to_string() }),
+            None => Ok(()),
+        }
+    }
+
+    /// Removes the evidence using any of the codes rather than scoring it, with a [`Rule::DisabledCode`] warning
+    /// for each explaining why.
+    pub fn without_codes(mut self, codes: &[Code]) -> AcmgAssessment {
+        for code in codes {
+            let evidence_code = code.evidence_code();
+            let removed: Vec<String> = self.evidence.iter().filter(|evidence| evidence.evidence_code == evidence_code).map(|evidence| evidence.to_string()).collect();
+            if removed.is_empty() {
+                continue;
+            }
+            self.evidence = self.evidence.iter().filter(|evidence| evidence.evidence_code != evidence_code).cloned().collect();
+            self.resolved.push(Diagnostic::new(Rule::DisabledCode,
+                                               format!("{} was not scored as {} is disabled: {}", removed.join(", "), code, disabled_reason(*code)),
+                                               removed));
+        }
+        self
+    }
+
+    /// Leaves BA1 unscored with a warning if the variant, by its coordinates or HGVS description, is on the BA1
+    /// exception list, as those variants are common yet pathogenic. The variant must have been recorded first.
+    pub fn with_ba1_exceptions(mut self, exceptions: &Ba1Exceptions) -> AcmgAssessment {
+        let Some(exception) = exceptions.find(self.variant.as_ref(), self.hgvs.as_ref()) else {
+            return self;
+        };
+        let ba1 = Code::BA1.evidence_code();
+        let removed: Vec<String> = self.evidence.iter().filter(|evidence| evidence.evidence_code == ba1).map(|evidence| evidence.to_string()).collect();
+        if !removed.is_empty() {
+            self.evidence = self.evidence.iter().filter(|evidence| evidence.evidence_code != ba1).cloned().collect();
+            self.resolved.push(Diagnostic::new(Rule::Ba1Exception,
+                                               format!("BA1 was not scored as {} {} is on the BA1 exception list: {}", exception.gene, exception.hgvs, exception.reason),
+                                               removed));
+        }
+        self
+    }
+
+    /// Checks the evidence against the mode of inheritance of the disorder in the diagnostics, see
+    /// [`check_inheritance`].
+    pub fn with_inheritance(mut self, inheritance: Inheritance) -> AcmgAssessment {
+        self.inheritance = Some(inheritance);
+        self
+    }
+
+    pub fn inheritance(&self) -> Option<Inheritance> {
+        self.inheritance
+    }
+
+    /// Checks PVS1 against what ClinGen has curated of the gene in the diagnostics, warning if loss of function isn't an
+    /// established mechanism of disease or the gene's relationship to disease isn't, see [`GeneCuration`].
+    pub fn with_gene_curation(mut self, curation: GeneCuration) -> AcmgAssessment {
+        self.gene_curation = Some(curation);
+        self
+    }
+
+    pub fn gene_curation(&self) -> Option<&GeneCuration> {
+        self.gene_curation.as_ref()
+    }
+
+    /// The criteria specification applied to the evidence, if any.
+    pub fn spec(&self) -> Option<&CriteriaSpec> {
+        self.spec.as_ref()
+    }
+
+    /// Adds PP3 or BP4 at the strength calibrated for a computational predictor score, see
+    /// [`Predictor::calibrate`]. Fails if PP3 or BP4 has already been applied by hand.
+    pub fn with_predictor_score(mut self, predictor: Predictor, score: f64) -> Result<AcmgAssessment, AcmgError> {
+        self.check_not_given(&[Code::PP3, Code::BP4], &format!("a {} score", predictor))?;
+        self.evidence.extend(predictor.calibrate(score)?);
+        Ok(self)
+    }
+
+    /// Applies the SpliceAI splicing thresholds to a delta score, see [`spliceai_evidence`]. A score of at least 0.2
+    /// adds PP3 and one of at most 0.1 adds BP4, unless PP3 or BP4 was already applied e.g. from a missense
+    /// predictor. BP4 applied by the curator is kept when the score predicts an impact on splicing, which is reported
+    /// by a [`Rule::SplicePredictionConflict`] warning.
+    pub fn with_spliceai(mut self, delta: f64) -> Result<AcmgAssessment, AcmgError> {
+        let evidence = spliceai_evidence(delta)?;
+        let given = |code: Code| self.evidence.iter().any(|evidence| evidence.evidence_code == code.evidence_code());
+        if let (Some(evidence), false, false) = (evidence, given(Code::PP3), given(Code::BP4)) {
+            self.evidence.insert(evidence.with_note(format!("SpliceAI {}", delta)));
+        }
+        self.spliceai = Some(delta);
+        Ok(self)
+    }
+
+    /// The SpliceAI delta score the splicing thresholds were applied to, if any.
+    pub fn spliceai(&self) -> Option<f64> {
+        self.spliceai
+    }
+
+    /// Adds PS4 at the strength supported by case-control counts, see [`CaseControl::strength`], or nothing if they
+    /// don't support it. Fails if PS4 has already been applied by hand.
+    pub fn with_case_control(mut self, case_control: CaseControl) -> Result<AcmgAssessment, AcmgError> {
+        self.check_not_given(&[Code::PS4], "case-control counts")?;
+        self.evidence.extend(case_control.evidence());
+        self.case_control = Some(case_control);
+        Ok(self)
+    }
+
+    /// Classifies the variant as a low penetrance variant or a risk allele as well, see [`AlleleClassification`].
+    pub fn with_allele_model(mut self, allele_model: AlleleModel) -> AcmgAssessment {
+        self.allele_model = allele_model;
+        self
+    }
+
+    pub fn allele_model(&self) -> AlleleModel {
+        self.allele_model
+    }
+
+    /// Records the number of independent studies replicating the case-control association of a risk allele.
+    pub fn with_replications(mut self, replications: u32) -> AcmgAssessment {
+        self.replications = replications;
+        self
+    }
+
+    /// The classification of a low penetrance variant if it's P or LP, or of a risk allele, under the allele model.
+    pub fn allele_classification(&self) -> Option<AlleleClassification> {
+        match self.allele_model {
+            AlleleModel::Mendelian => None,
+            AlleleModel::LowPenetrance => AlleleClassification::low_penetrance(self.classify()),
+            AlleleModel::RiskAllele => Some(AlleleClassification::risk_allele(self.case_control.as_ref(), self.replications)),
+        }
+    }
+
+    /// Adds PM1 if the variant lies in one of the curated hotspot or critical domain regions, at the strength given for
+    /// the region or otherwise Moderate, noting the region. PM1 applied by hand is kept as it is.
+    pub fn with_pm1_regions(mut self, regions: &Regions, variant: &GenomicVariant) -> AcmgAssessment {
+        let given = self.evidence.iter().any(|evidence| evidence.evidence_code == Code::PM1.evidence_code());
+        if let Some(region) = regions.overlapping(variant).filter(|_| !given) {
+            let strength = region.strength.unwrap_or(EvidenceStrength::Moderate);
+            self.evidence.insert(Evidence::at_strength(Code::PM1, strength).with_note(format!("in {}", region.label())));
+        }
+        self
+    }
+
+    /// Adds BP3 if the variant is an in-frame indel lying entirely in one of the repetitive regions without a known
+    /// function, noting the region. Nothing is added if BP3 or PM4 was applied by hand.
+    pub fn with_repeat_regions(mut self, regions: &Regions, variant: &GenomicVariant) -> AcmgAssessment {
+        let given = self.evidence.iter()
+            .any(|evidence| [Code::BP3, Code::PM4].iter().any(|code| evidence.evidence_code == code.evidence_code()));
+        if let Some(region) = regions.containing(variant).filter(|_| !given && variant.is_in_frame_indel()) {
+            self.evidence.insert(Evidence::from(Code::BP3).with_note(format!("in-frame indel in repeat region {}", region.label())));
+        }
+        self
+    }
+
+    /// Adds the frequency evidence supported by an allele frequency, see [`FrequencyCriteria::evaluate`]. Fails if
+    /// BA1, BS1, BS2 or PM2 has already been applied by hand.
+    pub fn with_frequency(mut self, frequency: AlleleFrequency, criteria: &FrequencyCriteria) -> Result<AcmgAssessment, AcmgError> {
+        self.check_not_given(&[Code::BA1, Code::BS1, Code::BS2, Code::PM2], "an allele frequency")?;
+        self.evidence.extend(criteria.evaluate(&frequency)?);
+        self.frequency = Some(frequency);
+        Ok(self)
+    }
+
+    /// The allele frequency the frequency evidence was derived from, if any.
+    pub fn frequency(&self) -> Option<&AlleleFrequency> {
+        self.frequency.as_ref()
+    }
+
+    fn check_not_given(&self, codes: &[Code], origin: &str) -> Result<(), AcmgError> {
+        let given = self.evidence.iter()
+            .map(|evidence| &evidence.evidence_code)
+            .find(|evidence_code| codes.iter().any(|code| code.evidence_code() == **evidence_code));
+        match given {
+            Some(evidence_code) => Err(AcmgError::DerivedCodeGiven { code: evidence_code.to_string(), origin: origin.to_string() }),
+            None => Ok(()),
+        }
+    }
+
+    /// Records the variant being assessed, so results can be joined back to other tools.
+    pub fn with_hgvs(mut self, hgvs: HgvsVariant) -> AcmgAssessment {
+        self.hgvs = Some(hgvs);
+        self
+    }
+
+    pub fn hgvs(&self) -> Option<&HgvsVariant> {
+        self.hgvs.as_ref()
+    }
+
+    /// The gene of the variant, from its HGVS or otherwise the gene of the criteria specification.
+    pub fn gene(&self) -> Option<&str> {
+        self.hgvs.as_ref().and_then(|hgvs| hgvs.gene.as_deref())
+            .or_else(|| self.spec.as_ref().map(|spec| spec.gene.as_str()))
+            .filter(|gene| !gene.is_empty())
+    }
+
+    /// The ACMG secondary findings gene the variant is in, whose P/LP variants carry different reporting obligations.
+    pub fn secondary_findings(&self) -> Option<SecondaryFindingsGene> {
+        self.gene().and_then(secondary_findings_gene)
+    }
+
+    /// Records the genomic coordinates of the variant being assessed.
+    pub fn with_variant(mut self, variant: GenomicVariant) -> AcmgAssessment {
+        self.variant = Some(variant);
+        self
+    }
+
+    pub fn variant(&self) -> Option<&GenomicVariant> {
+        self.variant.as_ref()
+    }
+
+    /// Calculates the posterior probability with the given model, e.g. using a disease-specific prior.
+    pub fn with_model(mut self, model: BayesianModel) -> AcmgAssessment {
+        self.model = model;
+        self
+    }
+
+    pub fn model(&self) -> &BayesianModel {
+        &self.model
+    }
+
+    /// Scores the evidence with the given point system, e.g. a lab's own points for each strength, rather than the
+    /// points of Tavtigian et al. 2020. Fails if its thresholds aren't in descending order.
+    pub fn with_point_system(mut self, point_system: PointSystem) -> Result<AcmgAssessment, AcmgError> {
+        point_system.validate()?;
+        self.point_system = point_system;
+        Ok(self)
+    }
+
+    pub fn point_system(&self) -> &PointSystem {
+        &self.point_system
+    }
+
+    /// Starts assembling an assessment programmatically, e.g.
+    /// `AcmgAssessment::builder().add(Code::PVS1).add_with_strength(Code::PM2, Strength::Supporting).build()`
+    pub fn builder() -> AcmgAssessmentBuilder {
+        AcmgAssessmentBuilder::default()
+    }
+
+    /// The applied evidence in display order.
+    pub fn evidence(&self) -> impl Iterator<Item = &Evidence> {
+        self.evidence.iter()
+    }
+
+    pub fn evidence_set(&self) -> &EvidenceSet {
+        &self.evidence
+    }
+
+    /// Total ACMG score, the sum of the points of all the applied evidence.
+    pub fn points(&self) -> i32 {
+        self.evidence.total_points_with(&self.point_system)
+    }
+
+    /// Classifies the evidence using the selected guideline.
+    pub fn classify(&self) -> AcmgClassification {
+        match (self.guideline, self.classifier) {
+            (Guideline::Points2020, Classifier::Posterior) => posterior_classification(self.post_prob_path()),
+            (Guideline::Points2020, Classifier::Points) => self.point_system.classify(self.points()),
+            (guideline, _) => guideline.classify(&self.evidence),
+        }
+    }
+
+    /// The ACGS 2020 sub-classification of a variant of uncertain significance, only when using the ACGS
+    /// guideline.
+    pub fn vus_temperature(&self) -> Option<VusTemperature> {
+        if self.guideline == Guideline::Acgs2020 && self.classify() == AcmgClassification::UncertainSignificance {
+            Some(VusTemperature::from_points(self.points()))
+        } else {
+            None
+        }
+    }
+
+    /// The points of each direction of a variant of uncertain significance with pathogenic and benign evidence of at
+    /// least moderate strength, e.g. PS3 and BS1, which labs review differently from a VUS lacking evidence.
+    pub fn conflicting_evidence(&self) -> Option<ConflictingEvidence> {
+        if self.classify() != AcmgClassification::UncertainSignificance {
+            return None;
+        }
+        let significant = |category: Category| self.evidence.iter()
+            .any(|evidence| evidence.evidence_code.category == category && evidence.strength() <= EvidenceStrength::Moderate);
+        if !significant(Category::Pathogenic) || !significant(Category::Benign) {
+            return None;
+        }
+        Some(ConflictingEvidence {
+            pathogenic_points: self.evidence.pathogenic_points_with(&self.point_system),
+            benign_points: self.evidence.benign_points_with(&self.point_system),
+        })
+    }
+
+    /// The classification from the points-based system if it disagrees with the selected guideline or
+    /// classifier. The points-based system is the reference, so nothing is reported when it is selected.
+    pub fn disagreements(&self) -> Vec<Disagreement> {
+        if self.guideline == Guideline::Points2020 && self.classifier == Classifier::Points {
+            return vec![];
+        }
+        let reference = self.point_system.classify(self.points());
+        if reference == self.classify() {
+            return vec![];
+        }
+        vec![Disagreement { method: Guideline::Points2020.to_string(), classification: reference }]
+    }
+
+    /// Posterior probability of pathogenicity.
+    pub fn post_prob_path(&self) -> f64 {
+        self.model.post_prob_path(&self.evidence)
+    }
+
+    /// Problems with the combination of evidence, such as codes which double count the same evidence, along with
+    /// any repeated criteria resolved when parsing.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = self.resolved.clone();
+        diagnostics.extend(check_evidence_with_spec(&self.evidence, self.spec.as_ref()));
+        if let Some(inheritance) = self.inheritance {
+            diagnostics.extend(check_inheritance(&self.evidence, inheritance));
+        }
+        let pvs1 = Code::PVS1.evidence_code();
+        let pvs1: Vec<String> = self.evidence.iter().filter(|evidence| evidence.evidence_code == pvs1).map(|evidence| evidence.to_string()).collect();
+        if let (false, Some(curation)) = (pvs1.is_empty(), &self.gene_curation) {
+            if let Some(concern) = curation.lof_mechanism_concern() {
+                diagnostics.push(Diagnostic::new(Rule::UnestablishedLofMechanism,
+                                                 format!("PVS1 is applied but loss of function isn't an established mechanism of disease: {}", concern),
+                                                 pvs1.clone()));
+            }
+            if let Some(concern) = curation.validity_concern() {
+                diagnostics.push(Diagnostic::new(Rule::LimitedGeneValidity,
+                                                 format!("PVS1 is applied but the gene-disease relationship isn't established: {}", concern),
+                                                 pvs1.clone()));
+            }
+        }
+        if let (false, Some(hgvs), Some(delta)) = (pvs1.is_empty(), &self.hgvs, self.spliceai) {
+            if hgvs.is_canonical_splice_site() && delta <= SPLICEAI_BP4 {
+                diagnostics.push(Diagnostic::new(Rule::SplicePredictionConflict,
+                                                 format!("PVS1 is applied at the canonical splice site {} but SpliceAI {} predicts no impact on splicing", hgvs, delta),
+                                                 pvs1));
+            }
+        }
+        let bp4 = Code::BP4.evidence_code();
+        let bp4: Vec<String> = self.evidence.iter().filter(|evidence| evidence.evidence_code == bp4).map(|evidence| evidence.to_string()).collect();
+        if let (false, Some(delta)) = (bp4.is_empty(), self.spliceai.filter(|delta| *delta >= SPLICEAI_PP3)) {
+            diagnostics.push(Diagnostic::new(Rule::SplicePredictionConflict,
+                                             format!("BP4 is applied but SpliceAI {} predicts an impact on splicing, which would support PP3", delta),
+                                             bp4));
+        }
+        diagnostics
+    }
+
+    /// Returns an [`AcmgError::Diagnostics`] error if there are any diagnostics for the evidence.
+    pub fn strict(self) -> Result<AcmgAssessment, AcmgError> {
+        let diagnostics = self.diagnostics();
+        if diagnostics.is_empty() { Ok(self) } else { Err(AcmgError::Diagnostics(diagnostics)) }
+    }
+
+    /// A snapshot of the evidence and its scores, suitable for persisting or exchanging.
+    pub fn result(&self) -> AssessmentResult {
+        AssessmentResult {
+            hgvs: self.hgvs.clone(),
+            variant: self.variant.clone(),
+            evidence: self.evidence.iter().cloned().collect(),
+            points: self.points(),
+            pathogenic_points: self.evidence.pathogenic_points_with(&self.point_system),
+            benign_points: self.evidence.benign_points_with(&self.point_system),
+            classification: self.classify(),
+            post_prob_path: self.post_prob_path(),
+            guideline: self.guideline,
+            guideline_version: self.guideline.version().to_string(),
+            classifier: self.classifier,
+            point_system: Some(self.point_system).filter(|system| *system != PointSystem::default()),
+            vus_temperature: self.vus_temperature(),
+            conflicting_evidence: self.conflicting_evidence(),
+            spec: self.spec.as_ref().map(CriteriaSpec::label),
+            inheritance: self.inheritance,
+            frequency: self.frequency.clone(),
+            spliceai: self.spliceai,
+            secondary_findings: self.secondary_findings(),
+            allele_classification: self.allele_classification(),
+            disagreements: self.disagreements(),
+            diagnostics: self.diagnostics(),
+        }
+    }
+}
+
+/// The outcome of scoring an [`AcmgAssessment`]. The points of each criterion are serialized under the point system
+/// scored with.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AssessmentResult {
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub hgvs: Option<HgvsVariant>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub variant: Option<GenomicVariant>,
+    pub evidence: Vec<Evidence>,
+    /// The net score, the sum of the pathogenic and benign points.
+    pub points: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pathogenic_points: i32,
+    /// Zero or less.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub benign_points: i32,
+    pub classification: AcmgClassification,
+    pub post_prob_path: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub guideline: Guideline,
+    /// The version of the guideline, see [`Guideline::version`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub guideline_version: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub classifier: Classifier,
+    /// The point system scored with, if not the default of Tavtigian et al. 2020.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub point_system: Option<PointSystem>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub vus_temperature: Option<VusTemperature>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub conflicting_evidence: Option<ConflictingEvidence>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub spec: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub inheritance: Option<Inheritance>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub frequency: Option<AlleleFrequency>,
+    /// The SpliceAI delta score the splicing thresholds were applied to.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub spliceai: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub secondary_findings: Option<SecondaryFindingsGene>,
+    /// The classification of a low penetrance variant or a risk allele, see [`AcmgAssessment::allele_classification`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub allele_classification: Option<AlleleClassification>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub disagreements: Vec<Disagreement>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AssessmentResult {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        fn optional<S: SerializeStruct, T: serde::Serialize>(state: &mut S, key: &'static str, value: &Option<T>) -> Result<(), S::Error> {
+            match value {
+                Some(value) => state.serialize_field(key, value),
+                None => state.skip_field(key),
+            }
+        }
+
+        let system = self.point_system.unwrap_or_default();
+        let evidence: Vec<ScoredEvidence> = self.evidence.iter().map(|evidence| ScoredEvidence(evidence, &system)).collect();
+        let mut state = serializer.serialize_struct("AssessmentResult", 22)?;
+        optional(&mut state, "hgvs", &self.hgvs)?;
+        optional(&mut state, "variant", &self.variant)?;
+        state.serialize_field("evidence", &evidence)?;
+        state.serialize_field("points", &self.points)?;
+        state.serialize_field("pathogenic_points", &self.pathogenic_points)?;
+        state.serialize_field("benign_points", &self.benign_points)?;
+        state.serialize_field("classification", &self.classification)?;
+        state.serialize_field("post_prob_path", &self.post_prob_path)?;
+        state.serialize_field("guideline", &self.guideline)?;
+        state.serialize_field("guideline_version", &self.guideline_version)?;
+        state.serialize_field("classifier", &self.classifier)?;
+        optional(&mut state, "point_system", &self.point_system)?;
+        optional(&mut state, "vus_temperature", &self.vus_temperature)?;
+        optional(&mut state, "conflicting_evidence", &self.conflicting_evidence)?;
+        optional(&mut state, "spec", &self.spec)?;
+        optional(&mut state, "inheritance", &self.inheritance)?;
+        optional(&mut state, "frequency", &self.frequency)?;
+        optional(&mut state, "spliceai", &self.spliceai)?;
+        optional(&mut state, "secondary_findings", &self.secondary_findings)?;
+        optional(&mut state, "allele_classification", &self.allele_classification)?;
+        optional(&mut state, "disagreements", &Some(&self.disagreements).filter(|disagreements| !disagreements.is_empty()))?;
+        optional(&mut state, "diagnostics", &Some(&self.diagnostics).filter(|diagnostics| !diagnostics.is_empty()))?;
+        state.end()
+    }
+}
+
+/// The points of the pathogenic and of the benign evidence of a variant of uncertain significance with conflicting
+/// evidence, see [`AcmgAssessment::conflicting_evidence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ConflictingEvidence {
+    pub pathogenic_points: i32,
+    /// Zero or less.
+    pub benign_points: i32,
+}
+
+/// E.g. `conflicting evidence, +4 pathogenic and -4 benign points`.
+impl Display for ConflictingEvidence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "conflicting evidence, {:+} pathogenic and {} benign points", self.pathogenic_points, self.benign_points)
+    }
+}
+
+/// A classification from another method which differs from the assessment's classification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Disagreement {
+    pub method: String,
+    pub classification: AcmgClassification,
+}
+
+impl FromStr for AcmgAssessment {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<AcmgAssessment, Self::Err> {
+        parse_evidence_set(s).map(AcmgAssessment::new)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AcmgAssessmentBuilder {
+    evidence: EvidenceSet,
+    guideline: Guideline,
+    classifier: Classifier,
+    model: BayesianModel,
+}
+
+impl AcmgAssessmentBuilder {
+    /// Adds a code at its default strength.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, code: Code) -> AcmgAssessmentBuilder {
+        self.add_evidence(Evidence::from(code))
+    }
+
+    /// Adds a code with a modified strength, e.g. PM2_Supporting.
+    pub fn add_with_strength(self, code: Code, strength: EvidenceStrength) -> AcmgAssessmentBuilder {
+        self.add_evidence(Evidence::new(code.evidence_code(), Some(strength)))
+    }
+
+    pub fn add_evidence(mut self, evidence: Evidence) -> AcmgAssessmentBuilder {
+        self.evidence.insert(evidence);
+        self
+    }
+
+    pub fn guideline(mut self, guideline: Guideline) -> AcmgAssessmentBuilder {
+        self.guideline = guideline;
+        self
+    }
+
+    pub fn classifier(mut self, classifier: Classifier) -> AcmgAssessmentBuilder {
+        self.classifier = classifier;
+        self
+    }
+
+    pub fn model(mut self, model: BayesianModel) -> AcmgAssessmentBuilder {
+        self.model = model;
+        self
+    }
+
+    pub fn build(self) -> AcmgAssessment {
+        AcmgAssessment::new(self.evidence)
+            .with_guideline(self.guideline)
+            .with_classifier(self.classifier)
+            .with_model(self.model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assessment(evidence: &str) -> AcmgAssessment {
+        evidence.parse().unwrap()
+    }
+
+    #[test]
+    fn each_assessment_scores_with_its_own_point_system() {
+        let moderate_3 = PointSystem { moderate: 3, ..PointSystem::TAVTIGIAN_2020 };
+        let pathogenic_12 = PointSystem { pathogenic: 12, ..PointSystem::TAVTIGIAN_2020 };
+        let tavtigian = assessment("PVS1, PM2");
+        let moderate = assessment("PVS1, PM2").with_point_system(moderate_3).unwrap();
+        let stricter = assessment("PVS1, PM2").with_point_system(pathogenic_12).unwrap();
+
+        assert_eq!((tavtigian.points(), tavtigian.classify()), (10, AcmgClassification::Pathogenic));
+        assert_eq!((moderate.points(), moderate.classify()), (11, AcmgClassification::Pathogenic));
+        assert_eq!((stricter.points(), stricter.classify()), (10, AcmgClassification::LikelyPathogenic));
+        assert_eq!(tavtigian.point_system(), &PointSystem::TAVTIGIAN_2020);
+        assert_eq!(assessment("PVS1, PM2").points(), 10);
+    }
+
+    #[test]
+    fn results_record_points_under_the_point_system() {
+        let system = PointSystem { moderate: 3, ..PointSystem::TAVTIGIAN_2020 };
+        let result = assessment("PM1, PM2, BS1").with_point_system(system).unwrap().result();
+        assert_eq!((result.points, result.pathogenic_points, result.benign_points), (2, 6, -4));
+        assert_eq!(result.point_system, Some(system));
+        assert_eq!(assessment("PM1, PM2, BS1").result().point_system, None);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_results_score_each_criterion_under_the_point_system() {
+        let system = PointSystem { moderate: 3, ..PointSystem::TAVTIGIAN_2020 };
+        let result = assessment("PVS1, PM2").with_point_system(system).unwrap().result();
+        let json = serde_json::to_value(&result).unwrap();
+        let points: Vec<i64> = json["evidence"].as_array().unwrap().iter().map(|evidence| evidence["points"].as_i64().unwrap()).collect();
+        assert_eq!(points, vec![8, 3]);
+        assert_eq!(json["points"], 11);
+        assert_eq!(json["point_system"]["moderate"], 3);
+
+        let default = serde_json::to_value(assessment("PVS1, PM2").result()).unwrap();
+        assert_eq!(default["evidence"][1]["points"], 2);
+        assert!(default.get("point_system").is_none() && default.get("diagnostics").is_none());
+        let parsed: AssessmentResult = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, result);
+    }
+
+
+    #[test]
+    fn the_points_reference_uses_the_point_system() {
+        let system = PointSystem { likely_pathogenic: 4, ..PointSystem::TAVTIGIAN_2020 };
+        let richards = assessment("PS3, PP1").with_guideline(Guideline::Acmg2015).with_point_system(system).unwrap();
+        assert_eq!(richards.classify(), AcmgClassification::UncertainSignificance);
+        assert_eq!(richards.disagreements(), vec![Disagreement { method: Guideline::Points2020.to_string(), classification: AcmgClassification::LikelyPathogenic }]);
+    }
+
+    #[test]
+    fn invalid_point_systems_are_rejected() {
+        let rising = PointSystem { supporting: 3, ..PointSystem::TAVTIGIAN_2020 };
+        let overlapping = PointSystem { likely_pathogenic: 10, ..PointSystem::TAVTIGIAN_2020 };
+        assert!(matches!(assessment("PVS1").with_point_system(rising), Err(AcmgError::InvalidParameter(_))));
+        assert!(matches!(assessment("PVS1").with_point_system(overlapping), Err(AcmgError::InvalidParameter(_))));
+    }
+
+    fn codes(assessment: &AcmgAssessment) -> Vec<String> {
+        assessment.evidence.iter().map(|evidence| evidence.to_string()).collect()
+    }
+
+    fn splice_conflicts(assessment: &AcmgAssessment) -> usize {
+        assessment.diagnostics().iter().filter(|diagnostic| diagnostic.rule == Rule::SplicePredictionConflict).count()
+    }
+
+    #[test]
+    fn spliceai_impact_adds_pp3_and_keeps_curated_bp4_with_a_warning() {
+        let added = assessment("PM2_Supporting").with_spliceai(0.5).unwrap();
+        assert_eq!(codes(&added), vec!["PM2_Supporting", "PP3"]);
+        assert_eq!(splice_conflicts(&added), 0);
+
+        let curated = assessment("PM2_Supporting, BP4").with_spliceai(0.2).unwrap();
+        assert_eq!(codes(&curated), vec!["PM2_Supporting", "BP4"]);
+        assert_eq!(splice_conflicts(&curated), 1);
+    }
+
+    #[test]
+    fn spliceai_no_impact_adds_bp4_unless_pp3_or_bp4_is_applied() {
+        let added = assessment("PM2_Supporting").with_spliceai(0.1).unwrap();
+        assert_eq!(codes(&added), vec!["PM2_Supporting", "BP4"]);
+        let missense = assessment("PM2_Supporting, PP3").with_spliceai(0.02).unwrap();
+        assert_eq!(codes(&missense), vec!["PM2_Supporting", "PP3"]);
+        let curated = assessment("PM2_Supporting, BP4").with_spliceai(0.02).unwrap();
+        assert_eq!(codes(&curated), vec!["PM2_Supporting", "BP4"]);
+        assert_eq!(splice_conflicts(&curated), 0);
+    }
+
+    #[test]
+    fn spliceai_between_thresholds_adds_nothing() {
+        let uncertain = assessment("PM2_Supporting").with_spliceai(0.15).unwrap();
+        assert_eq!(codes(&uncertain), vec!["PM2_Supporting"]);
+        let curated = assessment("PM2_Supporting, BP4").with_spliceai(0.15).unwrap();
+        assert_eq!(codes(&curated), vec!["PM2_Supporting", "BP4"]);
+        assert_eq!(splice_conflicts(&curated), 0);
+    }
+}
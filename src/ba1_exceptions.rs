@@ -0,0 +1,87 @@
+use std::io::BufRead;
+
+use crate::{AcmgError, GenomeBuild, GenomicVariant, HgvsVariant};
+
+const BUILTIN_EXCEPTIONS: &str = include_str!("../data/ba1_exceptions.tsv");
+
+/// A variant common enough for BA1 which is nevertheless pathogenic, e.g. HFE c.845G>A (p.Cys282Tyr).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ba1Exception {
+    pub gene: String,
+    pub hgvs: HgvsVariant,
+    pub grch37: Option<GenomicVariant>,
+    pub grch38: Option<GenomicVariant>,
+    /// Why BA1 doesn't apply, e.g. causes hereditary hemochromatosis with reduced penetrance.
+    pub reason: String,
+}
+
+impl Ba1Exception {
+    /// Whether the exception is for the variant, by its coordinates on the build of the variant.
+    pub fn matches_variant(&self, variant: &GenomicVariant) -> bool {
+        let coordinates = match variant.build {
+            GenomeBuild::Grch37 => &self.grch37,
+            GenomeBuild::Grch38 => &self.grch38,
+        };
+        coordinates.as_ref() == Some(variant)
+    }
+
+    /// Whether the exception is for the variant, by its change to the same transcript of any version.
+    pub fn matches_hgvs(&self, hgvs: &HgvsVariant) -> bool {
+        self.hgvs.accession() == hgvs.accession() && self.hgvs.sequence_type == hgvs.sequence_type && self.hgvs.change == hgvs.change
+    }
+}
+
+/// A list of [`Ba1Exception`]s, by default the ClinGen SVI BA1 exception list of Ghosh et al. 2018.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ba1Exceptions {
+    pub exceptions: Vec<Ba1Exception>,
+}
+
+impl Default for Ba1Exceptions {
+    fn default() -> Ba1Exceptions {
+        Ba1Exceptions::from_tsv(BUILTIN_EXCEPTIONS.as_bytes()).expect("the built-in BA1 exception list is valid")
+    }
+}
+
+impl Ba1Exceptions {
+    /// Reads a list from TSV with the columns gene, hgvs, grch37, grch38 and reason, the coordinates given as e.g.
+    /// `6-26092913-G-A` or `.` if not known. Lines starting with `#` are comments.
+    pub fn from_tsv(reader: impl BufRead) -> Result<Ba1Exceptions, AcmgError> {
+        let mut exceptions = vec![];
+        let mut lines = reader.lines().enumerate()
+            .map(|(number, line)| line.map(|line| (number + 1, line)).map_err(|error| AcmgError::InvalidBa1Exceptions(error.to_string())))
+            .filter(|line| !matches!(line, Ok((_, line)) if line.starts_with('#') || line.trim().is_empty()));
+        match lines.next().transpose()? {
+            Some((_, header)) if header.split('\t').map(str::trim).eq(["gene", "hgvs", "grch37", "grch38", "reason"]) => {}
+            _ => return Err(AcmgError::InvalidBa1Exceptions("expected a header of gene, hgvs, grch37, grch38 and reason".to_string())),
+        }
+        for line in lines {
+            let (number, line) = line?;
+            let invalid = |reason: String| AcmgError::InvalidBa1Exceptions(format!("line {}: {}", number, reason));
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let [gene, hgvs, grch37, grch38, reason] = fields[..] else {
+                return Err(invalid("expected 5 columns".to_string()));
+            };
+            let coordinates = |coordinates: &str, build: GenomeBuild| match coordinates {
+                "." | "" => Ok(None),
+                coordinates => GenomicVariant::parse(coordinates, build).map(Some).map_err(|error| invalid(error.to_string())),
+            };
+            exceptions.push(Ba1Exception {
+                gene: gene.to_string(),
+                hgvs: hgvs.parse().map_err(|error: AcmgError| invalid(error.to_string()))?,
+                grch37: coordinates(grch37, GenomeBuild::Grch37)?,
+                grch38: coordinates(grch38, GenomeBuild::Grch38)?,
+                reason: reason.to_string(),
+            });
+        }
+        Ok(Ba1Exceptions { exceptions })
+    }
+
+    /// The exception for a variant, by its coordinates or HGVS description.
+    pub fn find(&self, variant: Option<&GenomicVariant>, hgvs: Option<&HgvsVariant>) -> Option<&Ba1Exception> {
+        self.exceptions.iter().find(|exception| {
+            variant.is_some_and(|variant| exception.matches_variant(variant)) || hgvs.is_some_and(|hgvs| exception.matches_hgvs(hgvs))
+        })
+    }
+}
@@ -0,0 +1,174 @@
+use crate::Category::Pathogenic;
+use crate::EvidenceStrength::{Moderate, StandAlone, Strong, Supporting, VeryStrong};
+use crate::{AcmgError, EvidenceSet, EvidenceStrength};
+
+pub(crate) const PRIOR_PROB: f64 = 0.1;
+pub(crate) const ODDS_PATH_VERY_STRONG: f64 = 350.0;
+pub(crate) const EXPONENTIAL_PROGRESSION: f64 = 2.0;
+
+/// The Bayesian adaptation of the ACMG/AMP guidelines from Tavtigian et al. 2018. Each evidence strength has
+/// an OddsPath which is the OddsPath of Supporting evidence raised to the power of the exponential progression
+/// for each step up in strength, so that Very Strong evidence has an OddsPath of `odds_path_very_strong`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BayesianModel {
+    prior_prob: f64,
+    odds_path_very_strong: f64,
+    exponential_progression: f64,
+}
+
+impl Default for BayesianModel {
+    /// A prior probability of 0.1, OddsPath of 350 for Very Strong evidence and an exponential progression of 2,
+    /// which is equivalent to the 1/2/4/8 point system.
+    fn default() -> BayesianModel {
+        BayesianModel { prior_prob: PRIOR_PROB, odds_path_very_strong: ODDS_PATH_VERY_STRONG, exponential_progression: EXPONENTIAL_PROGRESSION }
+    }
+}
+
+impl BayesianModel {
+    pub fn new(prior_prob: f64, odds_path_very_strong: f64, exponential_progression: f64) -> Result<BayesianModel, AcmgError> {
+        let mut model = BayesianModel::default();
+        model.set_prior_prob(prior_prob)?;
+        model.set_odds_path_very_strong(odds_path_very_strong)?;
+        model.set_exponential_progression(exponential_progression)?;
+        Ok(model)
+    }
+
+    pub fn prior_prob(&self) -> f64 {
+        self.prior_prob
+    }
+
+    pub fn odds_path_very_strong(&self) -> f64 {
+        self.odds_path_very_strong
+    }
+
+    pub fn exponential_progression(&self) -> f64 {
+        self.exponential_progression
+    }
+
+    /// Sets the prior probability of pathogenicity, which must be between 0 and 1 exclusive.
+    pub fn set_prior_prob(&mut self, prior_prob: f64) -> Result<(), AcmgError> {
+        if !(prior_prob > 0.0 && prior_prob < 1.0) {
+            return Err(AcmgError::InvalidParameter(format!("prior probability must be between 0 and 1, was {}", prior_prob)));
+        }
+        self.prior_prob = prior_prob;
+        Ok(())
+    }
+
+    /// Sets the OddsPath of Very Strong evidence, which must be greater than 1.
+    pub fn set_odds_path_very_strong(&mut self, odds_path_very_strong: f64) -> Result<(), AcmgError> {
+        if !(odds_path_very_strong > 1.0 && odds_path_very_strong.is_finite()) {
+            return Err(AcmgError::InvalidParameter(format!("OddsPath for Very Strong evidence must be greater than 1, was {}", odds_path_very_strong)));
+        }
+        self.odds_path_very_strong = odds_path_very_strong;
+        Ok(())
+    }
+
+    /// Sets the exponential progression between evidence strengths, which must be greater than 1.
+    pub fn set_exponential_progression(&mut self, exponential_progression: f64) -> Result<(), AcmgError> {
+        if !(exponential_progression > 1.0 && exponential_progression.is_finite()) {
+            return Err(AcmgError::InvalidParameter(format!("exponential progression must be greater than 1, was {}", exponential_progression)));
+        }
+        self.exponential_progression = exponential_progression;
+        Ok(())
+    }
+
+    /// OddsPath of a single piece of pathogenic evidence at the given strength.
+    pub fn odds_path(&self, strength: EvidenceStrength) -> f64 {
+        self.odds_path_supporting().powf(self.exponent(strength))
+    }
+
+    fn odds_path_supporting(&self) -> f64 {
+        self.odds_path_very_strong.powf(self.exponential_progression.powf(-3.0))
+    }
+
+    fn exponent(&self, strength: EvidenceStrength) -> f64 {
+        let steps = match strength {
+            StandAlone | VeryStrong => 3,
+            Strong => 2,
+            Moderate => 1,
+            Supporting => 0,
+        };
+        self.exponential_progression.powi(steps)
+    }
+
+    /// Posterior probability of pathogenicity for the combined evidence, with benign evidence dividing the odds.
+    pub fn post_prob_path(&self, evidence_set: &EvidenceSet) -> f64 {
+        let exponent: f64 = evidence_set.iter()
+            .map(|evidence| {
+                let exponent = self.exponent(evidence.strength());
+                if evidence.evidence_code.category == Pathogenic { exponent } else { -exponent }
+            })
+            .sum();
+        self.post_prob_from_odds(self.odds_path_supporting().powf(exponent))
+    }
+
+    /// Posterior probability of pathogenicity for a total ACMG score, treating each point as one piece of
+    /// Supporting evidence.
+    pub fn post_prob_path_for_points(&self, points: i32) -> f64 {
+        self.post_prob_from_odds(self.odds_path_supporting().powi(points))
+    }
+
+    fn post_prob_from_odds(&self, odds_path: f64) -> f64 {
+        (odds_path * self.prior_prob) / ((odds_path - 1.0) * self.prior_prob + 1.0)
+    }
+}
+
+/// Posterior probability of pathogenicity for a total ACMG score, using the default [`BayesianModel`].
+pub fn calc_post_prob_path(points: i32) -> f64 {
+    BayesianModel::default().post_prob_path_for_points(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_evidence_set;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} isn't {}", actual, expected);
+    }
+
+    #[test]
+    fn odds_path_follows_tavtigian_2018() {
+        let model = BayesianModel::default();
+        assert_close(model.odds_path(VeryStrong), 350.0);
+        assert_close(model.odds_path(StandAlone), 350.0);
+        assert_close(model.odds_path(Strong), 350f64.powf(0.5));
+        assert_close(model.odds_path(Moderate), 350f64.powf(0.25));
+        assert_close(model.odds_path(Supporting), 350f64.powf(0.125));
+    }
+
+    #[test]
+    fn posterior_of_points_matches_tavtigian_2020() {
+        assert_close(calc_post_prob_path(0), 0.1);
+        assert_close(calc_post_prob_path(8), 350.0 * 0.1 / (349.0 * 0.1 + 1.0));
+        assert!((calc_post_prob_path(10) - 0.99409).abs() < 1e-5);
+        assert!((calc_post_prob_path(6) - 0.89991).abs() < 1e-5);
+        assert!((calc_post_prob_path(-6) - 0.00137).abs() < 1e-5);
+    }
+
+    #[test]
+    fn posterior_of_evidence_matches_its_points() {
+        let model = BayesianModel::default();
+        let evidence_set = parse_evidence_set("PVS1, PM2, PP3, BP1").unwrap();
+        assert_close(model.post_prob_path(&evidence_set), model.post_prob_path_for_points(10));
+        let evidence_set = parse_evidence_set("BS1, BP4").unwrap();
+        assert_close(model.post_prob_path(&evidence_set), model.post_prob_path_for_points(-5));
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        for prior_prob in [0.0, 1.0, -0.1, f64::NAN] {
+            assert!(BayesianModel::new(prior_prob, 350.0, 2.0).is_err(), "prior {}", prior_prob);
+        }
+        for odds_path in [1.0, 0.5, f64::INFINITY, f64::NAN] {
+            assert!(BayesianModel::new(0.1, odds_path, 2.0).is_err(), "OddsPath {}", odds_path);
+        }
+        for progression in [1.0, 0.0, f64::INFINITY, f64::NAN] {
+            assert!(BayesianModel::new(0.1, 350.0, progression).is_err(), "progression {}", progression);
+        }
+        let model = BayesianModel::new(0.2, 100.0, 1.5).unwrap();
+        assert_eq!((model.prior_prob(), model.odds_path_very_strong(), model.exponential_progression()), (0.2, 100.0, 1.5));
+        assert_close(model.odds_path(Moderate), 100f64.powf(1.5f64.powi(-2)));
+    }
+}
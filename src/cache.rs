@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::AcmgError;
+
+/// An on-disk SQLite cache of lookup responses keyed by source, data version and variant, so reruns don't query the
+/// source again. Responses older than the time to live are looked up again. Clones share the database, which can
+/// also be shared by concurrent processes.
+#[derive(Debug, Clone)]
+pub struct LookupCache {
+    connection: Arc<Mutex<Connection>>,
+    ttl: Option<Duration>,
+}
+
+impl LookupCache {
+    /// The name of the database in a cache directory.
+    pub const FILE_NAME: &'static str = "lookups.sqlite";
+
+    /// Opens the cache database, creating it if needed.
+    pub fn open(path: &Path) -> Result<LookupCache, AcmgError> {
+        let error = |error: rusqlite::Error| AcmgError::Lookup(format!("Unable to open the cache {}: {}", path.display(), error));
+        let connection = Connection::open(path).map_err(error)?;
+        connection.busy_timeout(Duration::from_secs(10)).map_err(error)?;
+        connection.execute_batch("PRAGMA journal_mode = WAL;
+            CREATE TABLE IF NOT EXISTS lookups (
+                source TEXT NOT NULL,
+                version TEXT NOT NULL,
+                variant TEXT NOT NULL,
+                response TEXT NOT NULL,
+                fetched INTEGER NOT NULL,
+                PRIMARY KEY (source, version, variant)
+            );").map_err(error)?;
+        Ok(LookupCache { connection: Arc::new(Mutex::new(connection)), ttl: None })
+    }
+
+    /// Opens the cache database in a directory, creating both if needed.
+    pub fn in_dir(cache_dir: &Path) -> Result<LookupCache, AcmgError> {
+        fs::create_dir_all(cache_dir)
+            .map_err(|error| AcmgError::Lookup(format!("Unable to create the cache {}: {}", cache_dir.display(), error)))?;
+        LookupCache::open(&cache_dir.join(LookupCache::FILE_NAME))
+    }
+
+    /// Looks up responses older than the time to live again, rather than keeping them indefinitely.
+    pub fn with_ttl(mut self, ttl: Duration) -> LookupCache {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// The cached response for a variant, unless it's missing, expired or no longer deserializes.
+    pub fn get<T: DeserializeOwned>(&self, source: &str, version: &str, variant: &str) -> Option<T> {
+        let oldest = self.ttl.map_or(-1, |ttl| now().saturating_sub(ttl.as_secs()) as i64);
+        let connection = self.connection.lock().expect("cache lock");
+        let response: Option<String> = connection
+            .query_row("SELECT response FROM lookups WHERE source = ?1 AND version = ?2 AND variant = ?3 AND fetched > ?4",
+                       params![source, version, variant, oldest], |row| row.get(0))
+            .optional()
+            .ok()?;
+        serde_json::from_str(&response?).ok()
+    }
+
+    /// Caches the response for a variant, replacing any previous one.
+    pub fn put<T: Serialize>(&self, source: &str, version: &str, variant: &str, response: &T) -> Result<(), AcmgError> {
+        let json = serde_json::to_string(response).map_err(|error| AcmgError::Lookup(format!("Unable to cache {}: {}", variant, error)))?;
+        let connection = self.connection.lock().expect("cache lock");
+        connection.execute("INSERT OR REPLACE INTO lookups (source, version, variant, response, fetched) VALUES (?1, ?2, ?3, ?4, ?5)",
+                           params![source, version, variant, json, now() as i64])
+            .map_err(|error| AcmgError::Lookup(format!("Unable to cache {}: {}", variant, error)))?;
+        Ok(())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs())
+}
@@ -0,0 +1,166 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::EvidenceStrength::{Moderate, Strong, Supporting};
+use crate::{AcmgError, Code, Evidence, EvidenceStrength};
+
+/// A computational predictor of variant pathogenicity with calibrated PP3/BP4 score thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Predictor {
+    Revel,
+    /// CADD PHRED-scaled score.
+    Cadd,
+    /// BayesDel without allele frequency.
+    BayesDel,
+    AlphaMissense,
+}
+
+/// Score thresholds from strongest to weakest - scores at or above a PP3 threshold, or at or below a BP4
+/// threshold, are applied at its strength.
+struct Thresholds {
+    pp3: &'static [(f64, EvidenceStrength)],
+    bp4: &'static [(f64, EvidenceStrength)],
+    range: (f64, f64),
+}
+
+// Pejaver et al. 2022, Table 2. REVEL reaches BP4_VeryStrong at 0.003 or below, which is capped at Strong as
+// recommended by the SVI.
+const REVEL: Thresholds = Thresholds {
+    pp3: &[(0.932, Strong), (0.773, Moderate), (0.644, Supporting)],
+    bp4: &[(0.016, Strong), (0.183, Moderate), (0.290, Supporting)],
+    range: (0.0, 1.0),
+};
+
+const CADD: Thresholds = Thresholds {
+    pp3: &[(28.1, Moderate), (25.3, Supporting)],
+    bp4: &[(17.3, Moderate), (22.7, Supporting)],
+    range: (0.0, f64::INFINITY),
+};
+
+const BAYES_DEL: Thresholds = Thresholds {
+    pp3: &[(0.410, Strong), (0.270, Moderate), (0.130, Supporting)],
+    bp4: &[(-0.360, Moderate), (-0.180, Supporting)],
+    range: (f64::NEG_INFINITY, f64::INFINITY),
+};
+
+// Bergquist et al. 2025, calibrated with the same approach as Pejaver et al. 2022.
+const ALPHA_MISSENSE: Thresholds = Thresholds {
+    pp3: &[(0.990, Strong), (0.906, Moderate), (0.792, Supporting)],
+    bp4: &[(0.099, Moderate), (0.169, Supporting)],
+    range: (0.0, 1.0),
+};
+
+impl Predictor {
+    pub const ALL: [Predictor; 4] = [Predictor::Revel, Predictor::Cadd, Predictor::BayesDel, Predictor::AlphaMissense];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Predictor::Revel => "revel",
+            Predictor::Cadd => "cadd",
+            Predictor::BayesDel => "bayesdel",
+            Predictor::AlphaMissense => "alphamissense",
+        }
+    }
+
+    fn thresholds(&self) -> &'static Thresholds {
+        match self {
+            Predictor::Revel => &REVEL,
+            Predictor::Cadd => &CADD,
+            Predictor::BayesDel => &BAYES_DEL,
+            Predictor::AlphaMissense => &ALPHA_MISSENSE,
+        }
+    }
+
+    /// The PP3 or BP4 evidence at the calibrated strength for a score, or None if the score falls in the
+    /// indeterminate range between them.
+    pub fn calibrate(&self, score: f64) -> Result<Option<Evidence>, AcmgError> {
+        let thresholds = self.thresholds();
+        let (min, max) = thresholds.range;
+        if !(min..=max).contains(&score) {
+            return Err(AcmgError::InvalidParameter(format!("{} score must be between {} and {}, got {}", self, min, max, score)));
+        }
+        let pp3 = thresholds.pp3.iter().find(|(threshold, _)| score >= *threshold).map(|(_, strength)| (Code::PP3, *strength));
+        let bp4 = thresholds.bp4.iter().find(|(threshold, _)| score <= *threshold).map(|(_, strength)| (Code::BP4, *strength));
+        Ok(pp3.or(bp4).map(|(code, strength)| Evidence::at_strength(code, strength)))
+    }
+}
+
+impl Display for Predictor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Predictor {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<Predictor, Self::Err> {
+        Predictor::ALL.iter()
+            .find(|predictor| predictor.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| AcmgError::UnknownPredictor(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EvidenceStrength::VeryStrong;
+
+    type Calibrated = Option<(&'static str, EvidenceStrength)>;
+
+    fn calibrated(predictor: Predictor, score: f64) -> Calibrated {
+        predictor.calibrate(score).unwrap().map(|evidence| (evidence.evidence_code.name.parse::<Code>().unwrap().as_str(), evidence.strength()))
+    }
+
+    #[test]
+    fn each_threshold_applies_its_strength_up_to_the_next() {
+        for predictor in Predictor::ALL {
+            let thresholds = predictor.thresholds();
+            for (index, (threshold, strength)) in thresholds.pp3.iter().enumerate() {
+                assert_eq!(calibrated(predictor, *threshold), Some(("PP3", *strength)), "{} PP3 at {}", predictor, threshold);
+                let weaker = thresholds.pp3.get(index + 1).map(|(_, strength)| ("PP3", *strength));
+                assert_eq!(calibrated(predictor, threshold - 1e-6), weaker, "{} PP3 below {}", predictor, threshold);
+            }
+            for (index, (threshold, strength)) in thresholds.bp4.iter().enumerate() {
+                assert_eq!(calibrated(predictor, *threshold), Some(("BP4", *strength)), "{} BP4 at {}", predictor, threshold);
+                let weaker = thresholds.bp4.get(index + 1).map(|(_, strength)| ("BP4", *strength));
+                assert_eq!(calibrated(predictor, threshold + 1e-6), weaker, "{} BP4 above {}", predictor, threshold);
+            }
+        }
+    }
+
+    #[test]
+    fn thresholds_match_pejaver_2022() {
+        let expected: [(Predictor, f64, Calibrated); 22] = [
+            (Predictor::Revel, 0.932, Some(("PP3", Strong))),
+            (Predictor::Revel, 0.931, Some(("PP3", Moderate))),
+            (Predictor::Revel, 0.773, Some(("PP3", Moderate))),
+            (Predictor::Revel, 0.644, Some(("PP3", Supporting))),
+            (Predictor::Revel, 0.643, None),
+            (Predictor::Revel, 0.290, Some(("BP4", Supporting))),
+            (Predictor::Revel, 0.183, Some(("BP4", Moderate))),
+            (Predictor::Revel, 0.017, Some(("BP4", Moderate))),
+            (Predictor::Revel, 0.016, Some(("BP4", Strong))),
+            (Predictor::Revel, 0.003, Some(("BP4", Strong))),
+            (Predictor::Cadd, 28.1, Some(("PP3", Moderate))),
+            (Predictor::Cadd, 25.3, Some(("PP3", Supporting))),
+            (Predictor::Cadd, 22.7, Some(("BP4", Supporting))),
+            (Predictor::Cadd, 17.3, Some(("BP4", Moderate))),
+            (Predictor::BayesDel, 0.410, Some(("PP3", Strong))),
+            (Predictor::BayesDel, 0.270, Some(("PP3", Moderate))),
+            (Predictor::BayesDel, 0.130, Some(("PP3", Supporting))),
+            (Predictor::BayesDel, 0.129, None),
+            (Predictor::BayesDel, -0.180, Some(("BP4", Supporting))),
+            (Predictor::BayesDel, -0.360, Some(("BP4", Moderate))),
+            (Predictor::AlphaMissense, 0.990, Some(("PP3", Strong))),
+            (Predictor::AlphaMissense, 0.099, Some(("BP4", Moderate))),
+        ];
+        for (predictor, score, evidence) in expected {
+            assert_eq!(calibrated(predictor, score), evidence, "{} at {}", predictor, score);
+        }
+        assert!(Predictor::ALL.iter().all(|predictor| predictor.thresholds().bp4.iter().all(|(_, strength)| *strength != VeryStrong)));
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::EvidenceStrength::{Moderate, Strong, Supporting};
+use crate::{AcmgError, Code, Evidence, EvidenceStrength};
+
+/// The z score of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.959964;
+
+/// The carriers of a variant among a group of individuals, e.g. `12/800`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CarrierCount {
+    pub carriers: u32,
+    pub total: u32,
+}
+
+impl Display for CarrierCount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("{}/{}", self.carriers, self.total))
+    }
+}
+
+impl FromStr for CarrierCount {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<CarrierCount, Self::Err> {
+        let invalid = || AcmgError::InvalidCarrierCount(s.to_string());
+        let (carriers, total) = s.trim().split_once('/').ok_or_else(invalid)?;
+        let carriers: u32 = carriers.trim().parse().map_err(|_| invalid())?;
+        let total: u32 = total.trim().parse().map_err(|_| invalid())?;
+        if total == 0 || carriers > total {
+            return Err(invalid());
+        }
+        Ok(CarrierCount { carriers, total })
+    }
+}
+
+/// The carriers of a variant among affected cases and unaffected controls, for PS4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CaseControl {
+    pub cases: CarrierCount,
+    pub controls: CarrierCount,
+}
+
+impl CaseControl {
+    /// The cells of the 2x2 table, carrier and non-carrier cases then carrier and non-carrier controls, with 0.5
+    /// added to each if any is zero (the Haldane-Anscombe correction).
+    fn cells(&self) -> [f64; 4] {
+        let cells = [
+            self.cases.carriers, self.cases.total - self.cases.carriers,
+            self.controls.carriers, self.controls.total - self.controls.carriers,
+        ].map(f64::from);
+        if cells.contains(&0.0) { cells.map(|cell| cell + 0.5) } else { cells }
+    }
+
+    pub fn odds_ratio(&self) -> f64 {
+        let [a, b, c, d] = self.cells();
+        a * d / (b * c)
+    }
+
+    /// The 95% confidence interval of the odds ratio, by Woolf's method.
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        let [a, b, c, d] = self.cells();
+        let error = Z_95 * (1.0 / a + 1.0 / b + 1.0 / c + 1.0 / d).sqrt();
+        let log_odds_ratio = self.odds_ratio().ln();
+        ((log_odds_ratio - error).exp(), (log_odds_ratio + error).exp())
+    }
+
+    /// The two-sided p-value of Fisher's exact test, summing the probabilities of the tables with the same margins
+    /// which are no more likely than the one observed. The probabilities fall away from the most likely table, so
+    /// each tail is summed outwards from where they drop to the observed one until they're negligible.
+    pub fn fisher_p_value(&self) -> f64 {
+        let cases = u64::from(self.cases.total);
+        let carriers = u64::from(self.cases.carriers) + u64::from(self.controls.carriers);
+        let total = cases + u64::from(self.controls.total);
+        let non_carriers = total - carriers;
+        let ln_probability = |x: u64| ln_choose(carriers, x) + ln_choose(non_carriers, cases - x) - ln_choose(total, cases);
+        // The ratio of the probability of x + 1 carrier cases to that of x.
+        let ratio = |x: u64| ((carriers - x) as f64 * (cases - x) as f64) / ((x + 1) as f64 * (non_carriers + x + 1 - cases) as f64);
+        let (first, last) = (cases.saturating_sub(non_carriers), carriers.min(cases));
+        let mode = (((u128::from(cases) + 1) * (u128::from(carriers) + 1) / (u128::from(total) + 2)) as u64).clamp(first, last);
+        let observed = ln_probability(u64::from(self.cases.carriers));
+        let threshold = observed + 1e-7f64.ln_1p();
+        let negligible = observed.exp() * 1e-17;
+
+        let mut p_value = 0.0;
+        // Probabilities rise from the first table to the mode, so the lower tail ends at the last table from the
+        // first no more likely than the one observed.
+        if ln_probability(first) <= threshold {
+            let (mut low, mut high) = (first, mode);
+            while low < high {
+                let middle = high - (high - low) / 2;
+                if ln_probability(middle) <= threshold { low = middle } else { high = middle - 1 }
+            }
+            let (mut x, mut probability) = (low, ln_probability(low).exp());
+            loop {
+                p_value += probability;
+                if x == first || probability == 0.0 || probability < negligible {
+                    break;
+                }
+                x -= 1;
+                probability /= ratio(x);
+            }
+        }
+        // And fall from the mode to the last table, so the upper tail starts at the first table after the mode no
+        // more likely than the one observed.
+        if mode < last && ln_probability(last) <= threshold {
+            let (mut low, mut high) = (mode + 1, last);
+            while low < high {
+                let middle = low + (high - low) / 2;
+                if ln_probability(middle) <= threshold { high = middle } else { low = middle + 1 }
+            }
+            let (mut x, mut probability) = (low, ln_probability(low).exp());
+            loop {
+                p_value += probability;
+                if x == last || probability == 0.0 || probability < negligible {
+                    break;
+                }
+                probability *= ratio(x);
+                x += 1;
+            }
+        }
+        p_value.min(1.0)
+    }
+
+    /// Whether the variant is significantly more prevalent in cases, with a p-value below 0.05 and a confidence
+    /// interval of the odds ratio excluding 1.
+    pub fn significant(&self) -> bool {
+        self.fisher_p_value() < 0.05 && self.confidence_interval().0 > 1.0
+    }
+
+    /// The PS4 strength if the variant is significantly more prevalent in cases - Strong from an odds ratio of 5 as
+    /// Richards et al. 2015 recommend, Moderate from 3 and Supporting from 2, or None otherwise.
+    pub fn strength(&self) -> Option<EvidenceStrength> {
+        if !self.significant() {
+            return None;
+        }
+        match self.odds_ratio() {
+            odds_ratio if odds_ratio >= 5.0 => Some(Strong),
+            odds_ratio if odds_ratio >= 3.0 => Some(Moderate),
+            odds_ratio if odds_ratio >= 2.0 => Some(Supporting),
+            _ => None,
+        }
+    }
+
+    /// PS4 at the recommended strength, ready to be added to an assessment.
+    pub fn evidence(&self) -> Option<Evidence> {
+        self.strength().map(|strength| Evidence::at_strength(Code::PS4, strength))
+    }
+}
+
+/// The log of the number of ways of choosing k of n.
+fn ln_choose(n: u64, k: u64) -> f64 {
+    ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k)
+}
+
+/// The log of n factorial, summed up to 16 and by Stirling's series above.
+fn ln_factorial(n: u64) -> f64 {
+    if n <= 16 {
+        return (2..=n).map(|i| (i as f64).ln()).sum();
+    }
+    let n = n as f64;
+    n * n.ln() - n + 0.5 * (2.0 * std::f64::consts::PI * n).ln() + 1.0 / (12.0 * n) - 1.0 / (360.0 * n.powi(3)) + 1.0 / (1260.0 * n.powi(5)) - 1.0 / (1680.0 * n.powi(7))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(cases: &str, controls: &str) -> CaseControl {
+        CaseControl { cases: cases.parse().unwrap(), controls: controls.parse().unwrap() }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() <= expected * 1e-6, "{} isn't {}", actual, expected);
+    }
+
+    #[test]
+    fn odds_ratio_confidence_interval_and_p_value() {
+        let case_control = counts("12/800", "1/60000");
+        assert_close(case_control.odds_ratio(), 913.690355);
+        let (lower, upper) = case_control.confidence_interval();
+        assert_close(lower, 118.662858);
+        assert_close(upper, 7035.310608);
+        assert_close(case_control.fisher_p_value(), 3.1872888e-22);
+        assert_eq!(case_control.strength(), Some(Strong));
+    }
+
+    #[test]
+    fn zero_cells_are_haldane_corrected() {
+        let case_control = counts("5/100", "0/1000");
+        assert_close(case_control.odds_ratio(), 5.5 * 1000.5 / (95.5 * 0.5));
+        let (lower, upper) = case_control.confidence_interval();
+        assert_close(lower, 6.324084);
+        assert_close(upper, 2099.980095);
+        assert_close(case_control.fisher_p_value(), 5.6610154e-6);
+    }
+
+    #[test]
+    fn no_enrichment_is_not_significant() {
+        let case_control = counts("10/100", "40/400");
+        assert_close(case_control.odds_ratio(), 1.0);
+        assert_close(case_control.fisher_p_value(), 1.0);
+        assert_eq!(case_control.strength(), None);
+        assert_eq!(counts("3/100", "2/100").fisher_p_value(), 1.0);
+    }
+
+    #[test]
+    fn biobank_scale_counts_are_tested_without_tabulating_factorials() {
+        let case_control = counts("1/3000000000", "1/3000000000");
+        assert_close(case_control.fisher_p_value(), 1.0);
+        assert!(counts("40/50000000", "2/50000000").fisher_p_value() < 1e-8);
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::AcmgError;
+
+/// A DOI as Crossref recommends matching them, e.g. `10.1016/j.ajhg.2018.09.012`.
+static DOI: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^10\.\d{4,9}/\S+$").unwrap());
+
+/// The identifier system of a [`Citation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum CitationSource {
+    Pmid,
+    Doi,
+}
+
+/// A publication cited for an applied criterion, written `PMID:30311383` or `doi:10.1016/j.ajhg.2018.09.012`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Citation {
+    pub source: CitationSource,
+    /// The identifier without its prefix, e.g. `30311383`.
+    pub id: String,
+}
+
+impl Citation {
+    /// The PubMed or doi.org link of the publication.
+    pub fn url(&self) -> String {
+        match self.source {
+            CitationSource::Pmid => format!("https://pubmed.ncbi.nlm.nih.gov/{}/", self.id),
+            CitationSource::Doi => format!("https://doi.org/{}", self.id),
+        }
+    }
+}
+
+impl Display for Citation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.source {
+            CitationSource::Pmid => f.pad(&format!("PMID:{}", self.id)),
+            CitationSource::Doi => f.pad(&format!("doi:{}", self.id)),
+        }
+    }
+}
+
+/// Strips a case-insensitive prefix.
+fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    s.get(..prefix.len()).filter(|start| start.eq_ignore_ascii_case(prefix)).map(|_| s[prefix.len()..].trim_start())
+}
+
+/// Parses a PMID of up to nine digits, e.g. `PMID:30311383` or a PubMed link, or a DOI, e.g.
+/// `doi:10.1016/j.ajhg.2018.09.012`, a doi.org link or a bare DOI.
+impl FromStr for Citation {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<Citation, Self::Err> {
+        let s = s.trim();
+        let invalid = || AcmgError::InvalidCitation(s.to_string());
+        let pmid = ["PMID:", "PMID", "https://pubmed.ncbi.nlm.nih.gov/"].iter().find_map(|prefix| strip_prefix(s, prefix));
+        if let Some(pmid) = pmid {
+            let pmid = pmid.trim_end_matches('/');
+            let valid = (1..=9).contains(&pmid.len()) && pmid.bytes().all(|c| c.is_ascii_digit()) && !pmid.starts_with('0');
+            return valid.then(|| Citation { source: CitationSource::Pmid, id: pmid.to_string() }).ok_or_else(invalid);
+        }
+        let doi = ["doi:", "https://doi.org/", "https://dx.doi.org/"].iter().find_map(|prefix| strip_prefix(s, prefix)).unwrap_or(s);
+        if DOI.is_match(doi) {
+            return Ok(Citation { source: CitationSource::Doi, id: doi.to_string() });
+        }
+        Err(invalid())
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::EvidenceStrength::{self, Moderate, StandAlone, Strong, Supporting, VeryStrong};
+use crate::AcmgError;
+
+/// The five-tier ACMG/AMP variant classification.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum AcmgClassification {
+    Pathogenic,
+    LikelyPathogenic,
+    UncertainSignificance,
+    LikelyBenign,
+    Benign,
+}
+
+impl AcmgClassification {
+    pub const ALL: [AcmgClassification; 5] = [
+        AcmgClassification::Pathogenic,
+        AcmgClassification::LikelyPathogenic,
+        AcmgClassification::UncertainSignificance,
+        AcmgClassification::LikelyBenign,
+        AcmgClassification::Benign,
+    ];
+
+    /// The term used by ClinVar and in clinical reports, e.g. `Likely pathogenic`.
+    pub fn term(&self) -> &'static str {
+        match self {
+            AcmgClassification::Pathogenic => "Pathogenic",
+            AcmgClassification::LikelyPathogenic => "Likely pathogenic",
+            AcmgClassification::UncertainSignificance => "Uncertain significance",
+            AcmgClassification::LikelyBenign => "Likely benign",
+            AcmgClassification::Benign => "Benign",
+        }
+    }
+}
+
+/// Classifies a total ACMG score using the point thresholds from Tavtigian et al. 2020. Assessments scored with
+/// another [`PointSystem`] classify with its thresholds.
+pub fn classification(points: i32) -> AcmgClassification {
+    PointSystem::TAVTIGIAN_2020.classify(points)
+}
+
+/// The points awarded for evidence of each strength and the lowest total points of each classification above
+/// Benign, for labs following modified point schemes. The default is the point system of Tavtigian et al. 2020.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PointSystem {
+    pub stand_alone: i32,
+    pub very_strong: i32,
+    pub strong: i32,
+    pub moderate: i32,
+    pub supporting: i32,
+    pub pathogenic: i32,
+    pub likely_pathogenic: i32,
+    pub uncertain_significance: i32,
+    pub likely_benign: i32,
+}
+
+impl Default for PointSystem {
+    fn default() -> PointSystem {
+        PointSystem::TAVTIGIAN_2020
+    }
+}
+
+impl PointSystem {
+    /// 8/4/2/1 points for Very Strong to Supporting evidence, 8 for Stand-alone, and Pathogenic at 10 or more points,
+    /// Likely pathogenic at 6, Uncertain significance at 0 and Likely benign at -6.
+    pub const TAVTIGIAN_2020: PointSystem = PointSystem {
+        stand_alone: 8,
+        very_strong: 8,
+        strong: 4,
+        moderate: 2,
+        supporting: 1,
+        pathogenic: 10,
+        likely_pathogenic: 6,
+        uncertain_significance: 0,
+        likely_benign: -6,
+    };
+
+    pub fn points(&self, strength: EvidenceStrength) -> i32 {
+        match strength {
+            StandAlone => self.stand_alone,
+            VeryStrong => self.very_strong,
+            Strong => self.strong,
+            Moderate => self.moderate,
+            Supporting => self.supporting,
+        }
+    }
+
+    /// The lowest total points of a classification, or None for Benign which has no lower bound.
+    pub fn threshold(&self, classification: AcmgClassification) -> Option<i32> {
+        match classification {
+            AcmgClassification::Pathogenic => Some(self.pathogenic),
+            AcmgClassification::LikelyPathogenic => Some(self.likely_pathogenic),
+            AcmgClassification::UncertainSignificance => Some(self.uncertain_significance),
+            AcmgClassification::LikelyBenign => Some(self.likely_benign),
+            AcmgClassification::Benign => None,
+        }
+    }
+
+    pub fn classify(&self, points: i32) -> AcmgClassification {
+        AcmgClassification::ALL.into_iter()
+            .find(|classification| self.threshold(*classification).is_none_or(|threshold| points >= threshold))
+            .unwrap_or(AcmgClassification::Benign)
+    }
+
+    /// Checks the points are positive and don't rise as strengths weaken, and the thresholds fall from Pathogenic
+    /// to Likely benign.
+    pub fn validate(&self) -> Result<(), AcmgError> {
+        let strengths = [self.very_strong, self.strong, self.moderate, self.supporting];
+        if self.stand_alone <= 0 || strengths.iter().any(|points| *points <= 0) || strengths.windows(2).any(|pair| pair[0] < pair[1]) {
+            return Err(AcmgError::InvalidParameter(format!("points per strength must be positive and not rise as strengths weaken, were {}", self)));
+        }
+        if !(self.pathogenic > self.likely_pathogenic && self.likely_pathogenic > self.uncertain_significance && self.uncertain_significance > self.likely_benign) {
+            return Err(AcmgError::InvalidParameter(format!("classification thresholds must fall from Pathogenic to Likely benign, were {}", self)));
+        }
+        Ok(())
+    }
+}
+
+/// E.g. `8/8/4/2/1 points, P >= 10, LP >= 6, VUS >= 0, LB >= -6`, the points running from Stand-alone to Supporting.
+impl Display for PointSystem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}/{}/{}/{} points, P >= {}, LP >= {}, VUS >= {}, LB >= {}",
+               self.stand_alone, self.very_strong, self.strong, self.moderate, self.supporting,
+               self.pathogenic, self.likely_pathogenic, self.uncertain_significance, self.likely_benign)
+    }
+}
+
+/// Classifies a posterior probability of pathogenicity using the thresholds from Tavtigian et al. 2018 -
+/// Pathogenic >= 0.99, Likely pathogenic >= 0.90, VUS >= 0.10 and Likely benign >= 0.001. A small tolerance
+/// is allowed so that posteriors which are exactly on a threshold aren't pushed below it by rounding errors.
+pub fn posterior_classification(post_prob_path: f64) -> AcmgClassification {
+    const TOLERANCE: f64 = 1e-9;
+    match post_prob_path + TOLERANCE {
+        p if p >= 0.99 => AcmgClassification::Pathogenic,
+        p if p >= 0.90 => AcmgClassification::LikelyPathogenic,
+        p if p >= 0.10 => AcmgClassification::UncertainSignificance,
+        p if p >= 0.001 => AcmgClassification::LikelyBenign,
+        _ => AcmgClassification::Benign,
+    }
+}
+
+/// Whether the points-based guideline classifies from the integer points or from the posterior probability.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Classifier {
+    #[default]
+    Points,
+    Posterior,
+}
+
+impl Classifier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Classifier::Points => "points",
+            Classifier::Posterior => "posterior",
+        }
+    }
+}
+
+impl Display for Classifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Classifier {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<Classifier, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "points" => Ok(Classifier::Points),
+            "posterior" => Ok(Classifier::Posterior),
+            _ => Err(AcmgError::UnknownClassifier(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posterior_thresholds_follow_tavtigian_2018() {
+        let cases = [
+            (1.0, AcmgClassification::Pathogenic),
+            (0.99, AcmgClassification::Pathogenic),
+            (0.99 - 1e-10, AcmgClassification::Pathogenic),
+            (0.989, AcmgClassification::LikelyPathogenic),
+            (0.90, AcmgClassification::LikelyPathogenic),
+            (0.8999, AcmgClassification::UncertainSignificance),
+            (0.10, AcmgClassification::UncertainSignificance),
+            (0.0999, AcmgClassification::LikelyBenign),
+            (0.001, AcmgClassification::LikelyBenign),
+            (0.001 - 1e-10, AcmgClassification::LikelyBenign),
+            (0.000999, AcmgClassification::Benign),
+            (0.0, AcmgClassification::Benign),
+        ];
+        for (post_prob_path, expected) in cases {
+            assert_eq!(posterior_classification(post_prob_path), expected, "{}", post_prob_path);
+        }
+    }
+
+    #[test]
+    fn posteriors_rounded_below_a_threshold_are_within_tolerance() {
+        assert_eq!(posterior_classification(0.1 - 1e-12), AcmgClassification::UncertainSignificance);
+        assert_eq!(posterior_classification(0.99 - 1e-12), AcmgClassification::Pathogenic);
+        assert_eq!(posterior_classification(0.90 - 2e-9), AcmgClassification::UncertainSignificance);
+    }
+}
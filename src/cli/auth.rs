@@ -0,0 +1,156 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, MatchedPath, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use clap::Args;
+use subtle::{Choice, ConstantTimeEq};
+
+use acmg::AcmgError;
+
+use crate::cli::serve::{error_response, AppState};
+use crate::cli::CliError;
+
+/// Routes which can be requested without an API key.
+const PUBLIC_ROUTES: [&str; 1] = ["/openapi.json"];
+
+/// Rate limits are tracked for at most this many clients, forgetting those which have since been idle.
+const MAX_CLIENTS: usize = 10_000;
+
+/// Options restricting who can use the server and how often.
+#[derive(Debug, Clone, Args)]
+#[command(next_help_heading = "Access")]
+pub struct AccessArgs {
+    /// API keys accepted by the server, separated by commas. Requests must then give one as a bearer token or in an
+    /// X-API-Key header
+    #[arg(long, env = "ACMG_API_KEYS", value_delimiter = ',', hide_env_values = true)]
+    pub api_keys: Vec<String>,
+    /// File of API keys accepted by the server, one per line, ignoring blank lines and # comments
+    #[arg(long)]
+    pub api_key_file: Option<PathBuf>,
+    /// Requests per minute allowed for each API key, or each client address without API keys
+    #[arg(long)]
+    pub rate_limit: Option<u32>,
+    /// Also accept API keys as an api_key query parameter, for browsers opening WebSockets, which can't set headers.
+    /// Keys in URLs are written to proxy and access logs
+    #[arg(long)]
+    pub allow_query_api_key: bool,
+}
+
+impl AccessArgs {
+    pub fn resolve(&self) -> Result<Access, CliError> {
+        let mut keys: BTreeSet<String> = self.api_keys.iter().map(|key| key.trim().to_string()).filter(|key| !key.is_empty()).collect();
+        if let Some(path) = &self.api_key_file {
+            keys.extend(fs::read_to_string(path)?.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string));
+        }
+        let rate_limit = match self.rate_limit {
+            Some(0) => return Err(AcmgError::InvalidParameter("--rate-limit must be at least 1".to_string()).into()),
+            rate_limit => rate_limit,
+        };
+        Ok(Access { keys: keys.into_iter().collect(), allow_query_api_key: self.allow_query_api_key, rate_limit, buckets: Mutex::default() })
+    }
+}
+
+/// A token bucket holding up to a minute of requests.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// The API keys accepted by the server and the requests each client has left.
+#[derive(Debug)]
+pub struct Access {
+    keys: Vec<String>,
+    allow_query_api_key: bool,
+    /// Requests per minute.
+    rate_limit: Option<u32>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl Access {
+    /// Whether the key is one of the API keys, comparing it with every key in constant time so the time taken
+    /// doesn't reveal how much of a key was guessed.
+    fn accepts(&self, key: &str) -> bool {
+        self.keys.iter().fold(Choice::from(0), |accepted, other| accepted | keys_match(key, other)).into()
+    }
+
+    /// Takes a request from the client's bucket, or how long until it can make another.
+    fn take(&self, client: &str) -> Result<(), Duration> {
+        let Some(rate_limit) = self.rate_limit else {
+            return Ok(());
+        };
+        let capacity = rate_limit as f64;
+        let per_second = capacity / 60.0;
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().expect("rate limit lock");
+        if buckets.len() >= MAX_CLIENTS && !buckets.contains_key(client) {
+            buckets.retain(|_, bucket| bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * per_second < capacity);
+        }
+        let bucket = buckets.entry(client.to_string()).or_insert(Bucket { tokens: capacity, updated: now });
+        bucket.tokens = (bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * per_second).min(capacity);
+        bucket.updated = now;
+        if bucket.tokens < 1.0 {
+            return Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second));
+        }
+        bucket.tokens -= 1.0;
+        Ok(())
+    }
+}
+
+/// Whether two API keys are the same, in time independent of their contents.
+fn keys_match(key: &str, other: &str) -> Choice {
+    key.as_bytes().ct_eq(other.as_bytes())
+}
+
+/// The API key of a request, given as a bearer token or in an X-API-Key header, or with `--allow-query-api-key` as
+/// an `api_key` query parameter.
+fn api_key(request: &Request, allow_query_api_key: bool) -> Option<&str> {
+    let headers = request.headers();
+    headers.get(header::AUTHORIZATION)
+        .and_then(|authorization| authorization.to_str().ok())
+        .and_then(|authorization| authorization.strip_prefix("Bearer "))
+        .or_else(|| headers.get("x-api-key").and_then(|key| key.to_str().ok()))
+        .or_else(|| request.uri().query().filter(|_| allow_query_api_key)?.split('&').find_map(|parameter| parameter.strip_prefix("api_key=")))
+        .map(str::trim)
+}
+
+/// Rejects requests without a valid API key, if any are configured, and requests beyond the rate limit.
+pub async fn authorize(State(state): State<Arc<AppState>>, request: Request, next: Next) -> Response {
+    let access = &state.access;
+    let route = request.extensions().get::<MatchedPath>().map(MatchedPath::as_str);
+    if route.is_some_and(|route| PUBLIC_ROUTES.contains(&route)) {
+        return next.run(request).await;
+    }
+    let client = if access.keys.is_empty() {
+        request.extensions().get::<ConnectInfo<SocketAddr>>().map_or_else(String::new, |ConnectInfo(address)| address.ip().to_string())
+    } else {
+        match api_key(&request, access.allow_query_api_key) {
+            Some(key) if access.accepts(key) => key.to_string(),
+            Some(_) => return unauthorized("Invalid API key"),
+            None => return unauthorized("An API key is required"),
+        }
+    };
+    if let Err(retry_after) = access.take(&client) {
+        let mut response = error_response(StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded".to_string());
+        let seconds = retry_after.as_secs_f64().ceil() as u64;
+        response.headers_mut().insert(header::RETRY_AFTER, seconds.max(1).into());
+        return response;
+    }
+    next.run(request).await
+}
+
+fn unauthorized(error: &str) -> Response {
+    let mut response = error_response(StatusCode::UNAUTHORIZED, error.to_string());
+    response.headers_mut().insert(header::WWW_AUTHENTICATE, header::HeaderValue::from_static("Bearer"));
+    response
+}
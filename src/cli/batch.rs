@@ -0,0 +1,388 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use acmg::{AcmgAssessment, AcmgError};
+
+use crate::cli::logging::log_record;
+use crate::cli::metadata::{RunMetadata, StampedResult};
+use crate::cli::{parse_record, CliError, Scoring};
+
+/// The number of rows read before classifying them in parallel.
+const CHUNK_ROWS: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BatchFormat {
+    Tsv,
+    Jsonl,
+    /// An xlsx workbook, with the diagnostics of each row as a comment on its classification.
+    Xlsx,
+    /// A Parquet file, with the score and posterior probability as numbers and the other columns as strings.
+    Parquet,
+}
+
+#[derive(Debug)]
+pub struct BatchOptions<'a> {
+    pub input: &'a Path,
+    pub output: Option<&'a Path>,
+    pub evidence_column: &'a str,
+    /// Column of variant coordinates, normalized into the output and used to look up allele frequencies, if given.
+    pub variant_column: Option<&'a str>,
+    /// Column of SpliceAI delta scores the splicing thresholds are applied to, if given. Empty and `.` cells have no
+    /// score.
+    pub spliceai_column: Option<&'a str>,
+    /// The worksheet of an xlsx input read, or the first if not given.
+    pub sheet: Option<&'a str>,
+    pub format: BatchFormat,
+    /// Threads classifying rows, or 0 for one per CPU.
+    pub threads: usize,
+    /// File the rows which fail are written to with their line number and error, in place of the output.
+    pub rejects: Option<&'a Path>,
+    /// The fraction of rows which may fail before the run does, once every row has been classified.
+    pub max_error_rate: Option<f64>,
+    pub scoring: &'a Scoring,
+}
+
+#[derive(Debug, Default)]
+pub struct BatchSummary {
+    pub records: usize,
+    pub errors: usize,
+}
+
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    line: u64,
+    record: Columns<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<StampedResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The input columns of a row, serialized as a map in input order.
+struct Columns<'a> {
+    headers: &'a csv::StringRecord,
+    row: &'a csv::StringRecord,
+}
+
+impl Serialize for Columns<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.headers.iter().zip(self.row.iter()))
+    }
+}
+
+enum RecordWriter {
+    /// TSV output, with the normalized variant columns if variants were given.
+    Tsv(Box<csv::Writer<Box<dyn Write + Send>>>, bool),
+    Jsonl(BufWriter<Box<dyn Write + Send>>),
+    /// Workbook output with the columns of TSV output, and the column the diagnostics are commented on.
+    #[cfg(feature = "xlsx")]
+    Xlsx(Box<crate::cli::xlsx::XlsxWriter>, bool, usize),
+    /// Parquet output with the columns of TSV output.
+    #[cfg(feature = "parquet")]
+    Parquet(Box<crate::cli::parquet::ParquetWriter>, bool),
+}
+
+/// The columns appended to the input columns of TSV output.
+const OUTPUT_COLUMNS: [&str; 9] = ["acmg_score", "acmg_classification", "acmg_post_prob_path", "acmg_guideline", "acmg_version", "acmg_parameters", "acmg_input_sha256", "acmg_timestamp", "acmg_secondary_findings"];
+
+impl RecordWriter {
+    #[cfg_attr(not(feature = "xlsx"), allow(unused_variables))]
+    fn new(writer: Box<dyn Write + Send>, format: BatchFormat, headers: &csv::StringRecord, variants: bool, sheet: Option<&str>) -> Result<RecordWriter, CliError> {
+        let mut header = headers.clone();
+        if variants {
+            header.extend(["acmg_variant", "acmg_build"]);
+        }
+        header.extend(OUTPUT_COLUMNS);
+        let column = |name: &str| header.len() - OUTPUT_COLUMNS.len() + OUTPUT_COLUMNS.iter().position(|column| *column == name).expect("output column");
+        match format {
+            BatchFormat::Tsv => {
+                let mut tsv_writer = csv::WriterBuilder::new().delimiter(b'\t').from_writer(writer);
+                tsv_writer.write_record(&header)?;
+                Ok(RecordWriter::Tsv(Box::new(tsv_writer), variants))
+            }
+            BatchFormat::Jsonl => Ok(RecordWriter::Jsonl(BufWriter::new(writer))),
+            #[cfg(feature = "xlsx")]
+            BatchFormat::Xlsx => {
+                let mut xlsx_writer = crate::cli::xlsx::XlsxWriter::new(writer, sheet.unwrap_or("acmg"), vec![column("acmg_score"), column("acmg_post_prob_path")]);
+                let classification = column("acmg_classification");
+                xlsx_writer.write_row(&header, None);
+                Ok(RecordWriter::Xlsx(Box::new(xlsx_writer), variants, classification))
+            }
+            #[cfg(not(feature = "xlsx"))]
+            BatchFormat::Xlsx => Err(CliError::FeatureDisabled("xlsx")),
+            #[cfg(feature = "parquet")]
+            BatchFormat::Parquet => {
+                let types = [(column("acmg_score"), arrow_schema::DataType::Int32), (column("acmg_post_prob_path"), arrow_schema::DataType::Float64)];
+                Ok(RecordWriter::Parquet(Box::new(crate::cli::parquet::ParquetWriter::new(writer, &header, &types)?), variants))
+            }
+            #[cfg(not(feature = "parquet"))]
+            BatchFormat::Parquet => Err(CliError::FeatureDisabled("parquet")),
+        }
+    }
+
+    /// The input columns of a row followed by the normalized variant if variants were given and the output columns.
+    fn tsv_row(row: &csv::StringRecord, variants: bool, input: &str, assessment: &Result<AcmgAssessment, AcmgError>, timestamp: &str) -> csv::StringRecord {
+        let mut out = row.clone();
+        if variants {
+            match assessment.as_ref().ok().and_then(|assessment| assessment.variant()) {
+                Some(variant) => out.extend([variant.to_string(), variant.build.to_string()]),
+                None => out.extend(["", ""]),
+            }
+        }
+        match assessment {
+            Ok(assessment) => {
+                out.extend([
+                    assessment.points().to_string(),
+                    format!("{:?}", assessment.classify()),
+                    format!("{:.3}", assessment.post_prob_path()),
+                ]);
+                out.extend(RunMetadata::new(assessment, input, timestamp).tsv_fields());
+                out.push_field(&assessment.secondary_findings().map(|secondary_findings| secondary_findings.gene).unwrap_or_default());
+            }
+            Err(_) => out.extend([""; OUTPUT_COLUMNS.len()]),
+        }
+        out
+    }
+
+    /// Writes a row with its assessment, stamped with the metadata of the run, of which `timestamp` is the start.
+    fn write(&mut self, headers: &csv::StringRecord, row: &csv::StringRecord, line: u64, input: &str, assessment: &Result<AcmgAssessment, AcmgError>, timestamp: &str) -> Result<(), CliError> {
+        match self {
+            RecordWriter::Tsv(writer, variants) => writer.write_record(&RecordWriter::tsv_row(row, *variants, input, assessment, timestamp))?,
+            RecordWriter::Jsonl(writer) => {
+                let (result, error) = match assessment {
+                    Ok(assessment) => (Some(StampedResult::new(assessment, input, timestamp)), None),
+                    Err(error) => (None, Some(error.to_string())),
+                };
+                let record = JsonRecord { line, record: Columns { headers, row }, result, error };
+                serde_json::to_writer(&mut *writer, &record).expect("records serialize to JSON");
+                writeln!(writer)?;
+            }
+            #[cfg(feature = "xlsx")]
+            RecordWriter::Xlsx(writer, variants, classification) => {
+                let comment = match assessment {
+                    Ok(assessment) => {
+                        let diagnostics: Vec<String> = assessment.diagnostics().iter()
+                            .map(|diagnostic| format!("{}: {}", diagnostic.rule.id(), diagnostic.message))
+                            .collect();
+                        Some(diagnostics.join("\n")).filter(|comment| !comment.is_empty())
+                    }
+                    Err(error) => Some(error.to_string()),
+                };
+                writer.write_row(&RecordWriter::tsv_row(row, *variants, input, assessment, timestamp), comment.map(|comment| (*classification, comment)));
+            }
+            #[cfg(feature = "parquet")]
+            RecordWriter::Parquet(writer, variants) => writer.write_row(&RecordWriter::tsv_row(row, *variants, input, assessment, timestamp))?,
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), CliError> {
+        match self {
+            RecordWriter::Tsv(writer, _) => writer.flush()?,
+            RecordWriter::Jsonl(writer) => writer.flush()?,
+            #[cfg(feature = "xlsx")]
+            RecordWriter::Xlsx(writer, _, _) => writer.finish()?,
+            #[cfg(feature = "parquet")]
+            RecordWriter::Parquet(writer, _) => writer.finish()?,
+        }
+        Ok(())
+    }
+}
+
+/// The rows which failed, with their line number and error followed by the input columns.
+struct RejectWriter(csv::Writer<File>);
+
+impl RejectWriter {
+    fn create(path: &Path, headers: &csv::StringRecord) -> Result<RejectWriter, CliError> {
+        let mut writer = csv::WriterBuilder::new().delimiter(b'\t').flexible(true).from_path(path)?;
+        let mut header = csv::StringRecord::from(vec!["line", "error"]);
+        header.extend(headers);
+        writer.write_record(&header)?;
+        Ok(RejectWriter(writer))
+    }
+
+    fn write(&mut self, line: u64, error: &str, row: Option<&csv::StringRecord>) -> Result<(), CliError> {
+        let mut out = csv::StringRecord::from(vec![line.to_string(), error.to_string()]);
+        if let Some(row) = row {
+            out.extend(row);
+        }
+        self.0.write_record(&out)?;
+        Ok(())
+    }
+}
+
+/// Parses a fraction of rows from 0 to 1, e.g. `0.05`.
+pub fn parse_error_rate(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(rate) if (0.0..=1.0).contains(&rate) => Ok(rate),
+        _ => Err(format!("{} isn't a fraction from 0 to 1, e.g. 0.05", s)),
+    }
+}
+
+/// Classifies the evidence column of every row of a CSV, TSV, xlsx or Parquet file (or TSV on stdin), appending the score, classification and
+/// posterior probability. Rows which fail to parse or can't be read are logged with their line number, and written to
+/// the rejects file rather than the output if one is given, while processing continues. The diagnostics of rows which
+/// parse are logged. Rows are classified in parallel a chunk at a time, and written in input order. The run fails if
+/// more than the maximum error rate of rows failed.
+pub fn run_batch(options: &BatchOptions) -> Result<BatchSummary, CliError> {
+    let (headers, mut records) = read_records(options)?;
+    let evidence_index = headers.iter()
+        .position(|header| header == options.evidence_column)
+        .ok_or_else(|| CliError::MissingColumn(options.evidence_column.to_string()))?;
+    let variant_index = options.variant_column
+        .map(|variant_column| headers.iter()
+            .position(|header| header == variant_column)
+            .ok_or_else(|| CliError::MissingColumn(variant_column.to_string())))
+        .transpose()?;
+    let spliceai_index = options.spliceai_column
+        .map(|spliceai_column| headers.iter()
+            .position(|header| header == spliceai_column)
+            .ok_or_else(|| CliError::MissingColumn(spliceai_column.to_string())))
+        .transpose()?;
+
+    let output: Box<dyn Write + Send> = match options.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+    let variants = variant_index.is_some() || options.scoring.variant.is_some();
+    let mut writer = RecordWriter::new(output, options.format, &headers, variants, options.sheet)?;
+    let mut rejects = options.rejects.map(|path| RejectWriter::create(path, &headers)).transpose()?;
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(options.threads).build()
+        .map_err(|error| io::Error::other(error.to_string()))?;
+    let mut summary = BatchSummary::default();
+    let mut chunk = Vec::with_capacity(CHUNK_ROWS);
+    loop {
+        chunk.clear();
+        let mut read_error = None;
+        for row in records.by_ref().take(CHUNK_ROWS) {
+            match row {
+                Ok(row) => chunk.push(Ok(row)),
+                Err(error) if matches!(error.kind(), csv::ErrorKind::Utf8 { .. }) => chunk.push(Err(error)),
+                Err(error) => {
+                    read_error = Some(error);
+                    break;
+                }
+            }
+        }
+        if chunk.is_empty() && read_error.is_none() {
+            break;
+        }
+        let assessments: Vec<Option<(Result<AcmgAssessment, AcmgError>, Duration)>> = pool.install(|| chunk.par_iter()
+            .map(|row| {
+                let row = row.as_ref().ok()?;
+                let started = Instant::now();
+                let variant = variant_index.and_then(|variant_index| row.get(variant_index));
+                let assessment = spliceai_index.and_then(|spliceai_index| row.get(spliceai_index))
+                    .filter(|spliceai| !spliceai.is_empty() && *spliceai != ".")
+                    .map(|spliceai| spliceai.parse::<f64>()
+                        .map_err(|_| AcmgError::InvalidParameter(format!("invalid SpliceAI delta score '{}'", spliceai))))
+                    .transpose()
+                    .and_then(|spliceai| parse_record(row.get(evidence_index).unwrap_or_default(), variant, spliceai, options.scoring));
+                Some((assessment, started.elapsed()))
+            })
+            .collect());
+        for (row, assessment) in chunk.iter().zip(&assessments) {
+            summary.records += 1;
+            let row = match row {
+                Ok(row) => row,
+                Err(error) => {
+                    let line = error.position().map_or(0, |position| position.line());
+                    summary.errors += 1;
+                    tracing::error!(line, "{}", error);
+                    if let Some(rejects) = &mut rejects {
+                        rejects.write(line, &error.to_string(), None)?;
+                    }
+                    continue;
+                }
+            };
+            let (assessment, elapsed) = assessment.as_ref().expect("readable rows are classified");
+            let line = row.position().map_or(0, |position| position.line());
+            log_record(line, assessment, *elapsed);
+            if let Err(error) = assessment {
+                summary.errors += 1;
+                if let Some(rejects) = &mut rejects {
+                    rejects.write(line, &error.to_string(), Some(row))?;
+                    continue;
+                }
+            }
+            writer.write(&headers, row, line, row.get(evidence_index).unwrap_or_default(), assessment, &options.scoring.started)?;
+        }
+        if let Some(error) = read_error {
+            writer.flush()?;
+            return Err(error.into());
+        }
+    }
+    writer.flush()?;
+    if let Some(rejects) = &mut rejects {
+        rejects.0.flush()?;
+    }
+    if let Some(max_error_rate) = options.max_error_rate {
+        if summary.errors as f64 > max_error_rate * summary.records as f64 {
+            return Err(CliError::ErrorRate { errors: summary.errors, records: summary.records, max_error_rate });
+        }
+    }
+    Ok(summary)
+}
+
+type Records = Box<dyn Iterator<Item = Result<csv::StringRecord, csv::Error>>>;
+
+/// The header and rows of the input, read from the sheet of an xlsx workbook (.xlsx), a Parquet file (.parquet) or as
+/// CSV or TSV.
+fn read_records(options: &BatchOptions) -> Result<(csv::StringRecord, Records), CliError> {
+    if has_extension(options.input, "xlsx") {
+        return read_xlsx(options);
+    }
+    if has_extension(options.input, "parquet") {
+        return read_parquet(options);
+    }
+    let input: Box<dyn Read> = if options.input == Path::new("-") {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(File::open(options.input)?)
+    };
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .delimiter(input_delimiter(options.input))
+        .flexible(true)
+        .from_reader(input);
+    let headers = csv_reader.headers()?.clone();
+    Ok((headers, Box::new(csv_reader.into_records())))
+}
+
+#[cfg(feature = "xlsx")]
+fn read_xlsx(options: &BatchOptions) -> Result<(csv::StringRecord, Records), CliError> {
+    let sheet = crate::cli::xlsx::read_sheet(options.input, options.sheet)?;
+    Ok((sheet.headers, Box::new(sheet.rows.into_iter().map(Ok))))
+}
+
+#[cfg(not(feature = "xlsx"))]
+fn read_xlsx(_options: &BatchOptions) -> Result<(csv::StringRecord, Records), CliError> {
+    Err(CliError::FeatureDisabled("xlsx"))
+}
+
+#[cfg(feature = "parquet")]
+fn read_parquet(options: &BatchOptions) -> Result<(csv::StringRecord, Records), CliError> {
+    let (headers, rows) = crate::cli::parquet::read_parquet(options.input)?;
+    Ok((headers, Box::new(rows)))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn read_parquet(_options: &BatchOptions) -> Result<(csv::StringRecord, Records), CliError> {
+    Err(CliError::FeatureDisabled("parquet"))
+}
+
+fn has_extension(path: &Path, expected: &str) -> bool {
+    path.extension().and_then(|extension| extension.to_str()).is_some_and(|extension| extension.eq_ignore_ascii_case(expected))
+}
+
+pub(crate) fn input_delimiter(path: &Path) -> u8 {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("csv") => b',',
+        _ => b'\t',
+    }
+}
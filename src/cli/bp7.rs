@@ -0,0 +1,65 @@
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use acmg::{assess_bp7, bedgraph_score, Bp7Input, Bp7Outcome, Bp7Thresholds, GenomeBuild, GenomicVariant};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct Bp7Report<'a> {
+    input: &'a Bp7Input,
+    evidence: Option<String>,
+    #[serde(flatten)]
+    outcome: &'a Bp7Outcome,
+}
+
+/// The scores given, with the phyloP and GERP++ scores read from bedGraph tracks at the VCF coordinates `variant` if
+/// the tracks are given.
+pub fn bp7_input(mut input: Bp7Input, phylop: Option<&Path>, gerp: Option<&Path>, variant: Option<&str>, build: GenomeBuild) -> Result<Bp7Input, CliError> {
+    let Some(variant) = variant.map(|variant| GenomicVariant::parse(variant, build)).transpose()? else {
+        return Ok(input);
+    };
+    if let Some(path) = phylop {
+        input.phylop = read_bedgraph_score(path, &variant)?;
+    }
+    if let Some(path) = gerp {
+        input.gerp = read_bedgraph_score(path, &variant)?;
+    }
+    Ok(input)
+}
+
+/// Reads the score of a bedGraph track at the variant, warning if the track has none there.
+fn read_bedgraph_score(path: &Path, variant: &GenomicVariant) -> Result<Option<f64>, CliError> {
+    let score = bedgraph_score(BufReader::new(File::open(path)?), variant)?;
+    if score.is_none() {
+        tracing::warn!("{} has no score at {}", path.display(), variant);
+    }
+    Ok(score)
+}
+
+/// Prints whether BP7 applies to a synonymous variant with the scores, and why, which can be passed on to
+/// `acmg info`.
+pub fn run_bp7(input: Bp7Input, thresholds: &Bp7Thresholds, format: ReportFormat) -> Result<(), CliError> {
+    let outcome = assess_bp7(&input, thresholds)?;
+    let evidence = outcome.evidence();
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            match &evidence {
+                Some(evidence) => writeln!(out, "{}", evidence)?,
+                None => writeln!(out, "N/A")?,
+            }
+            for reason in &outcome.reasons {
+                writeln!(out, "{}", reason)?;
+            }
+        }
+        ReportFormat::Json => {
+            let report = Bp7Report { input: &input, evidence: evidence.map(|evidence| evidence.to_string()), outcome: &outcome };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("BP7 reports serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
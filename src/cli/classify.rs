@@ -0,0 +1,46 @@
+use std::io::{self, Write};
+
+use acmg::{AcmgAssessment, HgvsVariant};
+
+use crate::cli::info::{write_text, write_tsv, Format, TSV_HEADER, VARIANT_TSV_HEADER};
+use crate::cli::metadata::{RunMetadata, StampedResult};
+use crate::cli::plot::{write_plot, PlotFormat};
+use crate::cli::{parse_assessment, CliError, Scoring};
+
+/// Classifies the evidence for a single identified variant, carrying the variant into the output. The variant is
+/// identified by HGVS, by the coordinates given with `--variant` or both. Returns the assessment.
+pub fn run_classify(hgvs: Option<&str>, acmg_evidence: &str, format: Format, plot: Option<PlotFormat>, scoring: &Scoring) -> Result<AcmgAssessment, CliError> {
+    let hgvs: Option<HgvsVariant> = hgvs.map(str::parse).transpose()?;
+    let mut assessment = parse_assessment(acmg_evidence, None, scoring)?;
+    if let Some(hgvs) = &hgvs {
+        assessment = scoring.with_hgvs(assessment, hgvs.clone());
+    }
+    let mut out = io::stdout().lock();
+    if let Some(plot) = plot {
+        if let Some(hgvs) = &hgvs {
+            writeln!(out, "Variant: {}", hgvs)?;
+        }
+        write_plot(&mut out, &assessment, plot)?;
+        return Ok(assessment);
+    }
+    match format {
+        Format::Text => write_text(&mut out, &assessment)?,
+        Format::Json => writeln!(out, "{}", serde_json::to_string(&StampedResult::new(&assessment, acmg_evidence, &scoring.started)).expect("results serialize to JSON"))?,
+        Format::Tsv => {
+            let mut header = vec![];
+            if hgvs.is_some() {
+                header.push("hgvs");
+            }
+            if assessment.variant().is_some() {
+                header.push(VARIANT_TSV_HEADER);
+            }
+            header.push(TSV_HEADER);
+            writeln!(out, "{}", header.join("\t"))?;
+            if let Some(hgvs) = &hgvs {
+                write!(out, "{}\t", hgvs)?;
+            }
+            write_tsv(&mut out, &assessment, &RunMetadata::new(&assessment, acmg_evidence, &scoring.started))?;
+        }
+    }
+    Ok(assessment)
+}
@@ -0,0 +1,67 @@
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::path::Path;
+
+use acmg::{ClinVarIndex, ClinVarRecord, ClinVarReport, VariantQuery};
+
+use crate::cli::{CliError, ReportFormat};
+
+/// Whether a file is a variant index built with `acmg index build`, rather than a ClinVar variant_summary.txt.
+fn is_variant_index(path: &Path) -> io::Result<bool> {
+    let mut magic = [0u8; 8];
+    match File::open(path)?.read_exact(&mut magic) {
+        Ok(()) => Ok(&magic == b"ACMGIDX\0"),
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(feature = "index")]
+fn indexed_report(path: &Path, assembly: &str, query: &VariantQuery) -> Result<ClinVarReport, CliError> {
+    let index = acmg::VariantIndex::open(path)?;
+    if assembly.parse::<acmg::GenomeBuild>()? != index.build() {
+        let error = format!("{} is an index of {} but the assembly is {}", path.display(), index.build(), assembly);
+        return Err(acmg::AcmgError::InvalidParameter(error).into());
+    }
+    Ok(index.clinvar_report(query))
+}
+
+#[cfg(not(feature = "index"))]
+fn indexed_report(_path: &Path, _assembly: &str, _query: &VariantQuery) -> Result<ClinVarReport, CliError> {
+    Err(CliError::FeatureDisabled("index"))
+}
+
+/// Prints the evidence suggested by previously classified variants in a local ClinVar variant_summary.txt or
+/// variant index, along with the records each suggestion is based on.
+pub fn run_clinvar(index: &Path, assembly: &str, variant: &str, format: ReportFormat) -> Result<(), CliError> {
+    let query: VariantQuery = variant.parse()?;
+    let report = if is_variant_index(index)? {
+        indexed_report(index, assembly, &query)?
+    } else {
+        ClinVarIndex::from_variant_summary(BufReader::new(File::open(index)?), assembly)?.report(&query)
+    };
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            match &report.record {
+                Some(record) => writeln!(out, "{}", format_record(record))?,
+                None => writeln!(out, "{} not found in ClinVar", variant)?,
+            }
+            for suggestion in &report.suggestions {
+                writeln!(out, "{}: {}", suggestion.code, suggestion.reason)?;
+                for record in &suggestion.records {
+                    writeln!(out, "  - {}", format_record(record))?;
+                }
+            }
+            for warning in &report.warnings {
+                writeln!(out, "warning: {}", warning)?;
+            }
+        }
+        ReportFormat::Json => writeln!(out, "{}", serde_json::to_string(&report).expect("ClinVar reports serialize to JSON"))?,
+    }
+    Ok(())
+}
+
+fn format_record(record: &ClinVarRecord) -> String {
+    format!("ClinVar {} {}: {:?} ({})", record.variation_id, record.name, record.clinical_significance, record.review_status)
+}
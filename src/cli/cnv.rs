@@ -0,0 +1,78 @@
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use acmg::{AcmgClassification, ClinGenGenes, CnvAssessment, CnvInterval, CnvType, DosageCall};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct CnvReport<'a> {
+    #[serde(flatten)]
+    assessment: &'a CnvAssessment,
+    score: f64,
+    classification: AcmgClassification,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dosage: Vec<DosageCall>,
+}
+
+/// Parses a section score given as `section=points`, e.g. `4=0.45`.
+pub fn parse_section_score(s: &str) -> Result<(u8, f64), String> {
+    let (section, points) = s.split_once('=').ok_or_else(|| format!("expected section=points, got '{}'", s))?;
+    let section = section.trim().parse().map_err(|_| format!("invalid section '{}'", section))?;
+    let points = points.trim().parse().map_err(|_| format!("invalid points '{}'", points))?;
+    Ok((section, points))
+}
+
+/// Scores the criteria, gene count and section scores given for a CNV, printing the points of each with the total
+/// score and classification. Given its interval and the ClinGen dosage sensitivity curations, section 2 is derived
+/// from the dosage sensitive genes overlapped unless it's given.
+pub fn run_cnv(cnv_type: CnvType, criteria: &str, genes: Option<u32>, section_scores: &[(u8, f64)], dosage: Option<(&CnvInterval, &Path)>, format: ReportFormat) -> Result<(), CliError> {
+    let mut assessment = CnvAssessment::parse(cnv_type, criteria)?;
+    if let Some(genes) = genes {
+        assessment = assessment.with_gene_count(genes)?;
+    }
+    for (section, points) in section_scores {
+        assessment = assessment.with_section_score(*section, *points)?;
+    }
+    let calls = match dosage {
+        Some((interval, path)) => {
+            let curations = ClinGenGenes::default().with_dosage(BufReader::new(File::open(path)?))?;
+            cnv_type.dosage_calls(interval, &curations)
+        }
+        None => vec![],
+    };
+    if !assessment.has_section(2) {
+        if let Some(code) = calls.iter().find_map(|call| call.code) {
+            assessment = assessment.with_criterion(code, None)?;
+        }
+    }
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            for call in &calls {
+                writeln!(out, "{:4}: {}", call.code.unwrap_or("-"), call.reason)?;
+            }
+            if !calls.is_empty() {
+                writeln!(out, "--------")?;
+            }
+            for evidence in &assessment.evidence {
+                let description = assessment.cnv_type.criterion(evidence.code).map_or("", |criterion| criterion.description);
+                writeln!(out, "{:4}: {:5.2} '{}'", evidence.code, evidence.points, description)?;
+            }
+            for (section, points) in &assessment.section_scores {
+                writeln!(out, "Section {}: {:.2}", section, points)?;
+            }
+            writeln!(out, "--------")?;
+            writeln!(out, "Classification: {:?}", assessment.classify())?;
+            writeln!(out, "CNV Score: {:.2}", assessment.score())?;
+        }
+        ReportFormat::Json => {
+            let report = CnvReport { assessment: &assessment, score: assessment.score(), classification: assessment.classify(), dosage: calls };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("CNV reports serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
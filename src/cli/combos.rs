@@ -0,0 +1,140 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+
+use acmg::EvidenceStrength::{self, Moderate, StandAlone, Strong, Supporting, VeryStrong};
+use acmg::{classified_combinations, evidence_combinations, AcmgAssessment, AcmgClassification, Classifier, EvidenceCombination, Guideline};
+
+use crate::cli::list::ListFormat;
+use crate::cli::CliError;
+
+/// The classifications combinations of evidence can be enumerated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Target {
+    Pathogenic,
+    LikelyPathogenic,
+    LikelyBenign,
+    Benign,
+}
+
+impl From<Target> for AcmgClassification {
+    fn from(target: Target) -> AcmgClassification {
+        match target {
+            Target::Pathogenic => AcmgClassification::Pathogenic,
+            Target::LikelyPathogenic => AcmgClassification::LikelyPathogenic,
+            Target::LikelyBenign => AcmgClassification::LikelyBenign,
+            Target::Benign => AcmgClassification::Benign,
+        }
+    }
+}
+
+/// Prints the smallest combinations of evidence strengths which reach a classification under a guideline, or with
+/// `all` every combination classified as it, as a reference for teaching and for validating lab SOPs.
+pub fn run_combos(target: Target, guideline: Guideline, classifier: Classifier, max_criteria: usize, all: bool, format: ListFormat) -> Result<(), CliError> {
+    let target = AcmgClassification::from(target);
+    let combinations = combinations(target, guideline, classifier, max_criteria, all);
+    let mut out = io::stdout().lock();
+    match format {
+        ListFormat::Table => {
+            let strengths: Vec<EvidenceStrength> = [StandAlone, VeryStrong, Strong, Moderate, Supporting].into_iter()
+                .filter(|strength| combinations.iter().any(|combination| combination.strengths.contains(strength)))
+                .collect();
+            writeln!(out, "{} under {} with up to {} criteria:", target.term(), guideline, max_criteria)?;
+            for strength in &strengths {
+                write!(out, "{:>11}", format!("{:?}", strength))?;
+            }
+            writeln!(out, "  {:>6}  Classification", "Points")?;
+            for combination in &combinations {
+                for strength in &strengths {
+                    write!(out, "{:>11}", combination.strengths.iter().filter(|other| *other == strength).count())?;
+                }
+                writeln!(out, "  {:>6}  {}", combination.points, combination.classification.term())?;
+            }
+        }
+        ListFormat::Json => writeln!(out, "{}", serde_json::to_string(&combinations).expect("combinations serialize to JSON"))?,
+    }
+    Ok(())
+}
+
+/// The combinations of evidence printed by [`run_combos`], starting from no evidence.
+fn combinations(target: AcmgClassification, guideline: Guideline, classifier: Classifier, max_criteria: usize, all: bool) -> Vec<EvidenceCombination> {
+    let assessment = AcmgAssessment::default().with_guideline(guideline).with_classifier(classifier);
+    if all {
+        classified_combinations(&assessment, target, max_criteria)
+    } else {
+        evidence_combinations(&assessment, target, max_criteria)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smallest(target: Target, guideline: Guideline) -> Vec<(Vec<EvidenceStrength>, i32)> {
+        combinations(target.into(), guideline, Classifier::Points, 4, false).into_iter()
+            .map(|combination| (combination.strengths, combination.points))
+            .collect()
+    }
+
+    fn strengths(target: Target, guideline: Guideline) -> Vec<Vec<EvidenceStrength>> {
+        smallest(target, guideline).into_iter().map(|(strengths, _)| strengths).collect()
+    }
+
+    /// The rules of Table 5 which can't be weakened, e.g. PVS1 and PS1 isn't listed as two PS already suffice.
+    #[test]
+    fn table_5_pathogenic_combinations() {
+        let expected = vec![
+            vec![Strong, Strong],
+            vec![VeryStrong, Supporting, Supporting],
+            vec![Strong, Moderate, Moderate, Moderate],
+        ];
+        assert_eq!(sorted(strengths(Target::Pathogenic, Guideline::Acmg2015)), sorted(expected));
+    }
+
+    #[test]
+    fn table_5_likely_pathogenic_combinations() {
+        let expected = vec![
+            vec![Strong, Moderate],
+            vec![Strong, Supporting, Supporting],
+            vec![Moderate, Moderate, Moderate],
+            vec![Moderate, Moderate, Supporting, Supporting],
+        ];
+        assert_eq!(sorted(strengths(Target::LikelyPathogenic, Guideline::Acmg2015)), sorted(expected));
+    }
+
+    /// BA1 alone reaches Likely benign as well as Benign, where one BS doesn't.
+    #[test]
+    fn table_5_benign_combinations() {
+        assert_eq!(sorted(strengths(Target::Benign, Guideline::Acmg2015)), sorted(vec![vec![StandAlone], vec![Strong, Strong]]));
+        assert_eq!(sorted(strengths(Target::LikelyBenign, Guideline::Acmg2015)), sorted(vec![vec![StandAlone], vec![Supporting, Supporting]]));
+    }
+
+    #[test]
+    fn points_pathogenic_combinations_reach_10_points() {
+        let mut expected = vec![
+            (vec![VeryStrong, Moderate], 10),
+            (vec![VeryStrong, Supporting, Supporting], 10),
+            (vec![Strong, Strong, Moderate], 10),
+            (vec![Strong, Strong, Supporting, Supporting], 10),
+            (vec![Strong, Moderate, Moderate, Moderate], 10),
+        ];
+        let mut found = smallest(Target::Pathogenic, Guideline::Points2020);
+        found.sort();
+        expected.sort();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn all_combinations_are_classified_as_the_target() {
+        let all = combinations(AcmgClassification::LikelyPathogenic, Guideline::Points2020, Classifier::Points, 3, true);
+        assert!(all.iter().all(|combination| (6..=9).contains(&combination.points)), "{:?}", all);
+        assert!(all.iter().any(|combination| combination.strengths == vec![VeryStrong]));
+        assert!(all.iter().any(|combination| combination.strengths == vec![Moderate, Moderate, Moderate]));
+        assert!(!all.iter().any(|combination| combination.strengths == vec![VeryStrong, Moderate]));
+    }
+
+    fn sorted(mut combinations: Vec<Vec<EvidenceStrength>>) -> Vec<Vec<EvidenceStrength>> {
+        combinations.sort();
+        combinations
+    }
+}
@@ -0,0 +1,126 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use acmg::{parse_notation, AcmgAssessment, AcmgClassification, AcmgError, AssessmentDiff, AssessmentResult, CriterionChange, GenomicVariant};
+
+use crate::cli::{prepare_assessment, CliError, ReportFormat, Scoring};
+
+/// The column of InterVar's output holding its classification and the criteria it applied.
+const INTERVAR_COLUMN: &str = "InterVar: InterVar and Evidence";
+
+/// How the classification of a variant by InterVar compares to rescoring the criteria it applied.
+#[derive(Debug, Serialize)]
+struct Comparison {
+    line: u64,
+    variant: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gene: Option<String>,
+    intervar_classification: AcmgClassification,
+    classification: AcmgClassification,
+    points: i32,
+    /// How the criteria changed when rescored, e.g. BA1 no longer applied to a listed exception.
+    changes: Vec<CriterionChange>,
+}
+
+impl Comparison {
+    fn disagrees(&self) -> bool {
+        self.intervar_classification != self.classification || !self.changes.is_empty()
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct CompareSummary {
+    variants: usize,
+    errors: usize,
+    class_disagreements: usize,
+    criteria_disagreements: usize,
+    disagreements: Vec<Comparison>,
+}
+
+/// Rescores the criteria InterVar applied to each variant of its output, e.g. `example.hg19_multianno.txt.intervar`,
+/// and reports the variants where the criteria after scoring or the classification differ from InterVar's. The
+/// variant coordinates are given to the scoring, so frequency lookups and BA1 exceptions apply. Rows which fail to
+/// parse are logged with their line number and counted.
+pub fn run_compare_intervar(intervar: &Path, format: ReportFormat, scoring: &Scoring) -> Result<(), CliError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b'\t')
+        .flexible(true)
+        .quoting(false)
+        .from_reader(File::open(intervar)?);
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| headers.iter()
+        .position(|header| header.trim().trim_start_matches('#') == name)
+        .ok_or_else(|| CliError::MissingColumn(name.to_string()));
+    let (chromosome, start, reference, alternate, evidence) = (column("Chr")?, column("Start")?, column("Ref")?, column("Alt")?, column(INTERVAR_COLUMN)?);
+    let gene = column("Ref.Gene").ok();
+    let mut summary = CompareSummary::default();
+    for row in reader.records() {
+        let row = row?;
+        let line = row.position().map_or(0, |position| position.line());
+        let field = |index: usize| row.get(index).unwrap_or_default().trim();
+        summary.variants += 1;
+        let variant = format!("{}-{}-{}-{}", field(chromosome).trim_start_matches("chr"), field(start), field(reference), field(alternate));
+        match compare(field(evidence), &variant, scoring) {
+            Ok((intervar_classification, rescored, changes)) => {
+                let comparison = Comparison {
+                    line,
+                    variant,
+                    gene: gene.map(field).filter(|gene| !gene.is_empty() && *gene != ".").map(str::to_string),
+                    intervar_classification,
+                    classification: rescored.classification,
+                    points: rescored.points,
+                    changes,
+                };
+                if comparison.intervar_classification != comparison.classification {
+                    summary.class_disagreements += 1;
+                }
+                if !comparison.changes.is_empty() {
+                    summary.criteria_disagreements += 1;
+                }
+                if comparison.disagrees() {
+                    summary.disagreements.push(comparison);
+                }
+            }
+            Err(error) => {
+                summary.errors += 1;
+                tracing::error!(line, "{}", error);
+            }
+        }
+    }
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => write_text(&mut out, &summary)?,
+        ReportFormat::Json => writeln!(out, "{}", serde_json::to_string(&summary).expect("comparisons serialize to JSON"))?,
+    }
+    Ok(())
+}
+
+/// Parses InterVar's classification and criteria, e.g. `InterVar: Likely pathogenic PVS1=1 PS=[0, 0, 0, 0, 0] ...`,
+/// returning the classification with the rescored result and how its criteria changed.
+fn compare(evidence: &str, variant: &str, scoring: &Scoring) -> Result<(AcmgClassification, AssessmentResult, Vec<CriterionChange>), AcmgError> {
+    let term = evidence.trim().trim_start_matches("InterVar:").split("PVS1").next().unwrap_or_default().trim();
+    let intervar_classification = AcmgClassification::ALL.into_iter()
+        .find(|classification| classification.term().eq_ignore_ascii_case(term))
+        .ok_or_else(|| AcmgError::InvalidParameter(format!("unknown InterVar classification '{}'", term)))?;
+    let asserted = parse_notation(evidence)?;
+    let variant = GenomicVariant::parse(variant, scoring.build).ok().map(|_| variant);
+    let rescored = prepare_assessment(AcmgAssessment::new(asserted.clone()), variant, None, scoring)?.result();
+    let changes = AssessmentDiff::between(&AcmgAssessment::new(asserted).result(), &rescored).changes;
+    Ok((intervar_classification, rescored, changes))
+}
+
+fn write_text(out: &mut impl Write, summary: &CompareSummary) -> io::Result<()> {
+    for comparison in &summary.disagreements {
+        let gene = comparison.gene.as_deref().map(|gene| format!(" ({})", gene)).unwrap_or_default();
+        writeln!(out, "{}{}, line {}: InterVar {}, rescored {} ({} points)", comparison.variant, gene, comparison.line,
+            comparison.intervar_classification.term(), comparison.classification.term(), comparison.points)?;
+        for change in &comparison.changes {
+            writeln!(out, "  {}", change)?;
+        }
+    }
+    writeln!(out, "{} variants compared, {} disagree in classification and {} in criteria, {} failed to parse",
+        summary.variants, summary.class_disagreements, summary.criteria_disagreements, summary.errors)
+}
@@ -0,0 +1,215 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use acmg::{AcmgClassification, AssessmentDiff, AssessmentResult, CriterionChange, EvidenceCode, GenomicVariant};
+
+use crate::cli::batch::input_delimiter;
+use crate::cli::{parse_assessment, CliError, ReportFormat, Scoring};
+
+/// The columns the variants and their evidence are read from.
+#[derive(Debug)]
+pub struct ReviewerColumns<'a> {
+    pub variant_column: &'a str,
+    pub evidence_column: &'a str,
+}
+
+/// The agreement of two reviewers on the variants both assessed.
+#[derive(Debug, Serialize)]
+struct Concordance {
+    variants: usize,
+    only_a: Vec<String>,
+    only_b: Vec<String>,
+    /// Rows which failed to parse, which are left out.
+    errors: usize,
+    classification: Agreement,
+    /// Whether each criterion applied by either reviewer was applied, over every variant, ignoring strength.
+    criteria: Agreement,
+    per_criterion: Vec<CriterionAgreement>,
+    discordant: Vec<Discordance>,
+}
+
+#[derive(Debug, Serialize)]
+struct Agreement {
+    concordant: usize,
+    total: usize,
+    /// Cohen's kappa, or None if chance agreement is certain, as when both reviewers only ever gave one answer.
+    kappa: Option<f64>,
+}
+
+impl Agreement {
+    fn new<T: Copy + Eq + Hash>(pairs: &[(T, T)]) -> Agreement {
+        Agreement { concordant: pairs.iter().filter(|(a, b)| a == b).count(), total: pairs.len(), kappa: cohen_kappa(pairs) }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CriterionAgreement {
+    code: String,
+    applied_by_a: usize,
+    applied_by_b: usize,
+    applied_by_both: usize,
+    /// The variants both reviewers applied the criterion to at the same strength.
+    same_strength: usize,
+    kappa: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct Discordance {
+    variant: String,
+    a: AssessmentResult,
+    b: AssessmentResult,
+    /// The criteria of reviewer B's assessment which differ from reviewer A's.
+    changes: Vec<CriterionChange>,
+}
+
+/// Cohen's kappa, the agreement of paired ratings beyond what their frequencies would give by chance.
+fn cohen_kappa<T: Copy + Eq + Hash>(pairs: &[(T, T)]) -> Option<f64> {
+    let n = pairs.len() as f64;
+    let mut a: HashMap<T, f64> = HashMap::new();
+    let mut b: HashMap<T, f64> = HashMap::new();
+    for (rating_a, rating_b) in pairs {
+        *a.entry(*rating_a).or_default() += 1.0;
+        *b.entry(*rating_b).or_default() += 1.0;
+    }
+    let observed = pairs.iter().filter(|(a, b)| a == b).count() as f64 / n;
+    let chance: f64 = a.iter().map(|(rating, count)| count / n * b.get(rating).copied().unwrap_or_default() / n).sum();
+    (!pairs.is_empty() && chance < 1.0).then(|| (observed - chance) / (1.0 - chance))
+}
+
+/// Matches the assessments of the same variants in two reviewers' CSV or TSV files, by the variant column, and
+/// reports how far their classifications and criteria agree, with the variants they disagree on for consensus
+/// review. Coordinates are normalized before matching, so `chr7:117559590:G:A` matches `7-117559590-G-A`.
+pub fn run_compare_reviewers(a: &Path, b: &Path, columns: &ReviewerColumns, format: ReportFormat, scoring: &Scoring) -> Result<(), CliError> {
+    let mut errors = 0;
+    let reviewer_a = read_assessments(a, columns, scoring, &mut errors)?;
+    let reviewer_b = read_assessments(b, columns, scoring, &mut errors)?;
+    let matched: Vec<(&String, &AssessmentResult, &AssessmentResult)> = reviewer_a.iter()
+        .filter_map(|(variant, a)| reviewer_b.get(variant).map(|b| (variant, a, b)))
+        .collect();
+
+    let classifications: Vec<(AcmgClassification, AcmgClassification)> = matched.iter().map(|(_, a, b)| (a.classification, b.classification)).collect();
+    let mut codes: Vec<&EvidenceCode> = matched.iter()
+        .flat_map(|(_, a, b)| a.evidence.iter().chain(&b.evidence))
+        .map(|evidence| &*evidence.evidence_code)
+        .collect();
+    codes.sort();
+    codes.dedup();
+    let mut criteria = vec![];
+    let mut per_criterion = vec![];
+    for code in codes {
+        let applied = |result: &AssessmentResult| result.evidence.iter().find(|evidence| evidence.evidence_code == code).map(|evidence| evidence.strength());
+        let pairs: Vec<_> = matched.iter().map(|(_, a, b)| (applied(a), applied(b))).collect();
+        let presence: Vec<(bool, bool)> = pairs.iter().map(|(a, b)| (a.is_some(), b.is_some())).collect();
+        per_criterion.push(CriterionAgreement {
+            code: code.to_string(),
+            applied_by_a: presence.iter().filter(|(a, _)| *a).count(),
+            applied_by_b: presence.iter().filter(|(_, b)| *b).count(),
+            applied_by_both: presence.iter().filter(|(a, b)| *a && *b).count(),
+            same_strength: pairs.iter().filter(|(a, b)| a.is_some() && a == b).count(),
+            kappa: cohen_kappa(&presence),
+        });
+        criteria.extend(presence);
+    }
+    let discordant = matched.iter()
+        .filter_map(|(variant, a, b)| {
+            let diff = AssessmentDiff::between(a, b);
+            (!diff.is_empty()).then(|| Discordance { variant: variant.to_string(), a: (*a).clone(), b: (*b).clone(), changes: diff.changes })
+        })
+        .collect();
+    let concordance = Concordance {
+        variants: matched.len(),
+        only_a: reviewer_a.keys().filter(|variant| !reviewer_b.contains_key(*variant)).cloned().collect(),
+        only_b: reviewer_b.keys().filter(|variant| !reviewer_a.contains_key(*variant)).cloned().collect(),
+        errors,
+        classification: Agreement::new(&classifications),
+        criteria: Agreement::new(&criteria),
+        per_criterion,
+        discordant,
+    };
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => write_text(&mut out, &concordance, a, b)?,
+        ReportFormat::Json => writeln!(out, "{}", serde_json::to_string(&concordance).expect("concordance serializes to JSON"))?,
+    }
+    Ok(())
+}
+
+/// The assessment of each variant of a reviewer's file, keeping the first of repeated variants. Rows which fail to
+/// parse are logged with their file and line number, and counted.
+fn read_assessments(path: &Path, columns: &ReviewerColumns, scoring: &Scoring, errors: &mut usize) -> Result<BTreeMap<String, AssessmentResult>, CliError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(input_delimiter(path))
+        .flexible(true)
+        .from_reader(File::open(path)?);
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|header| header == name).ok_or_else(|| CliError::MissingColumn(name.to_string()));
+    let (variant_index, evidence_index) = (column(columns.variant_column)?, column(columns.evidence_column)?);
+    let file = path.display().to_string();
+    let mut assessments = BTreeMap::new();
+    for row in reader.records() {
+        let row = row?;
+        let line = row.position().map_or(0, |position| position.line());
+        let variant = row.get(variant_index).unwrap_or_default().trim();
+        let coordinates = GenomicVariant::parse(variant, scoring.build).ok();
+        let key = coordinates.as_ref().map_or_else(|| variant.to_string(), ToString::to_string);
+        if assessments.contains_key(&key) {
+            tracing::warn!(file, line, "{} was already assessed, keeping the first assessment", key);
+            continue;
+        }
+        match parse_assessment(row.get(evidence_index).unwrap_or_default(), coordinates.is_some().then_some(variant), scoring) {
+            Ok(assessment) => {
+                assessments.insert(key, assessment.result());
+            }
+            Err(error) => {
+                *errors += 1;
+                tracing::error!(file, line, "{}", error);
+            }
+        }
+    }
+    Ok(assessments)
+}
+
+fn percent(agreement: &Agreement) -> f64 {
+    if agreement.total == 0 { 0.0 } else { 100.0 * agreement.concordant as f64 / agreement.total as f64 }
+}
+
+fn kappa(kappa: Option<f64>) -> String {
+    kappa.map_or_else(|| "n/a".to_string(), |kappa| format!("{:.2}", kappa))
+}
+
+fn evidence(result: &AssessmentResult) -> String {
+    let evidence: Vec<String> = result.evidence.iter().map(ToString::to_string).collect();
+    evidence.join(", ")
+}
+
+fn write_text(out: &mut impl Write, concordance: &Concordance, a: &Path, b: &Path) -> io::Result<()> {
+    writeln!(out, "{} variants assessed by both reviewers, {} only in {}, {} only in {}, {} rows failed to parse",
+        concordance.variants, concordance.only_a.len(), a.display(), concordance.only_b.len(), b.display(), concordance.errors)?;
+    let classification = &concordance.classification;
+    writeln!(out, "Classification: {} of {} concordant ({:.1}%), Cohen's kappa {}",
+        classification.concordant, classification.total, percent(classification), kappa(classification.kappa))?;
+    writeln!(out, "Criteria: {:.1}% concordant, Cohen's kappa {}", percent(&concordance.criteria), kappa(concordance.criteria.kappa))?;
+    if !concordance.per_criterion.is_empty() {
+        writeln!(out, "\n{:<8}{:>8}{:>8}{:>8}{:>16}{:>8}", "Code", "A", "B", "Both", "Same strength", "Kappa")?;
+        for criterion in &concordance.per_criterion {
+            writeln!(out, "{:<8}{:>8}{:>8}{:>8}{:>16}{:>8}", criterion.code, criterion.applied_by_a, criterion.applied_by_b,
+                criterion.applied_by_both, criterion.same_strength, kappa(criterion.kappa))?;
+        }
+    }
+    if !concordance.discordant.is_empty() {
+        writeln!(out, "\nDiscordant:")?;
+        for discordance in &concordance.discordant {
+            writeln!(out, "{}: {} ({}) vs {} ({})", discordance.variant, discordance.a.classification.term(), evidence(&discordance.a),
+                discordance.b.classification.term(), evidence(&discordance.b))?;
+            for change in &discordance.changes {
+                writeln!(out, "  {}", change)?;
+            }
+        }
+    }
+    Ok(())
+}
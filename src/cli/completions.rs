@@ -0,0 +1,15 @@
+use std::io::{self, Write};
+
+use clap::Command;
+use clap_complete::Shell;
+
+use crate::cli::CliError;
+
+/// Writes a completion script for the shell to stdout, completing subcommands, options, their values and the
+/// evidence codes of `describe`.
+pub fn run_completions(shell: Shell, command: &mut Command) -> Result<(), CliError> {
+    let mut script = vec![];
+    clap_complete::generate(shell, command, "acmg", &mut script);
+    io::stdout().lock().write_all(&script)?;
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{Arg, ArgAction, Command};
+use toml::{Table, Value};
+
+use acmg::{AcmgError, CodeDefinition, CodeRegistry};
+
+use crate::cli::CliError;
+
+/// The name of the project-local config file, looked for in the current directory and its parents.
+const LOCAL_CONFIG: &str = ".acmgrc";
+
+/// A config file's defaults, keyed by the long name of the option they apply to, e.g. `prior = 0.05`, and its named
+/// profiles of further defaults, e.g. `[profile.cardiolab]`.
+struct ConfigFile {
+    path: PathBuf,
+    defaults: Table,
+    profiles: Table,
+}
+
+impl ConfigFile {
+    fn read(path: &Path) -> Result<ConfigFile, CliError> {
+        let mut defaults: Table = fs::read_to_string(path)?.parse()
+            .map_err(|error: toml::de::Error| CliError::Config(format!("{}: {}", path.display(), error.message())))?;
+        let profiles = match defaults.remove("profile") {
+            Some(Value::Table(profiles)) => profiles,
+            Some(_) => return Err(CliError::Config(format!("{}: profile must be a table of profiles, e.g. [profile.cardiolab]", path.display()))),
+            None => Table::new(),
+        };
+        Ok(ConfigFile { path: path.to_path_buf(), defaults, profiles })
+    }
+}
+
+/// The user config, $XDG_CONFIG_HOME/acmg/config.toml or ~/.config/acmg/config.toml.
+pub fn user_config() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|config| config.join("acmg").join("config.toml"))
+}
+
+/// The nearest .acmgrc in the current directory or its parents.
+pub fn local_config() -> Option<PathBuf> {
+    let current = env::current_dir().ok()?;
+    current.ancestors().map(|dir| dir.join(LOCAL_CONFIG)).find(|path| path.is_file())
+}
+
+/// Sets the defaults of the command's options from the user config and any project-local .acmgrc, which takes
+/// precedence, and then from the profile selected with `--profile` or `ACMG_PROFILE` in either. Options given on the
+/// command line or by environment variable take precedence over the config. A setting is ignored by commands whose
+/// option of that name doesn't accept its value, e.g. `format = "tsv"` by commands without TSV output. The evidence
+/// codes defined in `[code.NAME]` tables are registered in the registry returned, followed by the aliases of
+/// `[alias]` tables, e.g. `PM2sup = "PM2_Supporting"`.
+pub fn configure(command: Command, args: &[OsString]) -> Result<(Command, CodeRegistry), CliError> {
+    let files = [user_config(), local_config()].into_iter()
+        .flatten()
+        .filter(|path| path.is_file())
+        .map(|path| ConfigFile::read(&path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let profile = selected_profile(args);
+
+    let mut settings: Vec<(&Path, &str, &Value)> = vec![];
+    for file in &files {
+        settings.extend(file.defaults.iter().map(|(key, value)| (file.path.as_path(), key.as_str(), value)));
+    }
+    if let Some(profile) = &profile {
+        let mut found = false;
+        for file in &files {
+            match file.profiles.get(profile) {
+                Some(Value::Table(defaults)) => {
+                    found = true;
+                    settings.extend(defaults.iter().map(|(key, value)| (file.path.as_path(), key.as_str(), value)));
+                }
+                Some(_) => return Err(CliError::Config(format!("{}: profile.{} must be a table", file.path.display(), profile))),
+                None => {}
+            }
+        }
+        if !found {
+            let profiles: Vec<&str> = files.iter().flat_map(|file| file.profiles.keys()).map(String::as_str).collect();
+            return Err(CliError::Config(format!("no profile {} in the config files, configured profiles: {}", profile, profiles.join(", "))));
+        }
+    }
+    let (codes, settings): (Vec<_>, Vec<_>) = settings.into_iter().partition(|(_, key, _)| *key == "code");
+    let (aliases, settings): (Vec<_>, Vec<_>) = settings.into_iter().partition(|(_, key, _)| *key == "alias");
+    let mut registry = CodeRegistry::new();
+    for (path, _, codes) in codes {
+        let Value::Table(codes) = codes else {
+            return Err(CliError::Config(format!("{}: code must be a table of evidence codes, e.g. [code.PM2_1]", path.display())));
+        };
+        for (name, definition) in codes {
+            let definition = code_definition(name, definition).map_err(|reason| CliError::Config(format!("{}: code.{}: {}", path.display(), name, reason)))?;
+            registry.register_code(definition)?;
+        }
+    }
+    for (path, _, aliases) in aliases {
+        let Value::Table(aliases) = aliases else {
+            return Err(CliError::Config(format!("{}: alias must be a table of aliases, e.g. PM2sup = \"PM2_Supporting\"", path.display())));
+        };
+        for (alias, evidence) in aliases {
+            let Value::String(evidence) = evidence else {
+                return Err(CliError::Config(format!("{}: alias.{} must be the evidence it stands for, e.g. \"PM2_Supporting\"", path.display(), alias)));
+            };
+            registry.register_alias(alias, evidence)?;
+        }
+    }
+
+    let mut command = command;
+    for (path, key, value) in settings {
+        let values = setting_values(value).ok_or_else(|| CliError::Config(format!("{}: {} must be a string, number, boolean or array of them", path.display(), key)))?;
+        let mut known = false;
+        command = set_default(command, key, &values, &mut known).map_err(|reason| CliError::Config(format!("{}: {}", path.display(), reason)))?;
+        if !known {
+            return Err(CliError::Config(format!("{}: unknown setting {}, expected the long name of an option e.g. prior", path.display(), key)));
+        }
+    }
+    Ok((command, registry))
+}
+
+/// The profile given with `--profile` on the command line, or else by `ACMG_PROFILE`.
+fn selected_profile(args: &[OsString]) -> Option<String> {
+    let mut args = args.iter().map(|arg| arg.to_string_lossy());
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        } else if arg == "--profile" {
+            return args.next().map(|profile| profile.to_string());
+        } else if let Some(profile) = arg.strip_prefix("--profile=") {
+            return Some(profile.to_string());
+        }
+    }
+    env::var("ACMG_PROFILE").ok()
+}
+
+/// Reads the definition of an evidence code, e.g. `category = "pathogenic"`, `strength = "supporting"`,
+/// `description = "..."` and optionally `allowed-strengths = ["supporting", "moderate"]`.
+fn code_definition(name: &str, definition: &Value) -> Result<CodeDefinition, String> {
+    let Value::Table(definition) = definition else {
+        return Err("must be a table of category, strength, description and allowed-strengths".to_string());
+    };
+    if let Some(key) = definition.keys().find(|key| !["category", "strength", "description", "allowed-strengths"].contains(&key.as_str())) {
+        return Err(format!("unknown field {}, expected category, strength, description or allowed-strengths", key));
+    }
+    let field = |key: &str| match definition.get(key) {
+        Some(Value::String(value)) => Ok(value.as_str()),
+        Some(_) => Err(format!("{} must be a string", key)),
+        None => Err(format!("{} is required", key)),
+    };
+    let allowed_strengths = match definition.get("allowed-strengths") {
+        Some(Value::Array(strengths)) => strengths.iter()
+            .map(|strength| strength.as_str().ok_or_else(|| "allowed-strengths must be strings".to_string())?.parse().map_err(|error: AcmgError| error.to_string()))
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err("allowed-strengths must be an array of strengths".to_string()),
+        None => vec![],
+    };
+    Ok(CodeDefinition {
+        name: name.to_string(),
+        category: field("category")?.parse().map_err(|error: AcmgError| error.to_string())?,
+        strength: field("strength")?.parse().map_err(|error: AcmgError| error.to_string())?,
+        description: field("description")?.to_string(),
+        allowed_strengths,
+    })
+}
+
+fn setting_values(value: &Value) -> Option<Vec<String>> {
+    let scalar = |value: &Value| match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Integer(value) => Some(value.to_string()),
+        Value::Float(value) => Some(value.to_string()),
+        Value::Boolean(value) => Some(value.to_string()),
+        _ => None,
+    };
+    match value {
+        Value::Array(values) => values.iter().map(scalar).collect(),
+        value => scalar(value).map(|value| vec![value]),
+    }
+}
+
+/// Sets the default of the option named `key` of the command and its subcommands, noting in `known` whether any
+/// have it. Values are checked here so errors name the config file they're from.
+fn set_default(mut command: Command, key: &str, values: &[String], known: &mut bool) -> Result<Command, String> {
+    let arg = command.get_arguments()
+        .find(|arg| arg.get_long() == Some(key) && !arg.is_required_set() && !arg.is_positional())
+        .cloned();
+    if let Some(arg) = arg {
+        *known = true;
+        let possible = arg.get_possible_values();
+        let accepted = values.iter().all(|value| possible.is_empty() || possible.iter().any(|possible| possible.matches(value, arg.is_ignore_case_set())));
+        if accepted {
+            if values.len() != 1 && !matches!(arg.get_action(), ArgAction::Append) {
+                return Err(format!("{} takes a single value", key));
+            }
+            let check = Arg::new(arg.get_id().clone())
+                .long(key.to_string())
+                .value_names(arg.get_value_names().unwrap_or_default().to_vec())
+                .value_parser(arg.get_value_parser().clone())
+                .value_delimiter(arg.get_value_delimiter())
+                .ignore_case(arg.is_ignore_case_set());
+            for value in values {
+                Command::new("acmg").arg(check.clone()).try_get_matches_from(["acmg".to_string(), format!("--{}={}", key, value)])
+                    .map_err(|error| error.to_string().lines().next().unwrap_or_default().trim_start_matches("error: ").to_string())?;
+            }
+            command = command.mut_arg(arg.get_id().clone(), |arg| arg.default_values(values.to_vec()));
+        }
+    }
+    let names: Vec<String> = command.get_subcommands().map(|subcommand| subcommand.get_name().to_string()).collect();
+    for name in names {
+        let mut result = Ok(());
+        command = command.mut_subcommand(&name, |subcommand| match set_default(subcommand.clone(), key, values, known) {
+            Ok(subcommand) => subcommand,
+            Err(error) => {
+                result = Err(error);
+                subcommand
+            }
+        });
+        result?;
+    }
+    Ok(command)
+}
@@ -0,0 +1,41 @@
+use std::io::{self, BufRead, BufWriter, Write};
+
+use acmg::{parse_notation, write_notation, Notation};
+
+use crate::cli::CliError;
+
+/// Prints an evidence string written in any of the notations in the target notation, or with `-` each line of stdin,
+/// writing failed lines unchanged like [`run_fmt`](crate::cli::fmt::run_fmt). The modifiers and notes the notation
+/// can't express are logged as they're dropped. Returns the number of lines which failed to parse.
+pub fn run_convert(acmg_evidence: &str, to: Notation) -> Result<usize, CliError> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut failed = 0;
+    let mut convert_line = |line: u64, input: &str| -> io::Result<()> {
+        if input.trim().is_empty() {
+            return writeln!(out);
+        }
+        match parse_notation(input).and_then(|evidence| write_notation(&evidence, to)) {
+            Ok((converted, warnings)) => {
+                for warning in warnings {
+                    tracing::warn!(line, "{}", warning);
+                }
+                writeln!(out, "{}", converted)?;
+            }
+            Err(error) => {
+                failed += 1;
+                tracing::error!(line, "{}", error);
+                writeln!(out, "{}", input)?;
+            }
+        }
+        Ok(())
+    };
+    if acmg_evidence == "-" {
+        for (index, line) in io::stdin().lock().lines().enumerate() {
+            convert_line(index as u64 + 1, &line?)?;
+        }
+    } else {
+        convert_line(1, acmg_evidence)?;
+    }
+    out.flush()?;
+    Ok(failed)
+}
@@ -0,0 +1,153 @@
+use std::io::{self, BufRead, Write};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use acmg::{validate, AcmgError, Code, Diagnostic, HgvsVariant};
+
+use crate::cli::describe::CodeDescription;
+use crate::cli::{parse_assessment, CliError, Scoring};
+
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+/// The error code of evidence which can't be classified, e.g. an unknown evidence code.
+const ACMG_ERROR: i32 = -32000;
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i32,
+    message: String,
+    /// The diagnostics of evidence which failed validation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i32, message: impl Into<String>) -> RpcError {
+        RpcError { code, message: message.into(), data: None }
+    }
+}
+
+impl From<AcmgError> for RpcError {
+    fn from(error: AcmgError) -> RpcError {
+        let data = match &error {
+            AcmgError::Diagnostics(diagnostics) => Some(json!(diagnostics)),
+            _ => None,
+        };
+        RpcError { code: ACMG_ERROR, message: error.to_string(), data }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ClassifyParams {
+    evidence: String,
+    /// VCF coordinates of the variant, in place of the daemon's `--variant`.
+    #[serde(default)]
+    variant: Option<String>,
+    #[serde(default)]
+    hgvs: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidateParams {
+    evidence: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DescribeParams {
+    code: String,
+}
+
+fn params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|error| RpcError::new(INVALID_PARAMS, format!("Invalid params: {}", error)))
+}
+
+fn call(method: &str, params_value: Value, scoring: &Scoring) -> Result<Value, RpcError> {
+    match method {
+        "classify" => {
+            let params: ClassifyParams = params(params_value)?;
+            let mut assessment = parse_assessment(&params.evidence, params.variant.as_deref(), scoring)?;
+            if let Some(hgvs) = params.hgvs {
+                assessment = scoring.with_hgvs(assessment, hgvs.parse::<HgvsVariant>()?);
+            }
+            Ok(json!(assessment.result()))
+        }
+        "validate" => {
+            let params: ValidateParams = params(params_value)?;
+            let diagnostics = validate(&params.evidence);
+            let valid = !diagnostics.iter().any(Diagnostic::is_error);
+            Ok(json!({ "valid": valid, "diagnostics": diagnostics }))
+        }
+        "describe" => {
+            let params: DescribeParams = params(params_value)?;
+            Ok(json!(CodeDescription::from(params.code.parse::<Code>()?)))
+        }
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+    }
+}
+
+/// Responds to a request, or nothing for a notification, a valid request without an id.
+fn respond(request: Value, scoring: &Scoring) -> Option<RpcResponse> {
+    let id = request.get("id").cloned();
+    let outcome = match serde_json::from_value::<RpcRequest>(request) {
+        Ok(request) if request.jsonrpc == "2.0" => {
+            let outcome = call(&request.method, request.params, scoring);
+            id.as_ref()?;
+            outcome
+        }
+        _ => Err(RpcError::new(INVALID_REQUEST, "Invalid request")),
+    };
+    let id = id.unwrap_or(Value::Null);
+    Some(match outcome {
+        Ok(result) => RpcResponse { jsonrpc: "2.0", id, result: Some(result), error: None },
+        Err(error) => RpcResponse { jsonrpc: "2.0", id, result: None, error: Some(error) },
+    })
+}
+
+/// Serves JSON-RPC 2.0 requests to the classify, validate and describe methods, one per line of stdin, writing a
+/// line of stdout for each response until stdin is closed.
+pub fn run_daemon(scoring: &Scoring) -> Result<(), CliError> {
+    let stdin = io::stdin().lock();
+    let mut out = io::stdout().lock();
+    for line in stdin.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(Value::Array(batch)) if !batch.is_empty() => {
+                let responses: Vec<RpcResponse> = batch.into_iter().filter_map(|request| respond(request, scoring)).collect();
+                if responses.is_empty() { None } else { Some(json!(responses)) }
+            }
+            Ok(Value::Array(_)) => Some(json!(RpcResponse { jsonrpc: "2.0", id: Value::Null, result: None, error: Some(RpcError::new(INVALID_REQUEST, "Invalid request")) })),
+            Ok(request) => respond(request, scoring).map(|response| json!(response)),
+            Err(error) => Some(json!(RpcResponse { jsonrpc: "2.0", id: Value::Null, result: None, error: Some(RpcError::new(PARSE_ERROR, format!("Parse error: {}", error))) })),
+        };
+        if let Some(response) = response {
+            writeln!(out, "{}", response)?;
+            out.flush()?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,556 @@
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::iter;
+use std::path::{Path, PathBuf};
+
+use clap::{Subcommand, ValueEnum};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
+use rusqlite::{params, Connection, OptionalExtension, Row, ToSql};
+use serde::Serialize;
+
+use acmg::{AssessmentDiff, AssessmentResult, CodeRegistry, GenomeBuild, GenomicVariant, HgvsVariant};
+
+use crate::cli::{parse_assessment, CliError, ReportFormat, ScoringArgs};
+
+/// The schema as migrations from each version to the next, the version being kept in the database's user_version so
+/// older databases can be upgraded.
+const MIGRATIONS: [&str; 2] = [
+    "CREATE TABLE assessments (
+        id INTEGER PRIMARY KEY,
+        variant TEXT NOT NULL,
+        build TEXT NOT NULL,
+        hgvs TEXT,
+        evidence TEXT NOT NULL,
+        points INTEGER NOT NULL,
+        classification TEXT NOT NULL,
+        post_prob_path REAL NOT NULL,
+        guideline TEXT NOT NULL,
+        spec TEXT,
+        curator TEXT NOT NULL,
+        created TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+        acmg_version TEXT NOT NULL,
+        result TEXT NOT NULL
+    );
+    CREATE INDEX assessments_variant ON assessments (variant, build);",
+    "ALTER TABLE assessments ADD COLUMN state TEXT NOT NULL DEFAULT 'draft';
+    ALTER TABLE assessments ADD COLUMN reviewer TEXT;
+    ALTER TABLE assessments ADD COLUMN reviewed TEXT;
+    ALTER TABLE assessments ADD COLUMN approver TEXT;
+    ALTER TABLE assessments ADD COLUMN approved TEXT;
+    CREATE TRIGGER approved_update BEFORE UPDATE ON assessments WHEN OLD.state = 'approved'
+    BEGIN SELECT RAISE(ABORT, 'approved assessments are locked'); END;
+    CREATE TRIGGER approved_delete BEFORE DELETE ON assessments WHEN OLD.state = 'approved'
+    BEGIN SELECT RAISE(ABORT, 'approved assessments are locked'); END;",
+];
+
+const SCHEMA_VERSION: i32 = MIGRATIONS.len() as i32;
+
+const COLUMNS: &str = "id, variant, build, hgvs, evidence, points, classification, post_prob_path, guideline, spec, curator, created, acmg_version, result, state, reviewer, reviewed, approver, approved";
+
+/// Where a stored assessment is in sign-off, from draft through review to approval, after which it's locked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssessmentState {
+    Draft,
+    Reviewed,
+    Approved,
+}
+
+impl AssessmentState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AssessmentState::Draft => "draft",
+            AssessmentState::Reviewed => "reviewed",
+            AssessmentState::Approved => "approved",
+        }
+    }
+}
+
+impl FromSql for AssessmentState {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<AssessmentState> {
+        let name = value.as_str()?;
+        [AssessmentState::Draft, AssessmentState::Reviewed, AssessmentState::Approved].into_iter()
+            .find(|state| state.name() == name)
+            .ok_or_else(|| FromSqlError::Other(format!("unknown state {}", name).into()))
+    }
+}
+
+impl ToSql for AssessmentState {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.name()))
+    }
+}
+
+impl Display for AssessmentState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DbCommand {
+    /// Creates the database, or upgrades an existing one to the current schema
+    #[command(name = "init")]
+    Init,
+    /// Classifies the evidence for a variant and stores the assessment
+    #[command(arg_required_else_help = true, name = "add")]
+    Add {
+        /// ACMG evidence string, e.g 'PS3(PMID:12345), PM2, PP3'
+        #[arg(short, long)]
+        evidence: String,
+        /// HGVS description of the variant, e.g. 'NM_000257.4:c.1208G>A'
+        #[arg(long)]
+        hgvs: Option<String>,
+        /// Who curated the evidence [default: $USER]
+        #[arg(long, env = "ACMG_CURATOR")]
+        curator: Option<String>,
+        #[command(flatten)]
+        scoring: Box<ScoringArgs>,
+    },
+    /// Shows an assessment by id, or the latest assessment of a variant
+    #[command(arg_required_else_help = true, name = "get")]
+    Get {
+        /// Assessment id, or VCF coordinates of a variant e.g. 7-117559590-G-A
+        assessment: String,
+        /// Genome build of the variant coordinates, GRCh37 or GRCh38
+        #[arg(long, default_value_t = GenomeBuild::Grch38)]
+        build: GenomeBuild,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Shows the assessments of a variant oldest first, with what changed between each and the one before
+    #[command(arg_required_else_help = true, name = "history")]
+    History {
+        /// VCF coordinates of the variant e.g. 7-117559590-G-A
+        variant: String,
+        /// Genome build of the variant coordinates, GRCh37 or GRCh38
+        #[arg(long, default_value_t = GenomeBuild::Grch38)]
+        build: GenomeBuild,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Marks a draft assessment as reviewed, by someone other than its curator
+    #[command(arg_required_else_help = true, name = "review")]
+    Review {
+        /// Assessment id
+        id: i64,
+        /// Who reviewed the assessment
+        #[arg(long)]
+        by: String,
+    },
+    /// Approves a reviewed assessment, by someone other than its reviewer, locking it against changes
+    #[command(arg_required_else_help = true, name = "approve")]
+    Approve {
+        /// Assessment id
+        id: i64,
+        /// Who approved the assessment
+        #[arg(long)]
+        by: String,
+    },
+    /// Lists the stored assessments, newest first
+    #[command(name = "list")]
+    List {
+        /// Only assessments of this variant, as VCF coordinates
+        #[arg(long)]
+        variant: Option<String>,
+        /// Genome build of the variant coordinates, GRCh37 or GRCh38
+        #[arg(long, default_value_t = GenomeBuild::Grch38)]
+        build: GenomeBuild,
+        /// Only assessments by this curator
+        #[arg(long)]
+        curator: Option<String>,
+        /// Only assessments with this classification, e.g. LikelyPathogenic
+        #[arg(long)]
+        classification: Option<String>,
+        /// Only assessments in this sign-off state
+        #[arg(long, value_enum)]
+        state: Option<AssessmentState>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Writes every stored assessment, oldest first
+    #[command(name = "export")]
+    Export {
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = DbExportFormat::Jsonl)]
+        format: DbExportFormat,
+        /// Output file [default: stdout]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DbExportFormat {
+    /// One JSON object per assessment, including the full result
+    Jsonl,
+    /// One row per assessment, without the full result
+    Tsv,
+}
+
+/// An assessment as stored, with who made, reviewed and approved it and when.
+#[derive(Debug, Serialize)]
+pub struct StoredAssessment {
+    pub id: i64,
+    /// The normalized VCF coordinates of the variant.
+    pub variant: String,
+    pub build: String,
+    pub hgvs: Option<String>,
+    /// The evidence string as it was given.
+    pub evidence: String,
+    pub points: i32,
+    pub classification: String,
+    pub post_prob_path: f64,
+    pub guideline: String,
+    pub spec: Option<String>,
+    pub curator: String,
+    /// When the assessment was stored, in UTC.
+    pub created: String,
+    /// The version of acmg the assessment was classified with.
+    pub acmg_version: String,
+    pub result: AssessmentResult,
+    pub state: AssessmentState,
+    pub reviewer: Option<String>,
+    pub reviewed: Option<String>,
+    pub approver: Option<String>,
+    pub approved: Option<String>,
+}
+
+impl StoredAssessment {
+    fn from_row(row: &Row) -> rusqlite::Result<StoredAssessment> {
+        let result: String = row.get(13)?;
+        let result = serde_json::from_str(&result)
+            .map_err(|error| rusqlite::Error::FromSqlConversionFailure(13, rusqlite::types::Type::Text, Box::new(error)))?;
+        Ok(StoredAssessment {
+            id: row.get(0)?,
+            variant: row.get(1)?,
+            build: row.get(2)?,
+            hgvs: row.get(3)?,
+            evidence: row.get(4)?,
+            points: row.get(5)?,
+            classification: row.get(6)?,
+            post_prob_path: row.get(7)?,
+            guideline: row.get(8)?,
+            spec: row.get(9)?,
+            curator: row.get(10)?,
+            created: row.get(11)?,
+            acmg_version: row.get(12)?,
+            result,
+            state: row.get(14)?,
+            reviewer: row.get(15)?,
+            reviewed: row.get(16)?,
+            approver: row.get(17)?,
+            approved: row.get(18)?,
+        })
+    }
+}
+
+/// An assessment in a variant's history, with what changed since the one before.
+#[derive(Debug, Serialize)]
+struct HistoryEntry<'a> {
+    assessment: &'a StoredAssessment,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<&'a AssessmentDiff>,
+}
+
+/// The SQLite database of assessments.
+#[derive(Debug)]
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    /// Opens the database, creating it or upgrading its schema if needed.
+    pub fn init(path: &Path) -> Result<Store, CliError> {
+        let connection = Connection::open(path)?;
+        let version: i32 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version > SCHEMA_VERSION {
+            return Err(CliError::Store(format!("{} was created by a newer version of acmg", path.display())));
+        }
+        for (version, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+            connection.execute_batch(&format!("BEGIN; {} PRAGMA user_version = {}; COMMIT;", migration, version + 1))?;
+        }
+        Ok(Store { connection })
+    }
+
+    /// Opens an existing database.
+    pub fn open(path: &Path) -> Result<Store, CliError> {
+        if !path.exists() {
+            return Err(CliError::Store(format!("{} doesn't exist, create it with acmg db init", path.display())));
+        }
+        let connection = Connection::open(path)?;
+        let version: i32 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version != SCHEMA_VERSION {
+            return Err(CliError::Store(format!("{} has schema version {}, upgrade it with acmg db init", path.display(), version)));
+        }
+        Ok(Store { connection })
+    }
+
+    /// Stores an assessment of a variant, returning its id.
+    pub fn add(&self, variant: &GenomicVariant, evidence: &str, result: &AssessmentResult, curator: &str) -> Result<i64, CliError> {
+        self.connection.execute(
+            "INSERT INTO assessments (variant, build, hgvs, evidence, points, classification, post_prob_path, guideline, spec, curator, acmg_version, result)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                variant.to_string(),
+                variant.build.name(),
+                result.hgvs.as_ref().map(HgvsVariant::to_string),
+                evidence.trim(),
+                result.points,
+                format!("{:?}", result.classification),
+                result.post_prob_path,
+                result.guideline.name(),
+                result.spec,
+                curator,
+                env!("CARGO_PKG_VERSION"),
+                serde_json::to_string(result).expect("results serialize to JSON"),
+            ],
+        )?;
+        Ok(self.connection.last_insert_rowid())
+    }
+
+    pub fn get(&self, id: i64) -> Result<Option<StoredAssessment>, CliError> {
+        let sql = format!("SELECT {} FROM assessments WHERE id = ?1", COLUMNS);
+        Ok(self.connection.query_row(&sql, [id], StoredAssessment::from_row).optional()?)
+    }
+
+    /// The assessments matching the filter, newest first.
+    pub fn list(&self, filter: &AssessmentFilter) -> Result<Vec<StoredAssessment>, CliError> {
+        let sql = format!(
+            "SELECT {} FROM assessments
+             WHERE (?1 IS NULL OR (variant = ?1 AND build = ?2)) AND (?3 IS NULL OR curator = ?3)
+               AND (?4 IS NULL OR classification = ?4 COLLATE NOCASE) AND (?5 IS NULL OR state = ?5)
+             ORDER BY id DESC",
+            COLUMNS,
+        );
+        let mut statement = self.connection.prepare(&sql)?;
+        let variant = filter.variant;
+        let rows = statement.query_map(
+            params![variant.map(GenomicVariant::to_string), variant.map(|variant| variant.build.name()), filter.curator, filter.classification, filter.state],
+            StoredAssessment::from_row,
+        )?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Marks a draft assessment as reviewed. Curators can't review their own assessments.
+    pub fn review(&self, id: i64, by: &str) -> Result<(), CliError> {
+        let stored = self.get(id)?.ok_or_else(|| CliError::Store(format!("No assessment found for {}", id)))?;
+        if stored.state != AssessmentState::Draft {
+            return Err(CliError::Store(format!("Assessment {} is already {}", id, stored.state)));
+        }
+        if stored.curator == by {
+            return Err(CliError::Store(format!("Assessment {} was curated by {}, so must be reviewed by someone else", id, by)));
+        }
+        self.transition(id, AssessmentState::Draft, AssessmentState::Reviewed, "reviewer = ?4, reviewed", by)
+    }
+
+    /// Approves a reviewed assessment, after which it's locked. Reviewers can't approve assessments they reviewed.
+    pub fn approve(&self, id: i64, by: &str) -> Result<(), CliError> {
+        let stored = self.get(id)?.ok_or_else(|| CliError::Store(format!("No assessment found for {}", id)))?;
+        match stored.state {
+            AssessmentState::Draft => return Err(CliError::Store(format!("Assessment {} must be reviewed before it's approved", id))),
+            AssessmentState::Approved => return Err(CliError::Store(format!("Assessment {} is already approved", id))),
+            AssessmentState::Reviewed => {}
+        }
+        if stored.reviewer.as_deref() == Some(by) {
+            return Err(CliError::Store(format!("Assessment {} was reviewed by {}, so must be approved by someone else", id, by)));
+        }
+        self.transition(id, AssessmentState::Reviewed, AssessmentState::Approved, "approver = ?4, approved", by)
+    }
+
+    /// Moves an assessment between states, recording who by and when in `columns`, failing if another process moved
+    /// it first.
+    fn transition(&self, id: i64, from: AssessmentState, to: AssessmentState, columns: &str, by: &str) -> Result<(), CliError> {
+        let sql = format!(
+            "UPDATE assessments SET state = ?3, {} = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') WHERE id = ?1 AND state = ?2",
+            columns,
+        );
+        match self.connection.execute(&sql, params![id, from, to, by])? {
+            0 => Err(CliError::Store(format!("Assessment {} is no longer {}", id, from))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Which assessments to list, every assessment by default.
+#[derive(Debug, Default)]
+pub struct AssessmentFilter<'a> {
+    pub variant: Option<&'a GenomicVariant>,
+    pub curator: Option<&'a str>,
+    pub classification: Option<&'a str>,
+    pub state: Option<AssessmentState>,
+}
+
+/// Runs a `db` subcommand against the database at `path`.
+pub fn run_db(path: &Path, command: DbCommand, registry: &CodeRegistry) -> Result<(), CliError> {
+    match command {
+        DbCommand::Init => {
+            Store::init(path)?;
+            tracing::info!("Initialized {}", path.display());
+            Ok(())
+        }
+        DbCommand::Add { evidence, hgvs, curator, scoring } => {
+            let curator = curator.or_else(|| env::var("USER").ok())
+                .ok_or_else(|| CliError::Store("give the curator with --curator or ACMG_CURATOR".to_string()))?;
+            let store = Store::open(path)?;
+            let scoring = scoring.resolve(registry)?;
+            let variant = scoring.variant.clone()
+                .ok_or_else(|| CliError::Store("give the variant being assessed with --variant".to_string()))?;
+            let mut assessment = parse_assessment(&evidence, None, &scoring)?;
+            if let Some(hgvs) = hgvs {
+                assessment = scoring.with_hgvs(assessment, hgvs.parse::<HgvsVariant>()?);
+            }
+            let id = store.add(&variant, &evidence, &assessment.result(), &curator)?;
+            println!("{}", id);
+            Ok(())
+        }
+        DbCommand::Get { assessment, build, format } => {
+            let store = Store::open(path)?;
+            let stored = match assessment.parse::<i64>() {
+                Ok(id) => store.get(id)?,
+                Err(_) => store.list(&AssessmentFilter { variant: Some(&GenomicVariant::parse(&assessment, build)?), ..AssessmentFilter::default() })?.into_iter().next(),
+            };
+            let stored = stored.ok_or_else(|| CliError::Store(format!("No assessment found for {}", assessment)))?;
+            let mut out = io::stdout().lock();
+            match format {
+                ReportFormat::Text => write_assessment(&mut out, &stored)?,
+                ReportFormat::Json => writeln!(out, "{}", serde_json::to_string(&stored).expect("assessments serialize to JSON"))?,
+            }
+            Ok(())
+        }
+        DbCommand::History { variant, build, format } => {
+            let store = Store::open(path)?;
+            let variant = GenomicVariant::parse(&variant, build)?;
+            let mut assessments = store.list(&AssessmentFilter { variant: Some(&variant), ..AssessmentFilter::default() })?;
+            if assessments.is_empty() {
+                return Err(CliError::Store(format!("No assessment found for {}", variant)));
+            }
+            assessments.reverse();
+            let diffs: Vec<Option<AssessmentDiff>> = iter::once(None)
+                .chain(assessments.windows(2).map(|pair| Some(AssessmentDiff::between(&pair[0].result, &pair[1].result))))
+                .collect();
+            let mut out = io::stdout().lock();
+            match format {
+                ReportFormat::Text => {
+                    for (stored, diff) in assessments.iter().zip(&diffs) {
+                        writeln!(out, "Assessment {} ({}) by {} at {}: {} ({} points) {}",
+                                 stored.id, stored.state, stored.curator, stored.created, stored.result.classification.term(), stored.points, stored.evidence)?;
+                        if let Some(diff) = diff {
+                            for line in diff.to_string().lines() {
+                                writeln!(out, "    {}", line)?;
+                            }
+                        }
+                    }
+                }
+                ReportFormat::Json => {
+                    let history: Vec<HistoryEntry> = assessments.iter().zip(&diffs)
+                        .map(|(assessment, diff)| HistoryEntry { assessment, diff: diff.as_ref() })
+                        .collect();
+                    writeln!(out, "{}", serde_json::to_string(&history).expect("assessments serialize to JSON"))?;
+                }
+            }
+            Ok(())
+        }
+        DbCommand::Review { id, by } => {
+            Store::open(path)?.review(id, &by)?;
+            tracing::info!("Assessment {} reviewed by {}", id, by);
+            Ok(())
+        }
+        DbCommand::Approve { id, by } => {
+            Store::open(path)?.approve(id, &by)?;
+            tracing::info!("Assessment {} approved by {}", id, by);
+            Ok(())
+        }
+        DbCommand::List { variant, build, curator, classification, state, format } => {
+            let store = Store::open(path)?;
+            let variant = variant.map(|variant| GenomicVariant::parse(&variant, build)).transpose()?;
+            let filter = AssessmentFilter { variant: variant.as_ref(), curator: curator.as_deref(), classification: classification.as_deref(), state };
+            let assessments = store.list(&filter)?;
+            let mut out = io::stdout().lock();
+            match format {
+                ReportFormat::Text => {
+                    writeln!(out, "{:>5}  {:24} {:22} {:>6}  {:16} {:9} Created", "Id", "Variant", "Classification", "Points", "Curator", "State")?;
+                    for stored in &assessments {
+                        writeln!(out, "{:>5}  {:24} {:22} {:>6}  {:16} {:9} {}",
+                                 stored.id, stored.variant, stored.classification, stored.points, stored.curator, stored.state, stored.created)?;
+                    }
+                }
+                ReportFormat::Json => writeln!(out, "{}", serde_json::to_string(&assessments).expect("assessments serialize to JSON"))?,
+            }
+            Ok(())
+        }
+        DbCommand::Export { format, output } => {
+            let store = Store::open(path)?;
+            let mut assessments = store.list(&AssessmentFilter::default())?;
+            assessments.reverse();
+            let out: Box<dyn Write> = match output {
+                Some(output) => Box::new(BufWriter::new(File::create(output)?)),
+                None => Box::new(io::stdout().lock()),
+            };
+            export(out, &assessments, format)
+        }
+    }
+}
+
+fn write_assessment(out: &mut impl Write, stored: &StoredAssessment) -> io::Result<()> {
+    writeln!(out, "Assessment {} of {} ({})", stored.id, stored.variant, stored.build)?;
+    if let Some(hgvs) = &stored.hgvs {
+        writeln!(out, "HGVS: {}", hgvs)?;
+    }
+    writeln!(out, "Evidence: {}", stored.evidence)?;
+    writeln!(out, "Classification: {} ({} points, {})", stored.classification, stored.points, stored.guideline)?;
+    if let Some(spec) = &stored.spec {
+        writeln!(out, "Specification: {}", spec)?;
+    }
+    writeln!(out, "Curated by {} at {} with acmg {}", stored.curator, stored.created, stored.acmg_version)?;
+    if let (Some(reviewer), Some(reviewed)) = (&stored.reviewer, &stored.reviewed) {
+        writeln!(out, "Reviewed by {} at {}", reviewer, reviewed)?;
+    }
+    if let (Some(approver), Some(approved)) = (&stored.approver, &stored.approved) {
+        writeln!(out, "Approved by {} at {}", approver, approved)?;
+    }
+    Ok(())
+}
+
+fn export(mut out: impl Write, assessments: &[StoredAssessment], format: DbExportFormat) -> Result<(), CliError> {
+    match format {
+        DbExportFormat::Jsonl => {
+            for stored in assessments {
+                writeln!(out, "{}", serde_json::to_string(stored).expect("assessments serialize to JSON"))?;
+            }
+        }
+        DbExportFormat::Tsv => {
+            let mut writer = csv::WriterBuilder::new().delimiter(b'\t').from_writer(out);
+            writer.write_record(["id", "variant", "build", "hgvs", "evidence", "points", "classification", "post_prob_path", "guideline", "spec", "curator", "created", "acmg_version", "state", "reviewer", "reviewed", "approver", "approved"])?;
+            for stored in assessments {
+                writer.write_record([
+                    stored.id.to_string(),
+                    stored.variant.clone(),
+                    stored.build.clone(),
+                    stored.hgvs.clone().unwrap_or_default(),
+                    stored.evidence.clone(),
+                    stored.points.to_string(),
+                    stored.classification.clone(),
+                    format!("{:.3}", stored.post_prob_path),
+                    stored.guideline.clone(),
+                    stored.spec.clone().unwrap_or_default(),
+                    stored.curator.clone(),
+                    stored.created.clone(),
+                    stored.acmg_version.clone(),
+                    stored.state.to_string(),
+                    stored.reviewer.clone().unwrap_or_default(),
+                    stored.reviewed.clone().unwrap_or_default(),
+                    stored.approver.clone().unwrap_or_default(),
+                    stored.approved.clone().unwrap_or_default(),
+                ])?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use acmg::{DeNovoObservations, EvidenceStrength};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct DeNovoReport {
+    observations: DeNovoObservations,
+    points: f64,
+    evidence: Option<String>,
+    strength: Option<EvidenceStrength>,
+}
+
+/// Prints the PS2/PM6 strength recommended for the de novo observations, which can be passed on to `acmg info`.
+pub fn run_de_novo(observations: DeNovoObservations, format: ReportFormat) -> Result<(), CliError> {
+    let evidence = observations.evidence();
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            match &evidence {
+                Some(evidence) => writeln!(out, "{}", evidence)?,
+                None => writeln!(out, "N/A")?,
+            }
+            writeln!(out, "{} points", observations.points())?;
+        }
+        ReportFormat::Json => {
+            let report = DeNovoReport { observations, points: observations.points(), evidence: evidence.map(|evidence| evidence.to_string()), strength: observations.strength() };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("de novo reports serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
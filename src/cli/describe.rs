@@ -0,0 +1,95 @@
+use std::ffi::OsStr;
+use std::io::{self, Write};
+
+use clap::builder::{PossibleValue, TypedValueParser};
+use clap::{Arg, Command};
+use serde::Serialize;
+
+use acmg::{guidance, Category, Code, EvidenceStrength, POINTS_CITATION};
+
+use crate::cli::CliError;
+
+/// Parses an evidence code as [`Code`]'s `FromStr`, listing the built-in codes for shell completions.
+#[derive(Debug, Clone, Copy)]
+pub struct CodeParser;
+
+impl TypedValueParser for CodeParser {
+    type Value = Code;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<Code, clap::Error> {
+        (|value: &str| value.parse::<Code>()).parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(Code::ALL.into_iter().map(|code| PossibleValue::new(code.as_str()).help(code.evidence_code().description.as_ref()))))
+    }
+}
+
+/// The description of an evidence code as JSON.
+#[derive(Debug, Serialize)]
+pub struct CodeDescription {
+    code: String,
+    category: Category,
+    strength: EvidenceStrength,
+    points: i32,
+    description: &'static str,
+    allowed_strengths: Vec<AllowedStrength>,
+    caveats: &'static [&'static str],
+    citations: Vec<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+struct AllowedStrength {
+    strength: EvidenceStrength,
+    points: i32,
+    default: bool,
+}
+
+impl From<Code> for CodeDescription {
+    fn from(code: Code) -> CodeDescription {
+        let evidence_code = code.evidence_code();
+        let guidance = guidance(code);
+        let sign = if evidence_code.category == Category::Benign { -1 } else { 1 };
+        CodeDescription {
+            code: code.to_string(),
+            category: evidence_code.category,
+            strength: evidence_code.strength,
+            points: evidence_code.points(),
+            description: &evidence_code.description,
+            allowed_strengths: guidance.allowed_strengths.iter()
+                .map(|strength| AllowedStrength { strength: *strength, points: sign * strength.points(), default: *strength == evidence_code.strength })
+                .collect(),
+            caveats: guidance.caveats,
+            citations: guidance.citations.iter().copied().chain([POINTS_CITATION]).collect(),
+        }
+    }
+}
+
+/// Prints the definition, allowed strengths, caveats and citations for a single evidence code.
+pub fn run_describe(code: Code) -> Result<(), CliError> {
+    let evidence_code = code.evidence_code();
+    let guidance = guidance(code);
+    let mut out = io::stdout().lock();
+    writeln!(out, "{}: {:?} {:?} ({} points)", code, evidence_code.category, evidence_code.strength, evidence_code.points())?;
+    writeln!(out, "{}", evidence_code.description)?;
+    writeln!(out)?;
+    writeln!(out, "Allowed strengths:")?;
+    let sign = if evidence_code.category == Category::Benign { -1 } else { 1 };
+    for strength in guidance.allowed_strengths {
+        let default = if *strength == evidence_code.strength { " (default)" } else { "" };
+        writeln!(out, "  {:10} {:2}{}", format!("{:?}", strength), sign * strength.points(), default)?;
+    }
+    if !guidance.caveats.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "Caveats:")?;
+        for caveat in guidance.caveats {
+            writeln!(out, "  - {}", caveat)?;
+        }
+    }
+    writeln!(out)?;
+    writeln!(out, "References:")?;
+    for citation in guidance.citations.iter().chain([&POINTS_CITATION]) {
+        writeln!(out, "  - {}", citation)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use acmg::{AssessmentDiff, AssessmentResult};
+
+use crate::cli::{parse_assessment, CliError, ReportFormat, Scoring};
+
+/// Shows what changed between two assessments, each either the id of a stored assessment or an evidence string
+/// which is scored.
+pub fn run_diff(old: &str, new: &str, db: &Path, format: ReportFormat, scoring: &Scoring) -> Result<(), CliError> {
+    let diff = AssessmentDiff::between(&assessment(old, db, scoring)?, &assessment(new, db, scoring)?);
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => writeln!(out, "{}", diff)?,
+        ReportFormat::Json => writeln!(out, "{}", serde_json::to_string(&diff).expect("diffs serialize to JSON"))?,
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "db"), allow(unused_variables))]
+fn assessment(assessment: &str, db: &Path, scoring: &Scoring) -> Result<AssessmentResult, CliError> {
+    match assessment.trim().parse::<i64>() {
+        #[cfg(feature = "db")]
+        Ok(id) => crate::cli::db::Store::open(db)?.get(id)?
+            .map(|stored| stored.result)
+            .ok_or_else(|| CliError::Store(format!("No assessment found for {}", id))),
+        #[cfg(not(feature = "db"))]
+        Ok(_) => Err(CliError::FeatureDisabled("db")),
+        Err(_) => Ok(parse_assessment(assessment, None, scoring)?.result()),
+    }
+}
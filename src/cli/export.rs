@@ -0,0 +1,278 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use acmg::{AcmgAssessment, AcmgClassification, AcmgError, GenomeBuild, HgvsVariant, Inheritance};
+
+use crate::cli::{parse_assessment, CliError, Scoring};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Tsv,
+    Json,
+}
+
+/// The condition and curation details of a ClinVar submission, which aren't part of the evidence.
+#[derive(Debug)]
+pub struct ClinVarOptions<'a> {
+    pub hgvs: Option<&'a str>,
+    pub evidence: &'a str,
+    /// The condition as an ontology identifier, e.g. MONDO:0007254.
+    pub condition_id: Option<&'a str>,
+    pub condition: &'a str,
+    /// The date the variant was last evaluated, as YYYY-MM-DD.
+    pub date_evaluated: Option<&'a str>,
+    pub format: ExportFormat,
+    pub scoring: &'a Scoring,
+}
+
+/// A record of the Variant sheet of the ClinVar submission spreadsheet.
+#[derive(Debug, Default, Serialize)]
+struct ClinVarSubmission {
+    reference_sequence: Option<String>,
+    hgvs: Option<String>,
+    chromosome: Option<String>,
+    start: Option<u64>,
+    stop: Option<u64>,
+    reference_allele: Option<String>,
+    alternate_allele: Option<String>,
+    assembly: Option<String>,
+    condition_id_type: Option<String>,
+    condition_id_value: Option<String>,
+    preferred_condition_name: String,
+    mode_of_inheritance: Option<String>,
+    clinical_significance: &'static str,
+    date_last_evaluated: Option<String>,
+    assertion_method: String,
+    assertion_method_citation: &'static str,
+    comment_on_clinical_significance: String,
+}
+
+const TSV_HEADER: [&str; 17] = [
+    "Reference sequence", "HGVS", "Chromosome", "Start", "Stop", "Reference allele", "Alternate allele", "Assembly",
+    "Condition ID type", "Condition ID value", "Preferred condition name", "Mode of inheritance",
+    "Clinical significance", "Date last evaluated", "Assertion method", "Assertion method citation",
+    "Comment on clinical significance",
+];
+
+impl ClinVarSubmission {
+    fn new(assessment: &AcmgAssessment, options: &ClinVarOptions) -> ClinVarSubmission {
+        let mut submission = ClinVarSubmission {
+            preferred_condition_name: options.condition.to_string(),
+            mode_of_inheritance: assessment.inheritance().map(clinvar_inheritance),
+            clinical_significance: assessment.allele_classification().map_or(assessment.classify().term(), |allele_classification| allele_classification.term()),
+            date_last_evaluated: options.date_evaluated.map(str::to_string),
+            assertion_method: assertion_method(assessment),
+            assertion_method_citation: assessment.guideline().citation(),
+            comment_on_clinical_significance: evidence_summary(assessment),
+            ..ClinVarSubmission::default()
+        };
+        if let Some(hgvs) = assessment.hgvs() {
+            let description = hgvs.to_string();
+            submission.reference_sequence = Some(hgvs.reference_sequence.clone());
+            submission.hgvs = description.split_once(':').map(|(_, change)| change.to_string());
+        }
+        if let Some(variant) = assessment.variant() {
+            submission.chromosome = Some(variant.chromosome.clone());
+            submission.start = Some(variant.position);
+            submission.stop = Some(variant.position + variant.reference.len() as u64 - 1);
+            submission.reference_allele = Some(variant.reference.clone());
+            submission.alternate_allele = Some(variant.alternate.clone());
+            submission.assembly = Some(variant.build.to_string());
+        }
+        if let Some(condition_id) = options.condition_id {
+            let (id_type, value) = condition_id.split_once(':').unwrap_or(("", condition_id));
+            submission.condition_id_type = Some(id_type.to_string());
+            submission.condition_id_value = Some(value.to_string());
+        }
+        submission
+    }
+
+    fn tsv_fields(&self) -> [String; 17] {
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        let number = |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_default();
+        [
+            text(&self.reference_sequence), text(&self.hgvs), text(&self.chromosome), number(self.start),
+            number(self.stop), text(&self.reference_allele), text(&self.alternate_allele), text(&self.assembly),
+            text(&self.condition_id_type), text(&self.condition_id_value), self.preferred_condition_name.clone(),
+            text(&self.mode_of_inheritance), self.clinical_significance.to_string(), text(&self.date_last_evaluated),
+            self.assertion_method.clone(), self.assertion_method_citation.to_string(),
+            self.comment_on_clinical_significance.clone(),
+        ].map(|field| field.replace(['\t', '\n', '\r'], " "))
+    }
+}
+
+/// The guideline and any criteria specification the classification was made under.
+fn assertion_method(assessment: &AcmgAssessment) -> String {
+    match assessment.spec() {
+        Some(spec) => format!("ACMG/AMP {} with the {} specification", assessment.guideline().label(), spec.label()),
+        None => format!("ACMG/AMP {}", assessment.guideline().label()),
+    }
+}
+
+/// The ClinVar term of the mode of inheritance given with `--inheritance`, e.g. `Autosomal dominant inheritance`.
+fn clinvar_inheritance(inheritance: Inheritance) -> String {
+    let name = inheritance.name();
+    format!("{}{} inheritance", name[..1].to_uppercase(), &name[1..])
+}
+
+/// Summarises the classification and each applied criterion with its strength and any note and citations, e.g.
+/// `PS3 (Strong; MAVE assay; PMID:30311383)`, and whether the gene is an ACMG secondary findings gene.
+fn evidence_summary(assessment: &AcmgAssessment) -> String {
+    let criteria: Vec<String> = assessment.evidence()
+        .map(|evidence| {
+            let details: Vec<String> = std::iter::once(format!("{:?}", evidence.strength()))
+                .chain(evidence.note.clone())
+                .chain(evidence.citations.iter().map(ToString::to_string))
+                .collect();
+            format!("{} ({})", evidence, details.join("; "))
+        })
+        .collect();
+    let summary = format!("Classified as {} with {} points (posterior probability of pathogenicity {:.3}) under the {} guideline. Criteria applied: {}.",
+                          assessment.classify().term(), assessment.points(), assessment.post_prob_path(), assessment.guideline(), criteria.join(", "));
+    match assessment.secondary_findings() {
+        Some(secondary_findings) => format!("{} {}.", summary, secondary_findings),
+        None => summary,
+    }
+}
+
+/// Writes a ClinVar submission record for the classification of a variant, with the assertion criteria and a
+/// summary of the evidence as the comment on clinical significance.
+pub fn run_export_clinvar(options: &ClinVarOptions) -> Result<(), CliError> {
+    if let Some(date) = options.date_evaluated {
+        let valid = date.len() == 10 && date.char_indices().all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() });
+        if !valid {
+            return Err(AcmgError::InvalidParameter(format!("date last evaluated must be YYYY-MM-DD, got {}", date)).into());
+        }
+    }
+    let mut assessment = parse_assessment(options.evidence, None, options.scoring)?;
+    if let Some(hgvs) = options.hgvs {
+        assessment = options.scoring.with_hgvs(assessment, hgvs.parse::<HgvsVariant>()?);
+    }
+    if assessment.hgvs().is_none() && assessment.variant().is_none() {
+        return Err(AcmgError::InvalidParameter("a variant is required, given with --hgvs or --variant".to_string()).into());
+    }
+    let submission = ClinVarSubmission::new(&assessment, options);
+    let mut out = io::stdout().lock();
+    match options.format {
+        ExportFormat::Tsv => {
+            writeln!(out, "{}", TSV_HEADER.join("\t"))?;
+            writeln!(out, "{}", submission.tsv_fields().join("\t"))?;
+        }
+        ExportFormat::Json => writeln!(out, "{}", serde_json::to_string(&submission).expect("submissions serialize to JSON"))?,
+    }
+    Ok(())
+}
+
+const LOINC: &str = "http://loinc.org";
+
+fn loinc(code: &str, display: &str) -> Value {
+    json!({ "coding": [{ "system": LOINC, "code": code, "display": display }] })
+}
+
+/// The LOINC answer for a classification, from the answer list of 53037-8.
+fn clinical_significance(classification: AcmgClassification) -> Value {
+    let code = match classification {
+        AcmgClassification::Pathogenic => "LA6668-3",
+        AcmgClassification::LikelyPathogenic => "LA26332-9",
+        AcmgClassification::UncertainSignificance => "LA26333-7",
+        AcmgClassification::LikelyBenign => "LA26334-5",
+        AcmgClassification::Benign => "LA6675-8",
+    };
+    loinc(code, classification.term())
+}
+
+/// The components of a variant Observation of the HL7 Genomics Reporting implementation guide describing the variant.
+fn variant_components(assessment: &AcmgAssessment) -> Vec<Value> {
+    let mut components = vec![];
+    if let Some(hgvs) = assessment.hgvs() {
+        components.push(json!({ "code": loinc("48004-6", "DNA change (c.HGVS)"), "valueCodeableConcept": { "text": hgvs.to_string() } }));
+        if let Some(transcript) = hgvs.transcript() {
+            components.push(json!({ "code": loinc("51958-7", "Transcript reference sequence [ID]"), "valueCodeableConcept": { "text": transcript } }));
+        }
+    }
+    if let Some(variant) = assessment.variant() {
+        let assembly = match variant.build {
+            GenomeBuild::Grch37 => loinc("LA14029-5", "GRCh37"),
+            GenomeBuild::Grch38 => loinc("LA26806-2", "GRCh38"),
+        };
+        components.push(json!({ "code": loinc("62374-4", "Human reference sequence assembly version"), "valueCodeableConcept": assembly }));
+        components.push(json!({ "code": loinc("48000-4", "Chromosome (Autosomal, X, Y, Mitochondrial)"), "valueCodeableConcept": { "text": variant.chromosome } }));
+        components.push(json!({
+            "code": loinc("81254-5", "Variant exact start-end"),
+            "valueRange": { "low": { "value": variant.position }, "high": { "value": variant.position + variant.reference.len() as u64 - 1 } },
+        }));
+        components.push(json!({ "code": loinc("69547-8", "Genomic reference allele [ID]"), "valueString": variant.reference }));
+        components.push(json!({ "code": loinc("69551-0", "Genomic alternate allele [ID]"), "valueString": variant.alternate }));
+    }
+    components
+}
+
+/// Builds a FHIR R4 collection Bundle of a variant Observation and a diagnostic implication Observation derived
+/// from it, carrying the classification and each applied criterion with its strength, points and any note.
+fn fhir_bundle(assessment: &AcmgAssessment) -> Value {
+    let laboratory = json!([{ "coding": [{ "system": "http://terminology.hl7.org/CodeSystem/observation-category", "code": "laboratory" }] }]);
+    let variant = json!({
+        "resourceType": "Observation",
+        "id": "variant",
+        "meta": { "profile": ["http://hl7.org/fhir/uv/genomics-reporting/StructureDefinition/variant"] },
+        "status": "final",
+        "category": laboratory,
+        "code": loinc("69548-6", "Genetic variant assessment"),
+        "valueCodeableConcept": loinc("LA9633-4", "Present"),
+        "component": variant_components(assessment),
+    });
+    let mut components = vec![json!({
+        "code": loinc("53037-8", "Genetic variation clinical significance [Imp]"),
+        "valueCodeableConcept": clinical_significance(assessment.classify()),
+    })];
+    components.extend(assessment.evidence().map(|evidence| {
+        let mut component = json!({
+            "code": { "text": "ACMG criterion" },
+            "valueCodeableConcept": { "text": evidence.to_string() },
+            "extension": [
+                { "url": "urn:acmg:criterion-strength", "valueString": format!("{:?}", evidence.strength()) },
+                { "url": "urn:acmg:criterion-points", "valueInteger": evidence.points_with(assessment.point_system()) },
+            ],
+        });
+        let extensions = component["extension"].as_array_mut().expect("extensions are an array");
+        if let Some(note) = &evidence.note {
+            extensions.push(json!({ "url": "urn:acmg:criterion-note", "valueString": note }));
+        }
+        extensions.extend(evidence.citations.iter().map(|citation| json!({ "url": "urn:acmg:criterion-citation", "valueUri": citation.url() })));
+        component
+    }));
+    let implication = json!({
+        "resourceType": "Observation",
+        "id": "diagnostic-implication",
+        "meta": { "profile": ["http://hl7.org/fhir/uv/genomics-reporting/StructureDefinition/diagnostic-implication"] },
+        "status": "final",
+        "category": laboratory,
+        "code": { "coding": [{ "system": "http://hl7.org/fhir/uv/genomics-reporting/CodeSystem/tbd-codes-cs", "code": "diagnostic-implication" }] },
+        "derivedFrom": [{ "reference": "Observation/variant" }],
+        "method": { "text": assertion_method(assessment) },
+        "note": [{ "text": evidence_summary(assessment) }],
+        "component": components,
+    });
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            { "fullUrl": "Observation/variant", "resource": variant },
+            { "fullUrl": "Observation/diagnostic-implication", "resource": implication },
+        ],
+    })
+}
+
+/// Writes the classification of a variant as a FHIR Bundle of Genomics Reporting Observations.
+pub fn run_export_fhir(hgvs: Option<&str>, acmg_evidence: &str, scoring: &Scoring) -> Result<(), CliError> {
+    let mut assessment = parse_assessment(acmg_evidence, None, scoring)?;
+    if let Some(hgvs) = hgvs {
+        assessment = scoring.with_hgvs(assessment, hgvs.parse::<HgvsVariant>()?);
+    }
+    let mut out = io::stdout().lock();
+    writeln!(out, "{}", serde_json::to_string_pretty(&fhir_bundle(&assessment)).expect("bundles serialize to JSON"))?;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use std::io::{self, Read, Write};
+
+use acmg::extract_evidence;
+
+use crate::cli::{CliError, ReportFormat};
+
+/// Prints the canonical evidence string of the codes mentioned in free text, or with `-` in stdin, followed by the
+/// span of each mention. Returns false if no codes were found.
+pub fn run_extract(text: &str, format: ReportFormat) -> Result<bool, CliError> {
+    let text = if text == "-" {
+        let mut text = String::new();
+        io::stdin().lock().read_to_string(&mut text)?;
+        text
+    } else {
+        text.to_string()
+    };
+    let extraction = extract_evidence(&text);
+    for warning in &extraction.warnings {
+        tracing::warn!("{}", warning);
+    }
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            writeln!(out, "{}", extraction.acmg_evidence)?;
+            for span in &extraction.spans {
+                writeln!(out, "  {}-{}: '{}' as {}", span.start, span.end, span.text, span.evidence)?;
+            }
+        }
+        ReportFormat::Json => writeln!(out, "{}", serde_json::to_string(&extraction).expect("extraction serializes to JSON"))?,
+    }
+    Ok(!extraction.evidence.is_empty())
+}
@@ -0,0 +1,45 @@
+use std::io::{self, BufRead, BufWriter, Write};
+
+use acmg::CodeRegistry;
+
+use crate::cli::CliError;
+
+/// Prints an evidence string in canonical form, or with `-` each line of stdin, writing failed lines unchanged. With
+/// `check` nothing is printed and the lines which aren't in canonical form are logged instead, so curation
+/// spreadsheets can be checked in CI. Empty lines are left as they are. Returns the number of lines which failed to
+/// parse or, with `check`, weren't in canonical form.
+pub fn run_fmt(acmg_evidence: &str, check: bool, registry: &CodeRegistry) -> Result<usize, CliError> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut failed = 0;
+    let mut format_line = |line: u64, input: &str| -> io::Result<()> {
+        let formatted = match input.trim() {
+            "" => Ok(String::new()),
+            input => registry.format_evidence(input),
+        };
+        match formatted {
+            Ok(formatted) if check && formatted != input => {
+                failed += 1;
+                tracing::warn!(line, "'{}' isn't in canonical form, expected '{}'", input, formatted);
+            }
+            Ok(_) if check => {}
+            Ok(formatted) => writeln!(out, "{}", formatted)?,
+            Err(error) => {
+                failed += 1;
+                tracing::error!(line, "{}", error);
+                if !check {
+                    writeln!(out, "{}", input)?;
+                }
+            }
+        }
+        Ok(())
+    };
+    if acmg_evidence == "-" {
+        for (index, line) in io::stdin().lock().lines().enumerate() {
+            format_line(index as u64 + 1, &line?)?;
+        }
+    } else {
+        format_line(1, acmg_evidence)?;
+    }
+    out.flush()?;
+    Ok(failed)
+}
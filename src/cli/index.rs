@@ -0,0 +1,37 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use flate2::read::MultiGzDecoder;
+
+use acmg::{GenomeBuild, IndexBuilder};
+
+use crate::cli::CliError;
+
+/// Opens a file, decompressing it if it's gzip or bgzip compressed as gnomAD and ClinVar distribute them.
+fn open(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    let file = File::open(path)?;
+    Ok(match path.extension().and_then(|extension| extension.to_str()) {
+        Some("gz" | "bgz") => Box::new(BufReader::new(MultiGzDecoder::new(file))),
+        _ => Box::new(BufReader::new(file)),
+    })
+}
+
+/// Builds a variant index for offline lookups from gnomAD sites VCFs and the ClinVar variant_summary.txt. The index
+/// is written beside the output and renamed into place, so servers with the old index open aren't disturbed.
+pub fn run_index_build(gnomad: &[PathBuf], clinvar: Option<&Path>, build: GenomeBuild, output: &Path) -> Result<(), CliError> {
+    let mut builder = IndexBuilder::new(build);
+    for path in gnomad {
+        let variants = builder.add_gnomad_vcf(open(path)?)?;
+        tracing::info!("Read {} variants from {}", variants, path.display());
+    }
+    if let Some(path) = clinvar {
+        let records = builder.add_clinvar(open(path)?)?;
+        tracing::info!("Read {} {} records from {}", records, build, path.display());
+    }
+    let partial = output.with_extension("partial");
+    let (frequencies, records) = builder.write(BufWriter::new(File::create(&partial)?))?;
+    fs::rename(&partial, output)?;
+    tracing::info!("Indexed {} allele frequencies and {} ClinVar records in {}", frequencies, records, output.display());
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use std::io::{self, BufRead, BufWriter, Write};
+use std::time::Instant;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use acmg::{AcmgAssessment, Category, Evidence, GenomicVariant, Guideline, PointSystem};
+
+use crate::cli::logging::log_record;
+use crate::cli::metadata::{RunMetadata, StampedResult};
+use crate::cli::plot::{write_plot, PlotFormat};
+use crate::cli::{parse_assessment, CliError, Scoring};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Tsv,
+}
+
+pub const TSV_HEADER: &str = "evidence\tpoints\tclassification\tpost_prob_path\tguideline\tacmg_version\tparameters\tinput_sha256\ttimestamp\tsecondary_findings";
+
+/// Leading TSV columns for the normalized coordinates, when a variant is given.
+pub const VARIANT_TSV_HEADER: &str = "variant\tbuild";
+
+/// Prints the full scoring breakdown for a single evidence string, or a plot of it. Returns the assessment.
+pub fn run_info(acmg_evidence: &str, format: Format, plot: Option<PlotFormat>, scoring: &Scoring) -> Result<AcmgAssessment, CliError> {
+    let assessment = parse_assessment(acmg_evidence, None, scoring)?;
+    let mut out = io::stdout().lock();
+    if let Some(plot) = plot {
+        write_plot(&mut out, &assessment, plot)?;
+        return Ok(assessment);
+    }
+    match format {
+        Format::Text => write_text(&mut out, &assessment)?,
+        Format::Json => writeln!(out, "{}", serde_json::to_string(&StampedResult::new(&assessment, acmg_evidence, &scoring.started)).expect("results serialize to JSON"))?,
+        Format::Tsv => {
+            write_tsv_header(&mut out, scoring)?;
+            write_tsv(&mut out, &assessment, &RunMetadata::new(&assessment, acmg_evidence, &scoring.started))?;
+        }
+    }
+    Ok(assessment)
+}
+
+pub fn write_text(out: &mut impl Write, assessment: &AcmgAssessment) -> io::Result<()> {
+    if let Some(hgvs) = assessment.hgvs() {
+        writeln!(out, "Variant: {}", hgvs)?;
+    }
+    if let Some(variant) = assessment.variant() {
+        writeln!(out, "Coordinates: {} ({})", variant, variant.build)?;
+    }
+    let evidence_set = assessment.evidence_set();
+    let system = assessment.point_system();
+    for (category, points) in [(Category::Pathogenic, evidence_set.pathogenic_points_with(system)), (Category::Benign, evidence_set.benign_points_with(system))] {
+        let mut evidence: Vec<&Evidence> = assessment.evidence().filter(|evidence| evidence.evidence_code.category == category).collect();
+        if evidence.is_empty() {
+            continue;
+        }
+        evidence.sort_by_key(|evidence| evidence.strength());
+        writeln!(out, "{:?}: {:+}", category, points)?;
+        let mut strength = None;
+        for evidence in evidence {
+            if strength != Some(evidence.strength()) {
+                strength = Some(evidence.strength());
+                writeln!(out, "  {:?}", evidence.strength())?;
+            }
+            writeln!(out, "    {:4}:{:2} '{}'", evidence, evidence.points_with(system), evidence.evidence_code.description)?;
+            if let Some(note) = &evidence.note {
+                writeln!(out, "          Note: {}", note)?;
+            }
+            if !evidence.citations.is_empty() {
+                let citations: Vec<String> = evidence.citations.iter().map(ToString::to_string).collect();
+                writeln!(out, "          Cited: {}", citations.join(", "))?;
+            }
+        }
+    }
+    writeln!(out, "--------")?;
+    if assessment.guideline() != Guideline::Points2020 {
+        writeln!(out, "Guideline: {}", assessment.guideline().label())?;
+    }
+    if let Some(spec) = assessment.spec() {
+        writeln!(out, "Specification: {}", spec.label())?;
+    }
+    if let Some(inheritance) = assessment.inheritance() {
+        writeln!(out, "Inheritance: {} ({})", inheritance, inheritance.abbreviation())?;
+    }
+    if *system != PointSystem::default() {
+        writeln!(out, "Point system: {}", system)?;
+    }
+    let label = match assessment.allele_classification() {
+        Some(allele_classification) => {
+            writeln!(out, "Classification: {}", allele_classification)?;
+            "ACMG classification"
+        }
+        None => "Classification",
+    };
+    match (assessment.vus_temperature(), assessment.conflicting_evidence()) {
+        (Some(temperature), Some(conflicting)) => writeln!(out, "{}: {:?} ({:?}, {})", label, assessment.classify(), temperature, conflicting)?,
+        (Some(temperature), None) => writeln!(out, "{}: {:?} ({:?})", label, assessment.classify(), temperature)?,
+        (None, Some(conflicting)) => writeln!(out, "{}: {:?} ({})", label, assessment.classify(), conflicting)?,
+        (None, None) => writeln!(out, "{}: {:?}", label, assessment.classify())?,
+    }
+    for disagreement in assessment.disagreements() {
+        writeln!(out, "Classification ({}): {:?} - disagrees", disagreement.method, disagreement.classification)?;
+    }
+    if let Some(secondary_findings) = assessment.secondary_findings() {
+        match secondary_findings.is_reportable(assessment.classify()) {
+            true => writeln!(out, "Secondary findings: {}, reportable as a secondary finding", secondary_findings)?,
+            false => writeln!(out, "Secondary findings: {}", secondary_findings)?,
+        }
+    }
+    writeln!(out, "ACMG Score: {} ({:+} pathogenic, {} benign)", assessment.points(), evidence_set.pathogenic_points_with(system), evidence_set.benign_points_with(system))?;
+    writeln!(out, "Post Prob Path: {:.3}", assessment.post_prob_path())?;
+    for diagnostic in assessment.diagnostics() {
+        writeln!(out, "{}", diagnostic)?;
+    }
+    Ok(())
+}
+
+fn write_tsv_header(out: &mut impl Write, scoring: &Scoring) -> io::Result<()> {
+    match scoring.variant {
+        Some(_) => writeln!(out, "{}\t{}", VARIANT_TSV_HEADER, TSV_HEADER),
+        None => writeln!(out, "{}", TSV_HEADER),
+    }
+}
+
+/// Writes the assessment as a TSV row, led by the variant columns if it has coordinates and followed by the
+/// metadata of the run.
+pub fn write_tsv(out: &mut impl Write, assessment: &AcmgAssessment, metadata: &RunMetadata) -> io::Result<()> {
+    if let Some(variant) = assessment.variant() {
+        write!(out, "{}\t{}\t", variant, variant.build)?;
+    }
+    let secondary_findings = assessment.secondary_findings().map(|secondary_findings| secondary_findings.gene).unwrap_or_default();
+    writeln!(out, "{}\t{}\t{:?}\t{:.3}\t{}\t{}", assessment.evidence_set(), assessment.points(), assessment.classify(), assessment.post_prob_path(), metadata.tsv_fields().join("\t"), secondary_findings)
+}
+
+/// Writes the row of a line which failed to parse, with the input in the evidence column and the other columns of the
+/// header empty.
+fn write_tsv_error(out: &mut impl Write, input: &str, variant: Option<&GenomicVariant>) -> io::Result<()> {
+    if let Some(variant) = variant {
+        write!(out, "{}\t{}\t", variant, variant.build)?;
+    }
+    writeln!(out, "{}{}", input, "\t".repeat(TSV_HEADER.split('\t').count() - 1))
+}
+
+/// The guideline and version the assessment was classified with and any criteria specification, e.g.
+/// `points2020 (Tavtigian et al. 2020), MYH7 v1.0 specification`, stamped into tabular output.
+pub fn guideline_stamp(assessment: &AcmgAssessment) -> String {
+    match assessment.spec() {
+        Some(spec) => format!("{}, {} specification", assessment.guideline().label(), spec.label()),
+        None => assessment.guideline().label(),
+    }
+}
+
+#[derive(Serialize)]
+struct StreamRecord<'a> {
+    input: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<StampedResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Classifies one evidence string per line of stdin, writing exactly one output line per input line so the
+/// results can be pasted back against the input. Lines which fail to parse, and the diagnostics of those which
+/// don't, are logged and failed lines produce an empty result. Returns the number of lines which failed.
+pub fn run_info_stream(format: Format, scoring: &Scoring) -> Result<usize, CliError> {
+    let stdin = io::stdin().lock();
+    let mut out = BufWriter::new(io::stdout().lock());
+    if format == Format::Tsv {
+        write_tsv_header(&mut out, scoring)?;
+    }
+    let mut errors = 0;
+    for (index, line) in stdin.lines().enumerate() {
+        let line = line?;
+        let input = line.trim();
+        let started = Instant::now();
+        let assessment = parse_assessment(input, None, scoring);
+        log_record(index as u64 + 1, &assessment, started.elapsed());
+        if assessment.is_err() {
+            errors += 1;
+        }
+        match (format, &assessment) {
+            (Format::Text, Ok(assessment)) => {
+                writeln!(out, "{}: {:?} (score {}, post prob path {:.3})",
+                         assessment.evidence_set(), assessment.classify(), assessment.points(), assessment.post_prob_path())?;
+            }
+            (Format::Text, Err(error)) => writeln!(out, "{}: error: {}", input, error)?,
+            (Format::Tsv, Ok(assessment)) => write_tsv(&mut out, assessment, &RunMetadata::new(assessment, input, &scoring.started))?,
+            (Format::Tsv, Err(_)) => write_tsv_error(&mut out, input, scoring.variant.as_ref())?,
+            (Format::Json, _) => {
+                let record = match &assessment {
+                    Ok(assessment) => StreamRecord { input, result: Some(StampedResult::new(assessment, input, &scoring.started)), error: None },
+                    Err(error) => StreamRecord { input, result: None, error: Some(error.to_string()) },
+                };
+                serde_json::to_writer(&mut out, &record).expect("records serialize to JSON");
+                writeln!(out)?;
+            }
+        }
+    }
+    out.flush()?;
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_lines_have_a_field_for_each_column_of_the_header() {
+        let variant = GenomicVariant::parse("17-43045712-G-A", acmg::GenomeBuild::Grch38).unwrap();
+        for (header, variant) in [(TSV_HEADER.to_string(), None), (format!("{}\t{}", VARIANT_TSV_HEADER, TSV_HEADER), Some(&variant))] {
+            let mut out = vec![];
+            write_tsv_error(&mut out, "XYZ", variant).unwrap();
+            let row = String::from_utf8(out).unwrap();
+            assert_eq!(row.trim_end_matches('\n').split('\t').count(), header.split('\t').count(), "{:?}", row);
+            assert!(row.contains("XYZ"));
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use acmg::{guidance, AcmgAssessment, AcmgError, AssessmentResult, Code, Evidence};
+
+use crate::cli::{parse_assessment, prepare_assessment, CliError, Scoring};
+
+const HELP: &str = "Tab switch pane  Enter apply code  ←/→ strength  n note  Del remove  Ctrl-S save  Ctrl-O load  Esc quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Picker,
+    Applied,
+    Note,
+}
+
+/// The state of an interactive session: the code picker and its search, the applied evidence and any note being
+/// edited.
+struct Interactive<'a> {
+    scoring: &'a Scoring,
+    file: Option<PathBuf>,
+    query: String,
+    picker: ListState,
+    applied: Vec<Evidence>,
+    selected: ListState,
+    focus: Focus,
+    note: String,
+    status: String,
+    quit: bool,
+}
+
+/// Curates evidence in a terminal UI, scoring it as codes are applied, re-weighted and annotated. The assessment is
+/// loaded from and saved to `file` as assessment JSON, starting from `evidence` if the file doesn't exist yet.
+pub fn run_interactive(file: Option<&Path>, evidence: Option<&str>, scoring: &Scoring) -> Result<(), CliError> {
+    let mut interactive = Interactive {
+        scoring,
+        file: file.map(Path::to_path_buf),
+        query: String::new(),
+        picker: ListState::default().with_selected(Some(0)),
+        applied: vec![],
+        selected: ListState::default(),
+        focus: Focus::Picker,
+        note: String::new(),
+        status: HELP.to_string(),
+        quit: false,
+    };
+    match (file, evidence) {
+        (Some(file), _) if file.exists() => interactive.load()?,
+        (_, Some(evidence)) => interactive.applied = parse_assessment(evidence, None, scoring)?.evidence().cloned().collect(),
+        _ => {}
+    }
+    let mut terminal = ratatui::try_init()?;
+    let result = interactive.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl Interactive<'_> {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<(), CliError> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The codes matching the search, by name or description.
+    fn matches(&self) -> Vec<Code> {
+        let query = self.query.to_lowercase();
+        Code::ALL.into_iter()
+            .filter(|code| code.as_str().to_lowercase().contains(&query) || code.evidence_code().description.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    fn assessment(&self) -> Result<AssessmentResult, AcmgError> {
+        let assessment = AcmgAssessment::new(self.applied.iter().cloned().collect());
+        Ok(prepare_assessment(assessment, None, None, self.scoring)?.result())
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match (self.focus, key.code) {
+            (Focus::Note, KeyCode::Enter) => {
+                if let Some(evidence) = self.selected.selected().and_then(|index| self.applied.get_mut(index)) {
+                    evidence.note = Some(self.note.trim().to_string()).filter(|note| !note.is_empty());
+                }
+                self.focus = Focus::Applied;
+            }
+            (Focus::Note, KeyCode::Esc) => self.focus = Focus::Applied,
+            (Focus::Note, KeyCode::Backspace) => {
+                self.note.pop();
+            }
+            (Focus::Note, KeyCode::Char(c)) if !control => self.note.push(c),
+            (_, KeyCode::Char('c')) if control => self.quit = true,
+            (_, KeyCode::Char('s')) if control => self.save(),
+            (_, KeyCode::Char('o')) if control => {
+                if let Err(error) = self.load() {
+                    self.status = error.to_string();
+                }
+            }
+            (Focus::Note, _) => {}
+            (_, KeyCode::Esc) => self.quit = true,
+            (Focus::Picker, KeyCode::Tab) if !self.applied.is_empty() => {
+                self.focus = Focus::Applied;
+                if self.selected.selected().is_none() {
+                    self.selected.select(Some(0));
+                }
+            }
+            (Focus::Applied, KeyCode::Tab) => self.focus = Focus::Picker,
+            (Focus::Picker, KeyCode::Up) => self.picker.select_previous(),
+            (Focus::Picker, KeyCode::Down) => self.picker.select_next(),
+            (Focus::Picker, KeyCode::Enter) => {
+                if let Some(code) = self.picker.selected().and_then(|index| self.matches().get(index).copied()) {
+                    self.apply(code);
+                }
+            }
+            (Focus::Picker, KeyCode::Backspace) => {
+                self.query.pop();
+                self.picker.select(Some(0));
+            }
+            (Focus::Picker, KeyCode::Char(c)) => {
+                self.query.push(c);
+                self.picker.select(Some(0));
+            }
+            (Focus::Applied, KeyCode::Up) => self.selected.select_previous(),
+            (Focus::Applied, KeyCode::Down) => self.selected.select_next(),
+            (Focus::Applied, KeyCode::Left | KeyCode::Char('-')) => self.reweight(1),
+            (Focus::Applied, KeyCode::Right | KeyCode::Char('+')) => self.reweight(-1),
+            (Focus::Applied, KeyCode::Char('n')) => {
+                if let Some(evidence) = self.selected.selected().and_then(|index| self.applied.get(index)) {
+                    self.note = evidence.note.clone().unwrap_or_default();
+                    self.focus = Focus::Note;
+                }
+            }
+            (Focus::Applied, KeyCode::Delete | KeyCode::Backspace | KeyCode::Char('d')) => {
+                if let Some(index) = self.selected.selected().filter(|index| *index < self.applied.len()) {
+                    self.applied.remove(index);
+                    if self.applied.is_empty() {
+                        self.focus = Focus::Picker;
+                        self.selected.select(None);
+                    } else {
+                        self.selected.select(Some(index.min(self.applied.len() - 1)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies a code at its default strength, or selects it if it's already applied.
+    fn apply(&mut self, code: Code) {
+        let index = match self.applied.iter().position(|evidence| evidence.evidence_code == code.evidence_code()) {
+            Some(index) => index,
+            None => {
+                self.applied.push(Evidence::from(code));
+                self.applied.len() - 1
+            }
+        };
+        self.selected.select(Some(index));
+    }
+
+    /// Moves the selected evidence through the strengths allowed for its code, which run strongest first.
+    fn reweight(&mut self, step: isize) {
+        let Some(evidence) = self.selected.selected().and_then(|index| self.applied.get_mut(index)) else { return };
+        let Some(code) = Code::ALL.into_iter().find(|code| code.evidence_code() == evidence.evidence_code) else { return };
+        let allowed = guidance(code).allowed_strengths;
+        let index = allowed.iter().position(|strength| *strength == evidence.strength()).map_or(0, |index| index as isize + step);
+        if let Some(strength) = usize::try_from(index).ok().and_then(|index| allowed.get(index)) {
+            let (note, citations) = (evidence.note.take(), std::mem::take(&mut evidence.citations));
+            *evidence = Evidence::at_strength(code, *strength);
+            evidence.note = note;
+            evidence.citations = citations;
+        }
+    }
+
+    fn save(&mut self) {
+        let Some(file) = &self.file else {
+            self.status = "Give a file to save to when starting acmg interactive".to_string();
+            return;
+        };
+        self.status = match self.assessment() {
+            Ok(result) => match fs::write(file, serde_json::to_string_pretty(&result).expect("results serialize to JSON")) {
+                Ok(()) => format!("Saved {}", file.display()),
+                Err(error) => format!("Unable to save {}: {}", file.display(), error),
+            },
+            Err(error) => format!("Not saved: {}", error),
+        };
+    }
+
+    fn load(&mut self) -> Result<(), CliError> {
+        let Some(file) = &self.file else {
+            return Err(CliError::Acmg(AcmgError::InvalidParameter("give a file to load from when starting acmg interactive".to_string())));
+        };
+        let result: AssessmentResult = serde_json::from_str(&fs::read_to_string(file)?)
+            .map_err(|error| AcmgError::InvalidParameter(format!("unable to load {}: {}", file.display(), error)))?;
+        self.applied = result.evidence;
+        self.selected.select(if self.applied.is_empty() { None } else { Some(0) });
+        self.status = format!("Loaded {}", file.display());
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(frame.area());
+        let [picker, applied, score] = Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(25), Constraint::Percentage(30)]).areas(main);
+        let highlight = Style::new().add_modifier(Modifier::REVERSED);
+
+        let codes: Vec<ListItem> = self.matches().into_iter()
+            .map(|code| ListItem::new(format!("{:5} {}", code.as_str(), code.evidence_code().description)))
+            .collect();
+        let codes = List::new(codes)
+            .block(Block::bordered().title(format!("Codes, type to search: {}", self.query)))
+            .highlight_style(if self.focus == Focus::Picker { highlight } else { Style::new() });
+        frame.render_stateful_widget(codes, picker, &mut self.picker);
+
+        let evidence: Vec<ListItem> = self.applied.iter()
+            .map(|evidence| match &evidence.note {
+                Some(note) => ListItem::new(format!("{} ({})", evidence, note)),
+                None => ListItem::new(evidence.to_string()),
+            })
+            .collect();
+        let evidence = List::new(evidence)
+            .block(Block::bordered().title("Applied"))
+            .highlight_style(if self.focus == Focus::Picker { Style::new() } else { highlight });
+        frame.render_stateful_widget(evidence, applied, &mut self.selected);
+
+        let lines: Vec<Line> = match self.assessment() {
+            Ok(result) => {
+                let mut lines = vec![
+                    Line::from(result.classification.term()).style(Style::new().add_modifier(Modifier::BOLD)),
+                    Line::from(format!("{} points, posterior {:.3}", result.points, result.post_prob_path)),
+                    Line::from(format!("Guideline: {}", result.guideline)),
+                ];
+                lines.extend(result.diagnostics.iter().map(|diagnostic| Line::from(diagnostic.to_string())));
+                lines
+            }
+            Err(error) => vec![Line::from(error.to_string())],
+        };
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }).block(Block::bordered().title("Classification")), score);
+
+        let (title, text) = match self.focus {
+            Focus::Note => ("Note, Enter to keep  Esc to cancel", self.note.as_str()),
+            _ => ("acmg interactive", self.status.as_str()),
+        };
+        frame.render_widget(Paragraph::new(text).block(Block::bordered().title(title)), status);
+    }
+}
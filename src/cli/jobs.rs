@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use acmg::AssessmentResult;
+
+use crate::cli::serve::{classify, error_response, AppState, ClassifyRequest, ErrorBody, EvidenceInput};
+
+/// The largest batch accepted by `POST /jobs`.
+pub const MAX_JOB_BYTES: usize = 256 * 1024 * 1024;
+
+/// Finished jobs are kept for `GET /jobs/{id}` until there are more than this many, when the oldest are dropped.
+const MAX_FINISHED_JOBS: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    /// The job stopped before classifying every record.
+    Failed,
+}
+
+/// The classification of a record of a job, as a line of `GET /jobs/{id}/results`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JobRecord {
+    /// The line of the record in the batch.
+    line: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<AssessmentResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug)]
+struct Job {
+    status: JobStatus,
+    total: usize,
+    errors: usize,
+    records: Vec<JobRecord>,
+}
+
+/// The status of a job, with the location of its results once completed.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JobSummary {
+    id: u64,
+    status: JobStatus,
+    /// The number of records in the batch.
+    total: usize,
+    /// The number of records classified so far, of which `errors` failed.
+    processed: usize,
+    errors: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    results: Option<String>,
+}
+
+/// The batch jobs submitted to the server.
+#[derive(Debug, Default)]
+pub struct Jobs {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<u64, Job>>,
+}
+
+impl Jobs {
+    fn start(&self, total: usize) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut jobs = self.jobs.lock().expect("jobs lock");
+        let mut finished: Vec<u64> = jobs.iter().filter(|(_, job)| job.status != JobStatus::Running).map(|(id, _)| *id).collect();
+        if finished.len() >= MAX_FINISHED_JOBS {
+            finished.sort_unstable();
+            for id in &finished[..=finished.len() - MAX_FINISHED_JOBS] {
+                jobs.remove(id);
+            }
+        }
+        jobs.insert(id, Job { status: JobStatus::Running, total, errors: 0, records: Vec::with_capacity(total) });
+        id
+    }
+
+    fn record(&self, id: u64, record: JobRecord) {
+        if let Some(job) = self.jobs.lock().expect("jobs lock").get_mut(&id) {
+            if record.error.is_some() {
+                job.errors += 1;
+            }
+            job.records.push(record);
+        }
+    }
+
+    fn finish(&self, id: u64, status: JobStatus) {
+        if let Some(job) = self.jobs.lock().expect("jobs lock").get_mut(&id) {
+            job.status = status;
+        }
+    }
+
+    fn summary(&self, id: u64) -> Option<JobSummary> {
+        let jobs = self.jobs.lock().expect("jobs lock");
+        let job = jobs.get(&id)?;
+        Some(JobSummary {
+            id,
+            status: job.status,
+            total: job.total,
+            processed: job.records.len(),
+            errors: job.errors,
+            results: (job.status == JobStatus::Completed).then(|| format!("/jobs/{}/results", id)),
+        })
+    }
+
+    /// The records classified so far as JSON lines.
+    fn results(&self, id: u64) -> Option<String> {
+        let jobs = self.jobs.lock().expect("jobs lock");
+        let mut results = String::new();
+        for record in &jobs.get(&id)?.records {
+            results.push_str(&serde_json::to_string(record).expect("records serialize to JSON"));
+            results.push('\n');
+        }
+        Some(results)
+    }
+}
+
+/// The columns of a CSV or TSV batch.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BatchColumns {
+    /// Column of ACMG evidence strings
+    #[serde(default = "default_evidence_column")]
+    #[param(default = "evidence")]
+    evidence_column: String,
+    /// Column of variant coordinates, e.g. 7-117559590-G-A
+    variant_column: Option<String>,
+}
+
+fn default_evidence_column() -> String {
+    "evidence".to_string()
+}
+
+/// The records of a batch with their line numbers, or why a record couldn't be read.
+type BatchRecords = Vec<(u64, Result<ClassifyRequest, String>)>;
+
+fn jsonl_records(body: &str) -> BatchRecords {
+    body.lines().enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index as u64 + 1, serde_json::from_str(line).map_err(|error| format!("Invalid request: {}", error))))
+        .collect()
+}
+
+/// The records of a CSV or TSV batch, failing with the status and error to respond with if it can't be read.
+fn table_records(body: &str, delimiter: u8, columns: &BatchColumns) -> Result<BatchRecords, (StatusCode, String)> {
+    let mut reader = csv::ReaderBuilder::new().delimiter(delimiter).flexible(true).from_reader(body.as_bytes());
+    let headers = reader.headers().map_err(|error| (StatusCode::BAD_REQUEST, error.to_string()))?.clone();
+    let column = |name: &str| headers.iter().position(|header| header == name)
+        .ok_or_else(|| (StatusCode::UNPROCESSABLE_ENTITY, format!("Column '{}' not found in input header", name)));
+    let evidence_index = column(&columns.evidence_column)?;
+    let variant_index = columns.variant_column.as_deref().map(column).transpose()?;
+    let mut records = vec![];
+    for row in reader.records() {
+        let row = row.map_err(|error| (StatusCode::BAD_REQUEST, error.to_string()))?;
+        let line = row.position().map_or(0, |position| position.line());
+        let request = ClassifyRequest {
+            evidence: EvidenceInput::Text(row.get(evidence_index).unwrap_or_default().to_string()),
+            variant: variant_index.and_then(|variant_index| row.get(variant_index)).map(str::to_string),
+            hgvs: None,
+        };
+        records.push((line, Ok(request)));
+    }
+    Ok(records)
+}
+
+/// Starts classifying a batch in the background, given as JSON lines of classification requests, or as CSV or TSV
+/// with a column of evidence strings.
+#[utoipa::path(
+    post,
+    path = "/jobs",
+    params(BatchColumns),
+    request_body(content((String = "application/x-ndjson"), (String = "text/csv"), (String = "text/tab-separated-values"))),
+    responses(
+        (status = 202, description = "The job was started", body = JobSummary),
+        (status = 400, description = "The batch could not be read", body = ErrorBody),
+        (status = 422, description = "The batch has no records or is missing the evidence column", body = ErrorBody),
+    ),
+)]
+pub async fn post_jobs(State(state): State<Arc<AppState>>, Query(columns): Query<BatchColumns>, headers: HeaderMap, body: Bytes) -> Response {
+    let Ok(body) = std::str::from_utf8(&body) else {
+        return error_response(StatusCode::BAD_REQUEST, "Batch must be UTF-8".to_string());
+    };
+    let content_type = headers.get(header::CONTENT_TYPE).and_then(|content_type| content_type.to_str().ok()).unwrap_or_default();
+    let records = if content_type.starts_with("text/csv") {
+        table_records(body, b',', &columns)
+    } else if content_type.starts_with("text/tab-separated-values") {
+        table_records(body, b'\t', &columns)
+    } else {
+        Ok(jsonl_records(body))
+    };
+    let records = match records {
+        Ok(records) if records.is_empty() => return error_response(StatusCode::UNPROCESSABLE_ENTITY, "Batch has no records".to_string()),
+        Ok(records) => records,
+        Err((status, error)) => return error_response(status, error),
+    };
+    let id = state.jobs.start(records.len());
+    let summary = state.jobs.summary(id);
+    tokio::spawn(async move {
+        let worker = tokio::task::spawn_blocking({
+            let state = state.clone();
+            move || for (line, request) in records {
+                let record = match request.and_then(|request| classify(request, &state).map_err(|error| error.to_string())) {
+                    Ok(result) => JobRecord { line, result: Some(result), error: None },
+                    Err(error) => JobRecord { line, result: None, error: Some(error) },
+                };
+                state.jobs.record(id, record);
+            }
+        });
+        let status = if worker.await.is_ok() { JobStatus::Completed } else { JobStatus::Failed };
+        state.jobs.finish(id, status);
+    });
+    (StatusCode::ACCEPTED, [(header::LOCATION, format!("/jobs/{}", id))], Json(summary)).into_response()
+}
+
+fn unknown_job(id: u64) -> Response {
+    error_response(StatusCode::NOT_FOUND, format!("Unknown job {}", id))
+}
+
+/// The status of a job.
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}",
+    params(("id" = u64, Path, description = "The id of the job")),
+    responses(
+        (status = 200, description = "The status of the job", body = JobSummary),
+        (status = 404, description = "There is no such job", body = ErrorBody),
+    ),
+)]
+pub async fn get_job(State(state): State<Arc<AppState>>, Path(id): Path<u64>) -> Response {
+    match state.jobs.summary(id) {
+        Some(summary) => Json(summary).into_response(),
+        None => unknown_job(id),
+    }
+}
+
+/// The records of a job classified so far, as JSON lines in the order of the batch.
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}/results",
+    params(("id" = u64, Path, description = "The id of the job")),
+    responses(
+        (status = 200, description = "A JSON line for each record classified", body = JobRecord, content_type = "application/x-ndjson"),
+        (status = 404, description = "There is no such job", body = ErrorBody),
+    ),
+)]
+pub async fn get_job_results(State(state): State<Arc<AppState>>, Path(id): Path<u64>) -> Response {
+    match state.jobs.results(id) {
+        Some(results) => ([(header::CONTENT_TYPE, "application/x-ndjson")], results).into_response(),
+        None => unknown_job(id),
+    }
+}
@@ -0,0 +1,61 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use acmg::{Category, CodeRegistry, EvidenceCode, EvidenceStrength};
+
+use crate::cli::CliError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    Table,
+    Json,
+}
+
+/// A built-in evidence code as listed in JSON.
+#[derive(Serialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct CodeRecord {
+    code: String,
+    category: Category,
+    strength: EvidenceStrength,
+    points: i32,
+    description: String,
+}
+
+impl From<&EvidenceCode> for CodeRecord {
+    fn from(evidence_code: &EvidenceCode) -> CodeRecord {
+        CodeRecord {
+            code: evidence_code.to_string(),
+            category: evidence_code.category,
+            strength: evidence_code.strength,
+            points: evidence_code.points(),
+            description: evidence_code.description.to_string(),
+        }
+    }
+}
+
+/// Prints the built-in and registered evidence codes with their default strengths and points, optionally filtered by
+/// category and default strength.
+pub fn run_list(category: Option<Category>, strength: Option<EvidenceStrength>, format: ListFormat, registry: &CodeRegistry) -> Result<(), CliError> {
+    let codes: Vec<&EvidenceCode> = registry.evidence_codes().into_iter()
+        .filter(|code| category.is_none_or(|category| code.category == category))
+        .filter(|code| strength.is_none_or(|strength| code.strength == strength))
+        .collect();
+    let mut out = io::stdout().lock();
+    match format {
+        ListFormat::Table => {
+            writeln!(out, "{:5} {:10} {:10} {:>6}  Description", "Code", "Category", "Strength", "Points")?;
+            for code in codes {
+                writeln!(out, "{:5} {:10} {:10} {:>6}  {}",
+                         code, format!("{:?}", code.category), format!("{:?}", code.strength), code.points(), code.description)?;
+            }
+        }
+        ListFormat::Json => {
+            let records: Vec<CodeRecord> = codes.into_iter().map(CodeRecord::from).collect();
+            writeln!(out, "{}", serde_json::to_string(&records).expect("codes serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
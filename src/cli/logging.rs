@@ -0,0 +1,142 @@
+use std::fmt::{Debug, Write as _};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+use acmg::{AcmgAssessment, AcmgError, Severity};
+
+use crate::cli::metadata::timestamp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// The level, message and fields, e.g. `DEBUG looked up allele frequency source="index" variant="1-55051215-G-GA"`
+    Text,
+    /// One JSON object per event, with its timestamp, level, target, message and fields
+    Json,
+}
+
+/// Logs events to stderr from the level of the verbosity on, info by default, debug with `-v` and trace with `-vv`.
+/// Only the events of acmg are logged, not those of its dependencies.
+pub fn init_logging(verbosity: u8, format: LogFormat) {
+    let level = match verbosity {
+        0 => Level::INFO,
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    let _ = tracing::subscriber::set_global_default(StderrLogger { level, format, spans: AtomicU64::new(1) });
+}
+
+/// Logs the outcome of a record of a batch or stream: its error if it failed, any diagnostics of its evidence, and at
+/// trace level the time it took.
+pub fn log_record(line: u64, assessment: &Result<AcmgAssessment, AcmgError>, elapsed: Duration) {
+    match assessment {
+        Ok(assessment) => for diagnostic in assessment.diagnostics() {
+            match diagnostic.severity {
+                Severity::Error => tracing::error!(line, rule = diagnostic.rule.id(), "{}", diagnostic.message),
+                Severity::Warning => tracing::warn!(line, rule = diagnostic.rule.id(), "{}", diagnostic.message),
+            }
+        },
+        Err(error) => tracing::error!(line, "{}", error),
+    }
+    tracing::trace!(line, elapsed_us = elapsed.as_micros() as u64, "classified record");
+}
+
+/// Writes each event to stderr as a line of text or JSON. Spans aren't recorded.
+struct StderrLogger {
+    level: Level,
+    format: LogFormat,
+    spans: AtomicU64,
+}
+
+impl Subscriber for StderrLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.level && metadata.target().split("::").next() == Some("acmg")
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::from_level(self.level))
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(self.spans.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+        let line = match self.format {
+            LogFormat::Text => {
+                let mut line = format!("{:>5} {}", metadata.level(), fields.message);
+                for (name, value) in &fields.values {
+                    let _ = write!(line, " {}={}", name, value);
+                }
+                line
+            }
+            LogFormat::Json => {
+                let mut object = Map::new();
+                object.insert("timestamp".to_string(), Value::String(timestamp(SystemTime::now())));
+                object.insert("level".to_string(), Value::String(metadata.level().to_string()));
+                object.insert("target".to_string(), Value::String(metadata.target().to_string()));
+                object.insert("message".to_string(), Value::String(fields.message));
+                object.extend(fields.values.into_iter().map(|(name, value)| (name.to_string(), value)));
+                Value::Object(object).to_string()
+            }
+        };
+        let _ = writeln!(io::stderr().lock(), "{}", line);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+/// The message and other fields of an event, in the order they were given.
+#[derive(Default)]
+struct Fields {
+    message: String,
+    values: Vec<(&'static str, Value)>,
+}
+
+impl Visit for Fields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.values.push((field.name(), Value::from(value)));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.values.push((field.name(), Value::from(value)));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.values.push((field.name(), Value::from(value)));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.values.push((field.name(), Value::from(value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            name => self.values.push((name, Value::from(value))),
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        match field.name() {
+            "message" => self.message = format!("{:?}", value),
+            name => self.values.push((name, Value::String(format!("{:?}", value)))),
+        }
+    }
+}
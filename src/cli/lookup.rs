@@ -0,0 +1,109 @@
+use std::env;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "index")]
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+
+use acmg::{AcmgError, AlleleFrequency, GenomicVariant};
+
+use crate::cli::CliError;
+
+/// Sources of allele frequencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Lookup {
+    /// The gnomAD GraphQL API
+    Gnomad,
+    /// A variant index built with `acmg index build`, for offline lookups
+    Index,
+}
+
+/// The default lookup cache, $XDG_CACHE_HOME/acmg or ~/.cache/acmg.
+pub fn default_cache_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CACHE_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .map(|cache| cache.join("acmg"))
+}
+
+/// Looks up allele frequencies, blocking on the asynchronous gnomAD client so lookups can be made per record. With
+/// neither the gnomad nor the index feature lookups can't be constructed.
+#[derive(Debug)]
+pub enum FrequencyLookup {
+    #[cfg(feature = "gnomad")]
+    Gnomad { runtime: tokio::runtime::Runtime, client: acmg::GnomadClient },
+    #[cfg(feature = "index")]
+    Index { index: acmg::VariantIndex, lookups: AtomicU64 },
+}
+
+impl FrequencyLookup {
+    /// A gnomAD lookup, caching responses in the directory for the time to live, or indefinitely without one.
+    #[cfg(feature = "gnomad")]
+    pub fn gnomad(endpoint: &str, dataset: &str, cache_dir: Option<PathBuf>, cache_ttl: Option<Duration>) -> Result<FrequencyLookup, CliError> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        let client = acmg::GnomadClient::new(dataset).with_endpoint(endpoint);
+        let client = match cache_dir {
+            Some(cache_dir) => {
+                let cache = acmg::LookupCache::in_dir(&cache_dir)?;
+                client.with_cache(match cache_ttl {
+                    Some(ttl) => cache.with_ttl(ttl),
+                    None => cache,
+                })
+            }
+            None => client,
+        };
+        Ok(FrequencyLookup::Gnomad { runtime, client })
+    }
+
+    #[cfg(not(feature = "gnomad"))]
+    pub fn gnomad(_endpoint: &str, _dataset: &str, _cache_dir: Option<PathBuf>, _cache_ttl: Option<Duration>) -> Result<FrequencyLookup, CliError> {
+        Err(CliError::FeatureDisabled("gnomad"))
+    }
+
+    #[cfg(feature = "index")]
+    pub fn index(path: &Path) -> Result<FrequencyLookup, CliError> {
+        Ok(FrequencyLookup::Index { index: acmg::VariantIndex::open(path)?, lookups: AtomicU64::new(0) })
+    }
+
+    #[cfg(not(feature = "index"))]
+    pub fn index(_path: &Path) -> Result<FrequencyLookup, CliError> {
+        Err(CliError::FeatureDisabled("index"))
+    }
+
+    /// Looks up the allele frequency of a variant, logging the lookup, its source and time and whether the cache answered it.
+    #[cfg_attr(not(any(feature = "gnomad", feature = "index")), allow(unused_variables, unreachable_code))]
+    pub fn allele_frequency(&self, variant: &GenomicVariant) -> Result<AlleleFrequency, AcmgError> {
+        let started = Instant::now();
+        let (source, frequency, cached): (&str, Result<AlleleFrequency, AcmgError>, bool) = match *self {
+            #[cfg(feature = "gnomad")]
+            FrequencyLookup::Gnomad { ref runtime, ref client } => {
+                let cache_hits = client.cache_hits();
+                let frequency = runtime.block_on(client.allele_frequency(&variant.to_string()));
+                ("gnomad", frequency, client.cache_hits() > cache_hits)
+            }
+            #[cfg(feature = "index")]
+            FrequencyLookup::Index { ref index, ref lookups } => {
+                lookups.fetch_add(1, Ordering::Relaxed);
+                ("index", index.allele_frequency(variant), false)
+            }
+        };
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        match &frequency {
+            Ok(_) if cached => tracing::debug!(source, variant = %variant, elapsed_ms, "cache hit for allele frequency"),
+            Ok(_) => tracing::debug!(source, variant = %variant, elapsed_ms, "looked up allele frequency"),
+            Err(error) => tracing::debug!(source, variant = %variant, elapsed_ms, "allele frequency lookup failed: {}", error),
+        }
+        frequency
+    }
+
+    /// The number of lookups made and how many of those were answered by the cache.
+    #[cfg_attr(not(feature = "server"), allow(dead_code))]
+    pub fn lookups(&self) -> (u64, u64) {
+        match *self {
+            #[cfg(feature = "gnomad")]
+            FrequencyLookup::Gnomad { ref client, .. } => (client.lookups(), client.cache_hits()),
+            #[cfg(feature = "index")]
+            FrequencyLookup::Index { ref lookups, .. } => (lookups.load(Ordering::Relaxed), 0),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::{Display, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use acmg::{AcmgAssessment, AssessmentResult, Classifier, PointSystem, RESULT_SCHEMA_VERSION};
+
+use crate::cli::info::guideline_stamp;
+
+/// How a result was produced, stamped into JSON, TSV and report output so it can be reproduced and audited if the
+/// classification is challenged later.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunMetadata {
+    /// The version of the JSON schema of results, see [`RESULT_SCHEMA_VERSION`].
+    pub schema_version: u32,
+    pub acmg_version: &'static str,
+    /// The guideline, its version and any criteria specification, see [`guideline_stamp`].
+    pub guideline: String,
+    pub parameters: Parameters,
+    /// The SHA-256 of the evidence as given, in hex.
+    pub input_sha256: String,
+    /// When the run started, in UTC.
+    pub timestamp: String,
+}
+
+/// The parameters the evidence was scored and classified with.
+#[derive(Debug, Clone, Serialize)]
+pub struct Parameters {
+    pub classifier: Classifier,
+    pub prior: f64,
+    pub odds_vs: f64,
+    pub exponent: f64,
+    pub point_system: PointSystem,
+}
+
+/// E.g. `points classifier, prior 0.1, OddsPath 350, exponent 2, 8/8/4/2/1 points, P >= 10, ...`.
+impl Display for Parameters {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} classifier, prior {}, OddsPath {}, exponent {}, {}",
+               format!("{:?}", self.classifier).to_lowercase(), self.prior, self.odds_vs, self.exponent, self.point_system)
+    }
+}
+
+impl RunMetadata {
+    /// The metadata of an assessment of the evidence `input`, in a run started at `timestamp`.
+    pub fn new(assessment: &AcmgAssessment, input: &str, timestamp: &str) -> RunMetadata {
+        let model = assessment.model();
+        RunMetadata {
+            schema_version: RESULT_SCHEMA_VERSION,
+            acmg_version: env!("CARGO_PKG_VERSION"),
+            guideline: guideline_stamp(assessment),
+            parameters: Parameters {
+                classifier: assessment.classifier(),
+                prior: model.prior_prob(),
+                odds_vs: model.odds_path_very_strong(),
+                exponent: model.exponential_progression(),
+                point_system: *assessment.point_system(),
+            },
+            input_sha256: sha256(input),
+            timestamp: timestamp.to_string(),
+        }
+    }
+
+    /// The guideline, version, parameters, input hash and timestamp, as TSV columns.
+    pub fn tsv_fields(&self) -> [String; 5] {
+        [self.guideline.clone(), self.acmg_version.to_string(), self.parameters.to_string(), self.input_sha256.clone(), self.timestamp.clone()]
+    }
+}
+
+/// An assessment result with the metadata of the run, serialized as the result with a `metadata` object.
+#[derive(Debug, Serialize)]
+pub struct StampedResult {
+    #[serde(flatten)]
+    pub result: AssessmentResult,
+    pub metadata: RunMetadata,
+}
+
+impl StampedResult {
+    pub fn new(assessment: &AcmgAssessment, input: &str, timestamp: &str) -> StampedResult {
+        StampedResult { result: assessment.result(), metadata: RunMetadata::new(assessment, input, timestamp) }
+    }
+}
+
+fn sha256(input: &str) -> String {
+    Sha256::digest(input.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Formats a time as an RFC 3339 timestamp in UTC to the second, e.g. `2026-10-14T09:30:00Z`.
+pub fn timestamp(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+    // Howard Hinnant's days_from_civil inverted, counting from 0000-03-01 in 400-year eras.
+    let days = days as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use acmg::AcmgClassification;
+
+use crate::cli::serve::AppState;
+use crate::cli::FrequencySource;
+
+/// The upper bounds in seconds of the request latency histogram buckets.
+const LATENCY_BUCKETS: [f64; 11] = [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5];
+
+#[derive(Debug, Default)]
+struct Histogram {
+    /// The number of observations in each of the [`LATENCY_BUCKETS`], not cumulative.
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|bound| value <= *bound) {
+            self.buckets[bucket] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// The counters of the server, rendered in the Prometheus text format by `GET /metrics`.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// Requests by method, route and status code.
+    requests: Mutex<BTreeMap<(String, String, u16), u64>>,
+    /// Request latency by method and route.
+    latency: Mutex<BTreeMap<(String, String), Histogram>>,
+    /// Classifications, by their position in [`AcmgClassification::ALL`].
+    classifications: [AtomicU64; AcmgClassification::ALL.len()],
+}
+
+impl Metrics {
+    pub fn classified(&self, classification: AcmgClassification) {
+        let index = AcmgClassification::ALL.iter().position(|c| *c == classification).expect("every classification is listed");
+        self.classifications[index].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn request(&self, method: &str, route: &str, status: u16, seconds: f64) {
+        *self.requests.lock().expect("metrics lock").entry((method.to_string(), route.to_string(), status)).or_default() += 1;
+        self.latency.lock().expect("metrics lock").entry((method.to_string(), route.to_string())).or_default().observe(seconds);
+    }
+
+    fn render(&self, frequency: &FrequencySource) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# HELP acmg_http_requests_total HTTP requests by method, route and status code.");
+        let _ = writeln!(out, "# TYPE acmg_http_requests_total counter");
+        for ((method, route, status), count) in self.requests.lock().expect("metrics lock").iter() {
+            let _ = writeln!(out, "acmg_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}", method, route, status, count);
+        }
+        let _ = writeln!(out, "# HELP acmg_http_request_duration_seconds HTTP request latency by method and route.");
+        let _ = writeln!(out, "# TYPE acmg_http_request_duration_seconds histogram");
+        for ((method, route), histogram) in self.latency.lock().expect("metrics lock").iter() {
+            let labels = format!("method=\"{}\",route=\"{}\"", method, route);
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(out, "acmg_http_request_duration_seconds_bucket{{{},le=\"{}\"}} {}", labels, bound, cumulative);
+            }
+            let _ = writeln!(out, "acmg_http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}", labels, histogram.count);
+            let _ = writeln!(out, "acmg_http_request_duration_seconds_sum{{{}}} {}", labels, histogram.sum);
+            let _ = writeln!(out, "acmg_http_request_duration_seconds_count{{{}}} {}", labels, histogram.count);
+        }
+        let _ = writeln!(out, "# HELP acmg_classifications_total Variants classified, by classification.");
+        let _ = writeln!(out, "# TYPE acmg_classifications_total counter");
+        for (classification, count) in AcmgClassification::ALL.iter().zip(&self.classifications) {
+            let _ = writeln!(out, "acmg_classifications_total{{classification=\"{:?}\"}} {}", classification, count.load(Ordering::Relaxed));
+        }
+        if let FrequencySource::Lookup(lookup) = frequency {
+            let (lookups, cache_hits) = lookup.lookups();
+            let _ = writeln!(out, "# HELP acmg_lookups_total Allele frequency lookups, including those answered by the cache.");
+            let _ = writeln!(out, "# TYPE acmg_lookups_total counter");
+            let _ = writeln!(out, "acmg_lookups_total {}", lookups);
+            let _ = writeln!(out, "# HELP acmg_lookup_cache_hits_total Allele frequency lookups answered by the cache.");
+            let _ = writeln!(out, "# TYPE acmg_lookup_cache_hits_total counter");
+            let _ = writeln!(out, "acmg_lookup_cache_hits_total {}", cache_hits);
+        }
+        out
+    }
+}
+
+/// Counts each request to a route, and how long it took to respond to.
+pub async fn track(State(state): State<Arc<AppState>>, request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request.extensions().get::<MatchedPath>().map_or_else(|| request.uri().path().to_string(), |path| path.as_str().to_string());
+    let start = Instant::now();
+    let response = next.run(request).await;
+    state.metrics.request(&method, &route, response.status().as_u16(), start.elapsed().as_secs_f64());
+    response
+}
+
+/// The metrics of the server, for Prometheus.
+#[utoipa::path(get, path = "/metrics", responses((status = 200, description = "The metrics in the Prometheus text format", body = String, content_type = "text/plain; version=0.0.4")))]
+pub async fn get_metrics(State(state): State<Arc<AppState>>) -> Response {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], state.metrics.render(&state.scoring.frequency)).into_response()
+}
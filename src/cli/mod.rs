@@ -0,0 +1,747 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::str::FromStr;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use clap::{Args, ValueEnum};
+use thiserror::Error;
+
+use acmg::{AcmgAssessment, CodeRegistry, AlleleModel, AcmgClassification, AcmgError, AlleleFrequency, Ba1Exceptions, BayesianModel, ClinGenGenes, HgvsVariant, CarrierCount, CaseControl, Classifier, Code, CriteriaSpec, Diagnostic, DiseaseModel, DuplicatePolicy, EvidenceStrength, FrequencyCriteria, GenomeBuild, GenomicVariant, Guideline, Inheritance, PointSystem, Predictor, Regions, SpliceAiScores, RETIRED_CODES, spliceai_evidence};
+
+use crate::cli::lookup::{default_cache_dir, FrequencyLookup, Lookup};
+use crate::cli::metadata::timestamp;
+
+#[cfg(feature = "server")]
+pub mod auth;
+pub mod batch;
+pub mod bp7;
+pub mod classify;
+pub mod clinvar;
+pub mod completions;
+pub mod config;
+pub mod convert;
+pub mod cnv;
+pub mod combos;
+pub mod compare;
+pub mod compare_reviewers;
+pub mod daemon;
+#[cfg(feature = "db")]
+pub mod db;
+pub mod de_novo;
+pub mod describe;
+pub mod diff;
+pub mod export;
+pub mod extract;
+pub mod fmt;
+#[cfg(feature = "index")]
+pub mod index;
+pub mod info;
+#[cfg(feature = "tui")]
+pub mod interactive;
+#[cfg(feature = "server")]
+pub mod jobs;
+pub mod list;
+pub mod logging;
+pub mod lookup;
+pub mod metadata;
+#[cfg(feature = "server")]
+pub mod metrics;
+pub mod oncogenicity;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod phenopacket;
+pub mod plot;
+pub mod pm3;
+pub mod ps4;
+pub mod pvs1;
+pub mod report;
+pub mod schema;
+pub mod segregation;
+pub mod sensitivity;
+#[cfg(feature = "server")]
+pub mod serve;
+pub mod somatic;
+pub mod stats;
+pub mod suggest;
+pub mod trio;
+pub mod validate;
+#[cfg(feature = "server")]
+pub mod websocket;
+pub mod what_if;
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
+
+/// Errors which stop a CLI command, as opposed to per-record errors which are reported and skipped.
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error(transparent)]
+    Acmg(#[from] AcmgError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error("{0}")]
+    Config(String),
+    #[error("Column '{0}' not found in input header")]
+    MissingColumn(String),
+    #[error("{errors} of {records} records failed, more than the maximum error rate of {max_error_rate}")]
+    ErrorRate { errors: usize, records: usize, max_error_rate: f64 },
+    #[error("acmg was built without the {0} feature")]
+    #[cfg_attr(all(feature = "gnomad", feature = "index", feature = "parquet", feature = "templates", feature = "xlsx"), allow(dead_code))]
+    FeatureDisabled(&'static str),
+    #[cfg(feature = "templates")]
+    #[error("Unable to render template: {0}")]
+    Template(String),
+    #[cfg(feature = "xlsx")]
+    #[error("{0}")]
+    Xlsx(String),
+    #[cfg(feature = "parquet")]
+    #[error("{0}")]
+    Parquet(String),
+    #[cfg(feature = "db")]
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+    #[cfg(feature = "db")]
+    #[error("{0}")]
+    Store(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Acmg(_) | CliError::ErrorRate { .. } => 1,
+            _ => 2,
+        }
+    }
+}
+
+/// What to do with evidence using a disabled code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DisabledPolicy {
+    /// Fail, explaining why the code is disabled
+    Error,
+    /// Leave the evidence unscored, with a warning
+    Drop,
+}
+
+/// Classifications which set the exit code with `--exit-on`, so scripts can branch on the classification without
+/// parsing the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExitOn {
+    /// Exit with 10
+    Pathogenic,
+    /// Exit with 11
+    LikelyPathogenic,
+    /// Exit with 12
+    UncertainSignificance,
+    /// Exit with 13
+    LikelyBenign,
+    /// Exit with 14
+    Benign,
+}
+
+impl ExitOn {
+    fn classification(self) -> AcmgClassification {
+        match self {
+            ExitOn::Pathogenic => AcmgClassification::Pathogenic,
+            ExitOn::LikelyPathogenic => AcmgClassification::LikelyPathogenic,
+            ExitOn::UncertainSignificance => AcmgClassification::UncertainSignificance,
+            ExitOn::LikelyBenign => AcmgClassification::LikelyBenign,
+            ExitOn::Benign => AcmgClassification::Benign,
+        }
+    }
+
+    /// The exit code of the classification, from 10 for Pathogenic to 14 for Benign.
+    fn exit_code(self) -> i32 {
+        10 + self as i32
+    }
+}
+
+/// The exit code for an assessment: 1 if it has any error diagnostics, as `validate` fails on, and otherwise the code
+/// of its classification if it's one of those to exit on.
+pub fn assessment_exit_code(assessment: &AcmgAssessment, exit_on: &[ExitOn]) -> Option<i32> {
+    if assessment.diagnostics().iter().any(Diagnostic::is_error) {
+        return Some(1);
+    }
+    let classification = assessment.classify();
+    exit_on.iter().find(|exit_on| exit_on.classification() == classification).map(|exit_on| exit_on.exit_code())
+}
+
+/// A guideline given with `--guideline`, or `vcep:GENE` for the points-based guideline under the built-in criteria
+/// specification of a ClinGen Variant Curation Expert Panel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GuidelineChoice {
+    pub guideline: Guideline,
+    pub vcep: Option<String>,
+}
+
+impl FromStr for GuidelineChoice {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<GuidelineChoice, Self::Err> {
+        match s.split_once(':') {
+            Some((prefix, gene)) if prefix.eq_ignore_ascii_case("vcep") && !gene.is_empty() => {
+                Ok(GuidelineChoice { guideline: Guideline::Points2020, vcep: Some(gene.to_string()) })
+            }
+            _ => Ok(GuidelineChoice { guideline: s.parse()?, vcep: None }),
+        }
+    }
+}
+
+impl Display for GuidelineChoice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.vcep {
+            Some(gene) => write!(f, "vcep:{}", gene),
+            None => write!(f, "{}", self.guideline),
+        }
+    }
+}
+
+/// Options controlling how evidence is combined and checked, shared by the scoring commands.
+#[derive(Debug, Clone, Args)]
+pub struct ScoringArgs {
+    /// Treat warnings such as conflicting codes as errors
+    #[arg(long)]
+    pub strict: bool,
+    /// Guideline used to combine the evidence, points2020, richards2015 (or acmg2015), acgs2020, mito or vcep:GENE
+    /// for points2020 under the built-in specification of the gene's expert panel, e.g. vcep:MYH7
+    #[arg(short, long, default_value_t = GuidelineChoice::default())]
+    pub guideline: GuidelineChoice,
+    /// Resolve a criterion given at different strengths, e.g. PM2 and PM2_Supporting, with error, keep-strongest or
+    /// keep-first [default: keep each with a warning]
+    #[arg(long)]
+    pub duplicates: Option<DuplicatePolicy>,
+    /// Classify the points-based guideline from the points or the posterior probability
+    #[arg(long, default_value_t = Classifier::Points)]
+    pub classifier: Classifier,
+    /// Prior probability of pathogenicity
+    #[arg(long, default_value_t = 0.1)]
+    pub prior: f64,
+    /// OddsPath of Very Strong evidence
+    #[arg(long, default_value_t = 350.0)]
+    pub odds_vs: f64,
+    /// Exponential progression of the OddsPath between evidence strengths
+    #[arg(long, default_value_t = 2.0)]
+    pub exponent: f64,
+    /// Gene-specific criteria specification JSON file applied before scoring, in place of any the guideline defines
+    #[arg(long, conflicts_with = "gene")]
+    pub spec: Option<PathBuf>,
+    /// Apply the built-in criteria specification for this gene, e.g. MYH7
+    #[arg(long)]
+    pub gene: Option<String>,
+    /// Mode of inheritance of the disorder, AD, AR, XLR, XLD or MT, checking the codes applied are consistent with it
+    #[arg(long)]
+    pub inheritance: Option<Inheritance>,
+    /// Disable these codes, e.g. PP5,BP6, as for codes a lab doesn't use
+    #[arg(long, value_delimiter = ',')]
+    pub disable: Vec<Code>,
+    /// Disable the codes the ClinGen SVI recommends are no longer used, PP5 and BP6
+    #[arg(long)]
+    pub disable_retired: bool,
+    /// Reject evidence using a disabled code, or drop it unscored with a warning
+    #[arg(long, value_enum, default_value_t = DisabledPolicy::Error)]
+    pub on_disabled: DisabledPolicy,
+    /// Points of evidence of a strength in place of 8/8/4/2/1 for stand-alone to supporting, e.g. moderate=3
+    #[arg(long, value_delimiter = ',', value_name = "STRENGTH=POINTS", value_parser = parse_strength_points)]
+    pub strength_points: Vec<(EvidenceStrength, i32)>,
+    /// Lowest points of a classification in place of P 10, LP 6, VUS 0 and LB -6, e.g. likely-pathogenic=7
+    #[arg(long, value_delimiter = ',', value_name = "CLASSIFICATION=POINTS", value_parser = parse_threshold)]
+    pub thresholds: Vec<(AcmgClassification, i32)>,
+    /// REVEL score, applying PP3 or BP4 at the strength calibrated by Pejaver et al. 2022
+    #[arg(long, group = "predictor")]
+    pub revel: Option<f64>,
+    /// CADD PHRED score, applying PP3 or BP4 at the strength calibrated by Pejaver et al. 2022
+    #[arg(long, group = "predictor")]
+    pub cadd: Option<f64>,
+    /// BayesDel (no AF) score, applying PP3 or BP4 at the strength calibrated by Pejaver et al. 2022
+    #[arg(long, group = "predictor")]
+    pub bayesdel: Option<f64>,
+    /// AlphaMissense score, applying PP3 or BP4 at the strength calibrated by Bergquist et al. 2025
+    #[arg(long, group = "predictor")]
+    pub alphamissense: Option<f64>,
+    /// SpliceAI delta score, the largest of the acceptor and donor gain and loss scores, applying PP3 from 0.2 or BP4
+    /// up to 0.1
+    #[arg(long)]
+    pub spliceai: Option<f64>,
+    /// VCF of precomputed SpliceAI scores in the SpliceAI INFO field, looking up the delta score of the variant
+    #[arg(long, conflicts_with = "spliceai")]
+    pub spliceai_vcf: Option<PathBuf>,
+    /// BED file of curated hotspots and critical domains, applying PM1 to variants in them, at the strength given in
+    /// place of the score or Moderate
+    #[arg(long)]
+    pub pm1_bed: Option<PathBuf>,
+    /// BED file of repetitive regions without a known function, applying BP3 to in-frame indels in them
+    #[arg(long)]
+    pub repeat_bed: Option<PathBuf>,
+    /// BA1 exception list, a TSV of gene, hgvs, grch37, grch38 and reason, in place of the ClinGen SVI list
+    #[arg(long)]
+    pub ba1_exceptions: Option<PathBuf>,
+    /// ClinGen gene-disease validity summary CSV, warning when PVS1 is applied in a gene of at most Limited validity
+    #[arg(long)]
+    pub clingen_validity: Option<PathBuf>,
+    /// ClinGen dosage sensitivity gene curation list, e.g. ClinGen_gene_curation_list_GRCh38.tsv, warning when PVS1 is
+    /// applied in a gene without sufficient evidence of haploinsufficiency
+    #[arg(long)]
+    pub clingen_dosage: Option<PathBuf>,
+    /// Carriers among affected cases, e.g. 12/800, applying PS4 at the strength supported with --ps4-controls
+    #[arg(long, requires = "ps4_controls")]
+    pub ps4_cases: Option<CarrierCount>,
+    /// Carriers among unaffected controls, e.g. 1/60000
+    #[arg(long, requires = "ps4_cases")]
+    pub ps4_controls: Option<CarrierCount>,
+    /// Classify the variant as a low penetrance variant or a risk allele as the ClinGen Low Penetrance/Risk Allele
+    /// Working Group recommends, mendelian, low-penetrance or risk-allele. Risk alleles are classified from the
+    /// association of --ps4-cases and --ps4-controls
+    #[arg(long, default_value_t = AlleleModel::Mendelian)]
+    pub allele_model: AlleleModel,
+    /// Independent studies replicating the case-control association of a risk allele
+    #[arg(long, default_value_t = 0)]
+    pub replications: u32,
+    /// Variant being classified, as VCF coordinates e.g. 7-117559590-G-A, which are normalized and included in the
+    /// output
+    #[arg(long)]
+    pub variant: Option<String>,
+    /// Genome build of the variant coordinates, GRCh37 or GRCh38
+    #[arg(long, default_value_t = GenomeBuild::Grch38)]
+    pub build: GenomeBuild,
+    #[command(flatten)]
+    pub frequency: FrequencyArgs,
+}
+
+/// Options asserting the frequency criteria from an observed allele frequency.
+#[derive(Debug, Clone, Args)]
+#[command(next_help_heading = "Frequency")]
+pub struct FrequencyArgs {
+    /// Allele frequency, e.g. the gnomAD popmax filtering allele frequency, applying BA1, BS1, BS2 or PM2
+    #[arg(long)]
+    pub af: Option<f64>,
+    /// Population the allele frequency was observed in, e.g. nfe
+    #[arg(long, requires = "af")]
+    pub popmax: Option<String>,
+    /// Number of alleles genotyped in the population
+    #[arg(long, requires = "af")]
+    pub an: Option<u32>,
+    /// Number of homozygotes observed
+    #[arg(long, requires = "af")]
+    pub hom: Option<u32>,
+    /// For mtDNA, the heteroplasmic allele frequency, with --af the homoplasmic frequency
+    #[arg(long, requires = "af")]
+    pub heteroplasmic_af: Option<f64>,
+    /// Look up the allele frequency of the variant instead of giving it with --af
+    #[arg(long, value_enum, conflicts_with = "af")]
+    pub lookup: Option<Lookup>,
+    /// Variant index built with `acmg index build`, to look up allele frequencies offline with --lookup index
+    #[arg(long, required_if_eq("lookup", "index"))]
+    pub variant_index: Option<PathBuf>,
+    /// gnomAD dataset to look up allele frequencies in
+    #[arg(long, default_value = "gnomad_r4")]
+    pub gnomad_dataset: String,
+    /// URL of the gnomAD GraphQL API, e.g. a local mirror
+    #[arg(long, default_value = "https://gnomad.broadinstitute.org/api")]
+    pub gnomad_api: String,
+    /// Directory caching lookups [default: $XDG_CACHE_HOME/acmg or ~/.cache/acmg]
+    #[arg(long)]
+    pub cache_dir: Option<PathBuf>,
+    /// Don't cache lookups
+    #[arg(long, conflicts_with = "cache_dir")]
+    pub no_cache: bool,
+    /// Look up variants cached more than this many days ago again, e.g. 0 to refresh the cache [default: keep
+    /// lookups until the dataset changes]
+    #[arg(long, conflicts_with = "no_cache")]
+    pub cache_ttl: Option<u64>,
+    /// Apply BA1 above this allele frequency [default: 0.05]
+    #[arg(long)]
+    pub ba1: Option<f64>,
+    /// Apply BS1 above this allele frequency
+    #[arg(long)]
+    pub bs1: Option<f64>,
+    /// Apply PM2 at or below this allele frequency [default: 0, i.e. absent]
+    #[arg(long)]
+    pub pm2: Option<f64>,
+    /// Strength PM2 is applied at
+    #[arg(long, default_value = "supporting")]
+    pub pm2_strength: EvidenceStrength,
+    /// Apply BS2 with at least this many homozygotes
+    #[arg(long)]
+    pub bs2_homozygotes: Option<u32>,
+    /// Only apply BA1 and BS1 if at least this many alleles were genotyped
+    #[arg(long, default_value_t = 2000)]
+    pub min_an: u32,
+    /// Disease prevalence, applying BS1 above the maximum credible allele frequency of Whiffin et al. 2017
+    #[arg(long)]
+    pub prevalence: Option<f64>,
+    /// Largest proportion of cases attributable to a single variant
+    #[arg(long, default_value_t = 1.0, requires = "prevalence")]
+    pub allelic_contribution: f64,
+    /// Largest proportion of cases attributable to the gene
+    #[arg(long, default_value_t = 1.0, requires = "prevalence")]
+    pub genetic_contribution: f64,
+    /// Penetrance of the disorder
+    #[arg(long, default_value_t = 1.0, requires = "prevalence")]
+    pub penetrance: f64,
+}
+
+impl FrequencyArgs {
+    /// The criteria thresholds, taken from the command line over any criteria specification over the SVI
+    /// defaults.
+    fn criteria(&self, spec: Option<&CriteriaSpec>) -> Result<FrequencyCriteria, AcmgError> {
+        let mut criteria = FrequencyCriteria {
+            pm2_strength: self.pm2_strength,
+            bs2_homozygotes: self.bs2_homozygotes,
+            min_allele_number: self.min_an,
+            ..FrequencyCriteria::default()
+        };
+        if let Some(spec) = spec {
+            criteria = criteria.with_thresholds(&spec.frequency_thresholds);
+        }
+        if let Some(prevalence) = self.prevalence {
+            let model = DiseaseModel::new(prevalence, self.allelic_contribution, self.genetic_contribution, self.penetrance)?;
+            criteria = criteria.with_disease_model(&model);
+        }
+        criteria.ba1 = self.ba1.unwrap_or(criteria.ba1);
+        criteria.bs1 = self.bs1.or(criteria.bs1);
+        criteria.pm2 = self.pm2.unwrap_or(criteria.pm2);
+        Ok(criteria)
+    }
+
+    fn source(&self) -> Result<FrequencySource, CliError> {
+        match (self.lookup, &self.variant_index) {
+            (Some(Lookup::Gnomad), _) => {
+                let cache_dir = if self.no_cache { None } else { self.cache_dir.clone().or_else(default_cache_dir) };
+                let cache_ttl = self.cache_ttl.map(|days| Duration::from_secs(days * 24 * 60 * 60));
+                return Ok(FrequencySource::Lookup(FrequencyLookup::gnomad(&self.gnomad_api, &self.gnomad_dataset, cache_dir, cache_ttl)?));
+            }
+            (Some(Lookup::Index), Some(path)) => return Ok(FrequencySource::Lookup(FrequencyLookup::index(path)?)),
+            _ => {}
+        }
+        Ok(match self.af {
+            Some(af) => FrequencySource::Given(AlleleFrequency { af, population: self.popmax.clone(), allele_number: self.an, homozygotes: self.hom, heteroplasmic_af: self.heteroplasmic_af }),
+            None => FrequencySource::None,
+        })
+    }
+}
+
+/// Where the allele frequency each evidence string is assessed with comes from.
+#[derive(Debug)]
+pub enum FrequencySource {
+    None,
+    Given(AlleleFrequency),
+    Lookup(FrequencyLookup),
+}
+
+impl ScoringArgs {
+    /// Validates the model parameters and point system and loads any criteria specification, once per command.
+    /// Evidence is parsed with the codes of the registry.
+    pub fn resolve(&self, registry: &CodeRegistry) -> Result<Scoring, CliError> {
+        let point_system = self.point_system();
+        point_system.validate()?;
+        if self.guideline.vcep.is_some() && (self.spec.is_some() || self.gene.is_some()) {
+            return Err(CliError::Config(format!("--guideline {} selects a specification, so can't be combined with --spec or --gene", self.guideline)));
+        }
+        let spec = match (&self.spec, self.guideline.vcep.as_ref().or(self.gene.as_ref())) {
+            (Some(path), _) => Some(CriteriaSpec::from_json(&fs::read_to_string(path)?)?),
+            (None, Some(gene)) => Some(CriteriaSpec::builtin(gene)?),
+            (None, None) => self.guideline.guideline.spec_name().map(CriteriaSpec::builtin).transpose()?,
+        };
+        let predictor_score = self.predictor_score();
+        if let Some((predictor, score)) = predictor_score {
+            predictor.calibrate(score)?;
+        }
+        if let Some(delta) = self.spliceai {
+            spliceai_evidence(delta)?;
+        }
+        let frequency_criteria = self.frequency.criteria(spec.as_ref())?;
+        let frequency = self.frequency.source()?;
+        if let FrequencySource::Given(frequency) = &frequency {
+            frequency_criteria.evaluate(frequency)?;
+        }
+        Ok(Scoring {
+            strict: self.strict,
+            duplicates: self.duplicates,
+            guideline: self.guideline.guideline,
+            classifier: self.classifier,
+            model: BayesianModel::new(self.prior, self.odds_vs, self.exponent)?,
+            point_system,
+            registry: registry.clone(),
+            spec,
+            inheritance: self.inheritance,
+            disabled: self.disabled(),
+            on_disabled: self.on_disabled,
+            predictor_score,
+            spliceai: self.spliceai,
+            spliceai_scores: match &self.spliceai_vcf {
+                Some(path) => Some(SpliceAiScores::from_vcf(fs::read_to_string(path)?.as_bytes(), self.build)?),
+                None => None,
+            },
+            pm1_regions: match &self.pm1_bed {
+                Some(path) => Some(Regions::from_bed(fs::read_to_string(path)?.as_bytes())?),
+                None => None,
+            },
+            repeat_regions: match &self.repeat_bed {
+                Some(path) => Some(Regions::from_bed(fs::read_to_string(path)?.as_bytes())?),
+                None => None,
+            },
+            ba1_exceptions: match &self.ba1_exceptions {
+                Some(path) => Ba1Exceptions::from_tsv(fs::read_to_string(path)?.as_bytes())?,
+                None => Ba1Exceptions::default(),
+            },
+            clingen: self.clingen()?,
+            case_control: self.ps4_cases.zip(self.ps4_controls).map(|(cases, controls)| CaseControl { cases, controls }),
+            allele_model: self.allele_model,
+            replications: self.replications,
+            frequency,
+            frequency_criteria,
+            variant: self.variant.as_deref().map(|variant| GenomicVariant::parse(variant, self.build)).transpose()?,
+            build: self.build,
+            started: timestamp(SystemTime::now()),
+        })
+    }
+
+    /// The ClinGen curations given with `--clingen-validity` and `--clingen-dosage`.
+    fn clingen(&self) -> Result<ClinGenGenes, CliError> {
+        let mut clingen = ClinGenGenes::default();
+        if let Some(path) = &self.clingen_validity {
+            clingen = clingen.with_validity(fs::read_to_string(path)?.as_bytes())?;
+        }
+        if let Some(path) = &self.clingen_dosage {
+            clingen = clingen.with_dosage(fs::read_to_string(path)?.as_bytes())?;
+        }
+        Ok(clingen)
+    }
+
+    /// The codes given with `--disable` and, with `--disable-retired`, those the SVI retired.
+    fn disabled(&self) -> Vec<Code> {
+        let mut disabled = self.disable.clone();
+        if self.disable_retired {
+            disabled.extend(RETIRED_CODES.map(|(code, _)| code).into_iter().filter(|code| !self.disable.contains(code)));
+        }
+        disabled
+    }
+
+    /// The point system of Tavtigian et al. 2020 with the points and thresholds given.
+    fn point_system(&self) -> PointSystem {
+        let mut system = PointSystem::default();
+        for (strength, points) in &self.strength_points {
+            match strength {
+                EvidenceStrength::StandAlone => system.stand_alone = *points,
+                EvidenceStrength::VeryStrong => system.very_strong = *points,
+                EvidenceStrength::Strong => system.strong = *points,
+                EvidenceStrength::Moderate => system.moderate = *points,
+                EvidenceStrength::Supporting => system.supporting = *points,
+            }
+        }
+        for (classification, points) in &self.thresholds {
+            match classification {
+                AcmgClassification::Pathogenic => system.pathogenic = *points,
+                AcmgClassification::LikelyPathogenic => system.likely_pathogenic = *points,
+                AcmgClassification::UncertainSignificance => system.uncertain_significance = *points,
+                AcmgClassification::LikelyBenign => system.likely_benign = *points,
+                AcmgClassification::Benign => {}
+            }
+        }
+        system
+    }
+
+    fn predictor_score(&self) -> Option<(Predictor, f64)> {
+        [
+            (Predictor::Revel, self.revel),
+            (Predictor::Cadd, self.cadd),
+            (Predictor::BayesDel, self.bayesdel),
+            (Predictor::AlphaMissense, self.alphamissense),
+        ].into_iter().find_map(|(predictor, score)| score.map(|score| (predictor, score)))
+    }
+}
+
+/// Parses the points of a strength given as `strength=points`, e.g. `moderate=3` or `very-strong=6`.
+pub fn parse_strength_points(s: &str) -> Result<(EvidenceStrength, i32), String> {
+    let (strength, points) = s.split_once('=').ok_or_else(|| format!("expected strength=points, got '{}'", s))?;
+    let strength = strength.trim().replace('-', "").parse().map_err(|_| format!("invalid strength '{}'", strength))?;
+    let points = points.trim().parse().map_err(|_| format!("invalid points '{}'", points))?;
+    Ok((strength, points))
+}
+
+/// Parses the lowest points of a classification given as `classification=points`, e.g. `likely-pathogenic=7` or
+/// `lp=7`. Benign has no lower bound so can't be given.
+pub fn parse_threshold(s: &str) -> Result<(AcmgClassification, i32), String> {
+    let (classification, points) = s.split_once('=').ok_or_else(|| format!("expected classification=points, got '{}'", s))?;
+    let classification = match classification.trim().to_lowercase().as_str() {
+        "pathogenic" | "p" => AcmgClassification::Pathogenic,
+        "likely-pathogenic" | "lp" => AcmgClassification::LikelyPathogenic,
+        "uncertain-significance" | "vus" => AcmgClassification::UncertainSignificance,
+        "likely-benign" | "lb" => AcmgClassification::LikelyBenign,
+        _ => return Err(format!("invalid classification '{}', expected pathogenic, likely-pathogenic, uncertain-significance or likely-benign", classification)),
+    };
+    let points = points.trim().parse().map_err(|_| format!("invalid points '{}'", points))?;
+    Ok((classification, points))
+}
+
+/// The scoring options resolved from [`ScoringArgs`].
+#[derive(Debug)]
+pub struct Scoring {
+    pub strict: bool,
+    pub duplicates: Option<DuplicatePolicy>,
+    pub guideline: Guideline,
+    pub classifier: Classifier,
+    pub model: BayesianModel,
+    pub point_system: PointSystem,
+    /// The codes registered in the config, which the evidence may apply.
+    pub registry: CodeRegistry,
+    pub spec: Option<CriteriaSpec>,
+    pub inheritance: Option<Inheritance>,
+    pub disabled: Vec<Code>,
+    pub on_disabled: DisabledPolicy,
+    pub predictor_score: Option<(Predictor, f64)>,
+    /// The SpliceAI delta score, unless given per record or looked up.
+    pub spliceai: Option<f64>,
+    /// Precomputed SpliceAI scores the delta score of the variant is looked up in.
+    pub spliceai_scores: Option<SpliceAiScores>,
+    pub case_control: Option<CaseControl>,
+    pub allele_model: AlleleModel,
+    /// Independent studies replicating the case-control association of a risk allele.
+    pub replications: u32,
+    /// Hotspot and critical domain regions PM1 is applied in.
+    pub pm1_regions: Option<Regions>,
+    /// Repetitive regions BP3 is applied to in-frame indels in.
+    pub repeat_regions: Option<Regions>,
+    /// Variants BA1 isn't applied to, as they're common yet pathogenic.
+    pub ba1_exceptions: Ba1Exceptions,
+    /// ClinGen curations PVS1 is checked against for the gene of the specification or HGVS description.
+    pub clingen: ClinGenGenes,
+    pub frequency: FrequencySource,
+    pub frequency_criteria: FrequencyCriteria,
+    /// The variant being classified, unless given per record.
+    pub variant: Option<GenomicVariant>,
+    /// The genome build of variants given per record.
+    pub build: GenomeBuild,
+    /// When the command started, stamped into results, see [`RunMetadata`](metadata::RunMetadata).
+    pub started: String,
+}
+
+impl Scoring {
+    /// Records the HGVS description of the variant, applying the BA1 exceptions and the ClinGen curations of its gene.
+    pub fn with_hgvs(&self, mut assessment: AcmgAssessment, hgvs: HgvsVariant) -> AcmgAssessment {
+        if let Some(gene) = hgvs.gene.as_deref().filter(|_| !self.clingen.is_empty()) {
+            assessment = assessment.with_gene_curation(self.clingen.curation(gene));
+        }
+        assessment.with_hgvs(hgvs).with_ba1_exceptions(&self.ba1_exceptions)
+    }
+
+    /// The allele frequency to assess a variant with, if any.
+    fn frequency(&self, variant: Option<&GenomicVariant>) -> Result<Option<AlleleFrequency>, AcmgError> {
+        match &self.frequency {
+            FrequencySource::None => Ok(None),
+            FrequencySource::Given(frequency) => Ok(Some(frequency.clone())),
+            FrequencySource::Lookup(lookup) => {
+                let variant = variant
+                    .ok_or_else(|| AcmgError::InvalidParameter("a variant is required to look up its allele frequency".to_string()))?;
+                lookup.allele_frequency(variant).map(Some)
+            }
+        }
+    }
+}
+
+/// Parses an evidence string ready for scoring, adding any evidence derived from scores and frequencies, applying
+/// any criteria specification and failing on or dropping disabled codes or, in strict mode, any diagnostics such as conflicting
+/// codes.
+/// `variant` defaults to the `--variant`, and its allele frequency is looked up if a lookup was requested.
+pub fn parse_assessment(acmg_evidence: &str, variant: Option<&str>, scoring: &Scoring) -> Result<AcmgAssessment, AcmgError> {
+    parse_record(acmg_evidence, variant, None, scoring)
+}
+
+/// Parses an evidence string as [`parse_assessment`], with a SpliceAI delta score given for the record in place of
+/// the `--spliceai` or `--spliceai-vcf` score.
+pub fn parse_record(acmg_evidence: &str, variant: Option<&str>, spliceai: Option<f64>, scoring: &Scoring) -> Result<AcmgAssessment, AcmgError> {
+    let assessment = AcmgAssessment::parse_with_registry(acmg_evidence, &scoring.registry, scoring.duplicates)?;
+    prepare_assessment(assessment, variant, spliceai, scoring)
+}
+
+/// Readies evidence which has already been parsed for scoring, as [`parse_record`].
+pub fn prepare_assessment(assessment: AcmgAssessment, variant: Option<&str>, spliceai: Option<f64>, scoring: &Scoring) -> Result<AcmgAssessment, AcmgError> {
+    let variant = match variant {
+        Some(variant) => Some(GenomicVariant::parse(variant, scoring.build)?),
+        None => scoring.variant.clone(),
+    };
+    let spliceai = spliceai.or(scoring.spliceai).or_else(|| {
+        scoring.spliceai_scores.as_ref().zip(variant.as_ref()).and_then(|(scores, variant)| scores.get(variant))
+    });
+    let mut assessment = assessment
+        .with_guideline(scoring.guideline)
+        .with_classifier(scoring.classifier)
+        .with_model(scoring.model)
+        .with_point_system(scoring.point_system)?;
+    if let Some((predictor, score)) = scoring.predictor_score {
+        assessment = assessment.with_predictor_score(predictor, score)?;
+    }
+    if let Some(delta) = spliceai {
+        assessment = assessment.with_spliceai(delta)?;
+    }
+    if let Some(case_control) = scoring.case_control {
+        assessment = assessment.with_case_control(case_control)?;
+    }
+    assessment = assessment.with_allele_model(scoring.allele_model).with_replications(scoring.replications);
+    if let Some(frequency) = scoring.frequency(variant.as_ref())? {
+        assessment = assessment.with_frequency(frequency, &scoring.frequency_criteria)?;
+    }
+    if let (Some(regions), Some(variant)) = (&scoring.pm1_regions, &variant) {
+        assessment = assessment.with_pm1_regions(regions, variant);
+    }
+    if let (Some(regions), Some(variant)) = (&scoring.repeat_regions, &variant) {
+        assessment = assessment.with_repeat_regions(regions, variant);
+    }
+    if let Some(spec) = &scoring.spec {
+        assessment = assessment.apply_spec(spec)?;
+    }
+    if let Some(inheritance) = scoring.inheritance {
+        assessment = assessment.with_inheritance(inheritance);
+    }
+    if let Some(spec) = scoring.spec.as_ref().filter(|_| !scoring.clingen.is_empty()) {
+        assessment = assessment.with_gene_curation(scoring.clingen.curation(&spec.gene));
+    }
+    match scoring.on_disabled {
+        DisabledPolicy::Error => assessment.check_disabled(&scoring.disabled)?,
+        DisabledPolicy::Drop => assessment = assessment.without_codes(&scoring.disabled),
+    }
+    if let Some(variant) = variant {
+        assessment = assessment.with_variant(variant).with_ba1_exceptions(&scoring.ba1_exceptions);
+    }
+    if scoring.strict { assessment.strict() } else { Ok(assessment) }
+}
+
+/// Output format for commands which print a human-readable report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Text,
+    Json,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exit_code(evidence: &str, exit_on: &[ExitOn]) -> Option<i32> {
+        assessment_exit_code(&evidence.parse().unwrap(), exit_on)
+    }
+
+    #[test]
+    fn error_diagnostics_fail_with_exit_code_1() {
+        assert_eq!(exit_code("BA1, PVS1", &[]), Some(1));
+        assert_eq!(exit_code("BA1, PVS1", &[ExitOn::Benign, ExitOn::UncertainSignificance]), Some(1));
+    }
+
+    #[test]
+    fn classifications_exit_with_their_code_if_listed() {
+        assert_eq!(exit_code("PVS1, PM2", &[]), None);
+        assert_eq!(exit_code("PVS1, PM2", &[ExitOn::Pathogenic, ExitOn::LikelyPathogenic]), Some(10));
+        assert_eq!(exit_code("PVS1, PM2", &[ExitOn::LikelyPathogenic]), None);
+        assert_eq!(exit_code("BA1", &[ExitOn::Benign]), Some(14));
+    }
+
+    #[test]
+    fn warning_diagnostics_dont_fail() {
+        let assessment: AcmgAssessment = "PM2, BA1_Supporting".parse().unwrap();
+        assert!(!assessment.diagnostics().is_empty());
+        assert_eq!(assessment_exit_code(&assessment, &[]), None);
+    }
+}
@@ -0,0 +1,35 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use acmg::{OncogenicityAssessment, OncogenicityClassification, OncogenicityEvidence};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct OncogenicityReport<'a> {
+    evidence: &'a [OncogenicityEvidence],
+    points: i32,
+    classification: OncogenicityClassification,
+}
+
+/// Scores somatic oncogenicity evidence, printing the points of each code with the total and classification.
+pub fn run_oncogenicity(evidence: &str, format: ReportFormat) -> Result<(), CliError> {
+    let assessment: OncogenicityAssessment = evidence.parse()?;
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            for evidence in assessment.evidence() {
+                writeln!(out, "{:5}:{:2} '{}'", evidence, evidence.points(), evidence.code.description())?;
+            }
+            writeln!(out, "--------")?;
+            writeln!(out, "Classification: {:?}", assessment.classify())?;
+            writeln!(out, "Oncogenicity Score: {}", assessment.points())?;
+        }
+        ReportFormat::Json => {
+            let report = OncogenicityReport { evidence: assessment.evidence(), points: assessment.points(), classification: assessment.classify() };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("oncogenicity reports serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
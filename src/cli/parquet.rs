@@ -0,0 +1,203 @@
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::builder::{Float64Builder, Int32Builder, StringBuilder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+
+use crate::cli::CliError;
+
+/// The number of rows read or written at a time, as one record batch.
+const BATCH_ROWS: usize = 8192;
+
+/// Opens a Parquet file, returning its column names and its rows. Rows are read a record batch at a time, with every
+/// value as it's displayed by Arrow, e.g. `0.5` or `[1, 2]`, and nulls as empty cells. Each row is positioned at its
+/// row number in the file, counting from 1.
+pub fn read_parquet(path: &Path) -> Result<(csv::StringRecord, ParquetRows), CliError> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?).map_err(|error| invalid(path, error))?;
+    let headers = builder.schema().fields().iter().map(|field| field.name().as_str()).collect();
+    let reader = builder.with_batch_size(BATCH_ROWS).build().map_err(|error| invalid(path, error))?;
+    Ok((headers, ParquetRows { reader, rows: vec![].into_iter(), line: 0 }))
+}
+
+fn invalid(path: &Path, error: impl std::fmt::Display) -> CliError {
+    CliError::Parquet(format!("{} isn't a valid Parquet file: {}", path.display(), error))
+}
+
+/// The rows of a Parquet file, read as records like those of a CSV file.
+pub struct ParquetRows {
+    reader: ParquetRecordBatchReader,
+    /// The rows of the record batch being read.
+    rows: std::vec::IntoIter<csv::StringRecord>,
+    line: u64,
+}
+
+impl ParquetRows {
+    fn records(&mut self, batch: &RecordBatch) -> Result<Vec<csv::StringRecord>, ArrowError> {
+        let options = FormatOptions::default();
+        let formatters = batch.columns().iter()
+            .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut cell = String::new();
+        let mut records = Vec::with_capacity(batch.num_rows());
+        for index in 0..batch.num_rows() {
+            self.line += 1;
+            let mut record = csv::StringRecord::with_capacity(0, formatters.len());
+            for formatter in &formatters {
+                cell.clear();
+                write!(cell, "{}", formatter.value(index)).map_err(|error| ArrowError::ExternalError(Box::new(error)))?;
+                record.push_field(&cell);
+            }
+            let mut position = csv::Position::new();
+            position.set_line(self.line);
+            record.set_position(Some(position));
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+impl Iterator for ParquetRows {
+    type Item = Result<csv::StringRecord, csv::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.rows.next() {
+                return Some(Ok(row));
+            }
+            let records = self.reader.next()?.and_then(|batch| self.records(&batch));
+            match records {
+                Ok(records) => self.rows = records.into_iter(),
+                Err(error) => return Some(Err(io::Error::other(error).into())),
+            }
+        }
+    }
+}
+
+/// The values of a column of the record batch being written.
+enum Column {
+    Utf8(StringBuilder),
+    Int32(Int32Builder),
+    Float64(Float64Builder),
+}
+
+impl Column {
+    fn new(data_type: &DataType) -> Column {
+        match data_type {
+            DataType::Int32 => Column::Int32(Int32Builder::with_capacity(BATCH_ROWS)),
+            DataType::Float64 => Column::Float64(Float64Builder::with_capacity(BATCH_ROWS)),
+            _ => Column::Utf8(StringBuilder::with_capacity(BATCH_ROWS, BATCH_ROWS * 8)),
+        }
+    }
+
+    /// Appends a cell, as null if it's empty or doesn't parse as the type of the column.
+    fn append(&mut self, cell: &str) {
+        match self {
+            Column::Utf8(builder) => builder.append_option(Some(cell).filter(|cell| !cell.is_empty())),
+            Column::Int32(builder) => builder.append_option(cell.parse().ok()),
+            Column::Float64(builder) => builder.append_option(cell.parse().ok()),
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            Column::Utf8(builder) => Arc::new(builder.finish()),
+            Column::Int32(builder) => Arc::new(builder.finish()),
+            Column::Float64(builder) => Arc::new(builder.finish()),
+        }
+    }
+}
+
+/// Writes rows to a zstd compressed Parquet file, a record batch at a time. Columns are nullable strings, with empty
+/// cells written as nulls, other than those given a type. The file is complete once [`ParquetWriter::finish`] is
+/// called.
+pub struct ParquetWriter {
+    writer: ArrowWriter<Box<dyn Write + Send>>,
+    schema: SchemaRef,
+    columns: Vec<Column>,
+    rows: usize,
+}
+
+impl ParquetWriter {
+    /// Starts a file with a column for each of the header, typed as given by its index, e.g. `Int32` for scores.
+    pub fn new(out: Box<dyn Write + Send>, header: &csv::StringRecord, types: &[(usize, DataType)]) -> Result<ParquetWriter, CliError> {
+        let fields: Vec<Field> = header.iter().enumerate()
+            .map(|(index, name)| {
+                let data_type = types.iter().find(|(column, _)| *column == index).map_or(DataType::Utf8, |(_, data_type)| data_type.clone());
+                Field::new(name, data_type, true)
+            })
+            .collect();
+        let columns = fields.iter().map(|field| Column::new(field.data_type())).collect();
+        let schema = Arc::new(Schema::new(fields));
+        let properties = WriterProperties::builder().set_compression(Compression::ZSTD(ZstdLevel::default())).build();
+        let writer = ArrowWriter::try_new(out, schema.clone(), Some(properties)).map_err(|error| CliError::Parquet(error.to_string()))?;
+        Ok(ParquetWriter { writer, schema, columns, rows: 0 })
+    }
+
+    /// Appends a row, writing a record batch once enough rows have been appended.
+    pub fn write_row(&mut self, row: &csv::StringRecord) -> Result<(), CliError> {
+        for (index, column) in self.columns.iter_mut().enumerate() {
+            column.append(row.get(index).unwrap_or_default());
+        }
+        self.rows += 1;
+        if self.rows == BATCH_ROWS {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    fn write_batch(&mut self) -> Result<(), CliError> {
+        let columns = self.columns.iter_mut().map(Column::finish).collect();
+        let batch = RecordBatch::try_new(self.schema.clone(), columns).map_err(|error| CliError::Parquet(error.to_string()))?;
+        self.writer.write(&batch).map_err(|error| CliError::Parquet(error.to_string()))?;
+        self.rows = 0;
+        Ok(())
+    }
+
+    /// Writes the rows still buffered and the footer of the file, once every row has been appended.
+    pub fn finish(&mut self) -> Result<(), CliError> {
+        if self.rows > 0 {
+            self.write_batch()?;
+        }
+        self.writer.finish().map_err(|error| CliError::Parquet(error.to_string()))?;
+        self.writer.inner_mut().flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_written_are_read_back() {
+        let path = std::env::temp_dir().join(format!("acmg-round-trip-{}.parquet", std::process::id()));
+        let header = csv::StringRecord::from(vec!["acmg_evidence", "acmg_score", "acmg_post_prob_path"]);
+        let mut writer = ParquetWriter::new(Box::new(File::create(&path).unwrap()), &header, &[(1, DataType::Int32), (2, DataType::Float64)]).unwrap();
+        writer.write_row(&csv::StringRecord::from(vec!["PVS1, PM2", "10", "0.994"])).unwrap();
+        writer.write_row(&csv::StringRecord::from(vec!["PX9", "", ""])).unwrap();
+        for _ in 0..BATCH_ROWS {
+            writer.write_row(&csv::StringRecord::from(vec!["BA1", "-8", "0.000"])).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let (headers, rows) = read_parquet(&path).unwrap();
+        let rows: Vec<csv::StringRecord> = rows.collect::<Result<_, _>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(headers, header);
+        assert_eq!(rows.len(), BATCH_ROWS + 2);
+        assert_eq!(rows[0], csv::StringRecord::from(vec!["PVS1, PM2", "10", "0.994"]));
+        assert_eq!(rows[1], csv::StringRecord::from(vec!["PX9", "", ""]));
+        assert_eq!(rows[BATCH_ROWS + 1], csv::StringRecord::from(vec!["BA1", "-8", "0.0"]));
+        let lines: Vec<u64> = rows.iter().map(|row| row.position().unwrap().line()).collect();
+        assert_eq!(lines, (1..=BATCH_ROWS as u64 + 2).collect::<Vec<_>>());
+    }
+}
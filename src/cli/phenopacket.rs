@@ -0,0 +1,89 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use acmg::{AcmgClassification, AcmgError};
+
+use crate::cli::{parse_assessment, CliError, Scoring};
+
+/// The name of the variation descriptor extension holding the ACMG evidence string for the variant.
+const EVIDENCE_EXTENSION: &str = "acmg_evidence";
+
+/// The Phenopacket `ACMGPathogenicityClassification` of a classification.
+fn pathogenicity_classification(classification: AcmgClassification) -> &'static str {
+    match classification {
+        AcmgClassification::Pathogenic => "PATHOGENIC",
+        AcmgClassification::LikelyPathogenic => "LIKELY_PATHOGENIC",
+        AcmgClassification::UncertainSignificance => "UNCERTAIN_SIGNIFICANCE",
+        AcmgClassification::LikelyBenign => "LIKELY_BENIGN",
+        AcmgClassification::Benign => "BENIGN",
+    }
+}
+
+/// Every `variantInterpretation` in the interpretations of a Phenopacket v2.
+fn variant_interpretations(phenopacket: &mut Value) -> Vec<&mut Value> {
+    let Some(interpretations) = phenopacket.get_mut("interpretations").and_then(Value::as_array_mut) else {
+        return vec![];
+    };
+    interpretations.iter_mut()
+        .filter_map(|interpretation| interpretation.pointer_mut("/diagnosis/genomicInterpretations").and_then(Value::as_array_mut))
+        .flatten()
+        .filter_map(|genomic_interpretation| genomic_interpretation.get_mut("variantInterpretation"))
+        .collect()
+}
+
+/// The evidence recorded in the `acmg_evidence` extension of a variation descriptor.
+fn extension_evidence(descriptor: &Value) -> Option<&str> {
+    descriptor.get("extensions")?.as_array()?.iter()
+        .find(|extension| extension.get("name").and_then(Value::as_str) == Some(EVIDENCE_EXTENSION))?
+        .get("value")?.as_str()
+}
+
+/// The VCF record of a variation descriptor as coordinates, e.g. `14-23424119-C-T`.
+fn vcf_coordinates(descriptor: &Value) -> Option<String> {
+    let record = descriptor.get("vcfRecord")?;
+    let field = |name: &str| record.get(name).map(|value| value.as_str().map_or_else(|| value.to_string(), str::to_string));
+    Some(format!("{}-{}-{}-{}", field("chrom")?, field("pos")?, field("ref")?, field("alt")?))
+}
+
+/// Classifies each variant interpretation of a Phenopacket v2 from the evidence in its `acmg_evidence` extension,
+/// or the evidence given for a Phenopacket with a single variant, and writes the Phenopacket back with the
+/// `acmgPathogenicityClassification` of each set. Variant interpretations without any evidence are left as they
+/// were.
+pub fn run_phenopacket(input: &Path, evidence: Option<&str>, output: Option<&Path>, scoring: &Scoring) -> Result<(), CliError> {
+    let mut phenopacket: Value = serde_json::from_str(&fs::read_to_string(input)?)
+        .map_err(|error| AcmgError::InvalidParameter(format!("invalid Phenopacket JSON: {}", error)))?;
+    let mut interpretations = variant_interpretations(&mut phenopacket);
+    if evidence.is_some() && interpretations.len() != 1 {
+        return Err(AcmgError::InvalidParameter(format!("--evidence needs a Phenopacket with a single variant interpretation, found {}; give the evidence for each in an {} extension", interpretations.len(), EVIDENCE_EXTENSION)).into());
+    }
+    let mut classified = 0;
+    for interpretation in interpretations.iter_mut() {
+        let descriptor = interpretation.get("variationDescriptor").cloned().unwrap_or(Value::Null);
+        let Some(acmg_evidence) = evidence.or(extension_evidence(&descriptor)) else {
+            continue;
+        };
+        let assessment = parse_assessment(acmg_evidence, vcf_coordinates(&descriptor).as_deref(), scoring)?;
+        interpretation["acmgPathogenicityClassification"] = json!(pathogenicity_classification(assessment.classify()));
+        if evidence.is_some() && extension_evidence(&descriptor).is_none() {
+            let extension = json!({ "name": EVIDENCE_EXTENSION, "value": acmg_evidence });
+            match interpretation.pointer_mut("/variationDescriptor/extensions").and_then(Value::as_array_mut) {
+                Some(extensions) => extensions.push(extension),
+                None => interpretation["variationDescriptor"]["extensions"] = json!([extension]),
+            }
+        }
+        classified += 1;
+    }
+    if classified == 0 {
+        return Err(AcmgError::InvalidParameter(format!("no variant interpretations with evidence, give it with --evidence or in an {} extension", EVIDENCE_EXTENSION)).into());
+    }
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    writeln!(out, "{}", serde_json::to_string_pretty(&phenopacket).expect("Phenopackets serialize to JSON"))?;
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+
+use acmg::{AcmgAssessment, AcmgClassification};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PlotFormat {
+    /// Unicode bars
+    Terminal,
+    /// An SVG image to embed in reports
+    Svg,
+}
+
+
+/// Terminal columns per point.
+const COLUMNS: i32 = 2;
+
+/// Pixels per point and per row of the SVG, and the width of its criterion labels.
+const UNIT: i32 = 16;
+const ROW: i32 = 22;
+const LABEL_WIDTH: i32 = 140;
+
+/// The points contributed by each criterion as a waterfall, each bar running from the total before the criterion to
+/// the total after it, over the range of points to plot, which always includes every classification band.
+struct Waterfall {
+    bars: Vec<(String, i32, i32)>,
+    /// The lowest points of each classification band above Benign, lowest first.
+    thresholds: [(i32, &'static str); 4],
+    points: i32,
+    classification: AcmgClassification,
+    low: i32,
+    high: i32,
+}
+
+impl Waterfall {
+    fn new(assessment: &AcmgAssessment) -> Waterfall {
+        let system = assessment.point_system();
+        let mut total = 0;
+        let bars: Vec<(String, i32, i32)> = assessment.evidence()
+            .map(|evidence| {
+                let from = total;
+                total += evidence.points_with(system);
+                (evidence.to_string(), from, total)
+            })
+            .collect();
+        let points = assessment.points();
+        let thresholds = [(system.likely_benign, "LB"), (system.uncertain_significance, "VUS"), (system.likely_pathogenic, "LP"), (system.pathogenic, "P")];
+        let ends = bars.iter().flat_map(|(_, from, to)| [*from, *to]).chain([points]);
+        Waterfall {
+            low: ends.clone().min().unwrap_or_default().min(system.likely_benign - 2),
+            high: ends.max().unwrap_or_default().max(system.pathogenic + 2),
+            bars,
+            thresholds,
+            points,
+            classification: assessment.classify(),
+        }
+    }
+}
+
+/// Plots the points contributed by each criterion against the classification thresholds.
+pub fn write_plot(out: &mut impl Write, assessment: &AcmgAssessment, format: PlotFormat) -> io::Result<()> {
+    match format {
+        PlotFormat::Terminal => write_bars(out, assessment),
+        PlotFormat::Svg => writeln!(out, "{}", score_svg(assessment)),
+    }
+}
+
+/// Draws the criteria as unicode bars, pathogenic solid and benign shaded, with the thresholds dotted beneath them.
+pub fn write_bars(out: &mut impl Write, assessment: &AcmgAssessment) -> io::Result<()> {
+    let waterfall = Waterfall::new(assessment);
+    let column = |points: i32| ((points - waterfall.low) * COLUMNS) as usize;
+    let width = column(waterfall.high);
+    let label_width = waterfall.bars.iter().map(|(label, _, _)| label.len()).max().unwrap_or_default().max("Total".len());
+    let row = |from: i32, to: i32| {
+        let mut cells = vec![' '; width];
+        for (threshold, _) in waterfall.thresholds {
+            cells[column(threshold)] = '┊';
+        }
+        let fill = if to >= from { '█' } else { '▒' };
+        for cell in &mut cells[column(from.min(to))..column(from.max(to))] {
+            *cell = fill;
+        }
+        cells.into_iter().collect::<String>()
+    };
+
+    for (label, from, to) in &waterfall.bars {
+        writeln!(out, "{:label_width$} {} {:+}", label, row(*from, *to), to - from)?;
+    }
+    writeln!(out, "{:label_width$} {} {}", "Total", row(0, waterfall.points), waterfall.points)?;
+
+    let mut ticks = vec![' '; width];
+    for (threshold, _) in waterfall.thresholds {
+        for (offset, c) in threshold.to_string().chars().enumerate() {
+            if let Some(tick) = ticks.get_mut(column(threshold) + offset) {
+                *tick = c;
+            }
+        }
+    }
+    writeln!(out, "{:label_width$} {}", "", ticks.into_iter().collect::<String>().trim_end())?;
+    let mut edges = vec![waterfall.low];
+    edges.extend(waterfall.thresholds.map(|(threshold, _)| threshold));
+    edges.push(waterfall.high);
+    let mut bands = String::new();
+    for (edge, label) in edges.windows(2).zip(["B"].into_iter().chain(waterfall.thresholds.map(|(_, label)| label))) {
+        let _ = write!(bands, "{:^span$}", label, span = column(edge[1]) - column(edge[0]));
+    }
+    writeln!(out, "{:label_width$} {}", "", bands.trim_end())?;
+    writeln!(out, "{} points: {}", waterfall.points, waterfall.classification.term())
+}
+
+/// Renders the criteria as an SVG bar chart, pathogenic red and benign blue, with a dashed line at each threshold.
+pub fn score_svg(assessment: &AcmgAssessment) -> String {
+    let waterfall = Waterfall::new(assessment);
+    let x = |points: i32| LABEL_WIDTH + (points - waterfall.low) * UNIT;
+    let width = x(waterfall.high) + UNIT;
+    let rows = waterfall.bars.len() as i32 + 1;
+    let height = ROW * (rows + 2);
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" font-family="sans-serif" font-size="12">"#, width, height, width, height);
+    let _ = writeln!(svg, "<title>{} points: {}</title>", waterfall.points, waterfall.classification.term());
+    for (threshold, label) in waterfall.thresholds {
+        let _ = writeln!(svg, r##"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#888" stroke-dasharray="4 3"/>"##, x(threshold), ROW, x(threshold), ROW * (rows + 1));
+        let _ = writeln!(svg, r##"<text x="{}" y="{}" fill="#555">{} {}</text>"##, x(threshold) + 3, ROW - 6, label, threshold);
+    }
+    let bars = waterfall.bars.iter().map(|(label, from, to)| (label.as_str(), *from, *to)).chain([("Total", 0, waterfall.points)]);
+    for (index, (label, from, to)) in bars.enumerate() {
+        let y = ROW * (index as i32 + 1);
+        let fill = if to >= from { "#c0392b" } else { "#2471a3" };
+        let _ = writeln!(svg, r#"<text x="4" y="{}">{}</text>"#, y + ROW - 7, escape_xml(label));
+        let _ = writeln!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#, x(from.min(to)), y + 3, (to - from).abs() * UNIT, ROW - 6, fill);
+        let _ = writeln!(svg, r#"<text x="{}" y="{}">{:+}</text>"#, x(from.max(to)) + 4, y + ROW - 7, to - from);
+    }
+    let _ = writeln!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="black"/>"#, x(0), ROW, x(0), ROW * (rows + 1));
+    let _ = writeln!(svg, r#"<text x="4" y="{}" font-weight="bold">{} points: {}</text>"#, ROW * (rows + 2) - 7, waterfall.points, waterfall.classification.term());
+    svg.push_str("</svg>");
+    svg
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,35 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use acmg::{EvidenceStrength, Pm3Observations};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct Pm3Report {
+    observations: Pm3Observations,
+    points: f64,
+    evidence: Option<String>,
+    strength: Option<EvidenceStrength>,
+}
+
+/// Prints the PM3 strength recommended for the in trans observations, which can be passed on to `acmg info`.
+pub fn run_pm3(observations: Pm3Observations, format: ReportFormat) -> Result<(), CliError> {
+    let evidence = observations.evidence();
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            match &evidence {
+                Some(evidence) => writeln!(out, "{}", evidence)?,
+                None => writeln!(out, "N/A")?,
+            }
+            writeln!(out, "{} points", observations.points())?;
+        }
+        ReportFormat::Json => {
+            let report = Pm3Report { observations, points: observations.points(), evidence: evidence.map(|evidence| evidence.to_string()), strength: observations.strength() };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("PM3 reports serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
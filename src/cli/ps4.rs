@@ -0,0 +1,47 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use acmg::{CaseControl, EvidenceStrength};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct Ps4Report {
+    case_control: CaseControl,
+    odds_ratio: f64,
+    confidence_interval: (f64, f64),
+    p_value: f64,
+    evidence: Option<String>,
+    strength: Option<EvidenceStrength>,
+}
+
+/// Prints the odds ratio, its 95% confidence interval and Fisher's exact p-value for the case-control counts, and the
+/// PS4 strength they support, which can be passed on to `acmg info` or applied there with `--ps4-cases`.
+pub fn run_ps4(case_control: CaseControl, format: ReportFormat) -> Result<(), CliError> {
+    let evidence = case_control.evidence();
+    let (lower, upper) = case_control.confidence_interval();
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            match &evidence {
+                Some(evidence) => writeln!(out, "{}", evidence)?,
+                None => writeln!(out, "N/A")?,
+            }
+            writeln!(out, "Odds ratio: {:.2} (95% CI {:.2}-{:.2})", case_control.odds_ratio(), lower, upper)?;
+            writeln!(out, "Fisher's exact p-value: {:.3e}", case_control.fisher_p_value())?;
+        }
+        ReportFormat::Json => {
+            let report = Ps4Report {
+                case_control,
+                odds_ratio: case_control.odds_ratio(),
+                confidence_interval: (lower, upper),
+                p_value: case_control.fisher_p_value(),
+                evidence: evidence.map(|evidence| evidence.to_string()),
+                strength: case_control.strength(),
+            };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("PS4 reports serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::io::{self, Write};
+
+use clap::Args;
+use serde::Serialize;
+
+use acmg::{assess_pvs1, EvidenceStrength, Pvs1Input, VariantType};
+
+use crate::cli::{CliError, ReportFormat};
+
+/// Answers to the questions of the PVS1 decision tree, each answered no unless given.
+#[derive(Debug, Clone, Args)]
+pub struct Pvs1Args {
+    /// Loss of function is not a known mechanism of disease for the gene
+    #[arg(long)]
+    pub no_lof_mechanism: bool,
+    /// The reading frame is preserved, e.g. by an in-frame exon skip or deletion
+    #[arg(long)]
+    pub in_frame: bool,
+    /// The variant is predicted to undergo nonsense mediated decay
+    #[arg(long)]
+    pub nmd: bool,
+    /// The truncated or altered region is critical to protein function
+    #[arg(long)]
+    pub critical_region: bool,
+    /// Loss of function variants in the exon are frequent in the general population
+    #[arg(long)]
+    pub frequent_lof: bool,
+    /// The exon is absent from biologically relevant transcripts
+    #[arg(long)]
+    pub absent_from_relevant_transcripts: bool,
+    /// The variant removes more than 10% of the protein
+    #[arg(long)]
+    pub removes_over_10_percent: bool,
+    /// A different functional transcript uses an alternative start codon
+    #[arg(long)]
+    pub alternative_start_codon: bool,
+    /// Pathogenic variants are upstream of the closest potential in-frame start codon
+    #[arg(long)]
+    pub upstream_pathogenic: bool,
+}
+
+impl Pvs1Args {
+    fn input(&self, variant_type: VariantType) -> Pvs1Input {
+        Pvs1Input {
+            variant_type,
+            lof_mechanism: !self.no_lof_mechanism,
+            in_frame: self.in_frame,
+            nmd: self.nmd,
+            critical_region: self.critical_region,
+            frequent_lof: self.frequent_lof,
+            absent_from_relevant_transcripts: self.absent_from_relevant_transcripts,
+            removes_over_10_percent: self.removes_over_10_percent,
+            alternative_start_codon: self.alternative_start_codon,
+            upstream_pathogenic: self.upstream_pathogenic,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Pvs1Report {
+    input: Pvs1Input,
+    evidence: Option<String>,
+    strength: Option<EvidenceStrength>,
+    reason: &'static str,
+}
+
+/// Prints the PVS1 strength recommended by the decision tree, which can be passed on to `acmg info`.
+pub fn run_pvs1(variant_type: VariantType, args: &Pvs1Args, format: ReportFormat) -> Result<(), CliError> {
+    let input = args.input(variant_type);
+    let outcome = assess_pvs1(&input);
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            writeln!(out, "{}", outcome)?;
+            writeln!(out, "{}", outcome.reason)?;
+        }
+        ReportFormat::Json => {
+            let report = Pvs1Report { input, evidence: outcome.evidence().map(|evidence| evidence.to_string()), strength: outcome.strength, reason: outcome.reason };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("PVS1 outcomes serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
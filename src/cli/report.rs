@@ -0,0 +1,324 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use acmg::{AcmgAssessment, AcmgClassification, Citation, ConflictingEvidence, EvidenceStrength, Guideline, HgvsVariant, PointSystem};
+
+use crate::cli::metadata::RunMetadata;
+use crate::cli::plot::score_svg;
+use crate::cli::{parse_assessment, CliError, Scoring};
+
+/// The formats reports are rendered in. There is no PDF output, a PDF can be printed from the HTML by a browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DocumentFormat {
+    Html,
+    Markdown,
+}
+
+/// The context a report is rendered from, which is also given to user templates.
+#[derive(Debug, Serialize)]
+pub struct ReportContext {
+    /// The variant as HGVS and/or coordinates, if given.
+    variant: Option<String>,
+    hgvs: Option<String>,
+    coordinates: Option<String>,
+    build: Option<String>,
+    evidence: Vec<EvidenceContext>,
+    points: i32,
+    classification: AcmgClassification,
+    /// The classification of a low penetrance variant or a risk allele, e.g. `Established risk allele`.
+    allele_classification: Option<&'static str>,
+    /// The points of each direction, if the evidence conflicts.
+    conflicting_evidence: Option<ConflictingEvidence>,
+    post_prob_path: f64,
+    /// The ACMG secondary findings gene the variant is in, e.g. `BRCA1 is an ACMG SF v3.2 gene (cancer)`.
+    secondary_findings: Option<String>,
+    /// The guideline and any criteria specification, with the version of acmg.
+    guideline: String,
+    spec: Option<String>,
+    version: &'static str,
+    /// Each diagnostic, e.g. `warning[W002]: ...`.
+    warnings: Vec<String>,
+    /// An SVG plot of the points of each criterion against the classification thresholds.
+    plot: String,
+    /// The publications cited for the criteria, numbered in order of first citation.
+    references: Vec<ReferenceContext>,
+    metadata: RunMetadata,
+}
+
+/// An applied criterion in a [`ReportContext`].
+#[derive(Debug, Serialize)]
+struct EvidenceContext {
+    code: String,
+    description: String,
+    strength: EvidenceStrength,
+    points: i32,
+    note: Option<String>,
+    /// The numbers of the references cited for the criterion.
+    references: Vec<usize>,
+}
+
+/// A numbered entry of the bibliography of a [`ReportContext`].
+#[derive(Debug, Serialize)]
+struct ReferenceContext {
+    number: usize,
+    /// The citation as written, e.g. `PMID:30311383`.
+    citation: String,
+    url: String,
+}
+
+impl ReportContext {
+    fn new(assessment: &AcmgAssessment, input: &str, timestamp: &str) -> ReportContext {
+        let hgvs = assessment.hgvs().map(HgvsVariant::to_string);
+        let coordinates = assessment.variant().map(|variant| variant.to_string());
+        let build = assessment.variant().map(|variant| variant.build.to_string());
+        let variant = match (&hgvs, &coordinates, &build) {
+            (Some(hgvs), Some(coordinates), Some(build)) => Some(format!("{}, {} ({})", hgvs, coordinates, build)),
+            (Some(hgvs), _, _) => Some(hgvs.clone()),
+            (None, Some(coordinates), Some(build)) => Some(format!("{} ({})", coordinates, build)),
+            _ => None,
+        };
+        let version = env!("CARGO_PKG_VERSION");
+        let spec = assessment.spec().map(|spec| spec.label());
+        let guideline = match assessment.guideline() {
+            Guideline::Points2020 => format!("{} ({}, {} classifier)", assessment.guideline(), assessment.guideline().version(), format!("{:?}", assessment.classifier()).to_lowercase()),
+            guideline => guideline.label(),
+        };
+        let guideline = match assessment.point_system() {
+            system if *system != PointSystem::default() => format!("{} ({})", guideline, system),
+            _ => guideline,
+        };
+        let guideline = match &spec {
+            Some(spec) => format!("{}, {} specification, acmg {}", guideline, spec, version),
+            None => format!("{}, acmg {}", guideline, version),
+        };
+        let mut citations: Vec<&Citation> = vec![];
+        let evidence = assessment.evidence().map(|evidence| EvidenceContext {
+            code: evidence.to_string(),
+            description: evidence.evidence_code.description.to_string(),
+            strength: evidence.strength(),
+            points: evidence.points_with(assessment.point_system()),
+            note: evidence.note.clone(),
+            references: evidence.citations.iter().map(|citation| {
+                let index = citations.iter().position(|other| *other == citation).unwrap_or_else(|| {
+                    citations.push(citation);
+                    citations.len() - 1
+                });
+                index + 1
+            }).collect(),
+        }).collect();
+        ReportContext {
+            variant,
+            hgvs,
+            coordinates,
+            build,
+            evidence,
+            points: assessment.points(),
+            classification: assessment.classify(),
+            allele_classification: assessment.allele_classification().map(|allele_classification| allele_classification.term()),
+            conflicting_evidence: assessment.conflicting_evidence(),
+            post_prob_path: assessment.post_prob_path(),
+            secondary_findings: assessment.secondary_findings().map(|secondary_findings| secondary_findings.to_string()),
+            guideline,
+            spec,
+            version,
+            warnings: assessment.diagnostics().iter().map(|diagnostic| diagnostic.to_string()).collect(),
+            plot: score_svg(assessment),
+            references: citations.iter().enumerate().map(|(index, citation)| ReferenceContext {
+                number: index + 1,
+                citation: citation.to_string(),
+                url: citation.url(),
+            }).collect(),
+            metadata: RunMetadata::new(assessment, input, timestamp),
+        }
+    }
+}
+
+/// Renders a clinical summary of the evidence for a variant, for pasting into a case record, with the built-in
+/// layout for the format or a user template.
+pub fn run_report(hgvs: Option<&str>, acmg_evidence: &str, format: DocumentFormat, template: Option<&Path>, output: Option<&Path>, scoring: &Scoring) -> Result<(), CliError> {
+    let mut assessment = parse_assessment(acmg_evidence, None, scoring)?;
+    if let Some(hgvs) = hgvs {
+        assessment = scoring.with_hgvs(assessment, hgvs.parse::<HgvsVariant>()?);
+    }
+    let context = ReportContext::new(&assessment, acmg_evidence, &scoring.started);
+    let rendered = template.map(|template| render_template(template, &context)).transpose()?;
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    match (rendered, format) {
+        (Some(rendered), _) => out.write_all(rendered.as_bytes())?,
+        (None, DocumentFormat::Html) => write_html(&mut out, &context)?,
+        (None, DocumentFormat::Markdown) => write_markdown(&mut out, &context)?,
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Renders a Tera template with the report context, escaping HTML if the template is named `.html` or
+/// `.html.tera`.
+#[cfg(feature = "templates")]
+fn render_template(path: &Path, context: &ReportContext) -> Result<String, CliError> {
+    use std::error::Error;
+
+    let template = std::fs::read_to_string(path)?;
+    let name = path.to_string_lossy();
+    let autoescape = name.ends_with(".html") || name.ends_with(".html.tera");
+    let context = tera::Context::from_serialize(context).map_err(|error| CliError::Template(error.to_string()))?;
+    tera::Tera::one_off(&template, &context, autoescape).map_err(|error| {
+        let mut message = error.to_string();
+        let mut source = error.source();
+        while let Some(error) = source {
+            message = format!("{}: {}", message, error);
+            source = error.source();
+        }
+        CliError::Template(message)
+    })
+}
+
+#[cfg(not(feature = "templates"))]
+fn render_template(_path: &Path, _context: &ReportContext) -> Result<String, CliError> {
+    Err(CliError::FeatureDisabled("templates"))
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+/// The note of a criterion followed by the numbers of the references cited for it, e.g. `MAVE assay [1, 2]`.
+fn notes(note: String, references: &[String]) -> String {
+    match references {
+        [] => note,
+        references if note.is_empty() => format!("[{}]", references.join(", ")),
+        references => format!("{} [{}]", note, references.join(", ")),
+    }
+}
+
+fn write_html(out: &mut impl Write, context: &ReportContext) -> io::Result<()> {
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html lang=\"en\">")?;
+    writeln!(out, "<head>")?;
+    writeln!(out, "<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>ACMG classification</title>")?;
+    writeln!(out, "<style>")?;
+    writeln!(out, "body {{ font-family: sans-serif; }}")?;
+    writeln!(out, "table {{ border-collapse: collapse; }}")?;
+    writeln!(out, "th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; }}")?;
+    writeln!(out, "</style>")?;
+    writeln!(out, "</head>")?;
+    writeln!(out, "<body>")?;
+    writeln!(out, "<h1>ACMG classification</h1>")?;
+    writeln!(out, "<dl>")?;
+    if let Some(variant) = &context.variant {
+        writeln!(out, "<dt>Variant</dt><dd>{}</dd>", escape_html(variant))?;
+    }
+    if let Some(allele_classification) = context.allele_classification {
+        writeln!(out, "<dt>Classification</dt><dd>{}</dd>", allele_classification)?;
+    }
+    let label = if context.allele_classification.is_some() { "ACMG classification" } else { "Classification" };
+    match &context.conflicting_evidence {
+        Some(conflicting) => writeln!(out, "<dt>{}</dt><dd>{:?} ({})</dd>", label, context.classification, conflicting)?,
+        None => writeln!(out, "<dt>{}</dt><dd>{:?}</dd>", label, context.classification)?,
+    }
+    if let Some(secondary_findings) = &context.secondary_findings {
+        writeln!(out, "<dt>Secondary findings</dt><dd>{}</dd>", escape_html(secondary_findings))?;
+    }
+    writeln!(out, "<dt>Points</dt><dd>{}</dd>", context.points)?;
+    writeln!(out, "<dt>Posterior probability of pathogenicity</dt><dd>{:.3}</dd>", context.post_prob_path)?;
+    writeln!(out, "<dt>Guideline</dt><dd>{}</dd>", escape_html(&context.guideline))?;
+    writeln!(out, "<dt>Parameters</dt><dd>{}</dd>", escape_html(&context.metadata.parameters.to_string()))?;
+    writeln!(out, "<dt>Evidence SHA-256</dt><dd>{}</dd>", context.metadata.input_sha256)?;
+    writeln!(out, "<dt>Generated</dt><dd>{}</dd>", context.metadata.timestamp)?;
+    writeln!(out, "</dl>")?;
+    writeln!(out, "<h2>Evidence</h2>")?;
+    writeln!(out, "<table>")?;
+    writeln!(out, "<tr><th>Criterion</th><th>Description</th><th>Strength</th><th>Points</th><th>Notes</th></tr>")?;
+    for evidence in &context.evidence {
+        let references: Vec<String> = evidence.references.iter().map(|number| format!("<a href=\"#ref-{0}\">{0}</a>", number)).collect();
+        let notes = notes(escape_html(evidence.note.as_deref().unwrap_or("")), &references);
+        writeln!(out, "<tr><td>{}</td><td>{}</td><td>{:?}</td><td>{}</td><td>{}</td></tr>",
+                 evidence.code, escape_html(&evidence.description), evidence.strength, evidence.points, notes)?;
+    }
+    writeln!(out, "</table>")?;
+    writeln!(out, "<h2>Score</h2>")?;
+    writeln!(out, "{}", context.plot)?;
+    if !context.references.is_empty() {
+        writeln!(out, "<h2>References</h2>")?;
+        writeln!(out, "<ol>")?;
+        for reference in &context.references {
+            writeln!(out, "<li id=\"ref-{}\"><a href=\"{}\">{}</a></li>", reference.number, escape_html(&reference.url), escape_html(&reference.citation))?;
+        }
+        writeln!(out, "</ol>")?;
+    }
+    if !context.warnings.is_empty() {
+        writeln!(out, "<h2>Warnings</h2>")?;
+        writeln!(out, "<ul>")?;
+        for warning in &context.warnings {
+            writeln!(out, "<li>{}</li>", escape_html(warning))?;
+        }
+        writeln!(out, "</ul>")?;
+    }
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")
+}
+
+fn write_markdown(out: &mut impl Write, context: &ReportContext) -> io::Result<()> {
+    writeln!(out, "# ACMG classification")?;
+    writeln!(out)?;
+    if let Some(variant) = &context.variant {
+        writeln!(out, "- **Variant:** {}", variant)?;
+    }
+    if let Some(allele_classification) = context.allele_classification {
+        writeln!(out, "- **Classification:** {}", allele_classification)?;
+    }
+    let label = if context.allele_classification.is_some() { "ACMG classification" } else { "Classification" };
+    match &context.conflicting_evidence {
+        Some(conflicting) => writeln!(out, "- **{}:** {:?} ({})", label, context.classification, conflicting)?,
+        None => writeln!(out, "- **{}:** {:?}", label, context.classification)?,
+    }
+    if let Some(secondary_findings) = &context.secondary_findings {
+        writeln!(out, "- **Secondary findings:** {}", secondary_findings)?;
+    }
+    writeln!(out, "- **Points:** {}", context.points)?;
+    writeln!(out, "- **Posterior probability of pathogenicity:** {:.3}", context.post_prob_path)?;
+    writeln!(out, "- **Guideline:** {}", context.guideline)?;
+    writeln!(out, "- **Parameters:** {}", context.metadata.parameters)?;
+    writeln!(out, "- **Evidence SHA-256:** {}", context.metadata.input_sha256)?;
+    writeln!(out, "- **Generated:** {}", context.metadata.timestamp)?;
+    writeln!(out)?;
+    writeln!(out, "## Evidence")?;
+    writeln!(out)?;
+    writeln!(out, "| Criterion | Description | Strength | Points | Notes |")?;
+    writeln!(out, "|---|---|---|---|---|")?;
+    for evidence in &context.evidence {
+        let references: Vec<String> = evidence.references.iter().map(ToString::to_string).collect();
+        let notes = notes(escape_markdown(evidence.note.as_deref().unwrap_or("")), &references);
+        writeln!(out, "| {} | {} | {:?} | {} | {} |",
+                 evidence.code, escape_markdown(&evidence.description), evidence.strength, evidence.points, notes)?;
+    }
+    if !context.references.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "## References")?;
+        writeln!(out)?;
+        for reference in &context.references {
+            writeln!(out, "{}. [{}]({})", reference.number, reference.citation, reference.url)?;
+        }
+    }
+    if !context.warnings.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "## Warnings")?;
+        writeln!(out)?;
+        for warning in &context.warnings {
+            writeln!(out, "- {}", warning)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,11 @@
+use std::io::{self, Write};
+
+use acmg::RESULT_SCHEMA;
+
+use crate::cli::CliError;
+
+/// Prints the JSON schema of the results written with JSON output, for validating them and generating code from.
+pub fn run_schema() -> Result<(), CliError> {
+    io::stdout().lock().write_all(RESULT_SCHEMA.as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use acmg::{Pedigree, SegregationCounts};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct SegregationReport<'a> {
+    counts: SegregationCounts,
+    lod: f64,
+    evidence: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    families: Option<&'a BTreeMap<String, SegregationCounts>>,
+}
+
+/// Counts the informative meioses of each family of a PED file from the carriers and non-carriers of the variant.
+pub fn read_ped_counts(path: &Path, carriers: &[String], noncarriers: &[String], probands: &[String]) -> Result<BTreeMap<String, SegregationCounts>, CliError> {
+    let pedigree = Pedigree::from_ped(BufReader::new(File::open(path)?))?;
+    Ok(pedigree.segregation_counts(&ids(carriers), &ids(noncarriers), &ids(probands))?)
+}
+
+fn ids(ids: &[String]) -> Vec<&str> {
+    ids.iter().map(String::as_str).collect()
+}
+
+/// Prints the LOD score and the PP1 or BS4 evidence for the segregation counts, which can be passed on to
+/// `acmg info`, with the counts of each family if they were read from a PED file.
+pub fn run_segregation(counts: SegregationCounts, families: Option<&BTreeMap<String, SegregationCounts>>, format: ReportFormat) -> Result<(), CliError> {
+    let evidence = counts.evidence();
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            match &evidence {
+                Some(evidence) => writeln!(out, "{}", evidence)?,
+                None => writeln!(out, "N/A")?,
+            }
+            writeln!(out, "LOD: {:.2}", counts.lod())?;
+            if let Some(families) = families {
+                writeln!(out, "Counts: {}", describe_counts(&counts))?;
+                for (family, counts) in families {
+                    writeln!(out, "  {}: {}", family, describe_counts(counts))?;
+                }
+            }
+        }
+        ReportFormat::Json => {
+            let report = SegregationReport { counts, lod: counts.lod(), evidence: evidence.map(|evidence| evidence.to_string()), families };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("segregation reports serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
+
+fn describe_counts(counts: &SegregationCounts) -> String {
+    format!("{} affected carriers, {} unaffected non-carriers, {} affected non-carriers",
+            counts.affected_carriers, counts.unaffected_noncarriers, counts.affected_noncarriers)
+}
@@ -0,0 +1,97 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+
+use acmg::{posterior_classification, AcmgClassification, BayesianModel};
+
+use crate::cli::{parse_assessment, CliError, Scoring};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SensitivityFormat {
+    Table,
+    Csv,
+}
+
+/// Values of a model parameter to sweep, from `start` to `end` inclusive in steps of `step`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sweep {
+    start: f64,
+    end: f64,
+    step: f64,
+}
+
+impl Sweep {
+    pub fn single(value: f64) -> Sweep {
+        Sweep { start: value, end: value, step: 1.0 }
+    }
+
+    fn values(&self) -> Vec<f64> {
+        let steps = ((self.end - self.start) / self.step + 1e-9).floor() as usize;
+        (0..=steps).map(|index| ((self.start + index as f64 * self.step) * 1e9).round() / 1e9).collect()
+    }
+}
+
+/// Parses a sweep from START:END:STEP, e.g. 0.01:0.5:0.01, or a single value.
+pub fn parse_sweep(s: &str) -> Result<Sweep, String> {
+    let parse = |value: &str| value.trim().parse::<f64>().map_err(|_| format!("invalid number '{}'", value));
+    let values: Vec<&str> = s.split(':').collect();
+    let sweep = match values[..] {
+        [value] => Sweep::single(parse(value)?),
+        [start, end, step] => Sweep { start: parse(start)?, end: parse(end)?, step: parse(step)? },
+        _ => return Err(format!("expected START:END:STEP or a single value, got '{}'", s)),
+    };
+    if !(sweep.step > 0.0 && sweep.end >= sweep.start) {
+        return Err(format!("expected a positive step from START up to END, got '{}'", s));
+    }
+    Ok(sweep)
+}
+
+/// Classifies the evidence from its posterior probability with every combination of the swept prior probabilities,
+/// Very Strong OddsPaths and exponential progressions, showing how sensitive the classification is to the model.
+pub fn run_sensitivity(acmg_evidence: &str, priors: Sweep, odds_paths: Sweep, exponents: Sweep, format: SensitivityFormat, scoring: &Scoring) -> Result<(), CliError> {
+    let assessment = parse_assessment(acmg_evidence, None, scoring)?;
+    let mut models = vec![];
+    for prior in priors.values() {
+        for odds_path in odds_paths.values() {
+            for exponent in exponents.values() {
+                models.push(BayesianModel::new(prior, odds_path, exponent)?);
+            }
+        }
+    }
+    let mut out = io::stdout().lock();
+    match format {
+        SensitivityFormat::Table => {
+            let mut classifications = vec![];
+            writeln!(out, "{:>8} {:>10} {:>8} {:>14}  Classification", "Prior", "OddsPath", "Exponent", "Post_prob_path")?;
+            for model in &models {
+                let post_prob_path = model.post_prob_path(assessment.evidence_set());
+                let classification = posterior_classification(post_prob_path);
+                classifications.push(classification);
+                writeln!(out, "{:>8} {:>10} {:>8} {:>14.4}  {}",
+                         model.prior_prob(), model.odds_path_very_strong(), model.exponential_progression(), post_prob_path, classification.term())?;
+            }
+            let counts: Vec<String> = AcmgClassification::ALL.iter()
+                .map(|classification| (classification, classifications.iter().filter(|other| *other == classification).count()))
+                .filter(|(_, count)| *count > 0)
+                .map(|(classification, count)| format!("{} in {}", classification.term(), count))
+                .collect();
+            writeln!(out, "{}: {} of {} models", assessment.evidence_set(), counts.join(", "), models.len())?;
+        }
+        SensitivityFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            writer.write_record(["prior", "odds_path_very_strong", "exponential_progression", "post_prob_path", "classification"])?;
+            for model in &models {
+                let post_prob_path = model.post_prob_path(assessment.evidence_set());
+                writer.write_record([
+                    model.prior_prob().to_string(),
+                    model.odds_path_very_strong().to_string(),
+                    model.exponential_progression().to_string(),
+                    format!("{:.6}", post_prob_path),
+                    format!("{:?}", posterior_classification(post_prob_path)),
+                ])?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi, ToSchema};
+
+use acmg::{AcmgAssessment, AcmgError, AssessmentResult, Diagnostic, Evidence, EvidenceSet, HgvsVariant};
+
+use crate::cli::auth::{self, Access};
+use crate::cli::jobs::{self, Jobs, MAX_JOB_BYTES};
+use crate::cli::list::CodeRecord;
+use crate::cli::metrics::{self, Metrics};
+use crate::cli::websocket;
+use crate::cli::{parse_assessment, prepare_assessment, CliError, Scoring};
+
+/// Evidence given as a string, e.g. `"PVS1, PM2_Supporting"`, or as a list of evidence, e.g.
+/// `[{"code": "PVS1"}, {"code": "PM2", "modifier": "Supporting"}]`.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum EvidenceInput {
+    Text(String),
+    Structured(Vec<Evidence>),
+}
+
+/// The body of a `POST /classify` with a JSON content type.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ClassifyRequest {
+    pub evidence: EvidenceInput,
+    /// VCF coordinates of the variant, e.g. 7-117559590-G-A, in place of the server's `--variant`.
+    #[serde(default)]
+    pub variant: Option<String>,
+    /// The HGVS description of the variant, e.g. NM_000257.4:c.1208G>A.
+    #[serde(default)]
+    pub hgvs: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    error: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    diagnostics: Vec<Diagnostic>,
+}
+
+pub fn error_response(status: StatusCode, error: String) -> Response {
+    (status, Json(ErrorBody { error, diagnostics: vec![] })).into_response()
+}
+
+/// Failed lookups are the fault of the upstream service, anything else of the request.
+fn acmg_error_response(error: AcmgError) -> Response {
+    let status = match error {
+        AcmgError::Lookup(_) => StatusCode::BAD_GATEWAY,
+        _ => StatusCode::UNPROCESSABLE_ENTITY,
+    };
+    let diagnostics = match &error {
+        AcmgError::Diagnostics(diagnostics) => diagnostics.clone(),
+        _ => vec![],
+    };
+    (status, Json(ErrorBody { error: error.to_string(), diagnostics })).into_response()
+}
+
+/// Classifies a request, counting the classification in the metrics.
+pub fn classify(request: ClassifyRequest, state: &AppState) -> Result<AssessmentResult, AcmgError> {
+    let scoring = &state.scoring;
+    let mut assessment = match request.evidence {
+        EvidenceInput::Text(acmg_evidence) => parse_assessment(&acmg_evidence, request.variant.as_deref(), scoring)?,
+        EvidenceInput::Structured(evidence) => {
+            let mut set = EvidenceSet::new();
+            for evidence in evidence {
+                let name = evidence.to_string();
+                if !set.insert(evidence) {
+                    return Err(AcmgError::DuplicateCode(name));
+                }
+            }
+            if set.is_empty() {
+                return Err(AcmgError::EmptyInput);
+            }
+            prepare_assessment(AcmgAssessment::new(set), request.variant.as_deref(), None, scoring)?
+        }
+    };
+    if let Some(hgvs) = request.hgvs {
+        assessment = scoring.with_hgvs(assessment, hgvs.parse::<HgvsVariant>()?);
+    }
+    let result = assessment.result();
+    state.metrics.classified(result.classification);
+    Ok(result)
+}
+
+/// Classifies a JSON [`ClassifyRequest`], or a plain evidence string for any other content type. Classification
+/// may block on allele frequency lookups so runs off the async workers.
+#[utoipa::path(
+    post,
+    path = "/classify",
+    request_body(content((ClassifyRequest = "application/json"), (String = "text/plain", example = "PVS1, PM2_Supporting"))),
+    responses(
+        (status = 200, description = "The classification of the evidence", body = AssessmentResult),
+        (status = 400, description = "The request body could not be read", body = ErrorBody),
+        (status = 422, description = "The evidence is invalid", body = ErrorBody),
+        (status = 502, description = "An allele frequency lookup failed", body = ErrorBody),
+    ),
+)]
+async fn post_classify(State(state): State<Arc<AppState>>, headers: HeaderMap, body: Bytes) -> Response {
+    let is_json = headers.get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+    let request = if is_json {
+        match serde_json::from_slice::<ClassifyRequest>(&body) {
+            Ok(request) => request,
+            Err(error) => return error_response(StatusCode::BAD_REQUEST, format!("Invalid request: {}", error)),
+        }
+    } else {
+        match String::from_utf8(body.to_vec()) {
+            Ok(acmg_evidence) => ClassifyRequest { evidence: EvidenceInput::Text(acmg_evidence), variant: None, hgvs: None },
+            Err(_) => return error_response(StatusCode::BAD_REQUEST, "Evidence must be UTF-8".to_string()),
+        }
+    };
+    match tokio::task::spawn_blocking(move || classify(request, &state)).await {
+        Ok(Ok(result)) => Json(result).into_response(),
+        Ok(Err(error)) => acmg_error_response(error),
+        Err(_) => error_response(StatusCode::INTERNAL_SERVER_ERROR, "Classification failed".to_string()),
+    }
+}
+
+/// Lists the built-in evidence codes.
+#[utoipa::path(get, path = "/codes", responses((status = 200, description = "Every built-in evidence code", body = Vec<CodeRecord>)))]
+async fn get_codes(State(state): State<Arc<AppState>>) -> Json<Vec<CodeRecord>> {
+    Json(state.scoring.registry.evidence_codes().into_iter().map(CodeRecord::from).collect())
+}
+
+/// The OpenAPI document of the server.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "acmg", description = "Classify variants from ACMG/AMP evidence."),
+    paths(post_classify, get_codes, jobs::post_jobs, jobs::get_job, jobs::get_job_results, metrics::get_metrics, websocket::get_validate, get_openapi),
+    modifiers(&ApiKeys),
+    security((), ("bearer" = []), ("api_key" = [])),
+)]
+struct ApiDoc;
+
+/// The ways of giving an API key, which are required if the server has any.
+struct ApiKeys;
+
+impl Modify for ApiKeys {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme("bearer", SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()));
+        components.add_security_scheme("api_key", SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))));
+    }
+}
+
+/// The OpenAPI document of the server.
+#[utoipa::path(get, path = "/openapi.json", responses((status = 200, description = "The OpenAPI document", content_type = "application/json")))]
+async fn get_openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// The state shared by the handlers.
+#[derive(Debug)]
+pub struct AppState {
+    pub scoring: Scoring,
+    pub jobs: Jobs,
+    pub metrics: Metrics,
+    pub access: Access,
+}
+
+/// The routes of the server, scoring every request with the same options, with a Swagger UI at `/swagger-ui` if
+/// enabled.
+pub fn router(scoring: Scoring, access: Access) -> Router {
+    let router = Router::new()
+        .route("/classify", post(post_classify))
+        .route("/codes", get(get_codes))
+        .route("/jobs", post(jobs::post_jobs).layer(DefaultBodyLimit::max(MAX_JOB_BYTES)))
+        .route("/jobs/{id}", get(jobs::get_job))
+        .route("/jobs/{id}/results", get(jobs::get_job_results))
+        .route("/ws/validate", get(websocket::get_validate))
+        .route("/metrics", get(metrics::get_metrics))
+        .route("/openapi.json", get(get_openapi));
+    let state = Arc::new(AppState { scoring, jobs: Jobs::default(), metrics: Metrics::default(), access });
+    let router = router
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth::authorize))
+        .route_layer(middleware::from_fn_with_state(state.clone(), metrics::track))
+        .with_state(state);
+    #[cfg(feature = "swagger-ui")]
+    let router = router.merge(utoipa_swagger_ui::SwaggerUi::new("/swagger-ui").config(utoipa_swagger_ui::Config::from("/openapi.json")));
+    router
+}
+
+/// Serves the classifier over HTTP until the process is stopped.
+pub fn run_serve(host: &str, port: u16, scoring: Scoring, access: Access) -> Result<(), CliError> {
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind((host, port)).await?;
+        tracing::info!("Listening on http://{}", listener.local_addr()?);
+        axum::serve(listener, router(scoring, access).into_make_service_with_connect_info::<SocketAddr>()).await
+    })?;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use acmg::{EvidenceLevel, SomaticEvidence, SomaticTier};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct SomaticReport {
+    evidence: SomaticEvidence,
+    tier: SomaticTier,
+    level: Option<EvidenceLevel>,
+}
+
+/// Prints the AMP/ASCO/CAP tier of a somatic variant with the level of evidence determining it.
+pub fn run_somatic_tier(evidence: SomaticEvidence, format: ReportFormat) -> Result<(), CliError> {
+    let tier = evidence.tier();
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => match evidence.level().filter(|_| matches!(tier, SomaticTier::TierI | SomaticTier::TierII)) {
+            Some(level) => writeln!(out, "{}, Level {}", tier, level)?,
+            None => writeln!(out, "{}", tier)?,
+        },
+        ReportFormat::Json => {
+            let report = SomaticReport { evidence, tier, level: evidence.level() };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("somatic reports serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use acmg::{AcmgClassification, AssessmentResult, Severity};
+
+use crate::cli::{CliError, ReportFormat};
+
+/// Terminal columns of the longest histogram bar.
+const BAR_WIDTH: usize = 40;
+
+/// A summary of the results of a run, for quality control of large reanalyses.
+#[derive(Debug, Default, Serialize)]
+struct Stats {
+    records: usize,
+    /// Records which failed to classify in the run, or couldn't be read.
+    errors: usize,
+    /// Records with at least one warning diagnostic.
+    with_warnings: usize,
+    classifications: Vec<ClassificationCount>,
+    /// The number of records with each score, from the lowest to the highest.
+    points: Vec<PointsCount>,
+    /// The most frequently applied criteria, at the strength they were applied.
+    criteria: Vec<CriterionCount>,
+}
+
+#[derive(Debug, Serialize)]
+struct ClassificationCount {
+    classification: AcmgClassification,
+    count: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct PointsCount {
+    points: i32,
+    count: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct CriterionCount {
+    criterion: String,
+    count: usize,
+}
+
+/// Summarizes JSON lines of results, or `-` for stdin: the records of `acmg batch --format jsonl`, whose failed rows
+/// are counted as errors, or results as written by `acmg info --format json`. Lines which aren't results are logged
+/// with their line number and counted as errors. Lists the `top` most applied criteria.
+pub fn run_stats(input: &Path, top: usize, format: ReportFormat) -> Result<(), CliError> {
+    let reader: Box<dyn BufRead> = if input == Path::new("-") {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(BufReader::new(File::open(input)?))
+    };
+    let mut stats = Stats::default();
+    let mut classifications: HashMap<AcmgClassification, usize> = HashMap::new();
+    let mut points: BTreeMap<i32, usize> = BTreeMap::new();
+    let mut criteria: HashMap<String, usize> = HashMap::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        stats.records += 1;
+        let result = match read_result(&line) {
+            Ok(Some(result)) => result,
+            Ok(None) => {
+                stats.errors += 1;
+                continue;
+            }
+            Err(error) => {
+                stats.errors += 1;
+                tracing::error!(line = index as u64 + 1, "{}", error);
+                continue;
+            }
+        };
+        *classifications.entry(result.classification).or_default() += 1;
+        *points.entry(result.points).or_default() += 1;
+        for evidence in &result.evidence {
+            *criteria.entry(evidence.to_string()).or_default() += 1;
+        }
+        if result.diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Warning) {
+            stats.with_warnings += 1;
+        }
+    }
+    stats.classifications = AcmgClassification::ALL.into_iter()
+        .map(|classification| ClassificationCount { classification, count: classifications.get(&classification).copied().unwrap_or_default() })
+        .collect();
+    if let (Some(low), Some(high)) = (points.keys().next().copied(), points.keys().next_back().copied()) {
+        stats.points = (low..=high).map(|score| PointsCount { points: score, count: points.get(&score).copied().unwrap_or_default() }).collect();
+    }
+    let mut criteria: Vec<CriterionCount> = criteria.into_iter().map(|(criterion, count)| CriterionCount { criterion, count }).collect();
+    criteria.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.criterion.cmp(&b.criterion)));
+    criteria.truncate(top);
+    stats.criteria = criteria;
+
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => write_text(&mut out, &stats)?,
+        ReportFormat::Json => writeln!(out, "{}", serde_json::to_string(&stats).expect("stats serialize to JSON"))?,
+    }
+    Ok(())
+}
+
+/// The result of a line, or None for a batch record which failed.
+fn read_result(line: &str) -> Result<Option<AssessmentResult>, serde_json::Error> {
+    let mut value: serde_json::Value = serde_json::from_str(line)?;
+    if value.get("error").is_some_and(|error| !error.is_null()) {
+        return Ok(None);
+    }
+    if let Some(result) = value.get_mut("result") {
+        value = result.take();
+    }
+    serde_json::from_value(value).map(Some)
+}
+
+fn write_text(out: &mut impl Write, stats: &Stats) -> io::Result<()> {
+    writeln!(out, "{} records, {} errors, {} with warnings", stats.records, stats.errors, stats.with_warnings)?;
+    let classified: usize = stats.classifications.iter().map(|count| count.count).sum();
+    writeln!(out)?;
+    for count in &stats.classifications {
+        let percent = if classified == 0 { 0.0 } else { 100.0 * count.count as f64 / classified as f64 };
+        writeln!(out, "{:<24}{:>8}  {:>5.1}%", count.classification.term(), count.count, percent)?;
+    }
+    if !stats.points.is_empty() {
+        writeln!(out, "\nPoints:")?;
+        let most = stats.points.iter().map(|count| count.count).max().unwrap_or_default().max(1);
+        for count in &stats.points {
+            let bar = "█".repeat((count.count * BAR_WIDTH).div_ceil(most));
+            writeln!(out, "{:>5} {:<width$} {}", count.points, bar, count.count, width = BAR_WIDTH)?;
+        }
+    }
+    if !stats.criteria.is_empty() {
+        writeln!(out, "\nMost applied criteria:")?;
+        let width = stats.criteria.iter().map(|count| count.criterion.len()).max().unwrap_or_default();
+        for count in &stats.criteria {
+            writeln!(out, "  {:<width$}{:>8}", count.criterion, count.count, width = width)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use serde::Serialize;
+
+use acmg::{read_vep_vcf, suggest_from_consequence, suggested_evidence, ConsequenceSuggestion, GeneConstraint, GeneOverride, GenomeBuild, GenomicVariant, VepAnnotation};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct SuggestReport<'a> {
+    variant: &'a GenomicVariant,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    annotation: Option<&'a VepAnnotation>,
+    suggestions: Vec<ConsequenceSuggestion>,
+    evidence: String,
+}
+
+/// Options deciding whether genes are constrained for loss of function and missense variation, gating PVS1, PP2 and
+/// BP1.
+#[derive(Debug, Clone, Args)]
+#[command(next_help_heading = "Gene constraint")]
+pub struct ConstraintArgs {
+    /// gnomAD constraint metrics TSV, v2 or v4, with the pLI, LOEUF and missense Z score of each gene
+    #[arg(long)]
+    pub constraint: Option<PathBuf>,
+    /// Genes where loss of function is a mechanism of disease, one per line, whatever their constraint
+    #[arg(long)]
+    pub lof_genes: Option<PathBuf>,
+    /// Curated calls taking precedence over the metrics, e.g. TTN=no-lof or MYH7=missense, with lof, no-lof,
+    /// missense or no-missense
+    #[arg(long, value_delimiter = ',', value_name = "GENE=CALL")]
+    pub gene_override: Vec<GeneOverride>,
+    /// pLI from which a gene is constrained for loss of function
+    #[arg(long, default_value_t = GeneConstraint::default().min_pli)]
+    pub min_pli: f64,
+    /// LOEUF below which a gene is constrained for loss of function
+    #[arg(long, default_value_t = GeneConstraint::default().max_loeuf)]
+    pub max_loeuf: f64,
+    /// Missense Z score from which a gene is constrained for missense variation, suggesting PP2
+    #[arg(long, default_value_t = GeneConstraint::default().pp2_min_z)]
+    pub pp2_min_z: f64,
+    /// Missense Z score up to which a gene isn't constrained for missense variation, suggesting BP1 in genes
+    /// constrained for loss of function
+    #[arg(long, default_value_t = GeneConstraint::default().bp1_max_z, allow_hyphen_values = true)]
+    pub bp1_max_z: f64,
+}
+
+impl ConstraintArgs {
+    pub fn resolve(&self) -> Result<GeneConstraint, CliError> {
+        let mut constraint = GeneConstraint {
+            min_pli: self.min_pli,
+            max_loeuf: self.max_loeuf,
+            pp2_min_z: self.pp2_min_z,
+            bp1_max_z: self.bp1_max_z,
+            ..GeneConstraint::default()
+        };
+        if let Some(path) = &self.constraint {
+            constraint = constraint.with_metrics(BufReader::new(File::open(path)?))?;
+        }
+        if let Some(path) = &self.lof_genes {
+            constraint = constraint.with_lof_genes(BufReader::new(File::open(path)?))?;
+        }
+        for gene_override in &self.gene_override {
+            constraint = constraint.with_override(gene_override.clone());
+        }
+        Ok(constraint)
+    }
+}
+
+/// Prints the codes suggested by the VEP consequence of each variant of an annotated VCF, or `-` for stdin, with an
+/// evidence string for a curator to review and complete.
+pub fn run_suggest(vep_vcf: &Path, build: GenomeBuild, constraint: &GeneConstraint, format: ReportFormat) -> Result<(), CliError> {
+    let input: Box<dyn BufRead> = if vep_vcf == Path::new("-") {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(BufReader::new(File::open(vep_vcf)?))
+    };
+    let records = read_vep_vcf(input, build)?;
+    let mut out = io::stdout().lock();
+    for record in &records {
+        let annotation = record.annotation();
+        let suggestions = annotation.map(|annotation| suggest_from_consequence(annotation, constraint)).unwrap_or_default();
+        let evidence = suggested_evidence(&suggestions);
+        match format {
+            ReportFormat::Text => {
+                match annotation {
+                    Some(annotation) => {
+                        let description = [&annotation.gene, &annotation.hgvsc, &annotation.hgvsp].into_iter().flatten().cloned().collect::<Vec<_>>();
+                        writeln!(out, "{} {} ({})", record.variant, description.join(" "), annotation.consequences.join(", "))?;
+                    }
+                    None => writeln!(out, "{} (no CSQ annotation)", record.variant)?,
+                }
+                for suggestion in &suggestions {
+                    writeln!(out, "  {}: {}", suggestion.evidence, suggestion.reason)?;
+                }
+                match evidence.as_str() {
+                    "" => writeln!(out, "  No codes suggested")?,
+                    evidence => writeln!(out, "  Evidence: {}", evidence)?,
+                }
+            }
+            ReportFormat::Json => {
+                let report = SuggestReport { variant: &record.variant, annotation, suggestions, evidence };
+                writeln!(out, "{}", serde_json::to_string(&report).expect("suggestions serialize to JSON"))?;
+            }
+        }
+    }
+    tracing::info!("Suggested codes for {} variants", records.len());
+    Ok(())
+}
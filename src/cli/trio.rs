@@ -0,0 +1,59 @@
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use acmg::{read_trio_genotypes, GenomeBuild, GenomicVariant, Genotype, Trio, TrioOrigin};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct TrioReport {
+    trio: Trio,
+    origin: TrioOrigin,
+    evidence: Option<String>,
+}
+
+/// Parses the proband, mother and father samples, e.g. `P1,M1,F1`.
+pub fn parse_samples(s: &str) -> Result<[String; 3], String> {
+    match s.split(',').map(str::trim).collect::<Vec<_>>()[..] {
+        [proband, mother, father] if ![proband, mother, father].contains(&"") => Ok([proband, mother, father].map(str::to_string)),
+        _ => Err("expected the proband, mother and father samples, e.g. P1,M1,F1".to_string()),
+    }
+}
+
+/// Reads the genotypes of the proband, mother and father samples at the VCF coordinates `variant` from a trio VCF.
+pub fn read_trio_vcf(path: &Path, variant: &str, build: GenomeBuild, samples: [&str; 3]) -> Result<[Genotype; 3], CliError> {
+    let variant = GenomicVariant::parse(variant, build)?;
+    Ok(read_trio_genotypes(BufReader::new(File::open(path)?), &variant, samples)?)
+}
+
+/// Prints the PS2, PM6 or BS4 evidence proposed by the trio's genotypes, which can be passed on to `acmg info`, and
+/// the origin of the variant they show.
+pub fn run_trio(trio: Trio, format: ReportFormat) -> Result<(), CliError> {
+    let evidence = trio.evidence();
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            match &evidence {
+                Some(evidence) => writeln!(out, "{}", evidence)?,
+                None => writeln!(out, "N/A")?,
+            }
+            let parentage = match trio.origin() {
+                TrioOrigin::DeNovo if trio.parentage_confirmed => ", parentage confirmed",
+                TrioOrigin::DeNovo => ", parentage assumed",
+                _ => "",
+            };
+            writeln!(out, "Origin: {}{}", trio.origin(), parentage)?;
+            if trio.non_segregating() {
+                writeln!(out, "An affected parent doesn't carry the variant")?;
+            }
+        }
+        ReportFormat::Json => {
+            let report = TrioReport { trio, origin: trio.origin(), evidence: evidence.map(|evidence| evidence.to_string()) };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("trio reports serialize to JSON"))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use acmg::{CodeRegistry, Diagnostic, Inheritance};
+
+use crate::cli::{CliError, ReportFormat};
+
+#[derive(Serialize)]
+struct ValidationReport<'a> {
+    valid: bool,
+    diagnostics: &'a [Diagnostic],
+}
+
+/// Prints the diagnostics for an evidence string, checked against the mode of inheritance if given, returning true if
+/// there were no errors.
+pub fn run_validate(acmg_evidence: &str, inheritance: Option<Inheritance>, format: ReportFormat, registry: &CodeRegistry) -> Result<bool, CliError> {
+    let diagnostics = registry.validate(acmg_evidence, inheritance);
+    let valid = !diagnostics.iter().any(|diagnostic| diagnostic.is_error());
+    let mut out = io::stdout().lock();
+    match format {
+        ReportFormat::Text => {
+            for diagnostic in &diagnostics {
+                writeln!(out, "{}", diagnostic)?;
+            }
+            if diagnostics.is_empty() {
+                writeln!(out, "ok")?;
+            }
+        }
+        ReportFormat::Json => {
+            let report = ValidationReport { valid, diagnostics: &diagnostics };
+            writeln!(out, "{}", serde_json::to_string(&report).expect("diagnostics serialize to JSON"))?;
+        }
+    }
+    Ok(valid)
+}
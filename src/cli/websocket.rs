@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use acmg::{validate, AcmgClassification, Diagnostic};
+
+use crate::cli::parse_assessment;
+use crate::cli::serve::AppState;
+
+/// The score of valid evidence.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LiveScore {
+    points: i32,
+    classification: AcmgClassification,
+    post_prob_path: f64,
+}
+
+/// The response to each evidence string sent to `/ws/validate`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LiveValidation {
+    /// The evidence string validated, to match responses to what was typed.
+    evidence: String,
+    valid: bool,
+    diagnostics: Vec<Diagnostic>,
+    /// The score of the evidence if it's valid, with the server's scoring options.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<LiveScore>,
+    /// Why valid evidence couldn't be scored, e.g. a failed allele frequency lookup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn live_validation(evidence: String, state: &AppState) -> LiveValidation {
+    let diagnostics = validate(&evidence);
+    let valid = !diagnostics.iter().any(Diagnostic::is_error);
+    let (score, error) = if valid {
+        match parse_assessment(&evidence, None, &state.scoring) {
+            Ok(assessment) => {
+                let score = LiveScore { points: assessment.points(), classification: assessment.classify(), post_prob_path: assessment.post_prob_path() };
+                (Some(score), None)
+            }
+            Err(error) => (None, Some(error.to_string())),
+        }
+    } else {
+        (None, None)
+    };
+    LiveValidation { evidence, valid, diagnostics, score, error }
+}
+
+/// Validates each evidence string sent as a text message, e.g. as it's typed, responding with its diagnostics and
+/// score.
+#[utoipa::path(
+    get,
+    path = "/ws/validate",
+    responses((status = 101, description = "A WebSocket responding to each evidence string with a LiveValidation", body = LiveValidation)),
+)]
+pub async fn get_validate(State(state): State<Arc<AppState>>, upgrade: WebSocketUpgrade) -> Response {
+    upgrade.on_upgrade(|socket| validate_socket(socket, state))
+}
+
+async fn validate_socket(mut socket: WebSocket, state: Arc<AppState>) {
+    while let Some(Ok(message)) = socket.recv().await {
+        let evidence = match message {
+            Message::Text(text) => text.to_string(),
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let state = state.clone();
+        let Ok(validation) = tokio::task::spawn_blocking(move || live_validation(evidence, &state)).await else {
+            break;
+        };
+        let json = serde_json::to_string(&validation).expect("validations serialize to JSON");
+        if socket.send(Message::Text(json.into())).await.is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::io::{self, Write};
+
+use acmg::{what_if, MAX_ADDITIONS};
+
+use crate::cli::{parse_assessment, CliError, ReportFormat, Scoring};
+
+/// Reports the additional evidence needed to reach Likely pathogenic and Pathogenic, and the criteria whose removal
+/// would change the classification.
+pub fn run_what_if(acmg_evidence: &str, format: ReportFormat, scoring: &Scoring) -> Result<(), CliError> {
+    let assessment = parse_assessment(acmg_evidence, None, scoring)?;
+    let what_if = what_if(&assessment);
+    let mut out = io::stdout().lock();
+    if format == ReportFormat::Json {
+        writeln!(out, "{}", serde_json::to_string(&what_if).expect("what-if analyses serialize to JSON"))?;
+        return Ok(());
+    }
+    writeln!(out, "{}: {} ({} points)", assessment.evidence_set(), what_if.classification.term(), what_if.points)?;
+    for target in &what_if.targets {
+        if target.reached {
+            writeln!(out, "{} is already reached", target.target.term())?;
+        } else if target.combinations.is_empty() {
+            writeln!(out, "{} can't be reached with up to {} more criteria", target.target.term(), MAX_ADDITIONS)?;
+        } else {
+            writeln!(out, "{} needs one of:", target.target.term())?;
+            for combination in &target.combinations {
+                let strengths: Vec<String> = combination.strengths.iter().map(|strength| format!("{:?}", strength)).collect();
+                writeln!(out, "  {}", strengths.join(" + "))?;
+            }
+        }
+    }
+    if what_if.removals.is_empty() {
+        writeln!(out, "Removing any single criterion leaves the classification unchanged")?;
+    }
+    for removal in &what_if.removals {
+        writeln!(out, "Without {}: {}", removal.evidence, removal.classification.term())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use std::fs::File;
+use std::io::{Cursor, Read, Write};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::cli::CliError;
+
+static SHEET: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<sheet\b([^>]*)/?>").unwrap());
+static RELATIONSHIP: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<Relationship\b([^>]*)/?>").unwrap());
+static ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"([\w:]+)\s*=\s*"([^"]*)""#).unwrap());
+static SHARED_STRING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<si>(.*?)</si>|<si/>").unwrap());
+static PHONETIC: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<rPh\b.*?</rPh>").unwrap());
+static TEXT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<t(?:\s[^>]*)?>(.*?)</t>").unwrap());
+static ROW: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<row\b([^>]*?)(?:/>|>(.*?)</row>)").unwrap());
+static CELL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<c\b([^>]*?)(?:/>|>(.*?)</c>)").unwrap());
+static VALUE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<v>(.*?)</v>").unwrap());
+static ENTITY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"&(lt|gt|amp|quot|apos|#x[0-9A-Fa-f]+|#\d+);").unwrap());
+
+/// The header and rows of a worksheet of an xlsx workbook, with the rows padded to the columns of the header. Each
+/// row is positioned at its row number in the sheet, so errors are logged with the line spreadsheet users see.
+pub struct Sheet {
+    pub headers: csv::StringRecord,
+    pub rows: Vec<csv::StringRecord>,
+}
+
+/// Reads the named worksheet of an xlsx workbook, or the first without a name. Cells are read as the text of shared
+/// and inline strings or the stored value of others, e.g. `0.5` for a number formatted as `50%`, and missing cells
+/// are empty.
+pub fn read_sheet(path: &Path, sheet: Option<&str>) -> Result<Sheet, CliError> {
+    let mut archive = ZipArchive::new(File::open(path)?).map_err(|error| invalid(path, error))?;
+    let workbook = read_part(&mut archive, path, "xl/workbook.xml")?;
+    let sheets: Vec<(String, String)> = SHEET.captures_iter(&workbook)
+        .filter_map(|caps| {
+            let attributes = attributes(&caps[1]);
+            Some((attribute(&attributes, "name")?, attribute(&attributes, "r:id")?))
+        })
+        .collect();
+    let (_, id) = match sheet {
+        Some(sheet) => sheets.iter().find(|(name, _)| name == sheet).ok_or_else(|| {
+            let names: Vec<&str> = sheets.iter().map(|(name, _)| name.as_str()).collect();
+            CliError::Xlsx(format!("{} has no sheet named '{}', the sheets are {}", path.display(), sheet, names.join(", ")))
+        })?,
+        None => sheets.first().ok_or_else(|| CliError::Xlsx(format!("{} has no sheets", path.display())))?,
+    };
+    let relationships = read_part(&mut archive, path, "xl/_rels/workbook.xml.rels")?;
+    let target = RELATIONSHIP.captures_iter(&relationships)
+        .map(|caps| attributes(&caps[1]))
+        .find(|attributes| attribute(attributes, "Id").as_ref() == Some(id))
+        .and_then(|attributes| attribute(&attributes, "Target"))
+        .ok_or_else(|| CliError::Xlsx(format!("{} has no worksheet for relationship {}", path.display(), id)))?;
+    let target = match target.strip_prefix('/') {
+        Some(target) => target.to_string(),
+        None => format!("xl/{}", target),
+    };
+    let shared_strings: Vec<String> = match archive.index_for_name("xl/sharedStrings.xml") {
+        Some(_) => SHARED_STRING.captures_iter(&read_part(&mut archive, path, "xl/sharedStrings.xml")?)
+            .map(|caps| caps.get(1).map_or_else(String::new, |string| text(string.as_str())))
+            .collect(),
+        None => vec![],
+    };
+    let worksheet = read_part(&mut archive, path, &target)?;
+    let mut rows = vec![];
+    for (index, caps) in ROW.captures_iter(&worksheet).enumerate() {
+        let number = attribute(&attributes(&caps[1]), "r").and_then(|r| r.parse().ok()).unwrap_or(index as u64 + 1);
+        let mut cells: Vec<String> = vec![];
+        for cell in caps.get(2).map(|cells| CELL.captures_iter(cells.as_str())).into_iter().flatten() {
+            let attributes = attributes(&cell[1]);
+            let content = cell.get(2).map_or("", |content| content.as_str());
+            let value = match attribute(&attributes, "t").as_deref() {
+                Some("inlineStr") => text(content),
+                Some("s") => VALUE.captures(content)
+                    .and_then(|value| value[1].trim().parse::<usize>().ok())
+                    .and_then(|index| shared_strings.get(index).cloned())
+                    .unwrap_or_default(),
+                _ => VALUE.captures(content).map(|value| unescape(&value[1])).unwrap_or_default(),
+            };
+            let column = attribute(&attributes, "r").and_then(|r| column_index(&r)).unwrap_or(cells.len());
+            if column >= cells.len() {
+                cells.resize(column + 1, String::new());
+            }
+            cells[column] = value;
+        }
+        if cells.iter().any(|cell| !cell.trim().is_empty()) {
+            rows.push((number, cells));
+        }
+    }
+    let mut rows = rows.into_iter();
+    let (_, headers) = rows.next().ok_or_else(|| CliError::Xlsx(format!("{} has no header row", path.display())))?;
+    let columns = headers.len();
+    let rows = rows
+        .map(|(number, mut cells)| {
+            if cells.len() < columns {
+                cells.resize(columns, String::new());
+            }
+            let mut row = csv::StringRecord::from(cells);
+            let mut position = csv::Position::new();
+            position.set_line(number);
+            row.set_position(Some(position));
+            row
+        })
+        .collect();
+    Ok(Sheet { headers: csv::StringRecord::from(headers), rows })
+}
+
+fn read_part(archive: &mut ZipArchive<File>, path: &Path, name: &str) -> Result<String, CliError> {
+    let mut part = String::new();
+    archive.by_name(name)
+        .map_err(|error| invalid(path, format!("{}: {}", name, error)))?
+        .read_to_string(&mut part)?;
+    Ok(part)
+}
+
+fn invalid(path: &Path, error: impl std::fmt::Display) -> CliError {
+    CliError::Xlsx(format!("{} isn't a valid xlsx workbook: {}", path.display(), error))
+}
+
+fn attributes(tag: &str) -> Vec<(String, String)> {
+    ATTRIBUTE.captures_iter(tag).map(|caps| (caps[1].to_string(), unescape(&caps[2]))).collect()
+}
+
+fn attribute(attributes: &[(String, String)], name: &str) -> Option<String> {
+    attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.clone())
+}
+
+/// The text of a string, joining its formatted runs and leaving out phonetic guides.
+fn text(string: &str) -> String {
+    TEXT.captures_iter(&PHONETIC.replace_all(string, "")).map(|caps| unescape(&caps[1])).collect()
+}
+
+fn unescape(s: &str) -> String {
+    ENTITY.replace_all(s, |caps: &regex::Captures| match &caps[1] {
+        "lt" => "<".to_string(),
+        "gt" => ">".to_string(),
+        "amp" => "&".to_string(),
+        "quot" => "\"".to_string(),
+        "apos" => "'".to_string(),
+        code => code.strip_prefix("#x").map_or_else(|| code[1..].parse().ok(), |hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32)
+            .map_or_else(|| caps[0].to_string(), String::from),
+    }).into_owned()
+}
+
+fn escape(s: &str) -> String {
+    s.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .fold(String::with_capacity(s.len()), |mut escaped, c| {
+            match c {
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '&' => escaped.push_str("&amp;"),
+                '"' => escaped.push_str("&quot;"),
+                c => escaped.push(c),
+            }
+            escaped
+        })
+}
+
+/// The index of the column of a cell reference, e.g. 27 for `AB3`.
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: Vec<u8> = reference.bytes().take_while(u8::is_ascii_alphabetic).collect();
+    (!letters.is_empty()).then(|| letters.iter().fold(0, |index, letter| index * 26 + (letter.to_ascii_uppercase() - b'A') as usize + 1) - 1)
+}
+
+/// The letters of a column, e.g. `AB` for 27.
+fn column_name(index: usize) -> String {
+    let mut name = vec![];
+    let mut index = index + 1;
+    while index > 0 {
+        name.push(b'A' + ((index - 1) % 26) as u8);
+        index = (index - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).expect("column names are ASCII")
+}
+
+/// Writes rows to a single worksheet of an xlsx workbook, with notes attached to cells. The workbook is assembled in
+/// memory and written out by [`XlsxWriter::finish`].
+pub struct XlsxWriter {
+    out: Box<dyn Write>,
+    sheet: String,
+    /// Columns whose cells are written as numbers where they parse as one.
+    numeric: Vec<usize>,
+    rows: Vec<Vec<String>>,
+    comments: Vec<(usize, usize, String)>,
+}
+
+impl XlsxWriter {
+    pub fn new(out: Box<dyn Write>, sheet: &str, numeric: Vec<usize>) -> XlsxWriter {
+        XlsxWriter { out, sheet: sheet.to_string(), numeric, rows: vec![], comments: vec![] }
+    }
+
+    /// Appends a row, with a comment on the cell of the column if given.
+    pub fn write_row(&mut self, row: impl IntoIterator<Item = impl Into<String>>, comment: Option<(usize, String)>) {
+        if let Some((column, comment)) = comment {
+            self.comments.push((self.rows.len(), column, comment));
+        }
+        self.rows.push(row.into_iter().map(Into::into).collect());
+    }
+
+    /// Writes the workbook, once every row has been appended.
+    pub fn finish(&mut self) -> Result<(), CliError> {
+        let mut zip = ZipWriter::new(Cursor::new(vec![]));
+        let comments = !self.comments.is_empty();
+        let mut parts = vec![
+            ("[Content_Types].xml", self.content_types(comments)),
+            ("_rels/.rels", RELS.to_string()),
+            ("xl/workbook.xml", format!(r#"{}<workbook xmlns="{}" xmlns:r="{}"><sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#, XML, MAIN, RELATIONSHIPS, escape(&self.sheet))),
+            ("xl/_rels/workbook.xml.rels", format!(r#"{}<Relationships xmlns="{}"><Relationship Id="rId1" Type="{}/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#, XML, PACKAGE_RELATIONSHIPS, RELATIONSHIPS)),
+            ("xl/worksheets/sheet1.xml", self.worksheet(comments)),
+        ];
+        if comments {
+            parts.push(("xl/worksheets/_rels/sheet1.xml.rels", format!(concat!(
+                r#"{}<Relationships xmlns="{}">"#,
+                r#"<Relationship Id="rId1" Type="{}/vmlDrawing" Target="../drawings/vmlDrawing1.vml"/>"#,
+                r#"<Relationship Id="rId2" Type="{}/comments" Target="../comments1.xml"/></Relationships>"#,
+            ), XML, PACKAGE_RELATIONSHIPS, RELATIONSHIPS, RELATIONSHIPS)));
+            parts.push(("xl/comments1.xml", self.comments()));
+            parts.push(("xl/drawings/vmlDrawing1.vml", self.vml_drawing()));
+        }
+        for (name, part) in parts {
+            zip.start_file(name, SimpleFileOptions::default()).map_err(|error| CliError::Xlsx(error.to_string()))?;
+            zip.write_all(part.as_bytes())?;
+        }
+        let workbook = zip.finish().map_err(|error| CliError::Xlsx(error.to_string()))?.into_inner();
+        self.out.write_all(&workbook)?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    fn content_types(&self, comments: bool) -> String {
+        let mut types = format!(concat!(
+            r#"{}<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+            r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+            r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+            r#"<Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+            r#"<Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+        ), XML);
+        if comments {
+            types.push_str(r#"<Default Extension="vml" ContentType="application/vnd.openxmlformats-officedocument.vmlDrawing"/>"#);
+            types.push_str(r#"<Override PartName="/xl/comments1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.comments+xml"/>"#);
+        }
+        types.push_str("</Types>");
+        types
+    }
+
+    fn worksheet(&self, comments: bool) -> String {
+        let mut sheet = format!(r#"{}<worksheet xmlns="{}" xmlns:r="{}"><sheetData>"#, XML, MAIN, RELATIONSHIPS);
+        for (index, row) in self.rows.iter().enumerate() {
+            sheet.push_str(&format!(r#"<row r="{}">"#, index + 1));
+            for (column, cell) in row.iter().enumerate().filter(|(_, cell)| !cell.is_empty()) {
+                let reference = format!("{}{}", column_name(column), index + 1);
+                match cell.parse::<f64>() {
+                    Ok(number) if index > 0 && number.is_finite() && self.numeric.contains(&column) => {
+                        sheet.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, cell));
+                    }
+                    _ => sheet.push_str(&format!(r#"<c r="{}" t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#, reference, escape(cell))),
+                }
+            }
+            sheet.push_str("</row>");
+        }
+        sheet.push_str("</sheetData>");
+        if comments {
+            sheet.push_str(r#"<legacyDrawing r:id="rId1"/>"#);
+        }
+        sheet.push_str("</worksheet>");
+        sheet
+    }
+
+    fn comments(&self) -> String {
+        let mut comments = format!(r#"{}<comments xmlns="{}"><authors><author>acmg</author></authors><commentList>"#, XML, MAIN);
+        for (row, column, comment) in &self.comments {
+            comments.push_str(&format!(r#"<comment ref="{}{}" authorId="0"><text><t xml:space="preserve">{}</t></text></comment>"#, column_name(*column), row + 1, escape(comment)));
+        }
+        comments.push_str("</commentList></comments>");
+        comments
+    }
+
+    /// The legacy drawing Excel shows comments with, one hidden note shape per comment.
+    fn vml_drawing(&self) -> String {
+        let mut drawing = String::from(concat!(
+            r#"<xml xmlns:v="urn:schemas-microsoft-com:vml" xmlns:o="urn:schemas-microsoft-com:office:office" xmlns:x="urn:schemas-microsoft-com:office:excel">"#,
+            r#"<v:shapetype id="_x0000_t202" coordsize="21600,21600" o:spt="202" path="m,l,21600r21600,l21600,xe">"#,
+            r#"<v:stroke joinstyle="miter"/><v:path gradientshapeok="t" o:connecttype="rect"/></v:shapetype>"#,
+        ));
+        for (index, (row, column, _)) in self.comments.iter().enumerate() {
+            drawing.push_str(&format!(concat!(
+                r##"<v:shape id="_x0000_s{}" type="#_x0000_t202" style="position:absolute;width:240pt;height:80pt;z-index:{};visibility:hidden" fillcolor="#ffffe1">"##,
+                r##"<v:fill color2="#ffffe1"/><v:shadow on="t" obscured="t"/><v:textbox/>"##,
+                r#"<x:ClientData ObjectType="Note"><x:MoveWithCells/><x:SizeWithCells/><x:AutoFill>False</x:AutoFill>"#,
+                r#"<x:Row>{}</x:Row><x:Column>{}</x:Column></x:ClientData></v:shape>"#,
+            ), 1025 + index, index + 1, row, column));
+        }
+        drawing.push_str("</xml>");
+        drawing
+    }
+}
+
+const XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#;
+const MAIN: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+const RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const PACKAGE_RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+const RELS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>"#,
+    r#"</Relationships>"#,
+);
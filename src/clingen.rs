@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{AcmgError, CnvInterval};
+
+/// A ClinGen gene-disease validity classification, from the strongest to the weakest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ValidityClassification {
+    Definitive,
+    Strong,
+    Moderate,
+    Limited,
+    Disputed,
+    Refuted,
+    NoKnownDiseaseRelationship,
+}
+
+impl ValidityClassification {
+    /// Whether the gene-disease relationship is established, with at least moderate evidence.
+    pub fn is_established(&self) -> bool {
+        *self <= ValidityClassification::Moderate
+    }
+}
+
+impl FromStr for ValidityClassification {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<ValidityClassification, Self::Err> {
+        match s.trim().to_lowercase().replace(['-', '_'], " ").as_str() {
+            "definitive" => Ok(ValidityClassification::Definitive),
+            "strong" => Ok(ValidityClassification::Strong),
+            "moderate" => Ok(ValidityClassification::Moderate),
+            "limited" => Ok(ValidityClassification::Limited),
+            "disputed" | "disputed evidence" => Ok(ValidityClassification::Disputed),
+            "refuted" | "refuted evidence" => Ok(ValidityClassification::Refuted),
+            "no known disease relationship" => Ok(ValidityClassification::NoKnownDiseaseRelationship),
+            _ => Err(AcmgError::InvalidClinGen(format!("unknown gene-disease validity classification '{}'", s))),
+        }
+    }
+}
+
+impl Display for ValidityClassification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            ValidityClassification::Definitive => "Definitive",
+            ValidityClassification::Strong => "Strong",
+            ValidityClassification::Moderate => "Moderate",
+            ValidityClassification::Limited => "Limited",
+            ValidityClassification::Disputed => "Disputed",
+            ValidityClassification::Refuted => "Refuted",
+            ValidityClassification::NoKnownDiseaseRelationship => "No Known Disease Relationship",
+        })
+    }
+}
+
+/// A ClinGen curation of the validity of a gene's relationship to a disease.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneDiseaseValidity {
+    pub disease: String,
+    /// The mode of inheritance as curated, e.g. AD.
+    pub inheritance: String,
+    pub classification: ValidityClassification,
+}
+
+/// A ClinGen dosage sensitivity score, for haploinsufficiency or triplosensitivity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum DosageScore {
+    /// 0, no evidence for dosage pathogenicity.
+    NoEvidence,
+    /// 1, little evidence.
+    Little,
+    /// 2, emerging evidence.
+    Emerging,
+    /// 3, sufficient evidence, i.e. an established mechanism of disease.
+    Sufficient,
+    /// 30, a gene associated with an autosomal recessive phenotype.
+    AutosomalRecessive,
+    /// 40, dosage sensitivity unlikely.
+    Unlikely,
+}
+
+impl DosageScore {
+    /// Whether loss (or gain) of the gene is an established mechanism of disease, sufficient evidence or a recessive
+    /// phenotype.
+    pub fn is_established(&self) -> bool {
+        matches!(self, DosageScore::Sufficient | DosageScore::AutosomalRecessive)
+    }
+}
+
+impl FromStr for DosageScore {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<DosageScore, Self::Err> {
+        match s.trim() {
+            "0" => Ok(DosageScore::NoEvidence),
+            "1" => Ok(DosageScore::Little),
+            "2" => Ok(DosageScore::Emerging),
+            "3" => Ok(DosageScore::Sufficient),
+            "30" => Ok(DosageScore::AutosomalRecessive),
+            "40" => Ok(DosageScore::Unlikely),
+            _ => Err(AcmgError::InvalidClinGen(format!("unknown dosage sensitivity score '{}'", s))),
+        }
+    }
+}
+
+impl Display for DosageScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            DosageScore::NoEvidence => "0 (no evidence)",
+            DosageScore::Little => "1 (little evidence)",
+            DosageScore::Emerging => "2 (emerging evidence)",
+            DosageScore::Sufficient => "3 (sufficient evidence)",
+            DosageScore::AutosomalRecessive => "30 (autosomal recessive)",
+            DosageScore::Unlikely => "40 (dosage sensitivity unlikely)",
+        })
+    }
+}
+
+/// The ClinGen dosage sensitivity curation of a gene.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DosageSensitivity {
+    pub gene: String,
+    /// The genomic location, e.g. chr22:42692121-42721298, on the build of the curation list.
+    pub location: Option<String>,
+    pub haploinsufficiency: Option<DosageScore>,
+    pub triplosensitivity: Option<DosageScore>,
+}
+
+impl DosageSensitivity {
+    /// The genomic location as an interval, if given and valid.
+    pub fn interval(&self) -> Option<CnvInterval> {
+        self.location.as_deref()?.parse().ok()
+    }
+}
+
+/// What ClinGen has curated of a gene, its gene-disease validity and its dosage sensitivity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneCuration {
+    pub gene: String,
+    /// The gene-disease validity curations, if the validity curations were consulted.
+    pub validity: Option<Vec<GeneDiseaseValidity>>,
+    /// The dosage sensitivity curation, if the dosage sensitivity curations were consulted and the gene is curated.
+    pub dosage: Option<DosageSensitivity>,
+    /// Whether the dosage sensitivity curations were consulted.
+    pub dosage_consulted: bool,
+}
+
+impl GeneCuration {
+    /// The strongest classification of the gene's relationship to any disease.
+    pub fn strongest_validity(&self) -> Option<ValidityClassification> {
+        self.validity.as_ref()?.iter().map(|validity| validity.classification).min()
+    }
+
+    /// Why PVS1 may not apply in the gene, if loss of function isn't an established mechanism of disease: it's
+    /// curated with a haploinsufficiency score other than 3 or 30, or isn't curated.
+    pub fn lof_mechanism_concern(&self) -> Option<String> {
+        if !self.dosage_consulted {
+            return None;
+        }
+        match self.dosage.as_ref().and_then(|dosage| dosage.haploinsufficiency) {
+            Some(score) if score.is_established() => None,
+            Some(score) => Some(format!("{} has a ClinGen haploinsufficiency score of {}", self.gene, score)),
+            None => Some(format!("{} has no ClinGen haploinsufficiency curation", self.gene)),
+        }
+    }
+
+    /// Why PVS1 may not apply in the gene, if its relationship to disease isn't established: its strongest
+    /// gene-disease validity is Limited or weaker, or it isn't curated.
+    pub fn validity_concern(&self) -> Option<String> {
+        let validity = self.validity.as_ref()?;
+        match self.strongest_validity() {
+            Some(classification) if classification.is_established() => None,
+            Some(classification) => {
+                let diseases: Vec<String> = validity.iter().map(|validity| format!("{} for {}", validity.classification, validity.disease)).collect();
+                Some(format!("{} has at most {} gene-disease validity in ClinGen ({})", self.gene, classification, diseases.join(", ")))
+            }
+            None => Some(format!("{} has no ClinGen gene-disease validity curation", self.gene)),
+        }
+    }
+}
+
+/// Local dumps of the ClinGen gene-disease validity and dosage sensitivity curations, by gene.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClinGenGenes {
+    validity: Option<HashMap<String, Vec<GeneDiseaseValidity>>>,
+    dosage: Option<HashMap<String, DosageSensitivity>>,
+}
+
+impl ClinGenGenes {
+    /// Reads the gene-disease validity summary CSV downloaded from the ClinGen website, whose header row starts with
+    /// `"GENE SYMBOL"` after the file's preamble, with `+++` separator rows.
+    pub fn with_validity(mut self, reader: impl BufRead) -> Result<ClinGenGenes, AcmgError> {
+        let mut validity: HashMap<String, Vec<GeneDiseaseValidity>> = HashMap::new();
+        let mut columns: Option<(usize, usize, usize, usize)> = None;
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|error| AcmgError::InvalidClinGen(error.to_string()))?;
+            let fields = split_csv(&line);
+            let Some((gene, disease, inheritance, classification)) = columns else {
+                if fields.first().is_some_and(|field| field == "GENE SYMBOL") {
+                    let column = |name: &str| fields.iter().position(|field| field == name)
+                        .ok_or_else(|| AcmgError::InvalidClinGen(format!("missing column {}", name)));
+                    columns = Some((column("GENE SYMBOL")?, column("DISEASE LABEL")?, column("MOI")?, column("CLASSIFICATION")?));
+                }
+                continue;
+            };
+            if line.trim().is_empty() || fields.first().is_some_and(|field| field.starts_with("+++")) {
+                continue;
+            }
+            let field = |index: usize| fields.get(index).map(String::as_str).unwrap_or_default();
+            let classification = field(classification).parse()
+                .map_err(|error: AcmgError| AcmgError::InvalidClinGen(format!("line {}: {}", number + 1, error)))?;
+            validity.entry(field(gene).to_string()).or_default().push(GeneDiseaseValidity {
+                disease: field(disease).to_string(),
+                inheritance: field(inheritance).to_string(),
+                classification,
+            });
+        }
+        if columns.is_none() {
+            return Err(AcmgError::InvalidClinGen("expected a header row starting with \"GENE SYMBOL\"".to_string()));
+        }
+        self.validity = Some(validity);
+        Ok(self)
+    }
+
+    /// Reads the dosage sensitivity gene curation list, e.g. ClinGen_gene_curation_list_GRCh38.tsv, whose header is
+    /// the last `#` comment line.
+    pub fn with_dosage(mut self, reader: impl BufRead) -> Result<ClinGenGenes, AcmgError> {
+        let mut dosage = HashMap::new();
+        let mut header: Option<Vec<String>> = None;
+        let mut columns: Option<(usize, Option<usize>, usize, usize)> = None;
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|error| AcmgError::InvalidClinGen(error.to_string()))?;
+            if let Some(comment) = line.strip_prefix('#') {
+                header = Some(comment.split('\t').map(|column| column.trim().to_string()).collect());
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (gene, location, haploinsufficiency, triplosensitivity) = match columns {
+                Some(columns) => columns,
+                None => {
+                    let header = header.as_ref().ok_or_else(|| AcmgError::InvalidClinGen("expected a # header line".to_string()))?;
+                    let column = |name: &str| header.iter().position(|column| column == name);
+                    let required = |name: &str| column(name).ok_or_else(|| AcmgError::InvalidClinGen(format!("missing column {}", name)));
+                    let found = (required("Gene Symbol")?, column("Genomic Location"), required("Haploinsufficiency Score")?, required("Triplosensitivity Score")?);
+                    *columns.insert(found)
+                }
+            };
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let score = |index: usize| match fields.get(index).copied().unwrap_or_default() {
+                "" | "Not yet evaluated" => Ok(None),
+                score => score.parse().map(Some).map_err(|error: AcmgError| AcmgError::InvalidClinGen(format!("line {}: {}", number + 1, error))),
+            };
+            let gene = fields.get(gene).copied().unwrap_or_default().to_string();
+            dosage.insert(gene.clone(), DosageSensitivity {
+                gene,
+                location: location.and_then(|location| fields.get(location)).filter(|location| !location.is_empty()).map(|location| location.to_string()),
+                haploinsufficiency: score(haploinsufficiency)?,
+                triplosensitivity: score(triplosensitivity)?,
+            });
+        }
+        self.dosage = Some(dosage);
+        Ok(self)
+    }
+
+    /// The curated genes whose location overlaps the interval, by gene symbol.
+    pub fn dosage_overlapping(&self, interval: &CnvInterval) -> Vec<(&DosageSensitivity, CnvInterval)> {
+        let mut overlapping: Vec<(&DosageSensitivity, CnvInterval)> = self.dosage.iter().flat_map(HashMap::values)
+            .filter_map(|dosage| dosage.interval().filter(|location| location.overlaps(interval)).map(|location| (dosage, location)))
+            .collect();
+        overlapping.sort_by(|(a, _), (b, _)| a.gene.cmp(&b.gene));
+        overlapping
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validity.is_none() && self.dosage.is_none()
+    }
+
+    /// The curations of a gene in the dumps read.
+    pub fn curation(&self, gene: &str) -> GeneCuration {
+        GeneCuration {
+            gene: gene.to_string(),
+            validity: self.validity.as_ref().map(|validity| validity.get(gene).cloned().unwrap_or_default()),
+            dosage: self.dosage.as_ref().and_then(|dosage| dosage.get(gene).cloned()),
+            dosage_consulted: self.dosage.is_some(),
+        }
+    }
+}
+
+/// Splits a line of CSV into its fields, unquoting quoted fields.
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::{AcmgError, Code, GenomeBuild, GenomicVariant};
+
+static PROTEIN_CHANGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"p\.\(?([A-Z][a-z]{2})(\d+)([A-Z][a-z]{2}|=)\)?").unwrap());
+static GENE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\(([A-Za-z0-9-]+)\)").unwrap());
+
+/// The aggregate clinical significance of a ClinVar record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ClinicalSignificance {
+    Pathogenic,
+    LikelyPathogenic,
+    UncertainSignificance,
+    LikelyBenign,
+    Benign,
+    Conflicting,
+    Other,
+}
+
+impl ClinicalSignificance {
+    /// Parses the ClinicalSignificance column of variant_summary.txt, e.g. `Pathogenic/Likely pathogenic`.
+    pub fn parse(significance: &str) -> ClinicalSignificance {
+        let significance = significance.to_lowercase();
+        match significance.split([',', ';']).next().unwrap_or_default().trim() {
+            "pathogenic" | "pathogenic/likely pathogenic" => ClinicalSignificance::Pathogenic,
+            "likely pathogenic" => ClinicalSignificance::LikelyPathogenic,
+            "uncertain significance" => ClinicalSignificance::UncertainSignificance,
+            "likely benign" => ClinicalSignificance::LikelyBenign,
+            "benign" | "benign/likely benign" => ClinicalSignificance::Benign,
+            s if s.starts_with("conflicting") => ClinicalSignificance::Conflicting,
+            _ => ClinicalSignificance::Other,
+        }
+    }
+
+    pub fn is_pathogenic(&self) -> bool {
+        matches!(self, ClinicalSignificance::Pathogenic | ClinicalSignificance::LikelyPathogenic)
+    }
+
+    pub fn is_benign(&self) -> bool {
+        matches!(self, ClinicalSignificance::Benign | ClinicalSignificance::LikelyBenign)
+    }
+}
+
+/// An amino acid substitution, e.g. p.Arg41Gly.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProteinChange {
+    pub reference: String,
+    pub position: u32,
+    /// The substituted amino acid, `Ter` for a stop gain and `=` if synonymous.
+    pub alternate: String,
+}
+
+impl ProteinChange {
+    /// Finds the first protein change in some HGVS, e.g. `NM_000059.4(BRCA2):c.121C>G (p.Arg41Gly)`.
+    pub fn find(hgvs: &str) -> Option<ProteinChange> {
+        PROTEIN_CHANGE.captures(hgvs).and_then(|caps| Some(ProteinChange {
+            reference: caps[1].to_string(),
+            position: caps[2].parse().ok()?,
+            alternate: caps[3].to_string(),
+        }))
+    }
+
+    pub fn is_missense(&self) -> bool {
+        self.alternate != "=" && self.alternate != "Ter" && self.alternate != self.reference
+    }
+}
+
+/// A classified variant from ClinVar, recording where each suggestion came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClinVarRecord {
+    pub variation_id: String,
+    pub name: String,
+    pub gene: String,
+    pub clinical_significance: ClinicalSignificance,
+    pub review_status: String,
+    pub chromosome: String,
+    pub position: u64,
+    pub reference: String,
+    pub alternate: String,
+}
+
+impl ClinVarRecord {
+    pub fn protein_change(&self) -> Option<ProteinChange> {
+        ProteinChange::find(&self.name)
+    }
+}
+
+/// A variant to look up, by VCF coordinates e.g. `13-32316467-C-G`, or HGVS with a gene and protein change e.g.
+/// `BRCA2:p.Arg41Gly` or `NM_000059.4(BRCA2):c.121C>G (p.Arg41Gly)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VariantQuery {
+    Coordinates(GenomicVariant),
+    Hgvs { name: String, gene: String, protein_change: Option<ProteinChange> },
+}
+
+impl FromStr for VariantQuery {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<VariantQuery, Self::Err> {
+        let s = s.trim();
+        if let Ok(variant) = GenomicVariant::parse(s, GenomeBuild::default()) {
+            return Ok(VariantQuery::Coordinates(variant));
+        }
+        let gene = GENE.captures(s).map(|caps| caps[1].to_string())
+            .or_else(|| s.split_once(':').map(|(gene, _)| gene.to_string()).filter(|gene| !gene.contains('(')));
+        match gene {
+            Some(gene) => Ok(VariantQuery::Hgvs { name: s.to_string(), gene, protein_change: ProteinChange::find(s) }),
+            None => Err(AcmgError::InvalidVariant(s.to_string())),
+        }
+    }
+}
+
+/// Evidence suggested by previously classified variants, with the records supporting it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ClinVarSuggestion {
+    pub code: Code,
+    pub reason: String,
+    pub records: Vec<ClinVarRecord>,
+}
+
+/// What ClinVar has to say about a variant.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ClinVarReport {
+    /// The ClinVar record of the variant itself, if any.
+    pub record: Option<ClinVarRecord>,
+    pub suggestions: Vec<ClinVarSuggestion>,
+    pub warnings: Vec<String>,
+}
+
+/// An in-memory index of a local copy of the ClinVar variant_summary.txt, by gene and exact variant.
+#[derive(Debug, Clone, Default)]
+pub struct ClinVarIndex {
+    records: Vec<ClinVarRecord>,
+    by_gene: HashMap<String, Vec<usize>>,
+}
+
+impl ClinVarIndex {
+    /// Reads the tab-separated variant_summary.txt, keeping the records for one assembly e.g. GRCh38.
+    pub fn from_variant_summary(reader: impl BufRead, assembly: &str) -> Result<ClinVarIndex, AcmgError> {
+        let mut lines = reader.lines();
+        let header = match lines.next() {
+            Some(line) => line.map_err(|error| AcmgError::InvalidClinVar(error.to_string()))?,
+            None => return Err(AcmgError::InvalidClinVar("empty variant summary".to_string())),
+        };
+        let columns: Vec<&str> = header.trim_start_matches('#').split('\t').collect();
+        let column = |name: &str| columns.iter().position(|column| *column == name)
+            .ok_or_else(|| AcmgError::InvalidClinVar(format!("missing column {}", name)));
+        let (variation_id, name, gene, significance, review_status) =
+            (column("VariationID")?, column("Name")?, column("GeneSymbol")?, column("ClinicalSignificance")?, column("ReviewStatus")?);
+        let (assembly_column, chromosome, position, reference, alternate) =
+            (column("Assembly")?, column("Chromosome")?, column("PositionVCF")?, column("ReferenceAlleleVCF")?, column("AlternateAlleleVCF")?);
+
+        let mut index = ClinVarIndex::default();
+        for line in lines {
+            let line = line.map_err(|error| AcmgError::InvalidClinVar(error.to_string()))?;
+            let fields: Vec<&str> = line.split('\t').collect();
+            let field = |index: usize| fields.get(index).copied().unwrap_or_default();
+            if field(assembly_column) != assembly {
+                continue;
+            }
+            index.insert(ClinVarRecord {
+                variation_id: field(variation_id).to_string(),
+                name: field(name).to_string(),
+                gene: field(gene).to_string(),
+                clinical_significance: ClinicalSignificance::parse(field(significance)),
+                review_status: field(review_status).to_string(),
+                chromosome: field(chromosome).to_string(),
+                position: field(position).parse().unwrap_or_default(),
+                reference: field(reference).to_string(),
+                alternate: field(alternate).to_string(),
+            });
+        }
+        Ok(index)
+    }
+
+    pub fn insert(&mut self, record: ClinVarRecord) {
+        self.by_gene.entry(record.gene.clone()).or_default().push(self.records.len());
+        self.records.push(record);
+    }
+
+    pub fn records(&self) -> &[ClinVarRecord] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    fn find(&self, query: &VariantQuery) -> Option<&ClinVarRecord> {
+        match query {
+            VariantQuery::Coordinates(variant) => self.records.iter().find(|record| {
+                record.chromosome == variant.chromosome && record.position == variant.position
+                    && record.reference == variant.reference && record.alternate == variant.alternate
+            }),
+            VariantQuery::Hgvs { name, gene, .. } => self.gene_records(gene).find(|record| record.name == *name),
+        }
+    }
+
+    fn gene_records<'a>(&'a self, gene: &str) -> impl Iterator<Item = &'a ClinVarRecord> {
+        self.by_gene.get(gene).into_iter().flatten().map(|index| &self.records[*index])
+    }
+
+    /// Suggests PS1 for other pathogenic variants with the same amino acid change, PM5 for pathogenic missense
+    /// variants at the same codon and PP5 or BP6 if the variant itself has been classified, warning about
+    /// conflicting classifications. PP5 and BP6 are no longer recommended by the ClinGen SVI (Biesecker et al.
+    /// 2018) so are only suggested with a warning.
+    pub fn report(&self, query: &VariantQuery) -> ClinVarReport {
+        let record = self.find(query).cloned();
+        let (gene, protein_change) = match (query, &record) {
+            (_, Some(record)) => (record.gene.clone(), record.protein_change()),
+            (VariantQuery::Hgvs { gene, protein_change, .. }, None) => (gene.clone(), protein_change.clone()),
+            (VariantQuery::Coordinates(_), None) => (String::new(), None),
+        };
+        let mut report = ClinVarReport { record, ..ClinVarReport::default() };
+
+        if let Some(record) = &report.record {
+            let significance = record.clinical_significance;
+            if significance == ClinicalSignificance::Conflicting {
+                report.warnings.push(format!("ClinVar {} has conflicting classifications ({})", record.variation_id, record.review_status));
+            } else if significance.is_pathogenic() || significance.is_benign() {
+                let code = if significance.is_pathogenic() { Code::PP5 } else { Code::BP6 };
+                report.warnings.push(format!("{} relies on a reputable source without the evidence being available, which the SVI recommends against", code));
+                report.suggestions.push(ClinVarSuggestion { code, reason: format!("Classified {:?} in ClinVar", significance), records: vec![record.clone()] });
+            }
+        }
+
+        if let Some(protein_change) = protein_change.filter(|protein_change| protein_change.is_missense()) {
+            let at_codon: Vec<(&ClinVarRecord, ProteinChange)> = self.gene_records(&gene)
+                .filter(|other| Some(*other) != report.record.as_ref() && other.clinical_significance.is_pathogenic())
+                .filter_map(|other| other.protein_change().map(|change| (other, change)))
+                .filter(|(_, change)| change.position == protein_change.position && change.reference == protein_change.reference)
+                .collect();
+            let (same, different): (Vec<_>, Vec<_>) = at_codon.into_iter()
+                .filter(|(_, change)| change.is_missense())
+                .partition(|(_, change)| change.alternate == protein_change.alternate);
+            if !same.is_empty() {
+                report.suggestions.push(ClinVarSuggestion {
+                    code: Code::PS1,
+                    reason: format!("Same amino acid change p.{}{}{} previously classified pathogenic", protein_change.reference, protein_change.position, protein_change.alternate),
+                    records: same.into_iter().map(|(record, _)| record.clone()).collect(),
+                });
+            }
+            if !different.is_empty() {
+                report.suggestions.push(ClinVarSuggestion {
+                    code: Code::PM5,
+                    reason: format!("Different missense change at codon {}{} previously classified pathogenic", protein_change.reference, protein_change.position),
+                    records: different.into_iter().map(|(record, _)| record.clone()).collect(),
+                });
+            }
+        }
+        report
+    }
+}
@@ -0,0 +1,494 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::code::unknown_code;
+use crate::{AcmgClassification, AcmgError, ClinGenGenes, DosageScore};
+
+/// Whether a copy-number variant deletes or duplicates the region, each with its own scoring rubric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CnvType {
+    Loss,
+    Gain,
+}
+
+impl CnvType {
+    pub const ALL: [CnvType; 2] = [CnvType::Loss, CnvType::Gain];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CnvType::Loss => "loss",
+            CnvType::Gain => "gain",
+        }
+    }
+
+    /// The criteria of the scoring rubric for this type of CNV.
+    pub fn criteria(&self) -> &'static [CnvCriterion] {
+        match self {
+            CnvType::Loss => LOSS_CRITERIA,
+            CnvType::Gain => GAIN_CRITERIA,
+        }
+    }
+
+    pub fn criterion(&self, code: &str) -> Option<&'static CnvCriterion> {
+        self.criteria().iter().find(|criterion| criterion.code.eq_ignore_ascii_case(code))
+    }
+
+    /// The section 3 criterion for the number of protein-coding RefSeq genes wholly or partially included.
+    pub fn gene_count_criterion(&self, genes: u32) -> &'static CnvCriterion {
+        let (moderate, many) = match self {
+            CnvType::Loss => (25, 35),
+            CnvType::Gain => (35, 50),
+        };
+        let code = match genes {
+            genes if genes >= many => "3C",
+            genes if genes >= moderate => "3B",
+            _ => "3A",
+        };
+        self.criterion(code).expect("section 3 criteria exist for both CNV types")
+    }
+
+    /// The section 2 calls for the genes with a ClinGen dosage sensitivity curation which the CNV overlaps, in the
+    /// order of their points. Fully containing an established haploinsufficient gene suggests 2A for a loss, and an
+    /// established triplosensitive gene 2A for a gain, otherwise 2H for a haploinsufficient one. Breakpoints within
+    /// such a gene leave the curator to choose the criterion.
+    pub fn dosage_calls(&self, interval: &CnvInterval, genes: &ClinGenGenes) -> Vec<DosageCall> {
+        let mut calls = vec![];
+        for (dosage, location) in genes.dosage_overlapping(interval) {
+            let haploinsufficient = dosage.haploinsufficiency == Some(DosageScore::Sufficient);
+            let triplosensitive = dosage.triplosensitivity == Some(DosageScore::Sufficient);
+            let contained = interval.contains(&location);
+            let both_breakpoints = location.contains(interval);
+            let (code, sensitivity, choice) = match self {
+                CnvType::Loss if !haploinsufficient => continue,
+                CnvType::Loss if contained => (Some("2A"), "haploinsufficient", ""),
+                CnvType::Loss if both_breakpoints => (None, "haploinsufficient", ", score 2E by the PVS1 strength"),
+                CnvType::Loss => (None, "haploinsufficient", ", choose 2C or 2D by the end overlapped"),
+                CnvType::Gain if contained && triplosensitive => (Some("2A"), "triplosensitive", ""),
+                CnvType::Gain if !haploinsufficient => continue,
+                CnvType::Gain if contained => (Some("2H"), "haploinsufficient", ""),
+                CnvType::Gain if both_breakpoints => (None, "haploinsufficient", ", score 2I by the PVS1 strength"),
+                CnvType::Gain => (None, "haploinsufficient", ", choose 2J or 2K by the phenotype"),
+            };
+            let overlap = if contained { "is fully contained" } else if both_breakpoints { "contains both breakpoints" } else { "contains a breakpoint" };
+            calls.push(DosageCall {
+                gene: dosage.gene.clone(),
+                code,
+                reason: format!("{} ({}), established {}, {}{}", dosage.gene, location, sensitivity, overlap, choice),
+            });
+        }
+        let points = |call: &DosageCall| call.code.and_then(|code| self.criterion(code)).map_or(f64::MIN, |criterion| criterion.points);
+        calls.sort_by(|a, b| points(b).total_cmp(&points(a)).then_with(|| a.gene.cmp(&b.gene)));
+        calls
+    }
+}
+
+impl Display for CnvType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for CnvType {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<CnvType, Self::Err> {
+        CnvType::ALL.iter()
+            .find(|cnv_type| cnv_type.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| AcmgError::UnknownCnvType(s.to_string()))
+    }
+}
+
+/// The interval of a CNV with 1-based inclusive coordinates, e.g. `chr22:42692121-42721298`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CnvInterval {
+    /// The chromosome without a `chr` prefix, e.g. 22.
+    pub chromosome: String,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl CnvInterval {
+    pub fn overlaps(&self, other: &CnvInterval) -> bool {
+        self.chromosome == other.chromosome && self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn contains(&self, other: &CnvInterval) -> bool {
+        self.chromosome == other.chromosome && self.start <= other.start && other.end <= self.end
+    }
+}
+
+impl Display for CnvInterval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("{}:{}-{}", self.chromosome, self.start, self.end))
+    }
+}
+
+impl FromStr for CnvInterval {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<CnvInterval, Self::Err> {
+        let invalid = || AcmgError::InvalidParameter(format!("expected a CNV interval chromosome:start-end, got '{}'", s));
+        let (chromosome, range) = s.trim().rsplit_once(':').ok_or_else(invalid)?;
+        let (start, end) = range.split_once('-').ok_or_else(invalid)?;
+        let parse = |position: &str| position.trim().replace(',', "").parse::<u64>().map_err(|_| invalid());
+        let (start, end) = (parse(start)?, parse(end)?);
+        let chromosome = chromosome.trim().trim_start_matches("chr").trim_start_matches("CHR").to_uppercase();
+        if chromosome.is_empty() || start == 0 || start > end {
+            return Err(invalid());
+        }
+        Ok(CnvInterval { chromosome, start, end })
+    }
+}
+
+/// A gene with a ClinGen dosage sensitivity curation overlapped by a CNV, with the section 2 criterion it suggests, or
+/// None if a curator has to choose between criteria.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DosageCall {
+    pub gene: String,
+    pub code: Option<&'static str>,
+    pub reason: String,
+}
+
+/// A criterion of the ACMG/ClinGen CNV scoring rubric of Riggs et al. 2020, e.g. 2A.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CnvCriterion {
+    pub code: &'static str,
+    pub section: u8,
+    /// The suggested points, per observation for criteria which are counted.
+    pub points: f64,
+    /// The range the points can be adjusted within.
+    pub min: f64,
+    pub max: f64,
+    /// Whether the criterion can be applied once per case, family or study.
+    pub per_observation: bool,
+    pub description: &'static str,
+}
+
+const fn once(code: &'static str, section: u8, points: f64, description: &'static str) -> CnvCriterion {
+    CnvCriterion { code, section, points, min: points, max: points, per_observation: false, description }
+}
+
+const fn ranged(code: &'static str, section: u8, points: f64, (min, max): (f64, f64), description: &'static str) -> CnvCriterion {
+    CnvCriterion { code, section, points, min, max, per_observation: false, description }
+}
+
+const fn each(code: &'static str, section: u8, points: f64, (min, max): (f64, f64), description: &'static str) -> CnvCriterion {
+    CnvCriterion { code, section, points, min, max, per_observation: true, description }
+}
+
+/// Sections 1, 4 and 5 are shared by both rubrics other than 5H.
+const SECTION_1: [CnvCriterion; 2] = [
+    once("1A", 1, 0.0, "Contains protein-coding or other known functionally important elements"),
+    once("1B", 1, -0.6, "Does not contain protein-coding or any known functionally important elements"),
+];
+
+const SECTION_4: [CnvCriterion; 15] = [
+    each("4A", 4, 0.45, (0.15, 0.45), "Reported proband with a highly specific phenotype relatively unique to the region, 0.45 if confirmed de novo or 0.30 if assumed"),
+    each("4B", 4, 0.3, (0.15, 0.45), "Reported proband with a highly specific phenotype consistent with the region but not unique, 0.30 if confirmed de novo or 0.15 if assumed"),
+    each("4C", 4, 0.15, (0.0, 0.45), "Reported proband with a consistent but not highly specific phenotype or high genetic heterogeneity, 0.15 if confirmed de novo or 0.10 if assumed"),
+    each("4D", 4, 0.0, (-0.3, 0.0), "Reported proband with a phenotype inconsistent with the region"),
+    each("4E", 4, 0.1, (0.0, 0.15), "Reported proband with a highly specific phenotype consistent with the region, inheritance unknown"),
+    once("4F", 4, 0.15, "3-4 observed segregations"),
+    once("4G", 4, 0.3, "5-6 observed segregations"),
+    once("4H", 4, 0.45, "7 or more observed segregations"),
+    each("4I", 4, -0.45, (-0.45, 0.0), "Not found in another affected family member with a consistent, specific phenotype"),
+    each("4J", 4, -0.3, (-0.3, 0.0), "Found in an unaffected family member, for a specific phenotype without known reduced penetrance"),
+    each("4K", 4, -0.15, (-0.15, 0.0), "Found in an unaffected family member, for a nonspecific phenotype"),
+    each("4L", 4, 0.45, (0.0, 0.45), "Case-control study with a statistically significant increase in cases with a specific phenotype"),
+    each("4M", 4, 0.3, (0.0, 0.45), "Case-control study with a statistically significant increase in cases with a nonspecific phenotype"),
+    each("4N", 4, -0.9, (-0.9, 0.0), "Case-control study with no statistically significant difference between cases and controls"),
+    ranged("4O", 4, -1.0, (-1.0, 0.0), "Overlaps common population variation"),
+];
+
+const SECTION_5: [CnvCriterion; 7] = [
+    once("5A", 5, 0.0, "De novo, scored under section 4"),
+    ranged("5B", 5, -0.3, (-0.45, 0.0), "Inherited from an unaffected parent, for a specific phenotype without known reduced penetrance"),
+    ranged("5C", 5, -0.15, (-0.3, 0.0), "Inherited from an unaffected parent, for a nonspecific phenotype"),
+    once("5D", 5, 0.0, "Segregates with a consistent phenotype in the family, scored under section 4"),
+    once("5E", 5, 0.0, "Nonsegregation, scored under section 4"),
+    once("5F", 5, 0.0, "Inheritance unavailable or uninformative"),
+    ranged("5G", 5, 0.1, (0.0, 0.15), "Inheritance uninformative, phenotype nonspecific but consistent with the region"),
+];
+
+/// Both rubrics have 15 criteria in sections 2 and 3, which together with 5H complete the shared sections.
+const fn rubric(sections_2_3: [CnvCriterion; 15], section_5h: CnvCriterion) -> [CnvCriterion; 40] {
+    let mut criteria = [section_5h; 40];
+    let mut index = 0;
+    while index < 39 {
+        criteria[index] = match index {
+            i if i < 2 => SECTION_1[i],
+            i if i < 17 => sections_2_3[i - 2],
+            i if i < 32 => SECTION_4[i - 17],
+            i => SECTION_5[i - 32],
+        };
+        index += 1;
+    }
+    criteria
+}
+
+/// The copy-number loss rubric.
+pub const LOSS_CRITERIA: &[CnvCriterion] = &rubric([
+    once("2A", 2, 1.0, "Complete overlap of an established haploinsufficient gene or genomic region"),
+    once("2B", 2, 0.0, "Partial overlap of an established haploinsufficient genomic region"),
+    ranged("2C-1", 2, 0.9, (0.45, 1.0), "Partial overlap with the 5' end of an established haploinsufficient gene, involving coding sequence"),
+    ranged("2C-2", 2, 0.0, (0.0, 0.45), "Partial overlap with the 5' end of an established haploinsufficient gene, only involving the 5' UTR"),
+    ranged("2D-1", 2, 0.0, (0.0, 0.45), "Partial overlap with the 3' end of an established haploinsufficient gene, only involving the 3' UTR"),
+    ranged("2D-2", 2, 0.9, (0.45, 0.9), "Only the last exon of an established haploinsufficient gene, with other pathogenic variants in the exon"),
+    ranged("2D-3", 2, 0.3, (0.0, 0.45), "Only the last exon of an established haploinsufficient gene, without other pathogenic variants in the exon"),
+    ranged("2D-4", 2, 0.9, (0.45, 1.0), "Includes exons other than the last of an established haploinsufficient gene, with nonsense mediated decay expected"),
+    ranged("2E", 2, 0.9, (0.0, 0.9), "Both breakpoints within the same gene, scored as PVS1 0.90, Strong 0.45, Moderate 0.30, Supporting 0.15 or N/A 0"),
+    once("2F", 2, -1.0, "Completely contained within an established benign copy-number loss region"),
+    once("2G", 2, 0.0, "Overlaps an established benign copy-number loss region, but includes additional genomic material"),
+    once("2H", 2, 0.15, "Two or more haploinsufficiency predictors suggest at least one gene in the interval is haploinsufficient"),
+    once("3A", 3, 0.0, "0-24 protein-coding genes"),
+    once("3B", 3, 0.45, "25-34 protein-coding genes"),
+    once("3C", 3, 0.9, "35 or more protein-coding genes"),
+], ranged("5H", 5, 0.3, (0.0, 0.3), "Inheritance uninformative, phenotype highly specific and consistent with the region"));
+
+/// The copy-number gain rubric.
+pub const GAIN_CRITERIA: &[CnvCriterion] = &rubric([
+    once("2A", 2, 1.0, "Complete overlap of an established triplosensitive gene or minimal critical region"),
+    once("2B", 2, 0.0, "Partial overlap of an established triplosensitive region"),
+    once("2C", 2, -1.0, "Identical in gene content to an established benign copy-number gain"),
+    once("2D", 2, -1.0, "Smaller than an established benign copy-number gain, breakpoints do not interrupt protein-coding genes"),
+    once("2E", 2, 0.0, "Smaller than an established benign copy-number gain, breakpoints potentially interrupt a protein-coding gene"),
+    ranged("2F", 2, -0.9, (-1.0, 0.0), "Larger than an established benign copy-number gain, without additional protein-coding genes"),
+    once("2G", 2, 0.0, "Overlaps an established benign copy-number gain, but includes additional genomic material"),
+    once("2H", 2, 0.0, "An established haploinsufficient gene is fully contained"),
+    ranged("2I", 2, 0.9, (0.0, 0.9), "Both breakpoints within the same gene, scored as PVS1 0.90, Strong 0.45, Moderate 0.30, Supporting 0.15 or N/A 0"),
+    once("2J", 2, 0.0, "One breakpoint within an established haploinsufficient gene, phenotype inconsistent or unknown"),
+    once("2K", 2, 0.45, "One breakpoint within an established haploinsufficient gene, phenotype highly specific and consistent"),
+    once("2L", 2, 0.0, "One or both breakpoints within protein-coding genes of unknown clinical significance"),
+    once("3A", 3, 0.0, "0-34 protein-coding genes"),
+    once("3B", 3, 0.45, "35-49 protein-coding genes"),
+    once("3C", 3, 0.9, "50 or more protein-coding genes"),
+], ranged("5H", 5, 0.15, (0.0, 0.3), "Inheritance uninformative, phenotype highly specific and consistent with the region"));
+
+/// A criterion applied to a CNV, at its suggested points unless adjusted within its range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CnvEvidence {
+    pub code: &'static str,
+    pub points: f64,
+}
+
+impl Display for CnvEvidence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("{}={:.2}", self.code, self.points))
+    }
+}
+
+/// The evidence scored for a copy-number variant, by criterion or by the total score of a section.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CnvAssessment {
+    pub cnv_type: CnvType,
+    pub evidence: Vec<CnvEvidence>,
+    /// Total scores given for whole sections, e.g. as tallied by another tool.
+    pub section_scores: Vec<(u8, f64)>,
+}
+
+impl CnvAssessment {
+    pub fn new(cnv_type: CnvType) -> CnvAssessment {
+        CnvAssessment { cnv_type, evidence: vec![], section_scores: vec![] }
+    }
+
+    /// Parses comma-separated criteria, each optionally with adjusted points, e.g. `1A, 2H, 4A=0.30, 4A`.
+    pub fn parse(cnv_type: CnvType, criteria: &str) -> Result<CnvAssessment, AcmgError> {
+        let mut assessment = CnvAssessment::new(cnv_type);
+        for item in criteria.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (code, points) = match item.split_once('=') {
+                Some((code, points)) => {
+                    let points = points.trim().parse().map_err(|_| AcmgError::InvalidSyntax(item.to_string()))?;
+                    (code.trim(), Some(points))
+                }
+                None => (item, None),
+            };
+            assessment = assessment.with_criterion(code, points)?;
+        }
+        Ok(assessment)
+    }
+
+    /// Applies a criterion of the rubric, at its suggested points unless others are given. Criteria which
+    /// aren't counted per observation can only be applied once.
+    pub fn with_criterion(mut self, code: &str, points: Option<f64>) -> Result<CnvAssessment, AcmgError> {
+        let criterion = self.cnv_type.criterion(code).ok_or_else(|| unknown_code(code, self.cnv_type.criteria().iter().map(|criterion| criterion.code)))?;
+        let points = points.unwrap_or(criterion.points);
+        if !(criterion.min..=criterion.max).contains(&points) {
+            return Err(AcmgError::InvalidParameter(format!("{} points must be between {:.2} and {:.2}, got {}", criterion.code, criterion.min, criterion.max, points)));
+        }
+        if !criterion.per_observation && self.evidence.iter().any(|evidence| evidence.code == criterion.code) {
+            return Err(AcmgError::DuplicateCode(criterion.code.to_string()));
+        }
+        self.evidence.push(CnvEvidence { code: criterion.code, points });
+        Ok(self)
+    }
+
+    /// Applies the section 3 criterion for the number of protein-coding genes in the CNV.
+    pub fn with_gene_count(self, genes: u32) -> Result<CnvAssessment, AcmgError> {
+        let code = self.cnv_type.gene_count_criterion(genes).code;
+        self.with_criterion(code, None)
+    }
+
+    /// Adds the total score of a section in place of its individual criteria.
+    pub fn with_section_score(mut self, section: u8, points: f64) -> Result<CnvAssessment, AcmgError> {
+        if !(1..=5).contains(&section) {
+            return Err(AcmgError::InvalidParameter(format!("CNV sections are numbered 1 to 5, got {}", section)));
+        }
+        if self.has_section(section) {
+            return Err(AcmgError::DuplicateCode(format!("section {}", section)));
+        }
+        self.section_scores.push((section, points));
+        Ok(self)
+    }
+
+    /// Whether the section is scored, by its total or any of its criteria.
+    pub fn has_section(&self, section: u8) -> bool {
+        self.section_scores.iter().any(|(scored, _)| *scored == section)
+            || self.evidence.iter().any(|evidence| self.cnv_type.criterion(evidence.code).is_some_and(|criterion| criterion.section == section))
+    }
+
+    /// The total score, rounded to the two decimal places points are given in.
+    pub fn score(&self) -> f64 {
+        let total: f64 = self.evidence.iter().map(|evidence| evidence.points)
+            .chain(self.section_scores.iter().map(|(_, points)| *points))
+            .sum();
+        (total * 100.0).round() / 100.0
+    }
+
+    /// Classifies the score - Pathogenic at 0.99 or more, Likely Pathogenic from 0.90, Likely Benign from -0.90
+    /// and Benign at -0.99 or less.
+    pub fn classify(&self) -> AcmgClassification {
+        match self.score() {
+            score if score >= 0.99 => AcmgClassification::Pathogenic,
+            score if score >= 0.9 => AcmgClassification::LikelyPathogenic,
+            score if score <= -0.99 => AcmgClassification::Benign,
+            score if score <= -0.9 => AcmgClassification::LikelyBenign,
+            _ => AcmgClassification::UncertainSignificance,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section_score(cnv_type: CnvType, points: f64) -> CnvAssessment {
+        CnvAssessment::new(cnv_type).with_section_score(4, points).unwrap()
+    }
+
+    #[test]
+    fn gene_count_cut_offs_differ_for_losses_and_gains() {
+        let cases = [
+            (CnvType::Loss, 0, "3A"), (CnvType::Loss, 24, "3A"), (CnvType::Loss, 25, "3B"), (CnvType::Loss, 34, "3B"),
+            (CnvType::Loss, 35, "3C"), (CnvType::Loss, 200, "3C"),
+            (CnvType::Gain, 0, "3A"), (CnvType::Gain, 34, "3A"), (CnvType::Gain, 35, "3B"), (CnvType::Gain, 49, "3B"),
+            (CnvType::Gain, 50, "3C"), (CnvType::Gain, 200, "3C"),
+        ];
+        for (cnv_type, genes, code) in cases {
+            assert_eq!(cnv_type.gene_count_criterion(genes).code, code, "{} genes in a {}", genes, cnv_type);
+        }
+    }
+
+    #[test]
+    fn gene_count_criteria_score_as_the_rubric() {
+        for (cnv_type, genes) in [(CnvType::Loss, [10, 30, 40]), (CnvType::Gain, [10, 40, 60])] {
+            let points: Vec<f64> = genes.iter()
+                .map(|genes| CnvAssessment::new(cnv_type).with_gene_count(*genes).unwrap().score())
+                .collect();
+            assert_eq!(points, vec![0.0, 0.45, 0.9], "{}", cnv_type);
+        }
+    }
+
+    #[test]
+    fn classification_bounds_are_inclusive_at_099_and_090() {
+        use AcmgClassification::*;
+        let cases = [
+            (1.5, Pathogenic), (0.99, Pathogenic), (0.98, LikelyPathogenic), (0.9, LikelyPathogenic),
+            (0.89, UncertainSignificance), (0.0, UncertainSignificance), (-0.89, UncertainSignificance),
+            (-0.9, LikelyBenign), (-0.98, LikelyBenign), (-0.99, Benign), (-1.5, Benign),
+        ];
+        for cnv_type in CnvType::ALL {
+            for (points, expected) in cases {
+                assert_eq!(section_score(cnv_type, points).classify(), expected, "{} scoring {}", cnv_type, points);
+            }
+        }
+    }
+
+    #[test]
+    fn criteria_sum_to_the_classification() {
+        use AcmgClassification::*;
+        let cases = [
+            (CnvType::Loss, "1A, 2A", 1.0, Pathogenic),
+            (CnvType::Loss, "1A, 3C", 0.9, LikelyPathogenic),
+            (CnvType::Loss, "1A, 2H, 3B, 4B", 0.9, LikelyPathogenic),
+            (CnvType::Loss, "1B, 4O=-0.3", -0.9, LikelyBenign),
+            (CnvType::Loss, "1A, 2F", -1.0, Benign),
+            (CnvType::Gain, "1A, 2A", 1.0, Pathogenic),
+            (CnvType::Gain, "1A, 3B, 4A", 0.9, LikelyPathogenic),
+            (CnvType::Gain, "1A, 2F", -0.9, LikelyBenign),
+            (CnvType::Gain, "1A, 2H, 3A", 0.0, UncertainSignificance),
+        ];
+        for (cnv_type, criteria, score, expected) in cases {
+            let assessment = CnvAssessment::parse(cnv_type, criteria).unwrap();
+            assert_eq!((assessment.score(), assessment.classify()), (score, expected), "{}: {}", cnv_type, criteria);
+        }
+    }
+
+    const DOSAGE: &str = "#Gene Symbol\tGene ID\tGenomic Location\tHaploinsufficiency Score\tTriplosensitivity Score\n\
+        HIGENE\t1\tchr1:1000-2000\t3\t0\n\
+        TSGENE\t2\tchr1:5000-6000\t1\t3\n\
+        UNCERTAIN\t3\tchr1:8000-9000\t2\t2\n";
+
+    fn dosage_calls(cnv_type: CnvType, interval: &str) -> Vec<(String, Option<&'static str>)> {
+        let genes = ClinGenGenes::default().with_dosage(DOSAGE.as_bytes()).unwrap();
+        cnv_type.dosage_calls(&interval.parse().unwrap(), &genes).into_iter().map(|call| (call.gene, call.code)).collect()
+    }
+
+    fn calls(expected: &[(&str, Option<&'static str>)]) -> Vec<(String, Option<&'static str>)> {
+        expected.iter().map(|(gene, code)| (gene.to_string(), *code)).collect()
+    }
+
+    #[test]
+    fn contained_haploinsufficient_gene_is_2a_for_a_loss() {
+        assert_eq!(dosage_calls(CnvType::Loss, "chr1:500-2500"), calls(&[("HIGENE", Some("2A"))]));
+        assert_eq!(dosage_calls(CnvType::Loss, "chr1:500-9500"), calls(&[("HIGENE", Some("2A"))]));
+    }
+
+    #[test]
+    fn contained_triplosensitive_gene_is_2a_for_a_gain() {
+        assert_eq!(dosage_calls(CnvType::Gain, "chr1:4000-7000"), calls(&[("TSGENE", Some("2A"))]));
+        assert_eq!(dosage_calls(CnvType::Loss, "chr1:4000-7000"), calls(&[]));
+    }
+
+    #[test]
+    fn haploinsufficient_gene_in_a_gain_is_2h() {
+        assert_eq!(dosage_calls(CnvType::Gain, "chr1:500-2500"), calls(&[("HIGENE", Some("2H"))]));
+        assert_eq!(dosage_calls(CnvType::Gain, "chr1:500-9500"), calls(&[("TSGENE", Some("2A")), ("HIGENE", Some("2H"))]));
+    }
+
+    #[test]
+    fn breakpoints_within_a_gene_leave_the_call_to_the_curator() {
+        let cases = [
+            (CnvType::Loss, "chr1:1500-2500", "choose 2C or 2D"),
+            (CnvType::Loss, "chr1:1200-1800", "score 2E"),
+            (CnvType::Gain, "chr1:1500-2500", "choose 2J or 2K"),
+            (CnvType::Gain, "chr1:1200-1800", "score 2I"),
+        ];
+        let genes = ClinGenGenes::default().with_dosage(DOSAGE.as_bytes()).unwrap();
+        for (cnv_type, interval, choice) in cases {
+            let calls = cnv_type.dosage_calls(&interval.parse().unwrap(), &genes);
+            assert_eq!(calls.len(), 1, "{} {}", cnv_type, interval);
+            assert_eq!((calls[0].gene.as_str(), calls[0].code), ("HIGENE", None), "{} {}", cnv_type, interval);
+            assert!(calls[0].reason.contains(choice), "{} {}: {}", cnv_type, interval, calls[0].reason);
+        }
+        assert_eq!(dosage_calls(CnvType::Gain, "chr1:5500-6500"), calls(&[]));
+        assert_eq!(dosage_calls(CnvType::Loss, "chr2:500-2500"), calls(&[]));
+    }
+}
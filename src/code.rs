@@ -0,0 +1,146 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::evidence::evidence_code;
+use crate::{AcmgError, EvidenceCode};
+
+/// The built-in ACMG/AMP evidence codes, for assembling evidence without parsing strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Code {
+    PVS1,
+    PS1,
+    PS2,
+    PS3,
+    PS4,
+    PM1,
+    PM2,
+    PM3,
+    PM4,
+    PM5,
+    PM6,
+    PP1,
+    PP2,
+    PP3,
+    PP4,
+    PP5,
+    BA1,
+    BS1,
+    BS2,
+    BS3,
+    BS4,
+    BP1,
+    BP2,
+    BP3,
+    BP4,
+    BP5,
+    BP6,
+    BP7,
+}
+
+impl Code {
+    pub const ALL: [Code; 28] = [
+        Code::PVS1, Code::PS1, Code::PS2, Code::PS3, Code::PS4,
+        Code::PM1, Code::PM2, Code::PM3, Code::PM4, Code::PM5, Code::PM6,
+        Code::PP1, Code::PP2, Code::PP3, Code::PP4, Code::PP5,
+        Code::BA1, Code::BS1, Code::BS2, Code::BS3, Code::BS4,
+        Code::BP1, Code::BP2, Code::BP3, Code::BP4, Code::BP5, Code::BP6, Code::BP7,
+    ];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Code::PVS1 => "PVS1",
+            Code::PS1 => "PS1",
+            Code::PS2 => "PS2",
+            Code::PS3 => "PS3",
+            Code::PS4 => "PS4",
+            Code::PM1 => "PM1",
+            Code::PM2 => "PM2",
+            Code::PM3 => "PM3",
+            Code::PM4 => "PM4",
+            Code::PM5 => "PM5",
+            Code::PM6 => "PM6",
+            Code::PP1 => "PP1",
+            Code::PP2 => "PP2",
+            Code::PP3 => "PP3",
+            Code::PP4 => "PP4",
+            Code::PP5 => "PP5",
+            Code::BA1 => "BA1",
+            Code::BS1 => "BS1",
+            Code::BS2 => "BS2",
+            Code::BS3 => "BS3",
+            Code::BS4 => "BS4",
+            Code::BP1 => "BP1",
+            Code::BP2 => "BP2",
+            Code::BP3 => "BP3",
+            Code::BP4 => "BP4",
+            Code::BP5 => "BP5",
+            Code::BP6 => "BP6",
+            Code::BP7 => "BP7",
+        }
+    }
+
+    /// The full definition of this code from the built-in code table.
+    pub const fn evidence_code(&self) -> &'static EvidenceCode {
+        evidence_code(self.as_str()).expect("all codes are in the code table")
+    }
+}
+
+impl Display for Code {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for Code {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<Code, Self::Err> {
+        let upper = s.to_uppercase();
+        Code::ALL.iter()
+            .find(|code| code.as_str() == upper)
+            .copied()
+            .ok_or_else(|| unknown_code(s, Code::ALL.iter().map(Code::as_str)))
+    }
+}
+
+/// An [`AcmgError::UnknownCode`] for a code which isn't among the candidates, suggesting the closest of them.
+pub(crate) fn unknown_code<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> AcmgError {
+    let upper = name.to_uppercase();
+    let suggestion = candidates.into_iter()
+        .map(|candidate| (edit_distance(&upper, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, candidate)| (*distance, tie_break(&upper, candidate)))
+        .map(|(_, candidate)| candidate.to_string());
+    AcmgError::UnknownCode { code: name.to_string(), suggestion }
+}
+
+/// Between equally close candidates prefer one with the same first character, then the nearest final digit, so
+/// PM7 suggests PM6 rather than PM1.
+fn tie_break(name: &str, candidate: &str) -> (bool, u8) {
+    let last = |s: &str| s.bytes().last().unwrap_or_default();
+    (name.bytes().next() != candidate.bytes().next(), last(name).abs_diff(last(candidate)))
+}
+
+/// The optimal string alignment distance, which counts swapping two adjacent characters as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
@@ -0,0 +1,88 @@
+use crate::Code::{self, *};
+use crate::validation::{Diagnostic, Rule};
+use crate::EvidenceSet;
+
+/// A pair of codes which the ClinGen SVI recommends should not be applied together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict {
+    pub codes: (Code, Code),
+    pub rule: Rule,
+    pub reason: &'static str,
+}
+
+const fn double_counting(first: Code, second: Code, reason: &'static str) -> Conflict {
+    Conflict { codes: (first, second), rule: Rule::DoubleCounting, reason }
+}
+
+const fn contradictory(first: Code, second: Code, reason: &'static str) -> Conflict {
+    Conflict { codes: (first, second), rule: Rule::ContradictoryEvidence, reason }
+}
+
+/// Combinations of codes which either count the same evidence twice or contradict each other.
+pub const CONFLICTS: &[Conflict] = &[
+    double_counting(PVS1, PM4, "PM4 should not be applied to a variant where PVS1 has been applied"),
+    double_counting(PVS1, PP3, "PP3 should not be applied for a predicted splicing impact where PVS1 has been applied to a splice variant"),
+    double_counting(PS1, PM5, "PS1 and PM5 should not both be applied for the same residue"),
+    double_counting(PS2, PM6, "PS2 and PM6 should not both be applied for the same de novo observations"),
+    double_counting(PM1, PP3, "PM1 and PP3 should not both be applied for the same line of evidence on a critical domain"),
+    double_counting(PP1, PS4, "PP1 and PS4 should not both be applied for the same affected individuals"),
+    contradictory(PM2, BA1, "PM2 asserts the variant is rare while BA1 asserts it is common"),
+    contradictory(PM2, BS1, "PM2 asserts the variant is rare while BS1 asserts it is more common than expected"),
+    contradictory(PS3, BS3, "Functional studies cannot show both a damaging and no damaging effect"),
+    contradictory(PP3, BP4, "Computational evidence cannot both support and refute a deleterious effect"),
+    contradictory(PP1, BS4, "A variant cannot both cosegregate and fail to segregate with disease"),
+    contradictory(PM4, BP3, "An in-frame indel cannot be in both a non-repeat and a repetitive region"),
+    contradictory(PP5, BP6, "Reputable sources cannot report the variant as both pathogenic and benign"),
+];
+
+/// Checks the evidence for combinations of codes which should not be applied together.
+pub fn check_conflicts(evidence_set: &EvidenceSet) -> Vec<Diagnostic> {
+    let contains = |code: Code| evidence_set.iter().any(|evidence| evidence.evidence_code == code.evidence_code());
+    CONFLICTS.iter()
+        .filter(|conflict| contains(conflict.codes.0) && contains(conflict.codes.1))
+        .map(|conflict| {
+            let (first, second) = conflict.codes;
+            Diagnostic::new(conflict.rule,
+                            format!("{} and {}: {}", first, second, conflict.reason),
+                            vec![first.to_string(), second.to_string()])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_evidence_set;
+    use crate::validation::Severity;
+
+    #[test]
+    fn each_conflict_is_reported_for_its_pair_of_codes() {
+        for conflict in CONFLICTS {
+            let (first, second) = conflict.codes;
+            let evidence_set = parse_evidence_set(&format!("{}, {}", first, second)).unwrap();
+            let diagnostics = check_conflicts(&evidence_set);
+            assert_eq!(diagnostics.len(), 1, "{} {}", first, second);
+            assert_eq!(diagnostics[0].rule, conflict.rule);
+            assert_eq!(diagnostics[0].codes, vec![first.to_string(), second.to_string()]);
+            assert!(diagnostics[0].message.ends_with(conflict.reason));
+        }
+    }
+
+    #[test]
+    fn conflicts_apply_whatever_the_strength_of_the_codes() {
+        let evidence_set = parse_evidence_set("PVS1_Moderate, PM4_Supporting").unwrap();
+        assert_eq!(check_conflicts(&evidence_set)[0].rule, Rule::DoubleCounting);
+    }
+
+    #[test]
+    fn codes_which_may_be_applied_together_are_not_reported() {
+        for evidence in ["PVS1, PM2, PS3", "PS3, BS1", "PM2, BP4", "BA1, BS1, BP4"] {
+            assert!(check_conflicts(&parse_evidence_set(evidence).unwrap()).is_empty(), "{}", evidence);
+        }
+    }
+
+    #[test]
+    fn double_counting_and_contradictions_are_warnings() {
+        assert!(CONFLICTS.iter().all(|conflict| conflict.rule.severity() == Severity::Warning));
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::AcmgError;
+
+/// The gnomAD constraint metrics of a gene.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstraintMetrics {
+    /// The probability of being loss of function intolerant.
+    pub pli: Option<f64>,
+    /// The upper bound of the observed/expected ratio of loss of function variants, the LOEUF.
+    pub loeuf: Option<f64>,
+    pub missense_z: Option<f64>,
+}
+
+/// A curated call of the constraint of a gene taking precedence over its metrics, e.g. `TTN=no-lof`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneOverride {
+    pub gene: String,
+    /// Whether loss of function is a mechanism of disease, from `lof` or `no-lof`.
+    pub lof: Option<bool>,
+    /// Whether the gene is constrained for missense variation, from `missense` or `no-missense`.
+    pub missense: Option<bool>,
+}
+
+impl FromStr for GeneOverride {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<GeneOverride, Self::Err> {
+        let invalid = || AcmgError::InvalidConstraint(format!("invalid override '{}', expected GENE=lof, no-lof, missense or no-missense", s));
+        let (gene, call) = s.split_once('=').ok_or_else(invalid)?;
+        let (lof, missense) = match call.trim().to_lowercase().as_str() {
+            "lof" => (Some(true), None),
+            "no-lof" => (Some(false), None),
+            "missense" => (None, Some(true)),
+            "no-missense" => (None, Some(false)),
+            _ => return Err(invalid()),
+        };
+        match gene.trim() {
+            "" => Err(invalid()),
+            gene => Ok(GeneOverride { gene: gene.to_string(), lof, missense }),
+        }
+    }
+}
+
+/// Whether a gene is constrained, and why, e.g. `constrained for loss of function (LOEUF 0.21, pLI 1)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConstraintCall {
+    pub constrained: bool,
+    pub reason: String,
+}
+
+impl Display for ConstraintCall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+/// The constraint of genes against loss of function and missense variation, from the gnomAD constraint metrics and
+/// curated overrides, deciding whether PVS1, PP2 and BP1 apply in a gene.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneConstraint {
+    pub metrics: HashMap<String, ConstraintMetrics>,
+    /// Curated calls of whether loss of function is a mechanism of disease in a gene.
+    pub lof_overrides: HashMap<String, bool>,
+    /// Curated calls of whether a gene is constrained for missense variation.
+    pub missense_overrides: HashMap<String, bool>,
+    /// The pLI from which a gene is constrained for loss of function.
+    pub min_pli: f64,
+    /// The LOEUF below which a gene is constrained for loss of function.
+    pub max_loeuf: f64,
+    /// The missense Z score from which a gene is constrained for missense variation, for PP2.
+    pub pp2_min_z: f64,
+    /// The missense Z score up to which a gene isn't constrained for missense variation, for BP1.
+    pub bp1_max_z: f64,
+}
+
+/// A pLI of at least 0.9 or a LOEUF below 0.6 as gnomAD v4 recommends, PP2 from a missense Z score of 3.09, the
+/// gnomAD threshold of significant constraint, and BP1 up to 0.
+impl Default for GeneConstraint {
+    fn default() -> GeneConstraint {
+        GeneConstraint {
+            metrics: HashMap::new(),
+            lof_overrides: HashMap::new(),
+            missense_overrides: HashMap::new(),
+            min_pli: 0.9,
+            max_loeuf: 0.6,
+            pp2_min_z: 3.09,
+            bp1_max_z: 0.0,
+        }
+    }
+}
+
+impl GeneConstraint {
+    /// Reads the genes where loss of function is a mechanism of disease, one per line, e.g. the ClinGen dosage
+    /// sensitivity genes with a haploinsufficiency score of 3. Lines starting with `#` are comments.
+    pub fn with_lof_genes(mut self, reader: impl BufRead) -> Result<GeneConstraint, AcmgError> {
+        for line in reader.lines() {
+            let line = line.map_err(|error| AcmgError::InvalidConstraint(error.to_string()))?;
+            let gene = line.split('\t').next().unwrap_or_default().trim();
+            if !gene.is_empty() && !gene.starts_with('#') {
+                self.lof_overrides.insert(gene.to_string(), true);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Reads the gnomAD constraint metrics TSV, with a `gene` column and any of the pLI, LOEUF and missense Z score
+    /// columns of gnomAD v2 (`pLI`, `oe_lof_upper` and `mis_z`) or v4 (`lof.pLI`, `lof.oe_ci.upper` and
+    /// `mis.z_score`). Of a table with a row per transcript, only the canonical transcripts are read.
+    pub fn with_metrics(mut self, reader: impl BufRead) -> Result<GeneConstraint, AcmgError> {
+        let mut lines = reader.lines();
+        let header = match lines.next() {
+            Some(line) => line.map_err(|error| AcmgError::InvalidConstraint(error.to_string()))?,
+            None => return Err(AcmgError::InvalidConstraint("empty constraint metrics".to_string())),
+        };
+        let columns: Vec<&str> = header.split('\t').collect();
+        let column = |names: &[&str]| columns.iter().position(|column| names.contains(column));
+        let (pli, loeuf, missense_z) = (column(&["pLI", "lof.pLI"]), column(&["oe_lof_upper", "lof.oe_ci.upper"]), column(&["mis_z", "mis.z_score"]));
+        let Some(gene) = column(&["gene"]).filter(|_| pli.is_some() || loeuf.is_some() || missense_z.is_some()) else {
+            return Err(AcmgError::InvalidConstraint("expected a gene column and pLI, LOEUF or missense Z score columns".to_string()));
+        };
+        let canonical = column(&["canonical"]);
+        for line in lines {
+            let line = line.map_err(|error| AcmgError::InvalidConstraint(error.to_string()))?;
+            let fields: Vec<&str> = line.split('\t').collect();
+            if canonical.is_some_and(|canonical| fields.get(canonical) != Some(&"true")) {
+                continue;
+            }
+            let value = |index: Option<usize>| index.and_then(|index| fields.get(index)).and_then(|value| value.parse::<f64>().ok());
+            if let Some(gene) = fields.get(gene).filter(|gene| !gene.is_empty()) {
+                let metrics = ConstraintMetrics { pli: value(pli), loeuf: value(loeuf), missense_z: value(missense_z) };
+                self.metrics.entry(gene.to_string()).or_insert(metrics);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Applies a curated call, taking precedence over the metrics of the gene.
+    pub fn with_override(mut self, gene_override: GeneOverride) -> GeneConstraint {
+        if let Some(lof) = gene_override.lof {
+            self.lof_overrides.insert(gene_override.gene.clone(), lof);
+        }
+        if let Some(missense) = gene_override.missense {
+            self.missense_overrides.insert(gene_override.gene, missense);
+        }
+        self
+    }
+
+    /// Whether loss of function is a mechanism of disease in the gene, as curated or from a LOEUF or pLI in the
+    /// constrained range, or None if nothing is known of the gene.
+    pub fn lof_constrained(&self, gene: &str) -> Option<ConstraintCall> {
+        if let Some(&constrained) = self.lof_overrides.get(gene) {
+            let reason = if constrained { "curated as a gene where loss of function is a mechanism of disease" } else { "curated as a gene where loss of function isn't a mechanism of disease" };
+            return Some(ConstraintCall { constrained, reason: reason.to_string() });
+        }
+        let metrics = self.metrics.get(gene).filter(|metrics| metrics.loeuf.is_some() || metrics.pli.is_some())?;
+        let constrained = metrics.loeuf.is_some_and(|loeuf| loeuf < self.max_loeuf) || metrics.pli.is_some_and(|pli| pli >= self.min_pli);
+        let scores: Vec<String> = [("LOEUF", metrics.loeuf), ("pLI", metrics.pli)].into_iter()
+            .filter_map(|(name, score)| score.map(|score| format!("{} {}", name, score)))
+            .collect();
+        let reason = format!("{} for loss of function ({})", if constrained { "constrained" } else { "not constrained" }, scores.join(", "));
+        Some(ConstraintCall { constrained, reason })
+    }
+
+    /// Whether the gene is constrained for missense variation, as curated or from a missense Z score of at least
+    /// `pp2_min_z`, or not from one of at most `bp1_max_z`. None if nothing is known of the gene or its score is in
+    /// between.
+    pub fn missense_constrained(&self, gene: &str) -> Option<ConstraintCall> {
+        if let Some(&constrained) = self.missense_overrides.get(gene) {
+            let reason = if constrained { "curated as constrained for missense variation" } else { "curated as not constrained for missense variation" };
+            return Some(ConstraintCall { constrained, reason: reason.to_string() });
+        }
+        match self.metrics.get(gene).and_then(|metrics| metrics.missense_z)? {
+            z if z >= self.pp2_min_z => Some(ConstraintCall { constrained: true, reason: format!("constrained for missense variation (Z {})", z) }),
+            z if z <= self.bp1_max_z => Some(ConstraintCall { constrained: false, reason: format!("not constrained for missense variation (Z {})", z) }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::EvidenceStrength::{Moderate, Strong, Supporting, VeryStrong};
+use crate::{AcmgError, Code, Evidence, EvidenceStrength};
+
+/// How consistent the phenotype of an individual with a de novo variant is with the gene.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum PhenotypeConsistency {
+    /// The phenotype is highly specific for the gene.
+    HighlySpecific,
+    /// The phenotype is consistent with the gene but not highly specific.
+    #[default]
+    Consistent,
+    /// The phenotype is consistent with the gene but not highly specific, and the disorder has high genetic
+    /// heterogeneity.
+    Heterogeneous,
+    /// The phenotype is not consistent with the gene.
+    Inconsistent,
+}
+
+impl PhenotypeConsistency {
+    pub const ALL: [PhenotypeConsistency; 4] = [
+        PhenotypeConsistency::HighlySpecific, PhenotypeConsistency::Consistent,
+        PhenotypeConsistency::Heterogeneous, PhenotypeConsistency::Inconsistent,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PhenotypeConsistency::HighlySpecific => "highly-specific",
+            PhenotypeConsistency::Consistent => "consistent",
+            PhenotypeConsistency::Heterogeneous => "heterogeneous",
+            PhenotypeConsistency::Inconsistent => "inconsistent",
+        }
+    }
+
+    /// Points for a confirmed de novo observation, assumed observations score half.
+    fn confirmed_points(&self) -> f64 {
+        match self {
+            PhenotypeConsistency::HighlySpecific => 2.0,
+            PhenotypeConsistency::Consistent => 1.0,
+            PhenotypeConsistency::Heterogeneous => 0.5,
+            PhenotypeConsistency::Inconsistent => 0.0,
+        }
+    }
+}
+
+impl Display for PhenotypeConsistency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for PhenotypeConsistency {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<PhenotypeConsistency, Self::Err> {
+        PhenotypeConsistency::ALL.iter()
+            .find(|consistency| consistency.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| AcmgError::UnknownPhenotypeConsistency(s.to_string()))
+    }
+}
+
+/// De novo observations of a variant in affected individuals, scored according to the ClinGen SVI
+/// recommendation for de novo criteria (version 1.1).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeNovoObservations {
+    /// Observations with both maternity and paternity confirmed.
+    pub confirmed: u32,
+    /// Observations with maternity and paternity assumed.
+    pub assumed: u32,
+    pub phenotype: PhenotypeConsistency,
+}
+
+impl DeNovoObservations {
+    /// The total de novo points, from 2 points per confirmed observation with a highly specific phenotype down
+    /// to none with an inconsistent one. Assumed observations score half a confirmed one.
+    pub fn points(&self) -> f64 {
+        let points = self.phenotype.confirmed_points();
+        points * self.confirmed as f64 + 0.5 * points * self.assumed as f64
+    }
+
+    /// The recommended strength - Supporting from 0.5 points, Moderate from 1, Strong from 2 and Very Strong
+    /// from 4, or None if there are too few points to apply PS2 or PM6.
+    pub fn strength(&self) -> Option<EvidenceStrength> {
+        match self.points() {
+            points if points >= 4.0 => Some(VeryStrong),
+            points if points >= 2.0 => Some(Strong),
+            points if points >= 1.0 => Some(Moderate),
+            points if points >= 0.5 => Some(Supporting),
+            _ => None,
+        }
+    }
+
+    /// PS2 if any observation is confirmed otherwise PM6, at the recommended strength.
+    pub fn evidence(&self) -> Option<Evidence> {
+        let code = if self.confirmed > 0 { Code::PS2 } else { Code::PM6 };
+        self.strength().map(|strength| Evidence::at_strength(code, strength))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observations(confirmed: u32, assumed: u32, phenotype: PhenotypeConsistency) -> DeNovoObservations {
+        DeNovoObservations { confirmed, assumed, phenotype }
+    }
+
+    #[test]
+    fn confirmed_points_depend_on_the_phenotype_and_assumed_score_half() {
+        let cases = [
+            (PhenotypeConsistency::HighlySpecific, 2.0),
+            (PhenotypeConsistency::Consistent, 1.0),
+            (PhenotypeConsistency::Heterogeneous, 0.5),
+            (PhenotypeConsistency::Inconsistent, 0.0),
+        ];
+        for (phenotype, points) in cases {
+            assert_eq!(observations(1, 0, phenotype).points(), points, "{}", phenotype);
+            assert_eq!(observations(0, 1, phenotype).points(), points / 2.0, "{}", phenotype);
+            assert_eq!(observations(2, 2, phenotype).points(), 3.0 * points, "{}", phenotype);
+        }
+    }
+
+    #[test]
+    fn confirmed_observations_apply_ps2_and_assumed_pm6() {
+        let applied = |observations: DeNovoObservations| observations.evidence().map(|evidence| (evidence.evidence_code.name.parse::<Code>().unwrap().as_str(), evidence.strength()));
+        assert_eq!(applied(observations(1, 0, PhenotypeConsistency::HighlySpecific)), Some(("PS2", Strong)));
+        assert_eq!(applied(observations(2, 0, PhenotypeConsistency::HighlySpecific)), Some(("PS2", VeryStrong)));
+        assert_eq!(applied(observations(1, 0, PhenotypeConsistency::Consistent)), Some(("PS2", Moderate)));
+        assert_eq!(applied(observations(1, 0, PhenotypeConsistency::Heterogeneous)), Some(("PS2", Supporting)));
+        assert_eq!(applied(observations(1, 1, PhenotypeConsistency::Heterogeneous)), Some(("PS2", Supporting)));
+        assert_eq!(applied(observations(0, 1, PhenotypeConsistency::HighlySpecific)), Some(("PM6", Moderate)));
+        assert_eq!(applied(observations(0, 2, PhenotypeConsistency::HighlySpecific)), Some(("PM6", Strong)));
+        assert_eq!(applied(observations(0, 1, PhenotypeConsistency::Consistent)), Some(("PM6", Supporting)));
+        assert_eq!(applied(observations(0, 1, PhenotypeConsistency::Heterogeneous)), None);
+        assert_eq!(applied(observations(3, 3, PhenotypeConsistency::Inconsistent)), None);
+    }
+}
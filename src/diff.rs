@@ -0,0 +1,141 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{AcmgClassification, AssessmentResult, Evidence};
+
+/// How a criterion changed between two assessments of a variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "change", rename_all = "snake_case"))]
+pub enum CriterionChange {
+    /// Evidence applied only by the later assessment, adding `points` under its point system.
+    Added { evidence: Evidence, points: i32 },
+    /// Evidence applied only by the earlier assessment, taking away the points it scored under its point system.
+    Removed { evidence: Evidence, points: i32 },
+    /// The code was applied at a different strength, or scored under a different point system, changing the score
+    /// by `points`.
+    Reweighted { from: Evidence, to: Evidence, points: i32 },
+}
+
+impl Display for CriterionChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CriterionChange::Added { evidence, points } => write!(f, "Added {} ({:+} points)", evidence, points),
+            CriterionChange::Removed { evidence, points } => write!(f, "Removed {} ({:+} points)", evidence, points),
+            CriterionChange::Reweighted { from, to, points } => {
+                write!(f, "Re-weighted {} from {:?} to {:?} ({:+} points)", from.evidence_code, from.strength(), to.strength(), points)
+            }
+        }
+    }
+}
+
+/// The differences between an earlier and a later assessment of a variant, e.g. on reanalysis.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AssessmentDiff {
+    /// The changed criteria in code order.
+    pub changes: Vec<CriterionChange>,
+    pub old_points: i32,
+    pub new_points: i32,
+    pub old_classification: AcmgClassification,
+    pub new_classification: AcmgClassification,
+}
+
+impl AssessmentDiff {
+    /// Pairs up the evidence of the two assessments by code, so a code applied at another strength is re-weighted
+    /// rather than removed and added. Notes are ignored. The points of each change are scored under the point system
+    /// of the assessment the evidence is from.
+    pub fn between(old: &AssessmentResult, new: &AssessmentResult) -> AssessmentDiff {
+        let (old_system, new_system) = (old.point_system.unwrap_or_default(), new.point_system.unwrap_or_default());
+        let mut added: Vec<&Evidence> = new.evidence.iter().collect();
+        let mut changes = vec![];
+        for evidence in &old.evidence {
+            match added.iter().position(|other| other.evidence_code == evidence.evidence_code) {
+                Some(index) => {
+                    let other = added.remove(index);
+                    let points = other.points_with(&new_system) - evidence.points_with(&old_system);
+                    if other.strength() != evidence.strength() || points != 0 {
+                        changes.push(CriterionChange::Reweighted { from: evidence.clone(), to: other.clone(), points });
+                    }
+                }
+                None => changes.push(CriterionChange::Removed { evidence: evidence.clone(), points: -evidence.points_with(&old_system) }),
+            }
+        }
+        changes.extend(added.into_iter().map(|evidence| CriterionChange::Added { evidence: evidence.clone(), points: evidence.points_with(&new_system) }));
+        changes.sort_by_key(|change| match change {
+            CriterionChange::Added { evidence, .. } | CriterionChange::Removed { evidence, .. } | CriterionChange::Reweighted { from: evidence, .. } => evidence.evidence_code.clone(),
+        });
+        AssessmentDiff {
+            changes,
+            old_points: old.points,
+            new_points: new.points,
+            old_classification: old.classification,
+            new_classification: new.classification,
+        }
+    }
+
+    pub fn classification_changed(&self) -> bool {
+        self.old_classification != self.new_classification
+    }
+
+    /// True if neither the criteria nor the classification changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && !self.classification_changed()
+    }
+}
+
+impl Display for AssessmentDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        let points = if self.old_points == self.new_points {
+            format!("{} points", self.new_points)
+        } else {
+            format!("{} to {} points", self.old_points, self.new_points)
+        };
+        if self.classification_changed() {
+            write!(f, "Classification changed from {} to {} ({})", self.old_classification.term(), self.new_classification.term(), points)
+        } else {
+            write!(f, "Classification unchanged: {} ({})", self.new_classification.term(), points)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AcmgAssessment, PointSystem};
+
+    fn result(evidence: &str, system: PointSystem) -> AssessmentResult {
+        evidence.parse::<AcmgAssessment>().unwrap().with_point_system(system).unwrap().result()
+    }
+
+    #[test]
+    fn change_points_add_up_to_the_change_in_score() {
+        let moderate_3 = PointSystem { moderate: 3, ..PointSystem::TAVTIGIAN_2020 };
+        let cases = [
+            ("PVS1, PM2", PointSystem::TAVTIGIAN_2020, "PVS1, PM2_Supporting, PP3", PointSystem::TAVTIGIAN_2020),
+            ("PVS1, PM2, BS1", moderate_3, "PVS1_Strong, PM1, PP3", moderate_3),
+            ("PM1, PM2", PointSystem::TAVTIGIAN_2020, "PM1, PM2", moderate_3),
+        ];
+        for (old, old_system, new, new_system) in cases {
+            let (old, new) = (result(old, old_system), result(new, new_system));
+            let diff = AssessmentDiff::between(&old, &new);
+            let points: i32 = diff.changes.iter()
+                .map(|change| match change {
+                    CriterionChange::Added { points, .. } | CriterionChange::Removed { points, .. } | CriterionChange::Reweighted { points, .. } => *points,
+                })
+                .sum();
+            assert_eq!(points, new.points - old.points, "{}", diff);
+        }
+    }
+
+    #[test]
+    fn changes_are_displayed_with_the_points_of_the_point_system() {
+        let moderate_3 = PointSystem { moderate: 3, ..PointSystem::TAVTIGIAN_2020 };
+        let diff = AssessmentDiff::between(&result("PVS1, PM2_Supporting", moderate_3), &result("PVS1, PM2, PM1", moderate_3));
+        let changes: Vec<String> = diff.changes.iter().map(ToString::to_string).collect();
+        assert_eq!(changes, vec!["Added PM1 (+3 points)", "Re-weighted PM2 from Supporting to Moderate (+2 points)"]);
+        assert_eq!((diff.old_points, diff.new_points), (9, 14));
+    }
+}
@@ -0,0 +1,92 @@
+use thiserror::Error;
+
+use crate::Diagnostic;
+
+/// Errors arising from parsing and assessing ACMG evidence.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AcmgError {
+    #[error("No evidence codes given")]
+    EmptyInput,
+    #[error("Unable to parse evidence code '{0}'")]
+    InvalidSyntax(String),
+    #[error("Unknown evidence code {code}{}", .suggestion.as_ref().map(|s| format!(", did you mean {}?", s)).unwrap_or_default())]
+    UnknownCode { code: String, suggestion: Option<String> },
+    #[error("Invalid modifier '{modifier}' for evidence code {code}")]
+    InvalidModifier { code: String, modifier: String },
+    #[error("Invalid citation '{0}', expected a PMID such as PMID:30311383 or a DOI such as doi:10.1016/j.ajhg.2018.09.012")]
+    InvalidCitation(String),
+    #[error("Invalid category: {0}")]
+    InvalidCategory(String),
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+    #[error("Unknown guideline {0}")]
+    UnknownGuideline(String),
+    #[error("Unknown classifier {0}")]
+    UnknownClassifier(String),
+    #[error("Invalid strength value: {0}")]
+    InvalidStrength(String),
+    #[error("Evidence {0} was given more than once")]
+    DuplicateCode(String),
+    #[error("{code} was given more than once at different strengths: {applied}")]
+    RepeatedCriterion { code: String, applied: String },
+    #[error("Unknown duplicate policy {0}, expected error, keep-strongest or keep-first")]
+    UnknownDuplicatePolicy(String),
+    #[error("{code} is not applicable under the {spec} specification")]
+    DisallowedCode { code: String, spec: String },
+    #[error("{evidence} is not an allowed strength under the {spec} specification")]
+    DisallowedStrength { evidence: String, spec: String },
+    #[error("Invalid definition of evidence code {code}: {reason}")]
+    InvalidCodeDefinition { code: String, reason: String },
+    #[error("Invalid evidence alias {alias}: {reason}")]
+    InvalidAlias { alias: String, reason: String },
+    #[error("{code} is disabled: {reason}")]
+    DisabledCode { code: String, reason: String },
+    #[error("No built-in specification for {0}")]
+    UnknownSpec(String),
+    #[error("Invalid specification: {0}")]
+    InvalidSpec(String),
+    #[error("Unknown variant type {0}")]
+    UnknownVariantType(String),
+    #[error("Unknown predictor {0}")]
+    UnknownPredictor(String),
+    #[error("{code} was given alongside {origin}, which determines it")]
+    DerivedCodeGiven { code: String, origin: String },
+    #[error("Unknown phenotype consistency {0}")]
+    UnknownPhenotypeConsistency(String),
+    #[error("Unable to parse variant '{0}', expected VCF coordinates or HGVS with a gene")]
+    InvalidVariant(String),
+    #[error("Invalid HGVS '{hgvs}': {reason}")]
+    InvalidHgvs { hgvs: String, reason: String },
+    #[error("Unknown genome build {0}")]
+    UnknownBuild(String),
+    #[error("Unknown mode of inheritance {0}, expected AD, AR, XLR, XLD or MT")]
+    UnknownInheritance(String),
+    #[error("Unknown CNV type {0}")]
+    UnknownCnvType(String),
+    #[error("Unknown evidence level {0}, expected A, B, C or D")]
+    UnknownEvidenceLevel(String),
+    #[error("Invalid ClinVar variant summary: {0}")]
+    InvalidClinVar(String),
+    #[error("Invalid carrier count '{0}', expected carriers/total e.g. 12/800")]
+    InvalidCarrierCount(String),
+    #[error("Invalid BA1 exception list: {0}")]
+    InvalidBa1Exceptions(String),
+    #[error("Invalid BED file: {0}")]
+    InvalidBed(String),
+    #[error("Invalid PED file: {0}")]
+    InvalidPed(String),
+    #[error("Invalid ClinGen curations: {0}")]
+    InvalidClinGen(String),
+    #[error("Invalid gene constraint: {0}")]
+    InvalidConstraint(String),
+    #[error("Invalid VCF: {0}")]
+    InvalidVcf(String),
+    #[error("Invalid genotype '{0}', expected e.g. 0/1, 1|1, het or hom-ref")]
+    InvalidGenotype(String),
+    #[error("Invalid variant index {0}")]
+    InvalidIndex(String),
+    #[error("Lookup failed: {0}")]
+    Lookup(String),
+    #[error("{}", .0.iter().map(|diagnostic| diagnostic.to_string()).collect::<Vec<_>>().join("; "))]
+    Diagnostics(Vec<Diagnostic>),
+}
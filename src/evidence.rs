@@ -0,0 +1,525 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::Category::{Benign, Pathogenic};
+#[cfg(feature = "serde")]
+use crate::code::unknown_code;
+use crate::registry::RegisteredCode;
+use crate::{AcmgError, Citation, Code, PointSystem};
+use crate::EvidenceStrength::{Moderate, StandAlone, Strong, Supporting, VeryStrong};
+
+/// Direction of an evidence code - towards pathogenic or benign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum Category {
+    Pathogenic,
+    Benign,
+}
+
+impl FromStr for Category {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<Category, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "P" | "PATHOGENIC" => Ok(Pathogenic),
+            "B" | "BENIGN" => Ok(Benign),
+            _ => Err(AcmgError::InvalidCategory(s.to_string())),
+        }
+    }
+}
+
+/// Strength of an evidence code, either its default strength or a modified one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum EvidenceStrength {
+    StandAlone,
+    VeryStrong,
+    Strong,
+    Moderate,
+    Supporting,
+}
+
+impl EvidenceStrength {
+    /// Points awarded for this strength under the Tavtigian et al. 2020 point system.
+    pub fn points(&self) -> i32 {
+        PointSystem::TAVTIGIAN_2020.points(*self)
+    }
+}
+
+impl FromStr for EvidenceStrength {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<EvidenceStrength, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "A" | "STANDALONE" => Ok(StandAlone),
+            "VS" | "VERYSTRONG" => Ok(VeryStrong),
+            "S" | "STRONG" => Ok(Strong),
+            "M" | "MODERATE" => Ok(Moderate),
+            "P" | "SUPPORTING" => Ok(Supporting),
+            _ => Err(AcmgError::InvalidStrength(s.to_string())),
+        }
+    }
+}
+
+/// An ACMG/AMP evidence code as defined in Richards et al. 2015, e.g. PVS1, or one registered in a
+/// [`CodeRegistry`](crate::CodeRegistry).
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EvidenceCode {
+    pub category: Category,
+    pub strength: EvidenceStrength,
+    pub code: i32,
+    /// The name of the code, e.g. PVS1, which for built-in codes is made up of the category, strength and number.
+    pub name: Cow<'static, str>,
+    pub description: Cow<'static, str>,
+}
+
+impl EvidenceCode {
+    /// Points for this code at its default strength - positive for pathogenic, negative for benign.
+    pub fn points(&self) -> i32 {
+        let points = self.strength.points();
+        if self.category == Pathogenic { points } else { -points }
+    }
+}
+
+impl Display for EvidenceCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&self.name)
+    }
+}
+
+/// The definition of the code evidence is applied with, either a built-in code or one registered in a
+/// [`CodeRegistry`](crate::CodeRegistry), which is shared by all the evidence applying it. It dereferences to the
+/// [`EvidenceCode`].
+#[derive(Debug, Clone)]
+pub struct CodeRef(Definition);
+
+#[derive(Debug, Clone)]
+enum Definition {
+    Builtin(&'static EvidenceCode),
+    Registered(Arc<RegisteredCode>),
+}
+
+impl CodeRef {
+    pub(crate) fn registered(code: Arc<RegisteredCode>) -> CodeRef {
+        CodeRef(Definition::Registered(code))
+    }
+
+    /// The strengths a registered code may be applied at, unless it may be applied at any strength or is built in.
+    pub fn allowed_strengths(&self) -> Option<&[EvidenceStrength]> {
+        match &self.0 {
+            Definition::Builtin(_) => None,
+            Definition::Registered(code) => Some(code.allowed_strengths.as_slice()).filter(|allowed| !allowed.is_empty()),
+        }
+    }
+}
+
+impl Deref for CodeRef {
+    type Target = EvidenceCode;
+
+    fn deref(&self) -> &EvidenceCode {
+        match &self.0 {
+            Definition::Builtin(code) => code,
+            Definition::Registered(code) => &code.evidence_code,
+        }
+    }
+}
+
+impl From<&'static EvidenceCode> for CodeRef {
+    fn from(code: &'static EvidenceCode) -> CodeRef {
+        CodeRef(Definition::Builtin(code))
+    }
+}
+
+impl PartialEq for CodeRef {
+    fn eq(&self, other: &CodeRef) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for CodeRef {}
+
+impl PartialEq<&EvidenceCode> for CodeRef {
+    fn eq(&self, other: &&EvidenceCode) -> bool {
+        **self == **other
+    }
+}
+
+impl PartialEq<CodeRef> for &EvidenceCode {
+    fn eq(&self, other: &CodeRef) -> bool {
+        **self == **other
+    }
+}
+
+impl PartialOrd for CodeRef {
+    fn partial_cmp(&self, other: &CodeRef) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CodeRef {
+    fn cmp(&self, other: &CodeRef) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl Display for CodeRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// The name of the built-in code of the category, strength and number, e.g. PVS1.
+#[cfg(feature = "serde")]
+fn builtin_name(category: Category, strength: EvidenceStrength, code: i32) -> String {
+    let category = match category {
+        Pathogenic => "P",
+        Benign => "B",
+    };
+    let strength = match strength {
+        StandAlone => "A",
+        VeryStrong => "VS",
+        Strong => "S",
+        Moderate => "M",
+        Supporting => "P",
+    };
+    format!("{}{}{}", category, strength, code)
+}
+
+/// An applied evidence code, optionally with a modified strength e.g. PM2_Supporting.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct Evidence {
+    #[cfg_attr(feature = "serde", serde(rename = "code", deserialize_with = "code_name::deserialize"))]
+    pub evidence_code: CodeRef,
+    pub modifier: Option<EvidenceStrength>,
+    /// Where the evidence comes from, e.g. `PMID:12345; MAVE assay`, for auditing the assertion.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub note: Option<String>,
+    /// The publications cited for the evidence, e.g. `PS3[PMID:30311383]`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub citations: Vec<Citation>,
+}
+
+/// Evidence is compared by its code and modifier alone, so the same evidence with different notes or citations is
+/// still a duplicate.
+impl PartialEq for Evidence {
+    fn eq(&self, other: &Evidence) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Evidence {}
+
+impl PartialOrd for Evidence {
+    fn partial_cmp(&self, other: &Evidence) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Evidence {
+    fn cmp(&self, other: &Evidence) -> Ordering {
+        (&self.evidence_code, self.modifier).cmp(&(&other.evidence_code, other.modifier))
+    }
+}
+
+impl Display for Evidence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.modifier {
+            Some(modifier) => f.pad(&format!("{}_{:?}", self.evidence_code, modifier)),
+            None => f.pad(&format!("{}", self.evidence_code)),
+        }
+    }
+}
+
+impl Evidence {
+    pub fn new(evidence_code: impl Into<CodeRef>, modifier: Option<EvidenceStrength>) -> Evidence {
+        Evidence { evidence_code: evidence_code.into(), modifier, note: None, citations: vec![] }
+    }
+
+    /// Annotates the evidence with where it comes from.
+    pub fn with_note(mut self, note: impl Into<String>) -> Evidence {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Cites a publication for the evidence.
+    pub fn with_citation(mut self, citation: Citation) -> Evidence {
+        self.citations.push(citation);
+        self
+    }
+
+    /// The evidence applied at the given strength, without a modifier if that is the default strength of the code.
+    pub fn at_strength(code: Code, strength: EvidenceStrength) -> Evidence {
+        let evidence_code = code.evidence_code();
+        Evidence::new(evidence_code, Some(strength).filter(|strength| *strength != evidence_code.strength))
+    }
+
+    /// Points contributed by this evidence - positive for pathogenic, negative for benign.
+    pub fn points(&self) -> i32 {
+        self.points_with(&PointSystem::TAVTIGIAN_2020)
+    }
+
+    /// Points contributed by this evidence under a point system, e.g. the one an assessment was scored with.
+    pub fn points_with(&self, system: &PointSystem) -> i32 {
+        let points = system.points(self.strength());
+        if self.evidence_code.category == Pathogenic { points } else { -points }
+    }
+
+    /// The applied strength, i.e. the modifier if present otherwise the default strength of the code.
+    pub fn strength(&self) -> EvidenceStrength {
+        self.modifier.unwrap_or(self.evidence_code.strength)
+    }
+
+    /// True if the applied strength is worth fewer points than the default strength of the code.
+    pub fn is_downgraded(&self) -> bool {
+        self.strength().points() < self.evidence_code.strength.points()
+    }
+
+    /// True if the applied strength is worth more points than the default strength of the code.
+    pub fn is_upgraded(&self) -> bool {
+        self.strength().points() > self.evidence_code.strength.points()
+    }
+}
+
+impl From<Code> for Evidence {
+    fn from(code: Code) -> Evidence {
+        Evidence::new(code.evidence_code(), None)
+    }
+}
+
+/// Looks up a built-in evidence code by name, e.g. `evidence_code("PVS1")`. This can be used in constants.
+pub const fn evidence_code(name: &str) -> Option<&'static EvidenceCode> {
+    let (mut low, mut high) = (0, EVIDENCE_CODES.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        match compare(EVIDENCE_CODES[mid].0, name) {
+            Ordering::Less => low = mid + 1,
+            Ordering::Greater => high = mid,
+            Ordering::Equal => return Some(&EVIDENCE_CODES[mid].1),
+        }
+    }
+    None
+}
+
+/// The built-in evidence codes in display order, pathogenic codes first. Use
+/// [`CodeRegistry::evidence_codes`](crate::CodeRegistry::evidence_codes) for them along with any registered codes.
+pub fn evidence_codes() -> Vec<&'static EvidenceCode> {
+    let mut codes: Vec<&'static EvidenceCode> = EVIDENCE_CODES.iter().map(|(_, code)| code).collect();
+    codes.sort();
+    codes
+}
+
+/// The names of the built-in codes, for suggesting the closest to an unknown code.
+#[cfg(feature = "serde")]
+fn code_names() -> impl Iterator<Item = &'static str> {
+    EVIDENCE_CODES.iter().map(|(name, _)| *name)
+}
+
+/// Evidence is serialized along with its applied strength and points, which are ignored when deserializing. The
+/// points are those of the default point system, see [`ScoredEvidence`] for those of another.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Evidence {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&ScoredEvidence(self, &PointSystem::TAVTIGIAN_2020), serializer)
+    }
+}
+
+/// Evidence serialized with its points under a point system, e.g. that of an assessment's result.
+#[cfg(feature = "serde")]
+pub(crate) struct ScoredEvidence<'a>(pub &'a Evidence, pub &'a PointSystem);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ScoredEvidence<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let ScoredEvidence(evidence, system) = self;
+        let mut state = serializer.serialize_struct("Evidence", 6)?;
+        state.serialize_field("code", &evidence.evidence_code.to_string())?;
+        state.serialize_field("modifier", &evidence.modifier)?;
+        state.serialize_field("strength", &evidence.strength())?;
+        state.serialize_field("points", &evidence.points_with(system))?;
+        match &evidence.note {
+            Some(note) => state.serialize_field("note", note)?,
+            None => state.skip_field("note")?,
+        }
+        if evidence.citations.is_empty() {
+            state.skip_field("citations")?;
+        } else {
+            state.serialize_field("citations", &evidence.citations)?;
+        }
+        state.end()
+    }
+}
+
+/// The OpenAPI schema of [`Evidence`]: an evidence code applied at its default strength or a modifier.
+#[cfg(feature = "openapi")]
+#[derive(utoipa::ToSchema)]
+#[schema(as = Evidence)]
+#[allow(dead_code)]
+struct EvidenceSchema {
+    /// The evidence code, e.g. PM2.
+    code: String,
+    modifier: Option<EvidenceStrength>,
+    strength: EvidenceStrength,
+    points: i32,
+    #[schema(nullable = false)]
+    note: Option<String>,
+    citations: Vec<Citation>,
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for Evidence {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        <EvidenceSchema as utoipa::PartialSchema>::schema()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::ToSchema for Evidence {
+    fn schemas(schemas: &mut Vec<(String, utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>)>) {
+        <EvidenceSchema as utoipa::ToSchema>::schemas(schemas)
+    }
+}
+
+/// Evidence codes are deserialized by looking up the definition of a built-in code from the name, or from the
+/// category, strength and code number, so their descriptions always come from the code table. Other codes, such as
+/// registered codes, are deserialized as they were serialized.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for EvidenceCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<EvidenceCode, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct CodeFields {
+            category: Category,
+            strength: EvidenceStrength,
+            code: i32,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            description: Option<String>,
+        }
+        let fields = CodeFields::deserialize(deserializer)?;
+        let name = fields.name.unwrap_or_else(|| builtin_name(fields.category, fields.strength, fields.code));
+        if let Some(evidence_code) = evidence_code(&name) {
+            return Ok(evidence_code.clone());
+        }
+        match fields.description {
+            Some(description) => Ok(EvidenceCode { category: fields.category, strength: fields.strength, code: fields.code, name: Cow::Owned(name), description: Cow::Owned(description) }),
+            None => Err(serde::de::Error::custom(unknown_code(&name, code_names()))),
+        }
+    }
+}
+
+/// Deserializes a reference to a built-in evidence code from its name, e.g. "PVS1". Evidence applying registered
+/// codes is parsed from its evidence string with the [`CodeRegistry`](crate::CodeRegistry) instead.
+#[cfg(feature = "serde")]
+mod code_name {
+    use serde::{Deserialize, Deserializer};
+
+    use super::{code_names, evidence_code, unknown_code, CodeRef};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<CodeRef, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        evidence_code(&name.to_uppercase())
+            .map(CodeRef::from)
+            .ok_or_else(|| serde::de::Error::custom(unknown_code(&name, code_names())))
+    }
+}
+
+/// The built-in evidence codes, from Tables 3 and 4 of Richards et al. 2015, by name. They are sorted by name for
+/// lookups with a binary search. Use [`evidence_codes`] for them in display order.
+pub static EVIDENCE_CODES: [(&str, EvidenceCode); 28] = [
+    ("BA1", EvidenceCode{category: Benign, strength: StandAlone, code: 1, name: Cow::Borrowed("BA1"), description: Cow::Borrowed("Allele frequency is >5% in Exome Sequencing Project, 1000 Genomes Project, or Exome Aggregation Consortium")}),
+    ("BP1", EvidenceCode{category: Benign, strength: Supporting, code: 1, name: Cow::Borrowed("BP1"), description: Cow::Borrowed("Missense variant in a gene for which primarily truncating variants are known to cause disease")}),
+    ("BP2", EvidenceCode{category: Benign, strength: Supporting, code: 2, name: Cow::Borrowed("BP2"), description: Cow::Borrowed("Observed in trans with a pathogenic variant for a fully penetrant dominant gene/disorder or observed in cis with a pathogenic variant in any inheritance pattern")}),
+    ("BP3", EvidenceCode{category: Benign, strength: Supporting, code: 3, name: Cow::Borrowed("BP3"), description: Cow::Borrowed("In-frame deletions/insertions in a repetitive region without a known function")}),
+    ("BP4", EvidenceCode{category: Benign, strength: Supporting, code: 4, name: Cow::Borrowed("BP4"), description: Cow::Borrowed("Multiple lines of computational evidence suggest no impact on gene or gene product (conservation, evolutionary, splicing impact, etc.)")}),
+    ("BP5", EvidenceCode{category: Benign, strength: Supporting, code: 5, name: Cow::Borrowed("BP5"), description: Cow::Borrowed("Variant found in a case with an alternate molecular basis for disease")}),
+    ("BP6", EvidenceCode{category: Benign, strength: Supporting, code: 6, name: Cow::Borrowed("BP6"), description: Cow::Borrowed("Reputable source recently reports variant as benign, but the evidence is not available to the laboratory to perform an independent evaluation")}),
+    ("BP7", EvidenceCode{category: Benign, strength: Supporting, code: 7, name: Cow::Borrowed("BP7"), description: Cow::Borrowed("A synonymous (silent) variant for which splicing prediction algorithms predict no impact to the splice consensus sequence nor the creation of a new splice site AND the nucleotide is not highly conserved")}),
+    ("BS1", EvidenceCode{category: Benign, strength: Strong, code: 1, name: Cow::Borrowed("BS1"), description: Cow::Borrowed("Allele frequency is greater than expected for disorder")}),
+    ("BS2", EvidenceCode{category: Benign, strength: Strong, code: 2, name: Cow::Borrowed("BS2"), description: Cow::Borrowed("Observed in a healthy adult individual for a recessive (homozygous), dominant (heterozygous), or X-linked (hemizygous) disorder, with full penetrance expected at an early age")}),
+    ("BS3", EvidenceCode{category: Benign, strength: Strong, code: 3, name: Cow::Borrowed("BS3"), description: Cow::Borrowed("Well-established in vitro or in vivo functional studies show no damaging effect on protein function or splicing")}),
+    ("BS4", EvidenceCode{category: Benign, strength: Strong, code: 4, name: Cow::Borrowed("BS4"), description: Cow::Borrowed("Lack of segregation in affected members of a family")}),
+    ("PM1", EvidenceCode{category: Pathogenic, strength: Moderate, code: 1, name: Cow::Borrowed("PM1"), description: Cow::Borrowed("Located in a mutational hot spot and/or critical and well-established functional domain (e.g., active site of an enzyme) without benign variation")}),
+    ("PM2", EvidenceCode{category: Pathogenic, strength: Moderate, code: 2, name: Cow::Borrowed("PM2"), description: Cow::Borrowed("Absent from controls (or at extremely low frequency if recessive) in Exome Sequencing Project, 1000 Genomes Project, or Exome Aggregation Consortium")}),
+    ("PM3", EvidenceCode{category: Pathogenic, strength: Moderate, code: 3, name: Cow::Borrowed("PM3"), description: Cow::Borrowed("For recessive disorders, detected in trans with a pathogenic variant")}),
+    ("PM4", EvidenceCode{category: Pathogenic, strength: Moderate, code: 4, name: Cow::Borrowed("PM4"), description: Cow::Borrowed("Protein length changes as a result of in-frame deletions/insertions in a nonrepeat region or stop-loss variants")}),
+    ("PM5", EvidenceCode{category: Pathogenic, strength: Moderate, code: 5, name: Cow::Borrowed("PM5"), description: Cow::Borrowed("Novel missense change at an amino acid residue where a different missense change determined to be pathogenic has been seen before")}),
+    ("PM6", EvidenceCode{category: Pathogenic, strength: Moderate, code: 6, name: Cow::Borrowed("PM6"), description: Cow::Borrowed("Assumed de novo, but without confirmation of paternity and maternity")}),
+    ("PP1", EvidenceCode{category: Pathogenic, strength: Supporting, code: 1, name: Cow::Borrowed("PP1"), description: Cow::Borrowed("Cosegregation with disease in multiple affected family members in a gene definitively known to cause the disease")}),
+    ("PP2", EvidenceCode{category: Pathogenic, strength: Supporting, code: 2, name: Cow::Borrowed("PP2"), description: Cow::Borrowed("Missense variant in a gene that has a low rate of benign missense variation and in which missense variants are a common mechanism of disease")}),
+    ("PP3", EvidenceCode{category: Pathogenic, strength: Supporting, code: 3, name: Cow::Borrowed("PP3"), description: Cow::Borrowed("Multiple lines of computational evidence support a deleterious effect on the gene or gene product (conservation, evolutionary, splicing impact, etc.)")}),
+    ("PP4", EvidenceCode{category: Pathogenic, strength: Supporting, code: 4, name: Cow::Borrowed("PP4"), description: Cow::Borrowed("Patient’s phenotype or family history is highly specific for a disease with a single genetic etiology")}),
+    ("PP5", EvidenceCode{category: Pathogenic, strength: Supporting, code: 5, name: Cow::Borrowed("PP5"), description: Cow::Borrowed("Reputable source recently reports variant as pathogenic, but the evidence is not available to the laboratory to perform an independent evaluation")}),
+    ("PS1", EvidenceCode{category: Pathogenic, strength: Strong, code: 1, name: Cow::Borrowed("PS1"), description: Cow::Borrowed("Same amino acid change as a previously established pathogenic variant regardless of nucleotide change")}),
+    ("PS2", EvidenceCode{category: Pathogenic, strength: Strong, code: 2, name: Cow::Borrowed("PS2"), description: Cow::Borrowed("De novo (both maternity and paternity confirmed) in a patient with the disease and no family history")}),
+    ("PS3", EvidenceCode{category: Pathogenic, strength: Strong, code: 3, name: Cow::Borrowed("PS3"), description: Cow::Borrowed("Well-established in vitro or in vivo functional studies supportive of a damaging effect on the gene or gene product")}),
+    ("PS4", EvidenceCode{category: Pathogenic, strength: Strong, code: 4, name: Cow::Borrowed("PS4"), description: Cow::Borrowed("The prevalence of the variant in affected individuals is significantly increased compared with the prevalence in controls")}),
+    ("PVS1", EvidenceCode{category: Pathogenic, strength: VeryStrong, code: 1, name: Cow::Borrowed("PVS1"), description: Cow::Borrowed("Null variant (nonsense, frameshift, canonical ±1 or 2 splice sites, initiation codon, single or multiexon deletion) in a gene where LOF is a known mechanism of disease")}),
+];
+
+const _: () = assert!(sorted_by_name(&EVIDENCE_CODES), "EVIDENCE_CODES must be sorted by name");
+
+const fn sorted_by_name(codes: &[(&str, EvidenceCode)]) -> bool {
+    let mut i = 1;
+    while i < codes.len() {
+        if !matches!(compare(codes[i - 1].0, codes[i].0), Ordering::Less) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Compares strings byte by byte, as `Ord` does, in a const context.
+const fn compare(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        if a[i] != b[i] {
+            return if a[i] < b[i] { Ordering::Less } else { Ordering::Greater };
+        }
+        i += 1;
+    }
+    if a.len() < b.len() { Ordering::Less } else if a.len() > b.len() { Ordering::Greater } else { Ordering::Equal }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PVS1: Option<&EvidenceCode> = evidence_code("PVS1");
+
+    #[test]
+    fn evidence_codes_are_sorted_by_name_for_binary_search() {
+        for pair in EVIDENCE_CODES.windows(2) {
+            assert_eq!(compare(pair[0].0, pair[1].0), Ordering::Less, "{} before {}", pair[0].0, pair[1].0);
+            assert_eq!(pair[0].0.cmp(pair[1].0), Ordering::Less, "{} before {}", pair[0].0, pair[1].0);
+        }
+    }
+
+    #[test]
+    fn every_built_in_code_is_found_by_name() {
+        for (name, code) in &EVIDENCE_CODES {
+            let found = evidence_code(name).unwrap_or_else(|| panic!("{} not found", name));
+            assert!(std::ptr::eq(found, code), "{}", name);
+            assert_eq!(found.name, *name);
+        }
+        assert_eq!(PVS1.map(|code| code.strength), Some(VeryStrong));
+    }
+
+    #[test]
+    fn unknown_names_are_not_found() {
+        for name in ["", "A", "BA", "BA0", "BA2", "PVS", "PVS2", "PVS11", "pvs1", "PS5", "PP6", "ZZZ", "PVS1_Strong", " PVS1"] {
+            assert!(evidence_code(name).is_none(), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::btree_set;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+use crate::Category::{Benign, Pathogenic};
+use crate::{Category, Evidence, PointSystem};
+
+/// A deduplicated collection of applied evidence, iterated in a stable display order - pathogenic before
+/// benign, then strongest default strength first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct EvidenceSet {
+    evidence: BTreeSet<Evidence>,
+}
+
+impl EvidenceSet {
+    pub fn new() -> EvidenceSet {
+        EvidenceSet::default()
+    }
+
+    /// Adds the evidence, returning false if the identical evidence was already present.
+    pub fn insert(&mut self, evidence: Evidence) -> bool {
+        self.evidence.insert(evidence)
+    }
+
+    pub fn contains(&self, evidence: &Evidence) -> bool {
+        self.evidence.contains(evidence)
+    }
+
+    pub fn len(&self) -> usize {
+        self.evidence.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.evidence.is_empty()
+    }
+
+    pub fn iter(&self) -> btree_set::Iter<'_, Evidence> {
+        self.evidence.iter()
+    }
+
+    /// Net ACMG score, the sum of the pathogenic and benign points.
+    pub fn total_points(&self) -> i32 {
+        self.total_points_with(&PointSystem::TAVTIGIAN_2020)
+    }
+
+    /// Net score under a point system, e.g. one awarding 3 points for moderate evidence.
+    pub fn total_points_with(&self, system: &PointSystem) -> i32 {
+        self.iter().map(|evidence| evidence.points_with(system)).sum()
+    }
+
+    /// Sum of the points from pathogenic evidence.
+    pub fn pathogenic_points(&self) -> i32 {
+        self.category_points(Pathogenic, &PointSystem::TAVTIGIAN_2020)
+    }
+
+    /// Sum of the points from pathogenic evidence under a point system.
+    pub fn pathogenic_points_with(&self, system: &PointSystem) -> i32 {
+        self.category_points(Pathogenic, system)
+    }
+
+    /// Sum of the points from benign evidence. This is zero or negative.
+    pub fn benign_points(&self) -> i32 {
+        self.category_points(Benign, &PointSystem::TAVTIGIAN_2020)
+    }
+
+    /// Sum of the points from benign evidence under a point system.
+    pub fn benign_points_with(&self, system: &PointSystem) -> i32 {
+        self.category_points(Benign, system)
+    }
+
+    fn category_points(&self, category: Category, system: &PointSystem) -> i32 {
+        self.iter()
+            .filter(|evidence| evidence.evidence_code.category == category)
+            .map(|evidence| evidence.points_with(system))
+            .sum()
+    }
+
+    /// Evidence applied at a lower strength than the default for its code, e.g. PM2_Supporting.
+    pub fn downgraded(&self) -> impl Iterator<Item = &Evidence> {
+        self.iter().filter(|evidence| evidence.is_downgraded())
+    }
+
+    /// Evidence applied at a higher strength than the default for its code, e.g. PP1_Strong.
+    pub fn upgraded(&self) -> impl Iterator<Item = &Evidence> {
+        self.iter().filter(|evidence| evidence.is_upgraded())
+    }
+}
+
+/// Formats the evidence as a comma separated evidence string, e.g. `PVS1, PM2_Supporting`.
+impl Display for EvidenceSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let evidence: Vec<String> = self.iter().map(|evidence| evidence.to_string()).collect();
+        f.pad(&evidence.join(", "))
+    }
+}
+
+impl FromIterator<Evidence> for EvidenceSet {
+    fn from_iter<I: IntoIterator<Item = Evidence>>(iter: I) -> EvidenceSet {
+        EvidenceSet { evidence: BTreeSet::from_iter(iter) }
+    }
+}
+
+impl Extend<Evidence> for EvidenceSet {
+    fn extend<I: IntoIterator<Item = Evidence>>(&mut self, iter: I) {
+        self.evidence.extend(iter)
+    }
+}
+
+impl IntoIterator for EvidenceSet {
+    type Item = Evidence;
+    type IntoIter = btree_set::IntoIter<Evidence>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.evidence.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a EvidenceSet {
+    type Item = &'a Evidence;
+    type IntoIter = btree_set::Iter<'a, Evidence>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.evidence.iter()
+    }
+}
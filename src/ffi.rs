@@ -0,0 +1,79 @@
+//! The C API, declared in `include/acmg.h`, for annotation engines linking the scoring directly.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use crate::{AcmgAssessment, AcmgClassification};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// The outcome of a call to the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcmgStatus {
+    Ok = 0,
+    /// The evidence could not be parsed or scored, see `acmg_last_error`.
+    InvalidEvidence = 1,
+    /// An argument was null or not UTF-8.
+    InvalidArgument = 2,
+}
+
+/// The score and classification of an evidence string.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AcmgResult {
+    pub points: i32,
+    pub classification: AcmgClassification,
+    pub post_prob_path: f64,
+}
+
+fn fail(status: AcmgStatus, error: String) -> AcmgStatus {
+    let error = CString::new(error).unwrap_or_else(|_| CString::from(c"invalid error message"));
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(error));
+    status
+}
+
+/// Classifies an evidence string such as `"PVS1, PM2_Supporting"` with the points-based guideline, writing the
+/// score and classification to `result`.
+///
+/// # Safety
+///
+/// `evidence` must be null or a NUL-terminated string, and `result` null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn acmg_classify(evidence: *const c_char, result: *mut AcmgResult) -> AcmgStatus {
+    if evidence.is_null() || result.is_null() {
+        return fail(AcmgStatus::InvalidArgument, "evidence and result must not be null".to_string());
+    }
+    let Ok(evidence) = CStr::from_ptr(evidence).to_str() else {
+        return fail(AcmgStatus::InvalidArgument, "evidence must be UTF-8".to_string());
+    };
+    match evidence.parse::<AcmgAssessment>() {
+        Ok(assessment) => {
+            result.write(AcmgResult { points: assessment.points(), classification: assessment.classify(), post_prob_path: assessment.post_prob_path() });
+            AcmgStatus::Ok
+        }
+        Err(error) => fail(AcmgStatus::InvalidEvidence, error.to_string()),
+    }
+}
+
+/// The message of the last error on this thread, or null if there hasn't been one. The message is valid until the
+/// next failed call on the thread.
+#[no_mangle]
+pub extern "C" fn acmg_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| last_error.borrow().as_ref().map_or(ptr::null(), |error| error.as_ptr()))
+}
+
+/// The term for a classification, e.g. `"Likely pathogenic"`, as a static string.
+#[no_mangle]
+pub extern "C" fn acmg_classification_term(classification: AcmgClassification) -> *const c_char {
+    match classification {
+        AcmgClassification::Pathogenic => c"Pathogenic",
+        AcmgClassification::LikelyPathogenic => c"Likely pathogenic",
+        AcmgClassification::UncertainSignificance => c"Uncertain significance",
+        AcmgClassification::LikelyBenign => c"Likely benign",
+        AcmgClassification::Benign => c"Benign",
+    }.as_ptr()
+}
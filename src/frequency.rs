@@ -0,0 +1,177 @@
+use crate::EvidenceStrength::Supporting;
+use crate::{AcmgError, Code, Evidence, EvidenceStrength, FrequencyThresholds};
+
+/// An observed population allele frequency, e.g. the gnomAD popmax filtering allele frequency.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AlleleFrequency {
+    pub af: f64,
+    /// The population the frequency was observed in, e.g. nfe.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub population: Option<String>,
+    /// The number of alleles genotyped in the population.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub allele_number: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub homozygotes: Option<u32>,
+    /// For mtDNA, the frequency of heteroplasmic observations, with `af` the homoplasmic frequency.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub heteroplasmic_af: Option<f64>,
+}
+
+/// The genetic architecture of a dominant disorder, from which the maximum credible population allele frequency
+/// of a causative variant is derived, following Whiffin et al. 2017.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiseaseModel {
+    pub prevalence: f64,
+    /// The largest proportion of cases attributable to a single variant.
+    pub allelic_contribution: f64,
+    /// The largest proportion of cases attributable to the gene.
+    pub genetic_contribution: f64,
+    pub penetrance: f64,
+}
+
+impl DiseaseModel {
+    pub fn new(prevalence: f64, allelic_contribution: f64, genetic_contribution: f64, penetrance: f64) -> Result<DiseaseModel, AcmgError> {
+        for (name, value) in [("prevalence", prevalence), ("allelic contribution", allelic_contribution), ("genetic contribution", genetic_contribution), ("penetrance", penetrance)] {
+            if !(value > 0.0 && value <= 1.0) {
+                return Err(AcmgError::InvalidParameter(format!("{} must be greater than 0 and at most 1, got {}", name, value)));
+            }
+        }
+        Ok(DiseaseModel { prevalence, allelic_contribution, genetic_contribution, penetrance })
+    }
+
+    /// prevalence × allelic contribution × genetic contribution × 1 / penetrance × 1/2, each affected
+    /// heterozygote carrying a single copy of the allele.
+    pub fn max_credible_af(&self) -> f64 {
+        self.prevalence * self.allelic_contribution * self.genetic_contribution / self.penetrance / 2.0
+    }
+}
+
+/// Thresholds for asserting the frequency criteria BA1, BS1, BS2 and PM2 from an allele frequency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrequencyCriteria {
+    /// BA1 is applied above this frequency.
+    pub ba1: f64,
+    /// BS1 is applied above this frequency, if given.
+    pub bs1: Option<f64>,
+    /// PM2 is applied at or below this frequency, by default only if absent.
+    pub pm2: f64,
+    pub pm2_strength: EvidenceStrength,
+    /// BS2 is applied with at least this many homozygotes, if given.
+    pub bs2_homozygotes: Option<u32>,
+    /// BA1 and BS1 are only applied if at least this many alleles were genotyped.
+    pub min_allele_number: u32,
+}
+
+impl Default for FrequencyCriteria {
+    /// The ClinGen SVI recommendations - BA1 above 5% in populations of at least 2000 alleles and PM2 at Supporting.
+    fn default() -> FrequencyCriteria {
+        FrequencyCriteria { ba1: 0.05, bs1: None, pm2: 0.0, pm2_strength: Supporting, bs2_homozygotes: None, min_allele_number: 2000 }
+    }
+}
+
+impl FrequencyCriteria {
+    /// Applies BS1 above the maximum credible allele frequency of the disease model.
+    pub fn with_disease_model(mut self, model: &DiseaseModel) -> FrequencyCriteria {
+        self.bs1 = Some(model.max_credible_af());
+        self
+    }
+
+    /// Uses the thresholds given by a gene-specific criteria specification in place of the current ones.
+    pub fn with_thresholds(mut self, thresholds: &FrequencyThresholds) -> FrequencyCriteria {
+        self.ba1 = thresholds.ba1.unwrap_or(self.ba1);
+        self.bs1 = thresholds.bs1.or(self.bs1);
+        self.pm2 = thresholds.pm2.unwrap_or(self.pm2);
+        self
+    }
+
+    /// The frequency evidence supported by an allele frequency - BA1, otherwise BS1 or PM2, along with BS2 if
+    /// enough homozygotes were observed. For mtDNA, BA1 and BS1 are asserted from the homoplasmic frequency while
+    /// heteroplasmic observations also count against PM2.
+    pub fn evaluate(&self, frequency: &AlleleFrequency) -> Result<Vec<Evidence>, AcmgError> {
+        for af in [Some(frequency.af), frequency.heteroplasmic_af].into_iter().flatten() {
+            if !(0.0..=1.0).contains(&af) {
+                return Err(AcmgError::InvalidParameter(format!("allele frequency must be between 0 and 1, got {}", af)));
+            }
+        }
+        let well_sampled = frequency.allele_number.is_none_or(|allele_number| allele_number >= self.min_allele_number);
+        let mut evidence = vec![];
+        if well_sampled && frequency.af > self.ba1 {
+            return Ok(vec![Evidence::from(Code::BA1)]);
+        } else if well_sampled && self.bs1.is_some_and(|bs1| frequency.af > bs1) {
+            evidence.push(Evidence::from(Code::BS1));
+        } else if frequency.af + frequency.heteroplasmic_af.unwrap_or_default() <= self.pm2 {
+            evidence.push(Evidence::at_strength(Code::PM2, self.pm2_strength));
+        }
+        if let (Some(homozygotes), Some(threshold)) = (frequency.homozygotes, self.bs2_homozygotes) {
+            if homozygotes >= threshold {
+                evidence.push(Evidence::from(Code::BS2));
+            }
+        }
+        Ok(evidence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frequency(af: f64, allele_number: Option<u32>) -> AlleleFrequency {
+        AlleleFrequency { af, allele_number, ..AlleleFrequency::default() }
+    }
+
+    fn names(evidence: Vec<Evidence>) -> Vec<String> {
+        evidence.iter().map(Evidence::to_string).collect()
+    }
+
+    #[test]
+    fn max_credible_af_follows_whiffin_2017() {
+        let hcm = DiseaseModel::new(1.0 / 500.0, 0.02, 1.0, 0.5).unwrap();
+        assert!((hcm.max_credible_af() - 4e-5).abs() < 1e-12);
+        assert!(DiseaseModel::new(0.0, 0.02, 1.0, 0.5).is_err());
+        assert!(DiseaseModel::new(1.0 / 500.0, 1.5, 1.0, 0.5).is_err());
+        assert!(DiseaseModel::new(1.0 / 500.0, 0.02, 1.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn ba1_and_bs1_apply_above_their_thresholds() {
+        let criteria = FrequencyCriteria::default().with_disease_model(&DiseaseModel::new(1.0 / 500.0, 0.02, 1.0, 0.5).unwrap());
+        assert_eq!(names(criteria.evaluate(&frequency(0.051, None)).unwrap()), ["BA1"]);
+        assert_eq!(names(criteria.evaluate(&frequency(0.05, None)).unwrap()), ["BS1"]);
+        assert_eq!(names(criteria.evaluate(&frequency(4.1e-5, None)).unwrap()), ["BS1"]);
+        assert!(criteria.evaluate(&frequency(4e-5, None)).unwrap().is_empty());
+        assert_eq!(names(criteria.evaluate(&frequency(0.0, None)).unwrap()), ["PM2_Supporting"]);
+        assert!(criteria.evaluate(&frequency(1.5, None)).is_err());
+    }
+
+    #[test]
+    fn ba1_and_bs1_need_enough_alleles() {
+        let criteria = FrequencyCriteria { bs1: Some(0.01), ..FrequencyCriteria::default() };
+        assert_eq!(names(criteria.evaluate(&frequency(0.1, Some(2000))).unwrap()), ["BA1"]);
+        assert!(criteria.evaluate(&frequency(0.1, Some(1999))).unwrap().is_empty());
+        assert!(criteria.evaluate(&frequency(0.02, Some(100))).unwrap().is_empty());
+    }
+
+    #[test]
+    fn bs2_is_applied_from_enough_homozygotes() {
+        let criteria = FrequencyCriteria { bs2_homozygotes: Some(2), ..FrequencyCriteria::default() };
+        let homozygotes = |homozygotes| AlleleFrequency { af: 0.001, homozygotes: Some(homozygotes), ..AlleleFrequency::default() };
+        assert_eq!(names(criteria.evaluate(&homozygotes(2)).unwrap()), ["BS2"]);
+        assert!(criteria.evaluate(&homozygotes(1)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn heteroplasmic_observations_count_against_pm2() {
+        let criteria = FrequencyCriteria { ba1: 0.01, bs1: Some(0.005), pm2: 0.00002, ..FrequencyCriteria::default() };
+        let mtdna = |af, heteroplasmic_af| AlleleFrequency { af, heteroplasmic_af: Some(heteroplasmic_af), ..AlleleFrequency::default() };
+        assert_eq!(names(criteria.evaluate(&mtdna(0.00001, 0.00001)).unwrap()), ["PM2_Supporting"]);
+        assert!(criteria.evaluate(&mtdna(0.00001, 0.0001)).unwrap().is_empty());
+        assert_eq!(names(criteria.evaluate(&mtdna(0.006, 0.5)).unwrap()), ["BS1"]);
+        assert_eq!(names(criteria.evaluate(&mtdna(0.02, 0.0)).unwrap()), ["BA1"]);
+        assert!(criteria.evaluate(&mtdna(0.0, 1.5)).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::{AcmgError, AlleleFrequency, LookupCache};
+
+/// The public gnomAD GraphQL API.
+pub const GNOMAD_API: &str = "https://gnomad.broadinstitute.org/api";
+
+/// The source of cached lookups, with the dataset as their data version.
+const SOURCE: &str = "gnomad";
+
+const VARIANT_QUERY: &str = "query Variant($variantId: String!, $dataset: DatasetId!) {
+  variant(variantId: $variantId, dataset: $dataset) {
+    exome { ac an homozygote_count faf95 { popmax popmax_population } }
+    genome { ac an homozygote_count faf95 { popmax popmax_population } }
+  }
+}";
+
+/// Looks up population allele frequencies from the gnomAD GraphQL API, optionally caching them on disk by dataset so
+/// repeated lookups of the same variant don't query the API. Clones share the client and its lookup counts.
+#[derive(Debug, Clone)]
+pub struct GnomadClient {
+    http: reqwest::Client,
+    endpoint: String,
+    dataset: String,
+    cache: Option<LookupCache>,
+    lookups: Arc<AtomicU64>,
+    cache_hits: Arc<AtomicU64>,
+}
+
+impl GnomadClient {
+    /// A client for a gnomAD dataset, e.g. gnomad_r4.
+    pub fn new(dataset: &str) -> GnomadClient {
+        GnomadClient {
+            http: reqwest::Client::new(),
+            endpoint: GNOMAD_API.to_string(),
+            dataset: dataset.to_string(),
+            cache: None,
+            lookups: Arc::default(),
+            cache_hits: Arc::default(),
+        }
+    }
+
+    pub fn with_endpoint(mut self, endpoint: &str) -> GnomadClient {
+        self.endpoint = endpoint.to_string();
+        self
+    }
+
+    pub fn with_cache(mut self, cache: LookupCache) -> GnomadClient {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// The allele frequency of a variant, given as a gnomAD variant id e.g. 1-55051215-G-GA. This is the highest
+    /// popmax filtering allele frequency of the exomes and genomes, or the raw allele frequency if too few alleles
+    /// were observed to calculate it. Variants absent from gnomAD have an allele frequency of 0.
+    pub async fn allele_frequency(&self, variant_id: &str) -> Result<AlleleFrequency, AcmgError> {
+        self.lookups.fetch_add(1, Ordering::Relaxed);
+        let cached = self.cache.as_ref().and_then(|cache| cache.get(SOURCE, &self.dataset, variant_id));
+        if let Some(frequency) = cached {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(frequency);
+        }
+        let frequency = self.query(variant_id).await?;
+        if let Some(cache) = &self.cache {
+            cache.put(SOURCE, &self.dataset, variant_id, &frequency)?;
+        }
+        Ok(frequency)
+    }
+
+    /// The number of allele frequencies looked up, including those answered from the cache.
+    pub fn lookups(&self) -> u64 {
+        self.lookups.load(Ordering::Relaxed)
+    }
+
+    /// The number of allele frequencies answered from the cache.
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    async fn query(&self, variant_id: &str) -> Result<AlleleFrequency, AcmgError> {
+        let request = serde_json::json!({
+            "query": VARIANT_QUERY,
+            "variables": { "variantId": variant_id, "dataset": self.dataset },
+        });
+        let response: Response = self.http.post(&self.endpoint)
+            .json(&request)
+            .send().await
+            .and_then(|response| response.error_for_status())
+            .map_err(lookup_error)?
+            .json().await
+            .map_err(lookup_error)?;
+        if let Some(error) = response.errors.iter().find(|error| error.message != "Variant not found") {
+            return Err(AcmgError::Lookup(format!("gnomAD: {}", error.message)));
+        }
+        let variant = response.data.and_then(|data| data.variant);
+        Ok(variant.map(Variant::allele_frequency).unwrap_or(AlleleFrequency { af: 0.0, homozygotes: Some(0), ..AlleleFrequency::default() }))
+    }
+}
+
+fn lookup_error(error: reqwest::Error) -> AcmgError {
+    AcmgError::Lookup(format!("gnomAD: {}", error))
+}
+
+#[derive(Deserialize)]
+struct Response {
+    data: Option<Data>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct Data {
+    variant: Option<Variant>,
+}
+
+#[derive(Deserialize)]
+struct Variant {
+    exome: Option<SequencingData>,
+    genome: Option<SequencingData>,
+}
+
+#[derive(Deserialize)]
+struct SequencingData {
+    ac: u32,
+    an: u32,
+    homozygote_count: u32,
+    faf95: Option<FilteringAlleleFrequency>,
+}
+
+#[derive(Deserialize)]
+struct FilteringAlleleFrequency {
+    popmax: Option<f64>,
+    popmax_population: Option<String>,
+}
+
+impl Variant {
+    fn allele_frequency(self) -> AlleleFrequency {
+        let data: Vec<SequencingData> = self.exome.into_iter().chain(self.genome).collect();
+        let ac: u32 = data.iter().map(|data| data.ac).sum();
+        let an: u32 = data.iter().map(|data| data.an).sum();
+        let homozygotes = data.iter().map(|data| data.homozygote_count).sum();
+        let popmax = data.into_iter()
+            .filter_map(|data| data.faf95)
+            .filter_map(|faf| faf.popmax.map(|popmax| (popmax, faf.popmax_population)))
+            .max_by(|(a, _), (b, _)| a.total_cmp(b));
+        let (af, population) = match popmax {
+            Some((popmax, population)) => (popmax, population),
+            None if an > 0 => (ac as f64 / an as f64, None),
+            None => (0.0, None),
+        };
+        AlleleFrequency { af, population, allele_number: Some(an), homozygotes: Some(homozygotes), heteroplasmic_af: None }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::Code::{self, *};
+use crate::EvidenceStrength::{self, Moderate, StandAlone, Strong, Supporting, VeryStrong};
+
+pub(crate) const RICHARDS_2015: &str = "Richards et al. 2015, Standards and guidelines for the interpretation of sequence variants, DOI: https://doi.org/10.1038/gim.2015.30";
+pub(crate) const TAVTIGIAN_2020: &str = "Tavtigian et al. 2020, Fitting a naturally scaled point system to the ACMG/AMP variant classification guidelines, DOI: https://doi.org/10.1002/humu.24088";
+const ABOU_TAYOUN_2018: &str = "Abou Tayoun et al. 2018, Recommendations for interpreting the loss of function PVS1 ACMG/AMP variant criterion, DOI: https://doi.org/10.1002/humu.23626";
+const BRNICH_2019: &str = "Brnich et al. 2019, Recommendations for application of the functional evidence PS3/BS3 criterion, DOI: https://doi.org/10.1186/s13073-019-0690-2";
+const PEJAVER_2022: &str = "Pejaver et al. 2022, Calibration of computational tools for missense variant pathogenicity classification, DOI: https://doi.org/10.1016/j.ajhg.2022.10.013";
+const GHOSH_2018: &str = "Ghosh et al. 2018, Updated recommendation for the benign stand-alone ACMG/AMP criterion, DOI: https://doi.org/10.1002/humu.23642";
+const BIESECKER_2018: &str = "Biesecker & Harrison 2018, The ACMG/AMP reputable source criteria for the interpretation of sequence variants, DOI: https://doi.org/10.1038/gim.2018.42";
+const SVI_PM2: &str = "ClinGen SVI Recommendation for Absence/Rarity (PM2) - Version 1.0, https://clinicalgenome.org/working-groups/sequence-variant-interpretation/";
+const SVI_PM3: &str = "ClinGen SVI Recommendation for in trans Criterion (PM3) - Version 1.0, https://clinicalgenome.org/working-groups/sequence-variant-interpretation/";
+const SVI_DE_NOVO: &str = "ClinGen SVI Recommendation for de novo Criteria (PS2 & PM6) - Version 1.1, https://clinicalgenome.org/working-groups/sequence-variant-interpretation/";
+pub(crate) const ELLARD_2020: &str = "Ellard et al. 2020, ACGS Best Practice Guidelines for Variant Classification in Rare Disease 2020, https://www.acgs.uk.com/quality/best-practice-guidelines/";
+pub(crate) const MCCORMICK_2020: &str = "McCormick et al. 2020, Specifications of the ACMG/AMP standards and guidelines for mitochondrial DNA variant interpretation, DOI: https://doi.org/10.1002/humu.24107";
+const JARVIK_2016: &str = "Jarvik & Browning 2016, Consideration of cosegregation in the pathogenicity classification of genomic variants, DOI: https://doi.org/10.1016/j.ajhg.2016.04.003";
+
+const ALL_PATHOGENIC: &[EvidenceStrength] = &[VeryStrong, Strong, Moderate, Supporting];
+const UP_TO_STRONG: &[EvidenceStrength] = &[Strong, Moderate, Supporting];
+const MODERATE_OR_SUPPORTING: &[EvidenceStrength] = &[Moderate, Supporting];
+const STRONG_OR_SUPPORTING: &[EvidenceStrength] = &[Strong, Supporting];
+const SUPPORTING_ONLY: &[EvidenceStrength] = &[Supporting];
+
+/// Curation guidance for an evidence code - the strengths it may be applied at, caveats from the ClinGen SVI
+/// recommendations and the publications defining its use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeGuidance {
+    pub allowed_strengths: &'static [EvidenceStrength],
+    pub caveats: &'static [&'static str],
+    pub citations: &'static [&'static str],
+}
+
+/// Returns the curation guidance for a built-in evidence code.
+pub fn guidance(code: Code) -> CodeGuidance {
+    let (allowed_strengths, caveats, citations): (&'static [EvidenceStrength], &'static [&'static str], &'static [&'static str]) = match code {
+        PVS1 => (ALL_PATHOGENIC,
+                 &["Use the PVS1 decision tree to decide the strength, considering NMD, exon context and the LOF mechanism of the gene"],
+                 &[RICHARDS_2015, ABOU_TAYOUN_2018]),
+        PS1 => (UP_TO_STRONG,
+                &["Not applicable where the variant is predicted to affect splicing", "Do not combine with PM5 for the same residue"],
+                &[RICHARDS_2015]),
+        PS2 => (ALL_PATHOGENIC,
+                &["Strength depends on the number of de novo observations, confirmation status and phenotypic consistency", "Do not combine with PM6 for the same observations"],
+                &[RICHARDS_2015, SVI_DE_NOVO]),
+        PS3 => (UP_TO_STRONG,
+                &["Strength depends on the validation of the assay, as described by the SVI functional evidence recommendations"],
+                &[RICHARDS_2015, BRNICH_2019]),
+        PS4 => (UP_TO_STRONG,
+                &["Case-control data should show a significantly increased odds ratio, typically OR > 5 with a confidence interval excluding 1"],
+                &[RICHARDS_2015]),
+        PM1 => (UP_TO_STRONG,
+                &["Do not combine with PP3 for the same line of evidence"],
+                &[RICHARDS_2015]),
+        PM2 => (MODERATE_OR_SUPPORTING,
+                &["The SVI recommends applying PM2 at Supporting strength by default"],
+                &[RICHARDS_2015, SVI_PM2]),
+        PM3 => (ALL_PATHOGENIC,
+                &["Only applicable to recessive disorders", "Strength depends on the points from in trans observations as tabulated by the SVI"],
+                &[RICHARDS_2015, SVI_PM3]),
+        PM4 => (MODERATE_OR_SUPPORTING,
+                &["Do not combine with PVS1 for the same variant"],
+                &[RICHARDS_2015]),
+        PM5 => (UP_TO_STRONG,
+                &["Do not combine with PS1 for the same residue"],
+                &[RICHARDS_2015]),
+        PM6 => (ALL_PATHOGENIC,
+                &["Strength depends on the number of de novo observations and phenotypic consistency", "Do not combine with PS2 for the same observations"],
+                &[RICHARDS_2015, SVI_DE_NOVO]),
+        PP1 => (UP_TO_STRONG,
+                &["Strength depends on the number of informative meioses or the LOD score"],
+                &[RICHARDS_2015, JARVIK_2016]),
+        PP2 => (SUPPORTING_ONLY,
+                &["Only applicable to missense variants in genes with a low rate of benign missense variation"],
+                &[RICHARDS_2015]),
+        PP3 => (UP_TO_STRONG,
+                &["Use a single calibrated predictor to decide the strength", "Do not combine with PVS1 for splice variants"],
+                &[RICHARDS_2015, PEJAVER_2022]),
+        PP4 => (UP_TO_STRONG,
+                &["The phenotype should be highly specific for the gene"],
+                &[RICHARDS_2015]),
+        PP5 => (SUPPORTING_ONLY,
+                &["The SVI recommends this criterion is no longer used"],
+                &[RICHARDS_2015, BIESECKER_2018]),
+        BA1 => (&[StandAlone],
+                &["Not applicable to variants on the ClinGen BA1 exception list"],
+                &[RICHARDS_2015, GHOSH_2018]),
+        BS1 => (STRONG_OR_SUPPORTING,
+                &["The threshold should be calculated from the disease prevalence, penetrance and genetic heterogeneity"],
+                &[RICHARDS_2015]),
+        BS2 => (STRONG_OR_SUPPORTING,
+                &["Only applicable to disorders with full penetrance at an early age"],
+                &[RICHARDS_2015]),
+        BS3 => (UP_TO_STRONG,
+                &["Strength depends on the validation of the assay, as described by the SVI functional evidence recommendations"],
+                &[RICHARDS_2015, BRNICH_2019]),
+        BS4 => (STRONG_OR_SUPPORTING,
+                &["Beware of phenocopies and reduced penetrance when interpreting non-segregation"],
+                &[RICHARDS_2015]),
+        BP1 => (SUPPORTING_ONLY,
+                &["Only applicable to missense variants in genes where truncating variants cause disease"],
+                &[RICHARDS_2015]),
+        BP2 => (SUPPORTING_ONLY,
+                &[],
+                &[RICHARDS_2015]),
+        BP3 => (SUPPORTING_ONLY,
+                &["Only applicable to in-frame indels in repetitive regions without a known function"],
+                &[RICHARDS_2015]),
+        BP4 => (UP_TO_STRONG,
+                &["Use a single calibrated predictor to decide the strength"],
+                &[RICHARDS_2015, PEJAVER_2022]),
+        BP5 => (SUPPORTING_ONLY,
+                &["Use with caution for disorders where multiple pathogenic variants may contribute"],
+                &[RICHARDS_2015]),
+        BP6 => (SUPPORTING_ONLY,
+                &["The SVI recommends this criterion is no longer used"],
+                &[RICHARDS_2015, BIESECKER_2018]),
+        BP7 => (SUPPORTING_ONLY,
+                &["The nucleotide must not be highly conserved and splicing predictors must predict no impact"],
+                &[RICHARDS_2015]),
+    };
+    CodeGuidance { allowed_strengths, caveats, citations }
+}
+
+/// The codes the ClinGen SVI recommends are no longer used, with the reason.
+pub const RETIRED_CODES: [(Code, &str); 2] = [
+    (PP5, "the ClinGen SVI recommends it is no longer used, evaluating the evidence behind a reputable source's classification instead (Biesecker & Harrison 2018)"),
+    (BP6, "the ClinGen SVI recommends it is no longer used, evaluating the evidence behind a reputable source's classification instead (Biesecker & Harrison 2018)"),
+];
+
+/// Why a code is disabled, noting if the SVI retired it.
+pub(crate) fn disabled_reason(code: Code) -> &'static str {
+    RETIRED_CODES.iter()
+        .find(|(retired, _)| *retired == code)
+        .map_or("it isn't used under the configured guidelines", |(_, reason)| reason)
+}
+
+/// The paper describing the points-based scoring implemented by this crate.
+pub const POINTS_CITATION: &str = TAVTIGIAN_2020;
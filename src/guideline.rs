@@ -0,0 +1,396 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::guidance::{ELLARD_2020, MCCORMICK_2020, RICHARDS_2015, TAVTIGIAN_2020};
+use crate::Category::{Benign, Pathogenic};
+use crate::EvidenceStrength::{Moderate, StandAlone, Strong, Supporting, VeryStrong};
+use crate::{classification, AcmgClassification, AcmgError, EvidenceSet};
+
+/// The guideline used to combine evidence into a classification.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Guideline {
+    /// The naturally scaled point system of Tavtigian et al. 2020.
+    #[default]
+    Points2020,
+    /// The rule-based combining criteria of Richards et al. 2015, Table 5.
+    Acmg2015,
+    /// The ACGS Best Practice Guidelines for Variant Classification in Rare Disease 2020 (Ellard et al.).
+    Acgs2020,
+    /// The ClinGen specifications for mitochondrial DNA variants of McCormick et al. 2020, combining with the rules
+    /// of Richards et al. 2015 under the built-in MT criteria specification.
+    Mito,
+}
+
+impl Guideline {
+    pub const ALL: [Guideline; 4] = [Guideline::Points2020, Guideline::Acmg2015, Guideline::Acgs2020, Guideline::Mito];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Guideline::Points2020 => "points2020",
+            Guideline::Acmg2015 => "acmg2015",
+            Guideline::Acgs2020 => "acgs2020",
+            Guideline::Mito => "mito",
+        }
+    }
+
+    /// The model implementing the guideline.
+    pub fn model(&self) -> &'static dyn ClassificationModel {
+        match self {
+            Guideline::Points2020 => &Points2020Model,
+            Guideline::Acmg2015 => &Richards2015Model,
+            Guideline::Acgs2020 => &Acgs2020Model,
+            Guideline::Mito => &MitoModel,
+        }
+    }
+
+    pub fn classify(&self, evidence_set: &EvidenceSet) -> AcmgClassification {
+        self.model().classify(evidence_set)
+    }
+
+    /// The publication defining the guideline, for citing the assertion method.
+    pub fn citation(&self) -> &'static str {
+        self.model().citation()
+    }
+
+    /// The version of the guideline implemented, e.g. `ACGS 2020 v4.01`.
+    pub fn version(&self) -> &'static str {
+        self.model().version()
+    }
+
+    /// The name and version of the guideline, e.g. `acgs2020 (ACGS 2020 v4.01)`, for stamping results.
+    pub fn label(&self) -> String {
+        format!("{} ({})", self.name(), self.version())
+    }
+
+    /// The built-in criteria specification the guideline is defined with, if any, e.g. `MT` for
+    /// [`Guideline::Mito`].
+    pub fn spec_name(&self) -> Option<&'static str> {
+        match self {
+            Guideline::Mito => Some("MT"),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Guideline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// Parses a guideline by name, or `richards2015` for [`Guideline::Acmg2015`].
+impl FromStr for Guideline {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<Guideline, Self::Err> {
+        if s.eq_ignore_ascii_case("richards2015") {
+            return Ok(Guideline::Acmg2015);
+        }
+        Guideline::ALL.iter()
+            .find(|guideline| guideline.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| AcmgError::UnknownGuideline(s.to_string()))
+    }
+}
+
+/// A way of combining evidence into a classification. The built-in guidelines are implemented as models, and others
+/// such as a lab's own combining rules can be implemented to classify evidence sets alongside them.
+pub trait ClassificationModel: Send + Sync {
+    /// A short name identifying the model, e.g. `points2020`.
+    fn name(&self) -> &str;
+
+    /// The version of the guideline implemented, e.g. the publication or revision of its rules.
+    fn version(&self) -> &str;
+
+    /// The publication defining the guideline, for citing the assertion method.
+    fn citation(&self) -> &str;
+
+    fn classify(&self, evidence_set: &EvidenceSet) -> AcmgClassification;
+}
+
+/// The naturally scaled point system of Tavtigian et al. 2020, see [`classification`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Points2020Model;
+
+impl ClassificationModel for Points2020Model {
+    fn name(&self) -> &str {
+        "points2020"
+    }
+
+    fn version(&self) -> &str {
+        "Tavtigian et al. 2020"
+    }
+
+    fn citation(&self) -> &str {
+        TAVTIGIAN_2020
+    }
+
+    fn classify(&self, evidence_set: &EvidenceSet) -> AcmgClassification {
+        classification(evidence_set.total_points())
+    }
+}
+
+/// The rule-based combining criteria of Richards et al. 2015, see [`classify_acmg2015`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Richards2015Model;
+
+impl ClassificationModel for Richards2015Model {
+    fn name(&self) -> &str {
+        "acmg2015"
+    }
+
+    fn version(&self) -> &str {
+        "Richards et al. 2015"
+    }
+
+    fn citation(&self) -> &str {
+        RICHARDS_2015
+    }
+
+    fn classify(&self, evidence_set: &EvidenceSet) -> AcmgClassification {
+        classify_acmg2015(evidence_set)
+    }
+}
+
+/// The ACGS 2020 combining criteria, see [`classify_acgs2020`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Acgs2020Model;
+
+impl ClassificationModel for Acgs2020Model {
+    fn name(&self) -> &str {
+        "acgs2020"
+    }
+
+    fn version(&self) -> &str {
+        "ACGS 2020 v4.01"
+    }
+
+    fn citation(&self) -> &str {
+        ELLARD_2020
+    }
+
+    fn classify(&self, evidence_set: &EvidenceSet) -> AcmgClassification {
+        classify_acgs2020(evidence_set)
+    }
+}
+
+/// The mitochondrial DNA specifications of McCormick et al. 2020, combining with the rules of Richards et al. 2015.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MitoModel;
+
+impl ClassificationModel for MitoModel {
+    fn name(&self) -> &str {
+        "mito"
+    }
+
+    fn version(&self) -> &str {
+        "McCormick et al. 2020"
+    }
+
+    fn citation(&self) -> &str {
+        MCCORMICK_2020
+    }
+
+    fn classify(&self, evidence_set: &EvidenceSet) -> AcmgClassification {
+        classify_acmg2015(evidence_set)
+    }
+}
+
+#[derive(Debug, Default)]
+struct StrengthCounts {
+    stand_alone: usize,
+    very_strong: usize,
+    strong: usize,
+    moderate: usize,
+    supporting: usize,
+}
+
+impl StrengthCounts {
+    fn count(evidence_set: &EvidenceSet, category: crate::Category) -> StrengthCounts {
+        let mut counts = StrengthCounts::default();
+        for evidence in evidence_set.iter().filter(|evidence| evidence.evidence_code.category == category) {
+            match evidence.strength() {
+                StandAlone => counts.stand_alone += 1,
+                VeryStrong => counts.very_strong += 1,
+                Strong => counts.strong += 1,
+                Moderate => counts.moderate += 1,
+                Supporting => counts.supporting += 1,
+            }
+        }
+        counts
+    }
+}
+
+/// Combines evidence using the rules of Richards et al. 2015, Table 5, counting each code at its applied
+/// strength. Strengths which the original rules don't define for a category are counted at the nearest one -
+/// pathogenic StandAlone as VeryStrong, benign VeryStrong as Strong and benign Moderate as Supporting.
+/// Evidence which meets the criteria for both a pathogenic and benign classification is of uncertain
+/// significance.
+pub fn classify_acmg2015(evidence_set: &EvidenceSet) -> AcmgClassification {
+    let path = StrengthCounts::count(evidence_set, Pathogenic);
+    let benign = StrengthCounts::count(evidence_set, Benign);
+
+    let vs = path.stand_alone + path.very_strong;
+    let (s, m, p) = (path.strong, path.moderate, path.supporting);
+    // any additional very strong evidence counts towards the strong evidence for rule (i)
+    let pathogenic = (vs >= 1 && (s + vs >= 2 || m >= 2 || (m == 1 && p == 1) || p >= 2))
+        || s >= 2
+        || (s == 1 && (m >= 3 || (m == 2 && p >= 2) || (m == 1 && p >= 4)));
+    let likely_pathogenic = (vs == 1 && m == 1)
+        || (s == 1 && (1..=2).contains(&m))
+        || (s == 1 && p >= 2)
+        || m >= 3
+        || (m == 2 && p >= 2)
+        || (m == 1 && p >= 4);
+
+    let bs = benign.very_strong + benign.strong;
+    let bp = benign.moderate + benign.supporting;
+    let is_benign = benign.stand_alone >= 1 || bs >= 2;
+    let likely_benign = (bs == 1 && bp >= 1) || bp >= 2;
+
+    let path_call = if pathogenic { Some(AcmgClassification::Pathogenic) } else if likely_pathogenic { Some(AcmgClassification::LikelyPathogenic) } else { None };
+    let benign_call = if is_benign { Some(AcmgClassification::Benign) } else if likely_benign { Some(AcmgClassification::LikelyBenign) } else { None };
+    match (path_call, benign_call) {
+        (Some(path_call), None) => path_call,
+        (None, Some(benign_call)) => benign_call,
+        _ => AcmgClassification::UncertainSignificance,
+    }
+}
+
+/// Combines evidence using the ACGS 2020 best practice guidelines. These follow the Richards et al. 2015 rules,
+/// with the addition that 1 Very Strong and 1 Supporting pathogenic criterion, without any benign evidence, is
+/// Likely pathogenic.
+pub fn classify_acgs2020(evidence_set: &EvidenceSet) -> AcmgClassification {
+    let classification = classify_acmg2015(evidence_set);
+    if classification != AcmgClassification::UncertainSignificance {
+        return classification;
+    }
+    let path = StrengthCounts::count(evidence_set, Pathogenic);
+    let has_benign = evidence_set.iter().any(|evidence| evidence.evidence_code.category == Benign);
+    if path.stand_alone + path.very_strong >= 1 && path.supporting >= 1 && !has_benign {
+        AcmgClassification::LikelyPathogenic
+    } else {
+        classification
+    }
+}
+
+/// The ACGS 2020 sub-classification of variants of uncertain significance, from the total points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum VusTemperature {
+    Hot,
+    Warm,
+    Tepid,
+    Cool,
+    Cold,
+    IceCold,
+}
+
+impl VusTemperature {
+    /// Hot for 5 or more points, down one step per point to IceCold for 0 or fewer.
+    pub fn from_points(points: i32) -> VusTemperature {
+        match points {
+            p if p >= 5 => VusTemperature::Hot,
+            4 => VusTemperature::Warm,
+            3 => VusTemperature::Tepid,
+            2 => VusTemperature::Cool,
+            1 => VusTemperature::Cold,
+            _ => VusTemperature::IceCold,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_evidence_set;
+    use crate::AcmgClassification::{Benign, LikelyBenign, LikelyPathogenic, Pathogenic, UncertainSignificance};
+
+    fn assert_classified(classify: fn(&EvidenceSet) -> AcmgClassification, cases: &[(&str, AcmgClassification)]) {
+        for (evidence, expected) in cases {
+            assert_eq!(classify(&parse_evidence_set(evidence).unwrap()), *expected, "{}", evidence);
+        }
+    }
+
+    #[test]
+    fn richards_2015_pathogenic_rules() {
+        assert_classified(classify_acmg2015, &[
+            ("PVS1, PS1", Pathogenic),
+            ("PVS1, PM1, PM4", Pathogenic),
+            ("PVS1, PM1, PP1", Pathogenic),
+            ("PVS1, PP1, PP3", Pathogenic),
+            ("PS1, PS3", Pathogenic),
+            ("PS1, PM1, PM4, PM5", Pathogenic),
+            ("PS1, PM1, PM4, PP1, PP3", Pathogenic),
+            ("PS1, PM1, PP1, PP2, PP3, PP4", Pathogenic),
+            ("PVS1, PS1_VeryStrong", Pathogenic),
+        ]);
+    }
+
+    #[test]
+    fn richards_2015_likely_pathogenic_rules() {
+        assert_classified(classify_acmg2015, &[
+            ("PVS1, PM1", LikelyPathogenic),
+            ("PS1, PM1", LikelyPathogenic),
+            ("PS1, PM1, PM4", LikelyPathogenic),
+            ("PS1, PP1, PP3", LikelyPathogenic),
+            ("PM1, PM4, PM5", LikelyPathogenic),
+            ("PM1, PM4, PP1, PP3", LikelyPathogenic),
+            ("PM1, PP1, PP2, PP3, PP4", LikelyPathogenic),
+        ]);
+    }
+
+    #[test]
+    fn richards_2015_benign_rules() {
+        assert_classified(classify_acmg2015, &[
+            ("BA1", Benign),
+            ("BS1, BS2", Benign),
+            ("BS1, BP1", LikelyBenign),
+            ("BP1, BP4", LikelyBenign),
+            ("BS1", UncertainSignificance),
+            ("BP1", UncertainSignificance),
+        ]);
+    }
+
+    #[test]
+    fn richards_2015_insufficient_or_conflicting_evidence_is_uncertain() {
+        assert_classified(classify_acmg2015, &[
+            ("PVS1", UncertainSignificance),
+            ("PVS1, PP1", UncertainSignificance),
+            ("PS1, PP1", UncertainSignificance),
+            ("PM1, PM4, PP1", UncertainSignificance),
+            ("PM1, PP1, PP2, PP3", UncertainSignificance),
+            ("PVS1, PS1, BS1, BS2", UncertainSignificance),
+            ("PVS1, PS1, BP1, BP4", UncertainSignificance),
+            ("PM1, PM4, PM5, BA1", UncertainSignificance),
+        ]);
+        assert_classified(classify_acmg2015, &[("PVS1, PS1, BP1", Pathogenic), ("BS1, BS2, PP1", Benign)]);
+    }
+
+    #[test]
+    fn acgs_2020_adds_very_strong_and_supporting_without_benign_evidence() {
+        assert_classified(classify_acgs2020, &[
+            ("PVS1, PP1", LikelyPathogenic),
+            ("PVS1, PP1, PP3", Pathogenic),
+            ("PVS1, PP1, BP1", UncertainSignificance),
+            ("PVS1", UncertainSignificance),
+            ("PS1, PP1", UncertainSignificance),
+            ("PS1, PS3", Pathogenic),
+            ("BS1, BP1", LikelyBenign),
+        ]);
+    }
+
+    #[test]
+    fn vus_temperature_steps_down_a_point_at_a_time() {
+        let temperatures: Vec<VusTemperature> = (-1..=6).map(VusTemperature::from_points).collect();
+        assert_eq!(temperatures, [
+            VusTemperature::IceCold, VusTemperature::IceCold, VusTemperature::Cold, VusTemperature::Cool,
+            VusTemperature::Tepid, VusTemperature::Warm, VusTemperature::Hot, VusTemperature::Hot,
+        ]);
+    }
+}
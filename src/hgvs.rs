@@ -0,0 +1,173 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::AcmgError;
+
+static HGVS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([A-Z]{2}_\d+\.\d+|ENS[TGP]\d{11}\.\d+|LRG_\d+(?:[tp]\d+)?)(?:\(([A-Za-z0-9-]+)\))?:([cgnmp])\.(.+)$").unwrap());
+static NUCLEOTIDE_CHANGE: LazyLock<Regex> = LazyLock::new(|| {
+    let position = r"(?:[-*]?\d+(?:[+-]\d+)?|\?)";
+    Regex::new(&format!(r"^{p}(?:_{p})?(?:[ACGT]>[ACGT]|del[ACGT]*|dup[ACGT]*|ins[ACGT]+|delins[ACGT]+|inv|=)$", p = position)).unwrap()
+});
+static PROTEIN_CHANGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\(?(?:[A-Z][a-z]{2}\d+(?:_[A-Z][a-z]{2}\d+)?(?:[A-Z][a-z]{2}|Ter|=|del|dup|ins(?:[A-Z][a-z]{2})+|delins(?:[A-Z][a-z]{2})+|fs(?:Ter\d*)?|\?)|0|=|\?)\)?$").unwrap());
+static CANONICAL_SPLICE_SITE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[-*]?\d+[+-][12](?:\D|$)").unwrap());
+
+/// The coordinate system of an HGVS description, from its prefix e.g. `c.`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum SequenceType {
+    Coding,
+    Genomic,
+    NonCoding,
+    Mitochondrial,
+    Protein,
+}
+
+impl SequenceType {
+    fn from_prefix(prefix: &str) -> SequenceType {
+        match prefix {
+            "c" => SequenceType::Coding,
+            "g" => SequenceType::Genomic,
+            "n" => SequenceType::NonCoding,
+            "m" => SequenceType::Mitochondrial,
+            _ => SequenceType::Protein,
+        }
+    }
+
+    fn prefix(&self) -> &'static str {
+        match self {
+            SequenceType::Coding => "c",
+            SequenceType::Genomic => "g",
+            SequenceType::NonCoding => "n",
+            SequenceType::Mitochondrial => "m",
+            SequenceType::Protein => "p",
+        }
+    }
+
+    /// Whether the type of reference sequence can be described in this coordinate system, e.g. `c.` requires a
+    /// coding transcript such as NM_ or ENST.
+    fn accepts(&self, reference_sequence: &str) -> bool {
+        let prefixes: &[&str] = match self {
+            SequenceType::Coding => &["NM_", "XM_", "ENST", "LRG_"],
+            SequenceType::Genomic => &["NC_", "NG_", "NT_", "NW_", "ENSG", "LRG_"],
+            SequenceType::NonCoding => &["NR_", "XR_", "NM_", "XM_", "ENST", "LRG_"],
+            SequenceType::Mitochondrial => &["NC_"],
+            SequenceType::Protein => &["NP_", "XP_", "ENSP", "LRG_"],
+        };
+        prefixes.iter().any(|prefix| reference_sequence.starts_with(prefix))
+    }
+}
+
+/// A sequence variant described in HGVS notation against a versioned reference sequence, e.g.
+/// `NM_000257.4:c.1208G>A`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct HgvsVariant {
+    /// The versioned reference sequence, e.g. NM_000257.4.
+    pub reference_sequence: String,
+    pub gene: Option<String>,
+    pub sequence_type: SequenceType,
+    /// The change relative to the reference sequence, e.g. 1208G>A.
+    pub change: String,
+}
+
+impl HgvsVariant {
+    /// Whether the variant is at the canonical ±1 or ±2 splice sites of an intron, e.g. `c.1208+1G>A`.
+    pub fn is_canonical_splice_site(&self) -> bool {
+        matches!(self.sequence_type, SequenceType::Coding | SequenceType::NonCoding) && CANONICAL_SPLICE_SITE.is_match(&self.change)
+    }
+
+    /// The reference sequence accession without its version, e.g. NM_000257.
+    pub fn accession(&self) -> &str {
+        self.reference_sequence.split_once('.').map_or(&self.reference_sequence, |(accession, _)| accession)
+    }
+
+    pub fn version(&self) -> Option<u32> {
+        self.reference_sequence.split_once('.').and_then(|(_, version)| version.parse().ok())
+    }
+
+    /// The transcript, for variants described against one.
+    pub fn transcript(&self) -> Option<&str> {
+        matches!(self.sequence_type, SequenceType::Coding | SequenceType::NonCoding)
+            .then_some(self.reference_sequence.as_str())
+    }
+}
+
+impl Display for HgvsVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let gene = self.gene.as_ref().map(|gene| format!("({})", gene)).unwrap_or_default();
+        f.pad(&format!("{}{}:{}.{}", self.reference_sequence, gene, self.sequence_type.prefix(), self.change))
+    }
+}
+
+impl FromStr for HgvsVariant {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<HgvsVariant, Self::Err> {
+        let invalid = |reason: &str| AcmgError::InvalidHgvs { hgvs: s.to_string(), reason: reason.to_string() };
+        let caps = HGVS.captures(s.trim()).ok_or_else(|| invalid("expected a versioned reference sequence, e.g. NM_000257.4:c.1208G>A"))?;
+        let reference_sequence = caps[1].to_string();
+        let sequence_type = SequenceType::from_prefix(&caps[3]);
+        let change = caps[4].to_string();
+        if !sequence_type.accepts(&reference_sequence) {
+            return Err(invalid(&format!("{}. is not a valid coordinate system for {}", sequence_type.prefix(), reference_sequence)));
+        }
+        let valid_change = match sequence_type {
+            SequenceType::Protein => PROTEIN_CHANGE.is_match(&change),
+            _ => NUCLEOTIDE_CHANGE.is_match(&change),
+        };
+        if !valid_change {
+            return Err(invalid(&format!("unrecognised change {}", change)));
+        }
+        Ok(HgvsVariant { reference_sequence, gene: caps.get(2).map(|gene| gene.as_str().to_string()), sequence_type, change })
+    }
+}
+
+/// Variants are serialized along with their full HGVS description for joining on, which is ignored when
+/// deserializing.
+#[cfg(feature = "serde")]
+impl serde::Serialize for HgvsVariant {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("HgvsVariant", 5)?;
+        state.serialize_field("hgvs", &self.to_string())?;
+        state.serialize_field("reference_sequence", &self.reference_sequence)?;
+        state.serialize_field("gene", &self.gene)?;
+        state.serialize_field("sequence_type", &self.sequence_type)?;
+        state.serialize_field("change", &self.change)?;
+        state.end()
+    }
+}
+
+/// The OpenAPI schema of [`HgvsVariant`]: a variant described with HGVS nomenclature.
+#[cfg(feature = "openapi")]
+#[derive(utoipa::ToSchema)]
+#[schema(as = HgvsVariant)]
+#[allow(dead_code)]
+struct HgvsVariantSchema {
+    /// The full HGVS description, e.g. NM_000257.4:c.1208G>A.
+    hgvs: String,
+    reference_sequence: String,
+    gene: Option<String>,
+    sequence_type: SequenceType,
+    change: String,
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for HgvsVariant {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        <HgvsVariantSchema as utoipa::PartialSchema>::schema()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::ToSchema for HgvsVariant {
+    fn schemas(schemas: &mut Vec<(String, utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>)>) {
+        <HgvsVariantSchema as utoipa::ToSchema>::schemas(schemas)
+    }
+}
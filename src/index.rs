@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::{AcmgError, AlleleFrequency, ClinVarIndex, ClinVarRecord, ClinVarReport, ClinicalSignificance, GenomeBuild, GenomicVariant, VariantQuery};
+
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 40;
+/// A string in the string table, as its offset and length.
+const STR_LEN: usize = 12;
+const FREQUENCY_LEN: usize = STR_LEN + 8 + 4 + 4 + STR_LEN;
+const RECORD_LEN: usize = 8 * STR_LEN + 8 + 8;
+/// Marks an allele number or homozygote count which wasn't given.
+const NONE: u32 = u32::MAX;
+
+const SIGNIFICANCES: [ClinicalSignificance; 7] = [
+    ClinicalSignificance::Pathogenic,
+    ClinicalSignificance::LikelyPathogenic,
+    ClinicalSignificance::UncertainSignificance,
+    ClinicalSignificance::LikelyBenign,
+    ClinicalSignificance::Benign,
+    ClinicalSignificance::Conflicting,
+    ClinicalSignificance::Other,
+];
+
+/// A compact index of gnomAD allele frequencies and ClinVar classifications built by [`IndexBuilder`], memory-mapped
+/// so that the lookup-based criteria can be assessed offline without reading the whole index.
+///
+/// The file is a header followed by the allele frequencies sorted by variant, the ClinVar records sorted by gene and
+/// name, the record numbers sorted by coordinates and the strings they refer to, all little-endian.
+#[derive(Debug)]
+pub struct VariantIndex {
+    map: Mmap,
+    build: GenomeBuild,
+    frequencies: usize,
+    records: usize,
+}
+
+impl VariantIndex {
+    /// The first bytes of every index file.
+    pub const MAGIC: &'static [u8; 8] = b"ACMGIDX\0";
+
+    /// Maps an index file into memory. The file must not be modified while it's open, so rebuild indexes to a new
+    /// file and rename it into place.
+    pub fn open(path: &Path) -> Result<VariantIndex, AcmgError> {
+        let invalid = |reason: String| AcmgError::InvalidIndex(format!("{}: {}", path.display(), reason));
+        let file = File::open(path).map_err(|error| invalid(error.to_string()))?;
+        // Safety: the index is only read, and is documented to not be modified while open.
+        let map = unsafe { Mmap::map(&file) }.map_err(|error| invalid(error.to_string()))?;
+        if map.len() < HEADER_LEN || &map[..8] != VariantIndex::MAGIC {
+            return Err(invalid("not a variant index".to_string()));
+        }
+        let version = u32::from_le_bytes(map[8..12].try_into().expect("4 bytes"));
+        if version != VERSION {
+            return Err(invalid(format!("unsupported version {}, rebuild it with acmg index build", version)));
+        }
+        let build = if map[12] == 0 { GenomeBuild::Grch37 } else { GenomeBuild::Grch38 };
+        let count = |start: usize| u64::from_le_bytes(map[start..start + 8].try_into().expect("8 bytes")) as usize;
+        let (frequencies, records, strings) = (count(16), count(24), count(32));
+        let expected = frequencies.checked_mul(FREQUENCY_LEN)
+            .zip(records.checked_mul(RECORD_LEN + 4))
+            .and_then(|(frequencies, records)| HEADER_LEN.checked_add(frequencies)?.checked_add(records)?.checked_add(strings))
+            .ok_or_else(|| invalid("the header counts are too large, it may be corrupt".to_string()))?;
+        if map.len() != expected {
+            return Err(invalid(format!("expected {} bytes but found {}, it may be truncated", expected, map.len())));
+        }
+        let by_coordinates = HEADER_LEN + frequencies * FREQUENCY_LEN + records * RECORD_LEN;
+        let out_of_range = map[by_coordinates..by_coordinates + records * 4].chunks_exact(4)
+            .map(|record| u32::from_le_bytes(record.try_into().expect("4 bytes")) as usize)
+            .find(|record| *record >= records);
+        if let Some(record) = out_of_range {
+            return Err(invalid(format!("record {} is out of range of the {} records, it may be corrupt", record, records)));
+        }
+        Ok(VariantIndex { map, build, frequencies, records })
+    }
+
+    /// The genome build of the indexed coordinates.
+    pub fn build(&self) -> GenomeBuild {
+        self.build
+    }
+
+    /// The number of variants with an allele frequency.
+    pub fn frequency_count(&self) -> usize {
+        self.frequencies
+    }
+
+    pub fn clinvar_count(&self) -> usize {
+        self.records
+    }
+
+    /// The allele frequency of a variant as a [`GnomadClient`](crate::GnomadClient) would look it up. Variants
+    /// absent from the index have an allele frequency of 0.
+    pub fn allele_frequency(&self, variant: &GenomicVariant) -> Result<AlleleFrequency, AcmgError> {
+        if variant.build != self.build {
+            return Err(AcmgError::Lookup(format!("{} is on {} but the index is of {}", variant, variant.build, self.build)));
+        }
+        if self.frequencies == 0 {
+            return Err(AcmgError::Lookup("the index has no allele frequencies".to_string()));
+        }
+        let key = variant.to_string();
+        let found = binary_search(self.frequencies, |i| self.str(self.frequency_offset(i)).cmp(&key));
+        let Some(i) = found else {
+            return Ok(AlleleFrequency { af: 0.0, homozygotes: Some(0), ..AlleleFrequency::default() });
+        };
+        let offset = self.frequency_offset(i) + STR_LEN;
+        let optional = |count: u32| Some(count).filter(|count| *count != NONE);
+        let population = self.str(offset + 16);
+        Ok(AlleleFrequency {
+            af: f64::from_le_bytes(self.bytes(offset)),
+            population: Some(population.to_string()).filter(|population| !population.is_empty()),
+            allele_number: optional(u32::from_le_bytes(self.bytes(offset + 8))),
+            homozygotes: optional(u32::from_le_bytes(self.bytes(offset + 12))),
+            heteroplasmic_af: None,
+        })
+    }
+
+    /// What ClinVar has to say about a variant, as [`ClinVarIndex::report`] but reading only the variant's record
+    /// and the records of its gene.
+    pub fn clinvar_report(&self, query: &VariantQuery) -> ClinVarReport {
+        let mut index = ClinVarIndex::default();
+        let gene = match query {
+            VariantQuery::Coordinates(variant) => {
+                let key = variant.to_string();
+                let found = binary_search(self.records, |i| self.str(self.record_offset(self.by_coordinates(i))).cmp(&key));
+                match found.map(|i| self.record(self.by_coordinates(i))) {
+                    Some(record) => record.gene,
+                    None => return index.report(query),
+                }
+            }
+            VariantQuery::Hgvs { gene, .. } => gene.clone(),
+        };
+        let start = partition_point(self.records, |i| self.str(self.record_offset(i) + STR_LEN * 3) < gene.as_str());
+        (start..self.records)
+            .take_while(|i| self.str(self.record_offset(*i) + STR_LEN * 3) == gene)
+            .for_each(|i| index.insert(self.record(i)));
+        index.report(query)
+    }
+
+    fn frequency_offset(&self, i: usize) -> usize {
+        HEADER_LEN + i * FREQUENCY_LEN
+    }
+
+    fn record_offset(&self, i: usize) -> usize {
+        HEADER_LEN + self.frequencies * FREQUENCY_LEN + i * RECORD_LEN
+    }
+
+    /// The number of the record with the `i`th smallest coordinates, checked to be one of the records on opening.
+    fn by_coordinates(&self, i: usize) -> usize {
+        let offset = HEADER_LEN + self.frequencies * FREQUENCY_LEN + self.records * RECORD_LEN + i * 4;
+        u32::from_le_bytes(self.bytes(offset)) as usize
+    }
+
+    fn record(&self, i: usize) -> ClinVarRecord {
+        let offset = self.record_offset(i);
+        let field = |n: usize| self.str(offset + STR_LEN * n).to_string();
+        let fixed = offset + STR_LEN * 8;
+        ClinVarRecord {
+            variation_id: field(1),
+            name: field(2),
+            gene: field(3),
+            clinical_significance: SIGNIFICANCES.get(self.map[fixed + 8] as usize).copied().unwrap_or(ClinicalSignificance::Other),
+            review_status: field(4),
+            chromosome: field(5),
+            position: u64::from_le_bytes(self.bytes(fixed)),
+            reference: field(6),
+            alternate: field(7),
+        }
+    }
+
+    fn bytes<const N: usize>(&self, offset: usize) -> [u8; N] {
+        self.map[offset..offset + N].try_into().expect("in bounds of the checked length")
+    }
+
+    /// The string referred to at an offset, empty if the index is corrupt.
+    fn str(&self, offset: usize) -> &str {
+        let strings = HEADER_LEN + self.frequencies * FREQUENCY_LEN + self.records * (RECORD_LEN + 4);
+        let start = usize::try_from(u64::from_le_bytes(self.bytes(offset))).ok().and_then(|start| strings.checked_add(start));
+        let len = u32::from_le_bytes(self.bytes(offset + 8)) as usize;
+        start.and_then(|start| self.map.get(start..start.checked_add(len)?))
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .unwrap_or_default()
+    }
+}
+
+fn binary_search(len: usize, compare: impl Fn(usize) -> std::cmp::Ordering) -> Option<usize> {
+    let start = partition_point(len, |i| compare(i).is_lt());
+    Some(start).filter(|start| *start < len && compare(*start).is_eq())
+}
+
+/// The first of `0..len` for which `before` is false, given it's true for all of those before it.
+fn partition_point(len: usize, before: impl Fn(usize) -> bool) -> usize {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if before(mid) { low = mid + 1 } else { high = mid }
+    }
+    low
+}
+
+/// Counts observed for a variant across the gnomAD files indexed, e.g. the exomes and genomes.
+#[derive(Debug, Default)]
+struct Observed {
+    ac: u64,
+    an: u64,
+    homozygotes: u64,
+    popmax: Option<(f64, Option<String>)>,
+}
+
+impl Observed {
+    /// The allele frequency as the gnomAD API lookups give it: the highest popmax filtering allele frequency, or the
+    /// raw allele frequency if too few alleles were observed to calculate it.
+    fn allele_frequency(&self) -> AlleleFrequency {
+        let (af, population) = match &self.popmax {
+            Some((popmax, population)) => (*popmax, population.clone()),
+            None if self.an > 0 => (self.ac as f64 / self.an as f64, None),
+            None => (0.0, None),
+        };
+        let count = |count: u64| Some(count.min(NONE as u64 - 1) as u32);
+        AlleleFrequency { af, population, allele_number: count(self.an), homozygotes: count(self.homozygotes), heteroplasmic_af: None }
+    }
+}
+
+/// Builds a [`VariantIndex`] from gnomAD sites VCFs and the ClinVar variant_summary.txt. The variants are held in
+/// memory until the index is written.
+#[derive(Debug)]
+pub struct IndexBuilder {
+    build: GenomeBuild,
+    frequencies: HashMap<String, Observed>,
+    records: Vec<ClinVarRecord>,
+}
+
+impl IndexBuilder {
+    pub fn new(build: GenomeBuild) -> IndexBuilder {
+        IndexBuilder { build, frequencies: HashMap::new(), records: vec![] }
+    }
+
+    /// Adds the allele frequencies of a gnomAD sites VCF, returning the number of variants read. Reading both the
+    /// exomes and genomes combines their counts as the gnomAD API does. Symbolic alleles are skipped.
+    pub fn add_gnomad_vcf(&mut self, reader: impl BufRead) -> Result<usize, AcmgError> {
+        let mut variants = 0;
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|error| AcmgError::InvalidVcf(error.to_string()))?;
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            let invalid = |reason: &str| AcmgError::InvalidVcf(format!("line {}: {}", number + 1, reason));
+            let fields: Vec<&str> = line.splitn(9, '\t').collect();
+            if fields.len() < 8 {
+                return Err(invalid("expected at least 8 columns"));
+            }
+            let position: u64 = fields[1].parse().map_err(|_| invalid("invalid position"))?;
+            let info: HashMap<&str, &str> = fields[7].split(';').filter_map(|entry| entry.split_once('=')).collect();
+            for (allele, alternate) in fields[4].split(',').enumerate() {
+                let Ok(variant) = GenomicVariant::new(self.build, fields[0], position, fields[3], alternate) else {
+                    continue;
+                };
+                let value = |keys: &[&str]| keys.iter().find_map(|key| info.get(key))
+                    .map(|values| values.split(',').nth(allele).unwrap_or(values));
+                let count = |keys: &[&str]| value(keys).and_then(|count| count.parse::<u64>().ok()).unwrap_or_default();
+                let observed = self.frequencies.entry(variant.to_string()).or_default();
+                observed.ac += count(&["AC"]);
+                observed.an += count(&["AN"]);
+                observed.homozygotes += count(&["nhomalt"]);
+                let popmax = value(&["fafmax_faf95_max", "faf95_popmax"]).and_then(|popmax| popmax.parse::<f64>().ok());
+                if let Some(popmax) = popmax.filter(|popmax| observed.popmax.as_ref().is_none_or(|(max, _)| popmax > max)) {
+                    let population = value(&["fafmax_faf95_max_gen_anc", "popmax"]).map(str::to_string);
+                    observed.popmax = Some((popmax, population));
+                }
+                variants += 1;
+            }
+        }
+        Ok(variants)
+    }
+
+    /// Adds the records of the ClinVar variant_summary.txt on the builder's genome build, returning the number added.
+    pub fn add_clinvar(&mut self, reader: impl BufRead) -> Result<usize, AcmgError> {
+        let index = ClinVarIndex::from_variant_summary(reader, self.build.name())?;
+        self.records.extend(index.records().iter().cloned());
+        Ok(index.len())
+    }
+
+    /// Writes the index, returning the number of allele frequencies and ClinVar records written.
+    pub fn write(&self, mut out: impl Write) -> io::Result<(usize, usize)> {
+        let mut strings = Strings::default();
+        let mut frequencies: Vec<(&String, &Observed)> = self.frequencies.iter().collect();
+        frequencies.sort_unstable_by_key(|(key, _)| *key);
+        let mut records: Vec<&ClinVarRecord> = self.records.iter().collect();
+        records.sort_by(|a, b| (&a.gene, &a.name).cmp(&(&b.gene, &b.name)));
+        let keys: Vec<String> = records.iter().map(|record| {
+            format!("{}-{}-{}-{}", record.chromosome, record.position, record.reference, record.alternate)
+        }).collect();
+        let mut by_coordinates: Vec<u32> = (0..records.len() as u32).collect();
+        by_coordinates.sort_by_key(|i| &keys[*i as usize]);
+
+        let mut body = Vec::with_capacity(frequencies.len() * FREQUENCY_LEN + records.len() * (RECORD_LEN + 4));
+        for (key, observed) in &frequencies {
+            let frequency = observed.allele_frequency();
+            strings.push(&mut body, key);
+            body.extend(frequency.af.to_le_bytes());
+            body.extend(frequency.allele_number.unwrap_or(NONE).to_le_bytes());
+            body.extend(frequency.homozygotes.unwrap_or(NONE).to_le_bytes());
+            strings.intern(&mut body, frequency.population.as_deref().unwrap_or_default());
+        }
+        for (record, key) in records.iter().zip(&keys) {
+            strings.push(&mut body, key);
+            strings.push(&mut body, &record.variation_id);
+            strings.push(&mut body, &record.name);
+            strings.intern(&mut body, &record.gene);
+            strings.intern(&mut body, &record.review_status);
+            strings.intern(&mut body, &record.chromosome);
+            strings.intern(&mut body, &record.reference);
+            strings.intern(&mut body, &record.alternate);
+            body.extend(record.position.to_le_bytes());
+            let significance = SIGNIFICANCES.iter().position(|significance| *significance == record.clinical_significance);
+            body.extend([significance.unwrap_or(SIGNIFICANCES.len() - 1) as u8, 0, 0, 0, 0, 0, 0, 0]);
+        }
+        by_coordinates.iter().for_each(|i| body.extend(i.to_le_bytes()));
+
+        out.write_all(VariantIndex::MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        out.write_all(&[u8::from(self.build == GenomeBuild::Grch38), 0, 0, 0])?;
+        for count in [frequencies.len(), records.len(), strings.bytes.len()] {
+            out.write_all(&(count as u64).to_le_bytes())?;
+        }
+        out.write_all(&body)?;
+        out.write_all(&strings.bytes)?;
+        out.flush()?;
+        Ok((frequencies.len(), records.len()))
+    }
+}
+
+/// The string table of an index being written, sharing repeated strings such as gene names.
+#[derive(Debug, Default)]
+struct Strings {
+    bytes: Vec<u8>,
+    interned: HashMap<String, (u64, u32)>,
+}
+
+impl Strings {
+    fn push(&mut self, out: &mut Vec<u8>, s: &str) {
+        let offset = self.bytes.len() as u64;
+        self.bytes.extend(s.as_bytes());
+        write_str(out, offset, s.len() as u32);
+    }
+
+    fn intern(&mut self, out: &mut Vec<u8>, s: &str) {
+        let (offset, len) = match self.interned.get(s) {
+            Some(interned) => *interned,
+            None => {
+                let interned = (self.bytes.len() as u64, s.len() as u32);
+                self.bytes.extend(s.as_bytes());
+                self.interned.insert(s.to_string(), interned);
+                interned
+            }
+        };
+        write_str(out, offset, len);
+    }
+}
+
+fn write_str(out: &mut Vec<u8>, offset: u64, len: u32) {
+    out.extend(offset.to_le_bytes());
+    out.extend(len.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GNOMAD: &str = "##fileformat=VCFv4.2\n\
+        #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n\
+        17\t43045712\t.\tG\tA,T\t.\tPASS\tAC=5,1;AN=1000;nhomalt=1,0;faf95_popmax=0.004,.;popmax=nfe,.\n";
+    const CLINVAR: &str = "VariationID\tName\tGeneSymbol\tClinicalSignificance\tReviewStatus\tAssembly\tChromosome\tPositionVCF\tReferenceAlleleVCF\tAlternateAlleleVCF\n\
+        55555\tNM_007294.4(BRCA1):c.5266dup\tBRCA1\tPathogenic\treviewed by expert panel\tGRCh38\t17\t43057062\tT\tTG\n\
+        17661\tNM_007294.4(BRCA1):c.68_69del\tBRCA1\tPathogenic\treviewed by expert panel\tGRCh38\t17\t43124027\tACT\tA\n\
+        12345\tNM_000059.4(BRCA2):c.100G>A\tBRCA2\tBenign\tcriteria provided, single submitter\tGRCh38\t13\t32316500\tG\tA\n";
+
+    fn index_bytes() -> Vec<u8> {
+        let mut builder = IndexBuilder::new(GenomeBuild::Grch38);
+        assert_eq!(builder.add_gnomad_vcf(GNOMAD.as_bytes()).unwrap(), 2);
+        assert_eq!(builder.add_clinvar(CLINVAR.as_bytes()).unwrap(), 3);
+        let mut bytes = vec![];
+        assert_eq!(builder.write(&mut bytes).unwrap(), (2, 3));
+        bytes
+    }
+
+    fn open(name: &str, bytes: &[u8]) -> Result<VariantIndex, AcmgError> {
+        let path = std::env::temp_dir().join(format!("acmg-{}-{}.idx", name, std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        let index = VariantIndex::open(&path);
+        std::fs::remove_file(&path).unwrap();
+        index
+    }
+
+    fn variant(position: u64, reference: &str, alternate: &str) -> GenomicVariant {
+        GenomicVariant::new(GenomeBuild::Grch38, "17", position, reference, alternate).unwrap()
+    }
+
+    #[test]
+    fn built_index_opens_and_looks_up_variants() {
+        let index = open("round-trip", &index_bytes()).unwrap();
+        assert_eq!((index.build(), index.frequency_count(), index.clinvar_count()), (GenomeBuild::Grch38, 2, 3));
+
+        let frequency = index.allele_frequency(&variant(43045712, "G", "A")).unwrap();
+        assert_eq!((frequency.af, frequency.population.as_deref(), frequency.allele_number, frequency.homozygotes),
+                   (0.004, Some("nfe"), Some(1000), Some(1)));
+        let frequency = index.allele_frequency(&variant(43045712, "G", "T")).unwrap();
+        assert_eq!((frequency.af, frequency.population), (0.001, None));
+        assert_eq!(index.allele_frequency(&variant(43045712, "G", "C")).unwrap().af, 0.0);
+
+        let report = index.clinvar_report(&VariantQuery::Coordinates(variant(43124027, "ACT", "A")));
+        let record = report.record.unwrap();
+        assert_eq!((record.variation_id.as_str(), record.gene.as_str(), record.clinical_significance),
+                   ("17661", "BRCA1", ClinicalSignificance::Pathogenic));
+        let report = index.clinvar_report(&VariantQuery::Coordinates(variant(43000000, "A", "G")));
+        assert_eq!(report.record, None);
+    }
+
+    #[test]
+    fn truncated_index_is_invalid() {
+        let bytes = index_bytes();
+        assert!(matches!(open("truncated", &bytes[..bytes.len() - 1]), Err(AcmgError::InvalidIndex(_))));
+        assert!(matches!(open("header", &bytes[..HEADER_LEN - 1]), Err(AcmgError::InvalidIndex(_))));
+    }
+
+    #[test]
+    fn overflowing_header_counts_are_invalid() {
+        let mut bytes = index_bytes();
+        bytes[24..32].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(open("overflow", &bytes), Err(AcmgError::InvalidIndex(_))));
+    }
+
+    #[test]
+    fn record_numbers_out_of_range_are_invalid() {
+        let mut bytes = index_bytes();
+        let by_coordinates = HEADER_LEN + 2 * FREQUENCY_LEN + 3 * RECORD_LEN;
+        bytes[by_coordinates + 4..by_coordinates + 8].copy_from_slice(&3u32.to_le_bytes());
+        assert!(matches!(open("corrupt", &bytes), Err(AcmgError::InvalidIndex(_))));
+    }
+}
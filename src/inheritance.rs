@@ -0,0 +1,105 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::Code::{self, BP2, BS2, PM3};
+use crate::{AcmgError, Diagnostic, EvidenceSet, Rule};
+
+/// The mode of inheritance of the disorder, which determines whether some codes apply and what they require.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum Inheritance {
+    #[cfg_attr(feature = "serde", serde(rename = "AD"))]
+    AutosomalDominant,
+    #[cfg_attr(feature = "serde", serde(rename = "AR"))]
+    AutosomalRecessive,
+    #[cfg_attr(feature = "serde", serde(rename = "XLR"))]
+    XLinkedRecessive,
+    #[cfg_attr(feature = "serde", serde(rename = "XLD"))]
+    XLinkedDominant,
+    #[cfg_attr(feature = "serde", serde(rename = "MT"))]
+    Mitochondrial,
+}
+
+impl Inheritance {
+    pub const ALL: [Inheritance; 5] = [
+        Inheritance::AutosomalDominant, Inheritance::AutosomalRecessive, Inheritance::XLinkedRecessive,
+        Inheritance::XLinkedDominant, Inheritance::Mitochondrial,
+    ];
+
+    /// The abbreviation, e.g. `AR`.
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Inheritance::AutosomalDominant => "AD",
+            Inheritance::AutosomalRecessive => "AR",
+            Inheritance::XLinkedRecessive => "XLR",
+            Inheritance::XLinkedDominant => "XLD",
+            Inheritance::Mitochondrial => "MT",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Inheritance::AutosomalDominant => "autosomal dominant",
+            Inheritance::AutosomalRecessive => "autosomal recessive",
+            Inheritance::XLinkedRecessive => "X-linked recessive",
+            Inheritance::XLinkedDominant => "X-linked dominant",
+            Inheritance::Mitochondrial => "mitochondrial",
+        }
+    }
+}
+
+impl Display for Inheritance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// Parses the abbreviation or the name of a mode of inheritance, e.g. `AR` or `autosomal-recessive`.
+impl FromStr for Inheritance {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<Inheritance, Self::Err> {
+        let name = s.trim().replace(['-', '_'], " ");
+        Inheritance::ALL.into_iter()
+            .find(|inheritance| inheritance.abbreviation().eq_ignore_ascii_case(&name) || inheritance.name().eq_ignore_ascii_case(&name))
+            .ok_or_else(|| AcmgError::UnknownInheritance(s.to_string()))
+    }
+}
+
+/// Codes which don't apply under a mode of inheritance, and why.
+const INAPPLICABLE: &[(Code, &[Inheritance], &str)] = &[
+    (PM3, &[Inheritance::AutosomalDominant, Inheritance::XLinkedDominant, Inheritance::Mitochondrial],
+     "PM3 only applies to recessive disorders, for variants detected in trans with a pathogenic variant"),
+    (BP2, &[Inheritance::Mitochondrial], "BP2 doesn't apply to mitochondrial variants, which have no zygosity"),
+];
+
+/// What codes require under a mode of inheritance.
+const REQUIREMENTS: &[(Code, Inheritance, &str)] = &[
+    (PM3, Inheritance::XLinkedRecessive, "PM3 only applies to observations in females, as males are hemizygous for X-linked variants"),
+    (BS2, Inheritance::AutosomalRecessive, "BS2 requires the variant to be observed homozygous in a healthy adult"),
+    (BS2, Inheritance::XLinkedRecessive, "BS2 requires the variant to be observed hemizygous in a healthy adult male"),
+    (BS2, Inheritance::XLinkedDominant, "BS2 requires the variant to be observed hemizygous in a healthy adult male or heterozygous in a healthy adult female"),
+    (BP2, Inheritance::AutosomalRecessive, "BP2 only applies to observations in cis with a pathogenic variant, as observations in trans are expected"),
+];
+
+/// Checks the evidence against the mode of inheritance of the disorder, with a [`Rule::InapplicableInheritance`]
+/// error for codes which don't apply under it and a [`Rule::InheritanceRequirement`] warning for codes which
+/// require particular observations under it, e.g. BS2 a homozygous observation for a recessive disorder.
+pub fn check_inheritance(evidence_set: &EvidenceSet, inheritance: Inheritance) -> Vec<Diagnostic> {
+    let applied = |code: Code| -> Vec<String> {
+        evidence_set.iter().filter(|evidence| evidence.evidence_code == code.evidence_code()).map(|evidence| evidence.to_string()).collect()
+    };
+    let inapplicable = INAPPLICABLE.iter()
+        .filter(|(_, modes, _)| modes.contains(&inheritance))
+        .map(|(code, _, reason)| (*code, Rule::InapplicableInheritance, format!("{} is not applicable under {} inheritance: {}", code, inheritance, reason)));
+    let requirements = REQUIREMENTS.iter()
+        .filter(|(_, mode, _)| *mode == inheritance)
+        .map(|(code, _, requirement)| (*code, Rule::InheritanceRequirement, format!("Under {} inheritance {}", inheritance, requirement)));
+    inapplicable.chain(requirements)
+        .filter_map(|(code, rule, message)| {
+            let applied = applied(code);
+            (!applied.is_empty()).then(|| Diagnostic::new(rule, message, applied))
+        })
+        .collect()
+}
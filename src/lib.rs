@@ -0,0 +1,124 @@
+//! Scoring and classification of ACMG/AMP evidence codes using the points-based system recommended by
+//! the ClinGen Sequence Variant Interpretation (SVI) group - Tavtigian et al. 2020,
+//! DOI: <https://doi.org/10.1002/humu.24088>
+//!
+//! ```
+//! use acmg::{AcmgAssessment, AcmgClassification, Code, Strength};
+//!
+//! let assessment: AcmgAssessment = "PVS1, PS1, PM2_Supporting".parse().unwrap();
+//! assert_eq!(assessment.points(), 13);
+//! assert_eq!(assessment.classify(), AcmgClassification::Pathogenic);
+//!
+//! let built = AcmgAssessment::builder()
+//!     .add(Code::PVS1)
+//!     .add(Code::PS1)
+//!     .add_with_strength(Code::PM2, Strength::Supporting)
+//!     .build();
+//! assert_eq!(built, assessment);
+//! ```
+//!
+//! Enable the `serde` feature to (de)serialize evidence and [`AssessmentResult`]s.
+
+mod assessment;
+mod ba1_exceptions;
+mod bayes;
+#[cfg(feature = "gnomad")]
+mod cache;
+mod calibration;
+mod case_control;
+mod citation;
+mod classification;
+mod clingen;
+mod clinvar;
+mod cnv;
+mod code;
+mod conflicts;
+mod constraint;
+mod de_novo;
+mod diff;
+mod error;
+mod evidence;
+mod evidence_set;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod frequency;
+#[cfg(feature = "gnomad")]
+mod gnomad;
+mod guidance;
+mod guideline;
+mod hgvs;
+#[cfg(feature = "index")]
+mod index;
+mod inheritance;
+mod notation;
+mod oncogenicity;
+mod parser;
+mod pm3;
+mod pvs1;
+mod registry;
+mod regions;
+mod risk_allele;
+mod schema;
+mod secondary_findings;
+mod segregation;
+mod somatic;
+mod splicing;
+mod spec;
+mod trio;
+mod validation;
+mod variant;
+mod vep;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod what_if;
+
+pub use assessment::{AcmgAssessment, AcmgAssessmentBuilder, AssessmentResult, ConflictingEvidence, Disagreement};
+pub use ba1_exceptions::{Ba1Exception, Ba1Exceptions};
+pub use bayes::{calc_post_prob_path, BayesianModel};
+#[cfg(feature = "gnomad")]
+pub use cache::LookupCache;
+pub use calibration::Predictor;
+pub use case_control::{CarrierCount, CaseControl};
+pub use citation::{Citation, CitationSource};
+pub use classification::{classification, posterior_classification, AcmgClassification, Classifier, PointSystem};
+pub use clingen::{ClinGenGenes, DosageScore, DosageSensitivity, GeneCuration, GeneDiseaseValidity, ValidityClassification};
+pub use clinvar::{ClinVarIndex, ClinVarRecord, ClinVarReport, ClinVarSuggestion, ClinicalSignificance, ProteinChange, VariantQuery};
+pub use cnv::{CnvAssessment, CnvCriterion, CnvEvidence, CnvInterval, CnvType, DosageCall, GAIN_CRITERIA, LOSS_CRITERIA};
+pub use code::Code;
+pub use conflicts::{check_conflicts, Conflict, CONFLICTS};
+pub use constraint::{ConstraintCall, ConstraintMetrics, GeneConstraint, GeneOverride};
+pub use de_novo::{DeNovoObservations, PhenotypeConsistency};
+pub use diff::{AssessmentDiff, CriterionChange};
+pub use error::AcmgError;
+pub use evidence::{evidence_code, evidence_codes, Category, CodeRef, Evidence, EvidenceCode, EvidenceStrength, EvidenceStrength as Strength, EVIDENCE_CODES};
+pub use evidence_set::EvidenceSet;
+#[cfg(feature = "ffi")]
+pub use ffi::{acmg_classification_term, acmg_classify, acmg_last_error, AcmgResult, AcmgStatus};
+pub use frequency::{AlleleFrequency, DiseaseModel, FrequencyCriteria};
+#[cfg(feature = "gnomad")]
+pub use gnomad::{GnomadClient, GNOMAD_API};
+pub use guidance::{guidance, CodeGuidance, POINTS_CITATION, RETIRED_CODES};
+pub use guideline::{classify_acgs2020, classify_acmg2015, Acgs2020Model, ClassificationModel, Guideline, MitoModel, Points2020Model, Richards2015Model, VusTemperature};
+pub use hgvs::{HgvsVariant, SequenceType};
+#[cfg(feature = "index")]
+pub use index::{IndexBuilder, VariantIndex};
+pub use inheritance::{check_inheritance, Inheritance};
+pub use notation::{extract_evidence, parse_notation, write_notation, EvidenceSpan, Extraction, Notation};
+pub use oncogenicity::{oncogenicity_classification, OncogenicityAssessment, OncogenicityClassification, OncogenicityCode, OncogenicityEvidence};
+pub use parser::{format_evidence, normalize_input, parse_evidence, parse_evidence_set, parse_evidence_set_with_policy, DuplicatePolicy, Tokens};
+pub use pm3::Pm3Observations;
+pub use pvs1::{assess_pvs1, Pvs1Input, Pvs1Outcome, VariantType};
+pub use registry::{CodeDefinition, CodeRegistry};
+pub use regions::{bedgraph_score, Region, Regions};
+pub use risk_allele::{AlleleClassification, AlleleModel};
+pub use schema::{RESULT_SCHEMA, RESULT_SCHEMA_VERSION};
+pub use secondary_findings::{secondary_findings_gene, SecondaryFindingsGene, SfCategory, SF_VERSION};
+pub use segregation::{PedIndividual, Pedigree, SegregationCounts};
+pub use somatic::{EvidenceLevel, SomaticEvidence, SomaticTier};
+pub use splicing::{assess_bp7, spliceai_evidence, Bp7Input, Bp7Outcome, Bp7Thresholds, SpliceAiScores, SPLICEAI_BP4, SPLICEAI_PP3};
+pub use spec::{CriteriaSpec, CriterionSpec, FrequencyThresholds};
+pub use trio::{read_trio_genotypes, Genotype, Trio, TrioOrigin};
+pub use validation::{check_evidence, check_evidence_with_spec, validate, validate_with_inheritance, Diagnostic, Rule, Severity};
+pub use variant::{GenomeBuild, GenomicVariant};
+pub use vep::{read_vep_vcf, suggest_from_consequence, suggested_evidence, ConsequenceSuggestion, VepAnnotation, VepRecord};
+pub use what_if::{classified_combinations, evidence_combinations, what_if, EvidenceCombination, WhatIf, WhatIfRemoval, WhatIfTarget, MAX_ADDITIONS};
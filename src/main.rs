@@ -1,19 +1,76 @@
-use std::cmp::PartialEq;
-use std::collections::{BTreeSet, HashMap};
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+use std::env;
+use std::path::PathBuf;
+use std::process;
 
-use clap::{Parser, Subcommand};
-use lazy_static::lazy_static;
-use regex::Regex;
+use clap::{ArgAction, CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::Shell;
 
-use crate::Category::{Benign, Pathogenic};
-use crate::EvidenceStrength::{Moderate, StandAlone, Strong, Supporting, VeryStrong};
+use acmg::{AcmgAssessment, AcmgError, Bp7Input, Bp7Thresholds, CarrierCount, CaseControl, Category, Classifier, CnvInterval, CnvType, Code, DeNovoObservations, EvidenceLevel, EvidenceStrength, Genotype, Guideline, Inheritance, Notation, PhenotypeConsistency, Pm3Observations, SegregationCounts, SomaticEvidence, Trio, VariantType};
+
+#[cfg(feature = "server")]
+use crate::cli::auth::AccessArgs;
+use crate::cli::batch::{parse_error_rate, run_batch, BatchFormat, BatchOptions};
+use crate::cli::bp7::{bp7_input, run_bp7};
+use crate::cli::classify::run_classify;
+use crate::cli::clinvar::run_clinvar;
+use crate::cli::cnv::{parse_section_score, run_cnv};
+use crate::cli::combos::{run_combos, Target};
+use crate::cli::compare::run_compare_intervar;
+use crate::cli::compare_reviewers::{run_compare_reviewers, ReviewerColumns};
+use crate::cli::completions::run_completions;
+use crate::cli::config::configure;
+use crate::cli::convert::run_convert;
+use crate::cli::daemon::run_daemon;
+#[cfg(feature = "db")]
+use crate::cli::db::{run_db, DbCommand};
+use crate::cli::de_novo::run_de_novo;
+use crate::cli::describe::{run_describe, CodeParser};
+use crate::cli::diff::run_diff;
+use crate::cli::export::{run_export_clinvar, run_export_fhir, ClinVarOptions, ExportFormat};
+use crate::cli::extract::run_extract;
+#[cfg(feature = "index")]
+use crate::cli::index::run_index_build;
+use crate::cli::fmt::run_fmt;
+use crate::cli::info::{run_info, run_info_stream, Format};
+#[cfg(feature = "tui")]
+use crate::cli::interactive::run_interactive;
+use crate::cli::list::{run_list, ListFormat};
+use crate::cli::logging::{init_logging, LogFormat};
+use crate::cli::oncogenicity::run_oncogenicity;
+use crate::cli::phenopacket::run_phenopacket;
+use crate::cli::plot::PlotFormat;
+use crate::cli::pm3::run_pm3;
+use crate::cli::ps4::run_ps4;
+use crate::cli::pvs1::{run_pvs1, Pvs1Args};
+use crate::cli::report::{run_report, DocumentFormat};
+use crate::cli::schema::run_schema;
+use crate::cli::stats::run_stats;
+use crate::cli::segregation::{read_ped_counts, run_segregation};
+use crate::cli::sensitivity::{parse_sweep, run_sensitivity, SensitivityFormat, Sweep};
+#[cfg(feature = "server")]
+use crate::cli::serve::run_serve;
+use crate::cli::somatic::run_somatic_tier;
+use crate::cli::suggest::{run_suggest, ConstraintArgs};
+use crate::cli::trio::{parse_samples, read_trio_vcf, run_trio};
+use crate::cli::validate::run_validate;
+use crate::cli::what_if::run_what_if;
+use crate::cli::{assessment_exit_code, CliError, ExitOn, ReportFormat, ScoringArgs};
+
+mod cli;
 
 #[derive(Parser)]
 #[command(name = "acmg", version = "0.1.0")]
 #[command(bin_name = "acmg")]
 struct Cli {
+    /// Profile of the config files to take defaults from, e.g. cardiolab
+    #[arg(long, env = "ACMG_PROFILE", global = true)]
+    profile: Option<String>,
+    /// Log external lookups and cache hits with -v, and the time taken by each record with -vv
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Format of the log written to stderr
+    #[arg(long, value_enum, env = "ACMG_LOG_FORMAT", default_value_t = LogFormat::Text, global = true)]
+    log_format: LogFormat,
     #[command(subcommand)]
     command: Commands,
 }
@@ -26,223 +83,988 @@ enum Commands {
         about = "Calculates ACMG score and classifies pathogenicity from ACMG evidence codes",
     )]
     Info {
+        /// ACMG evidence string, e.g 'PVS1, PM2_Supporting', or '-' to read one evidence string per line from stdin
+        acmg_evidence: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Plot the points of each criterion against the classification thresholds in place of the output format
+        #[arg(long, value_enum)]
+        plot: Option<PlotFormat>,
+        /// Exit with the code of the classification if it's one of these, e.g. 10 for pathogenic and 11 for
+        /// likely-pathogenic, so scripts can branch on it
+        #[arg(long, value_enum, value_delimiter = ',')]
+        exit_on: Vec<ExitOn>,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Classifies the evidence for a variant, including the variant in the output
+    #[command(arg_required_else_help = true, name = "classify")]
+    Classify {
+        /// HGVS description of the variant against a versioned reference sequence, e.g. 'NM_000257.4:c.1208G>A'
+        #[arg(long, required_unless_present = "variant")]
+        hgvs: Option<String>,
+        /// ACMG evidence string, e.g 'PM2, PP3'
+        #[arg(short, long)]
+        evidence: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Plot the points of each criterion against the classification thresholds in place of the output format
+        #[arg(long, value_enum)]
+        plot: Option<PlotFormat>,
+        /// Exit with the code of the classification if it's one of these, e.g. 10 for pathogenic and 11 for
+        /// likely-pathogenic, so scripts can branch on it
+        #[arg(long, value_enum, value_delimiter = ',')]
+        exit_on: Vec<ExitOn>,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Shows which criteria were added, removed or re-weighted between two assessments and whether the classification changed
+    #[command(arg_required_else_help = true, name = "diff")]
+    Diff {
+        /// The earlier assessment, as the id of a stored assessment or an evidence string e.g. 'PM2, PP3'
+        old: String,
+        /// The later assessment, as the id of a stored assessment or an evidence string
+        new: String,
+        /// The assessment database the ids are stored in
+        #[arg(long, env = "ACMG_DB", default_value = "acmg.sqlite")]
+        db: PathBuf,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Rescores the criteria applied by InterVar and reports the variants where the criteria after scoring or the
+    /// classification disagree with InterVar's
+    #[command(arg_required_else_help = true, name = "compare")]
+    Compare {
+        /// InterVar output file, e.g. example.hg19_multianno.txt.intervar
+        #[arg(long)]
+        intervar: PathBuf,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Matches two curators' assessments of the same variants and reports their concordance, with Cohen's kappa, and
+    /// the discordant variants for consensus review
+    #[command(arg_required_else_help = true, name = "compare-reviewers")]
+    CompareReviewers {
+        /// The first curator's CSV or TSV file
+        a: PathBuf,
+        /// The second curator's CSV or TSV file
+        b: PathBuf,
+        /// Column containing the variants to match on
+        #[arg(long, default_value = "variant")]
+        variant_column: String,
+        /// Column containing ACMG evidence
+        #[arg(long, default_value = "acmg_evidence")]
+        evidence_column: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Curates evidence in a terminal UI, with a searchable code picker, notes and a live classification
+    #[cfg(feature = "tui")]
+    #[command(name = "interactive")]
+    Interactive {
+        /// Assessment JSON to load and save to, created on first save
+        file: Option<PathBuf>,
+        /// ACMG evidence string to start from, e.g 'PM2, PP3'
+        #[arg(short, long)]
+        evidence: Option<String>,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Shows the additional evidence needed to reach Likely pathogenic and Pathogenic, and which single criteria removals
+    /// would change the classification
+    #[command(arg_required_else_help = true, name = "what-if")]
+    WhatIf {
+        /// ACMG evidence string, e.g 'PM2, PP3'
+        acmg_evidence: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Classifies the evidence across ranges of the prior probability and OddsPath parameters of the Bayesian model
+    #[command(arg_required_else_help = true, name = "sensitivity")]
+    Sensitivity {
+        /// ACMG evidence string, e.g 'PM2, PP3'
+        acmg_evidence: String,
+        /// Prior probabilities to sweep as START:END:STEP, e.g. 0.05:0.2:0.05, defaulting to --prior
+        #[arg(long, value_parser = parse_sweep)]
+        prior_range: Option<Sweep>,
+        /// Very Strong OddsPaths to sweep as START:END:STEP, defaulting to --odds-vs
+        #[arg(long, value_parser = parse_sweep)]
+        odds_vs_range: Option<Sweep>,
+        /// Exponential progressions to sweep as START:END:STEP, defaulting to --exponent
+        #[arg(long, value_parser = parse_sweep)]
+        exponent_range: Option<Sweep>,
+        /// Output format, csv for plotting
+        #[arg(short, long, value_enum, default_value_t = SensitivityFormat::Table)]
+        format: SensitivityFormat,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Renders a clinical summary of the evidence for a variant, with each criterion, the score and classification
+    #[command(arg_required_else_help = true, name = "report")]
+    Report {
+        /// HGVS description of the variant against a versioned reference sequence, e.g. 'NM_000257.4:c.1208G>A'
+        #[arg(long)]
+        hgvs: Option<String>,
+        /// ACMG evidence string, e.g 'PS3(PMID:12345), PM2, PP3'
+        #[arg(short, long)]
+        evidence: String,
+        /// Output format, PDF isn't supported
+        #[arg(short, long, value_enum, default_value_t = DocumentFormat::Html)]
+        format: DocumentFormat,
+        /// Tera template to render in place of the built-in layout, e.g. mylab.html.tera, escaping HTML if named .html
+        /// or .html.tera
+        #[arg(long)]
+        template: Option<PathBuf>,
+        /// Output file, defaults to stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Exports the classification of a variant for submission to another system
+    #[command(arg_required_else_help = true, name = "export")]
+    Export {
+        #[command(subcommand)]
+        target: ExportTarget,
+    },
+    /// Classifies the variant interpretations of a GA4GH Phenopacket v2, writing back their ACMG classifications
+    #[command(arg_required_else_help = true, name = "phenopacket")]
+    Phenopacket {
+        /// Phenopacket v2 JSON file, with the evidence for each variant in an acmg_evidence extension of its
+        /// variation descriptor
+        input: PathBuf,
+        /// ACMG evidence string for a Phenopacket with a single variant interpretation, recorded in an acmg_evidence
+        /// extension
+        #[arg(short, long)]
+        evidence: Option<String>,
+        /// Output file, defaults to stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Classifies the evidence in each row of a CSV or TSV file
+    #[command(arg_required_else_help = true, name = "batch")]
+    Batch {
+        /// Input CSV (.csv) or TSV file with a header row, xlsx workbook (.xlsx) or Parquet file (.parquet), or '-' to
+        /// read TSV from stdin
+        #[arg(short, long)]
+        input: PathBuf,
+        /// Worksheet of an xlsx input to read, defaults to the first. Also names the sheet of xlsx output
+        #[arg(long)]
+        sheet: Option<String>,
+        /// Name of the column containing the ACMG evidence string
+        #[arg(short, long, default_value = "acmg_evidence")]
+        evidence_column: String,
+        /// Name of the column containing variant coordinates, e.g. 7-117559590-G-A, for looking up allele frequencies
+        /// with --lookup
+        #[arg(long)]
+        variant_column: Option<String>,
+        /// Name of the column containing SpliceAI delta scores, applying PP3 or BP4 per row as --spliceai
+        #[arg(long)]
+        spliceai_column: Option<String>,
+        /// Output file, defaults to stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = BatchFormat::Tsv)]
+        format: BatchFormat,
+        /// Number of threads classifying rows, or 0 for one per CPU. Rows are written in input order
+        #[arg(long, default_value_t = 1)]
+        threads: usize,
+        /// File to write the rows which fail to, as TSV with their line number and error, rather than the output
+        #[arg(long)]
+        rejects: Option<PathBuf>,
+        /// Fail the run if more than this fraction of rows fail, e.g. 0.05, once every row has been classified
+        #[arg(long, value_name = "FRACTION", value_parser = parse_error_rate)]
+        max_error_rate: Option<f64>,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Lists the built-in evidence codes with their default strengths, points and descriptions
+    #[command(name = "list")]
+    List {
+        /// Only list codes of this category, pathogenic or benign
+        #[arg(short, long)]
+        category: Option<Category>,
+        /// Only list codes with this default strength, e.g. strong
+        #[arg(short, long)]
+        strength: Option<EvidenceStrength>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+    },
+    /// Lists the smallest combinations of evidence strengths which reach a classification under a guideline
+    #[command(arg_required_else_help = true, name = "combos")]
+    Combos {
+        /// The classification to reach
+        #[arg(short, long, value_enum)]
+        target: Target,
+        /// Guideline used to combine the evidence, points2020, acmg2015, acgs2020 or mito
+        #[arg(short, long, default_value_t = Guideline::Points2020)]
+        guideline: Guideline,
+        /// Classify the points-based guideline from the points or the posterior probability
+        #[arg(long, default_value_t = Classifier::Points)]
+        classifier: Classifier,
+        /// The most criteria in a combination
+        #[arg(long, default_value_t = 6)]
+        max_criteria: usize,
+        /// List every combination classified as the target, not only the smallest which reach it
+        #[arg(long)]
+        all: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+    },
+    /// Describes an evidence code, its allowed strengths, caveats and citations
+    #[command(arg_required_else_help = true, name = "describe")]
+    Describe {
+        /// Evidence code, e.g. PVS1
+        #[arg(value_parser = CodeParser, hide_possible_values = true)]
+        code: Code,
+    },
+    /// Summarizes the JSON lines results of a run, e.g. of batch --format jsonl: classification counts, a histogram of
+    /// the scores, the most applied criteria and the records with warnings
+    #[command(arg_required_else_help = true, name = "stats")]
+    Stats {
+        /// JSON lines results, or '-' to read them from stdin
+        input: PathBuf,
+        /// Number of the most applied criteria to list
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Prints the versioned JSON schema of the results written with JSON output, for validating and generating code
+    #[command(name = "schema")]
+    Schema,
+    /// Prints a completion script for a shell, e.g. acmg completions bash > /etc/bash_completion.d/acmg
+    #[command(arg_required_else_help = true, name = "completions")]
+    Completions {
+        /// The shell to complete in
+        shell: Shell,
+    },
+    /// Recommends the strength of PVS1 for a loss of function variant using the Abou Tayoun et al. 2018 decision tree
+    #[command(arg_required_else_help = true, name = "pvs1")]
+    Pvs1 {
+        /// Variant type, e.g. nonsense, frameshift, canonical-splice, initiation-codon, exon-deletion
+        variant_type: VariantType,
+        #[command(flatten)]
+        answers: Pvs1Args,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Decides BP7 for a synonymous variant from its SpliceAI score and conservation
+    #[command(arg_required_else_help = true, name = "bp7")]
+    Bp7 {
+        /// Largest of the SpliceAI acceptor and donor gain and loss delta scores
+        #[arg(long)]
+        spliceai: Option<f64>,
+        /// phyloP score of the nucleotide, e.g. of phyloP100way
+        #[arg(long, conflicts_with = "phylop_bedgraph")]
+        phylop: Option<f64>,
+        /// GERP++ RS score of the nucleotide
+        #[arg(long, conflicts_with = "gerp_bedgraph")]
+        gerp: Option<f64>,
+        /// bedGraph of phyloP scores to read the score at --variant from, e.g. converted with bigWigToBedGraph
+        #[arg(long, requires = "variant")]
+        phylop_bedgraph: Option<PathBuf>,
+        /// bedGraph of GERP++ RS scores to read the score at --variant from
+        #[arg(long, requires = "variant")]
+        gerp_bedgraph: Option<PathBuf>,
+        /// Variant, as VCF coordinates e.g. 7-117559590-G-A
+        #[arg(long)]
+        variant: Option<String>,
+        /// Genome build of the variant coordinates, GRCh37 or GRCh38
+        #[arg(long, default_value_t = acmg::GenomeBuild::Grch38)]
+        build: acmg::GenomeBuild,
+        /// Largest SpliceAI delta score predicting no impact on splicing
+        #[arg(long, default_value_t = Bp7Thresholds::default().spliceai)]
+        spliceai_threshold: f64,
+        /// phyloP score from which the nucleotide is highly conserved
+        #[arg(long, default_value_t = Bp7Thresholds::default().phylop)]
+        phylop_threshold: f64,
+        /// GERP++ RS score from which the nucleotide is highly conserved
+        #[arg(long, default_value_t = Bp7Thresholds::default().gerp)]
+        gerp_threshold: f64,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Tabulates in trans observations into the PM3 strength recommended by the ClinGen SVI
+    #[command(arg_required_else_help = true, name = "pm3")]
+    Pm3 {
+        /// Observations confirmed in trans with a pathogenic or likely pathogenic variant
+        #[arg(long, default_value_t = 0)]
+        pathogenic_in_trans: u32,
+        /// Observations with a pathogenic or likely pathogenic variant, phase unknown
+        #[arg(long, default_value_t = 0)]
+        pathogenic_phase_unknown: u32,
+        /// Observations confirmed in trans with a variant of uncertain significance
+        #[arg(long, default_value_t = 0)]
+        vus_in_trans: u32,
+        /// Homozygous occurrences
+        #[arg(long, default_value_t = 0)]
+        homozygous: u32,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Tests case-control counts for the PS4 strength they support, from the odds ratio and Fisher's exact test
+    #[command(arg_required_else_help = true, name = "ps4")]
+    Ps4 {
+        /// Carriers among affected cases, e.g. 12/800
+        #[arg(long)]
+        cases: CarrierCount,
+        /// Carriers among unaffected controls, e.g. 1/60000
+        #[arg(long)]
+        controls: CarrierCount,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Scores de novo observations into the PS2/PM6 strength recommended by the ClinGen SVI
+    #[command(arg_required_else_help = true, name = "de-novo")]
+    DeNovo {
+        /// Observations with both maternity and paternity confirmed
+        #[arg(long, default_value_t = 0)]
+        confirmed: u32,
+        /// Observations with maternity and paternity assumed
+        #[arg(long, default_value_t = 0)]
+        assumed: u32,
+        /// Consistency of the phenotype with the gene, highly-specific, consistent, heterogeneous or inconsistent
+        #[arg(long, default_value_t = PhenotypeConsistency::Consistent)]
+        phenotype: PhenotypeConsistency,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Proposes PS2 or PM6 for a de novo variant, or BS4 if an affected parent lacks it, from the genotypes of a trio
+    #[command(arg_required_else_help = true, name = "trio")]
+    Trio {
+        /// Genotype of the proband, e.g. 0/1, het or hom-alt
+        #[arg(long, required_unless_present = "vcf", conflicts_with = "vcf")]
+        proband: Option<Genotype>,
+        /// Genotype of the mother
+        #[arg(long, required_unless_present = "vcf", conflicts_with = "vcf")]
+        mother: Option<Genotype>,
+        /// Genotype of the father
+        #[arg(long, required_unless_present = "vcf", conflicts_with = "vcf")]
+        father: Option<Genotype>,
+        /// Uncompressed multi-sample VCF to read the trio's genotypes from
+        #[arg(long, requires_all = ["variant", "samples"])]
+        vcf: Option<PathBuf>,
+        /// Variant to read from the VCF, as VCF coordinates e.g. 7-117559590-G-A
+        #[arg(long)]
+        variant: Option<String>,
+        /// Genome build of the variant coordinates, GRCh37 or GRCh38
+        #[arg(long, default_value_t = acmg::GenomeBuild::Grch38)]
+        build: acmg::GenomeBuild,
+        /// The proband, mother and father samples of the VCF, in that order, e.g. P1,M1,F1
+        #[arg(long, value_parser = parse_samples)]
+        samples: Option<[String; 3]>,
+        /// Maternity and paternity are confirmed, proposing PS2 rather than PM6 for a de novo variant
+        #[arg(long)]
+        parentage_confirmed: bool,
+        /// The mother is affected with the proband's disorder
+        #[arg(long)]
+        mother_affected: bool,
+        /// The father is affected with the proband's disorder
+        #[arg(long)]
+        father_affected: bool,
+        /// Consistency of the phenotype with the gene, highly-specific, consistent, heterogeneous or inconsistent
+        #[arg(long, default_value_t = PhenotypeConsistency::Consistent)]
+        phenotype: PhenotypeConsistency,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Converts counts of informative meioses into a LOD score and the PP1 or BS4 evidence it supports
+    #[command(arg_required_else_help = true, name = "segregation")]
+    Segregation {
+        /// Affected relatives carrying the variant, excluding the probands
+        #[arg(long, default_value_t = 0, conflicts_with = "ped")]
+        affected_carriers: u32,
+        /// Unaffected relatives not carrying the variant
+        #[arg(long, default_value_t = 0, conflicts_with = "ped")]
+        unaffected_noncarriers: u32,
+        /// Affected relatives not carrying the variant
+        #[arg(long, default_value_t = 0, conflicts_with = "ped")]
+        affected_noncarriers: u32,
+        /// PED file of the families, counting their informative meioses from the carriers and non-carriers given
+        #[arg(long)]
+        ped: Option<PathBuf>,
+        /// Individuals of the PED file carrying the variant, as FAMILY:ID or by an ID found in only one family
+        #[arg(long, value_delimiter = ',', requires = "ped")]
+        carriers: Vec<String>,
+        /// Individuals of the PED file tested and not carrying the variant
+        #[arg(long, value_delimiter = ',', requires = "ped")]
+        noncarriers: Vec<String>,
+        /// Probands of the PED file, not counted as affected carriers, by default the first affected carrier of each family
+        #[arg(long, value_delimiter = ',', requires = "ped")]
+        probands: Vec<String>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Suggests PS1, PM5, PP5 or BP6 from previously classified variants in a local copy of ClinVar
+    #[command(arg_required_else_help = true, name = "clinvar")]
+    Clinvar {
+        /// Variant, as VCF coordinates e.g. 13-32316467-C-G or HGVS with a gene e.g. BRCA2:p.Arg41Gly
+        variant: String,
+        /// ClinVar variant_summary.txt, uncompressed, or a variant index built with `acmg index build`
+        #[arg(short, long)]
+        index: PathBuf,
+        /// Genome assembly of the coordinates
+        #[arg(long, default_value = "GRCh38")]
+        assembly: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Suggests candidate codes from the VEP consequences of each variant of an annotated VCF, PVS1, PM4, PP2 or BP1,
+    /// with an evidence string for a curator to review
+    #[command(arg_required_else_help = true, name = "suggest")]
+    Suggest {
+        /// VCF annotated by VEP with the CSQ INFO field, uncompressed, or - for stdin
+        #[arg(long)]
+        vep_vcf: PathBuf,
+        /// Genome build of the VCF, GRCh37 or GRCh38
+        #[arg(long, default_value_t = acmg::GenomeBuild::Grch38)]
+        build: acmg::GenomeBuild,
+        /// Output format, JSON with one line per variant
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+        #[command(flatten)]
+        constraint: ConstraintArgs,
+    },
+    /// Stores assessments in a local SQLite database, a lightweight system of record
+    #[cfg(feature = "db")]
+    #[command(arg_required_else_help = true, name = "db")]
+    Db {
+        /// The assessment database
+        #[arg(long, env = "ACMG_DB", default_value = "acmg.sqlite", global = true)]
+        db: PathBuf,
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+    /// Builds and inspects the variant index used for offline lookups
+    #[cfg(feature = "index")]
+    #[command(arg_required_else_help = true, name = "index")]
+    Index {
+        #[command(subcommand)]
+        command: IndexCommand,
+    },
+    /// Scores a copy-number variant with the ACMG/ClinGen CNV rubrics of Riggs et al. 2020
+    #[command(arg_required_else_help = true, name = "cnv")]
+    Cnv {
+        /// Whether the CNV is a copy-number loss or gain
+        cnv_type: CnvType,
+        /// Criteria of sections 1 to 5, optionally with adjusted points, e.g. '1A, 2H, 4A=0.30, 4A'
+        #[arg(default_value = "")]
+        criteria: String,
+        /// Number of protein-coding genes in the CNV, applying the section 3 criterion
+        #[arg(long)]
+        genes: Option<u32>,
+        /// Total score of a section in place of its criteria, e.g. 4=0.45, may be repeated
+        #[arg(long, value_parser = parse_section_score)]
+        section: Vec<(u8, f64)>,
+        /// Interval of the CNV, e.g. chr22:42692121-42721298, on the build of the dosage sensitivity curations
+        #[arg(long, requires = "clingen_dosage")]
+        interval: Option<CnvInterval>,
+        /// ClinGen dosage sensitivity gene curation list TSV, deriving section 2 from the curated genes the interval
+        /// overlaps unless section 2 is given
+        #[arg(long, requires = "interval")]
+        clingen_dosage: Option<PathBuf>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Scores the oncogenicity of a somatic variant with the ClinGen/CGC/VICC codes of Horak et al. 2022
+    #[command(arg_required_else_help = true, name = "oncogenicity")]
+    Oncogenicity {
+        /// Oncogenicity evidence string, e.g. 'OS3, OM1, OP4'
+        evidence: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Classifies a somatic variant into the AMP/ASCO/CAP tiers of Li et al. 2017 from its levels of evidence
+    #[command(name = "somatic-tier")]
+    SomaticTier {
+        /// Strongest level of evidence, A to D, that the variant predicts response or resistance to a therapy
+        #[arg(long)]
+        therapeutic: Option<EvidenceLevel>,
+        /// Strongest level of evidence, A to D, that the variant is diagnostic
+        #[arg(long)]
+        diagnostic: Option<EvidenceLevel>,
+        /// Strongest level of evidence, A to D, that the variant is prognostic
+        #[arg(long)]
+        prognostic: Option<EvidenceLevel>,
+        /// The variant is observed at a significant allele frequency in the general population or known to be benign
+        #[arg(long)]
+        benign: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Answers JSON-RPC 2.0 requests to classify, validate and describe, one per line of stdin, for editor plugins
+    /// and pipelines
+    #[command(name = "daemon")]
+    Daemon {
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Serves the classifier over HTTP, with POST /classify, POST /jobs for large batches and GET /codes
+    #[cfg(feature = "server")]
+    #[command(name = "serve")]
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Port to listen on
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+        #[command(flatten)]
+        access: AccessArgs,
+    },
+    /// Checks an evidence string for errors, exiting with a non-zero code if any are found
+    #[command(arg_required_else_help = true, name = "validate")]
+    Validate {
         /// ACMG evidence string, e.g 'PVS1, PM2_Supporting'
         acmg_evidence: String,
+        /// Mode of inheritance of the disorder, AD, AR, XLR, XLD or MT, checking the codes applied are consistent with it
+        #[arg(long)]
+        inheritance: Option<Inheritance>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Rewrites evidence strings in canonical form, sorted and consistently cased, e.g. 'PVS1, PM2_Supporting, PP3'
+    #[command(arg_required_else_help = true, name = "fmt")]
+    Fmt {
+        /// ACMG evidence string, e.g 'pvs1 , pm2_supporting; pp3', or '-' to format one evidence string per line of stdin
+        acmg_evidence: String,
+        /// Print nothing and exit with a non-zero code if any evidence string isn't in canonical form
+        #[arg(long)]
+        check: bool,
+    },
+    /// Converts evidence strings between notations, reading strengths written as 'PM2 Supporting', 'PVS1 (Moderate)',
+    /// 'PM2_P' or InterVar's 'PVS1=1 PS=[0, 0, 0, 0, 0] ...' flags
+    #[command(arg_required_else_help = true, name = "convert")]
+    Convert {
+        /// ACMG evidence string in any notation, or '-' to convert one evidence string per line of stdin
+        acmg_evidence: String,
+        /// Notation to write, acmg (e.g. 'PM2_Supporting'), spaced ('PM2 Supporting'), parenthesized ('PM2 (Supporting)') or intervar
+        #[arg(long, default_value_t = Notation::Acmg)]
+        to: Notation,
+    },
+    /// Extracts the ACMG codes mentioned in free text, such as report prose or ClinVar assertion criteria comments,
+    /// with strengths written like 'PM2 (supporting)' or 'PVS1-strong', as a canonical evidence string
+    #[command(arg_required_else_help = true, name = "extract")]
+    Extract {
+        /// Text to extract evidence from, or '-' to read it from stdin
+        text: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
     },
 }
 
-fn main() {
-    let args = Cli::parse();
-    match args.command {
-        Commands::Info { acmg_evidence } => {
-            run_info_command(&acmg_evidence);
-        }
-    }
-}
-
-fn run_info_command(acmg_evidence: &str) {
-    let evidence_list = normalize_input(&acmg_evidence);
-    let set = BTreeSet::from_iter(evidence_list.iter()
-        .map(|evidence_code| parse_evidence(evidence_code).unwrap()));
-    let mut score = 0;
-    for evidence in set {
-        let evidence_code = evidence.evidence_code;
-        let points = evidence.points();
-        println!("{:4}:{:2} '{}'", evidence, points, evidence_code.description);
-        score += points;
-    }
-    println!("--------");
-    println!("Classification: {:?}", classification(score));
-    println!("ACMG Score: {}", score);
-    println!("Post Prob Path: {:.3}", calc_post_prob_path(score));
-}
-
-fn normalize_input(acmg_evidence: &str) -> Vec<String> {
-    let re = Regex::new(r"[\[\]]").unwrap();
-    let cleaned = re.replace_all(acmg_evidence, "").trim().to_string();
-    Regex::new(r"[ ,]+").unwrap().split(&cleaned).map(|s| s.to_string()).collect()
-}
-
-fn parse_evidence(evidence: &str) -> Result<Evidence, String> {
-    if let Some(caps) = RE.captures(&evidence.to_uppercase()) {
-        let ev_code_str = caps.get(1).map_or("", |m| m.as_str());
-        let evidence_code = match EVIDENCE_CODES.get(ev_code_str) {
-            Some(ev) => ev,
-            None => return Err(format!("Invalid evidence code {}", ev_code_str)),
-        };
-
-        let modifier = match caps.get(3).map_or("", |m| m.as_str()).to_uppercase().as_str() {
-            "STANDALONE" => Option::from(StandAlone),
-            "VERYSTRONG" => Option::from(VeryStrong),
-            "STRONG" => Option::from(Strong),
-            "MODERATE" => Option::from(Moderate),
-            "SUPPORTING" => Option::from(Supporting),
-            "" => None,
-            s => return Err(format!("Invalid modifier '{}' for evidence code {}", s, evidence)),
-        };
-        return Ok(Evidence { evidence_code, modifier });
-    }
-    Err(format!("Unable to parse evidence code {}", evidence))
-}
-
-fn calc_post_prob_path(points: i32) -> f64 {
-    let odds_path = ODDS_PATH_SUPPORTING.powi(points);
-    (odds_path * PRIOR_PROB) / ((odds_path - 1.0) * PRIOR_PROB + 1.0)
-}
-
-fn classification(points: i32) -> AcmgClassification {
-    match points {
-        p if p >= 10 => AcmgClassification::Pathogenic,
-        p if p >= 6 => AcmgClassification::LikelyPathogenic,
-        p if p >= 0 => AcmgClassification::UncertainSignificance,
-        p if p >= -6 => AcmgClassification::LikelyBenign,
-        _ => AcmgClassification::Benign,
-    }
-}
-
-#[derive(Debug)]
-enum AcmgClassification {
-    Pathogenic,
-    LikelyPathogenic,
-    UncertainSignificance,
-    LikelyBenign,
-    Benign,
-}
-
-#[derive(Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
-enum Category {
-    Pathogenic,
-    Benign,
+#[cfg(feature = "index")]
+#[derive(Debug, Subcommand)]
+enum IndexCommand {
+    /// Converts downloaded gnomAD sites VCFs and the ClinVar variant_summary.txt, optionally gzipped, into a
+    /// memory-mapped index for --lookup index and acmg clinvar
+    #[command(name = "build")]
+    #[command(group(clap::ArgGroup::new("sources").required(true).multiple(true).args(["gnomad", "clinvar"])))]
+    Build {
+        /// gnomAD sites VCF, e.g. the exomes and genomes, may be repeated
+        #[arg(long)]
+        gnomad: Vec<PathBuf>,
+        /// ClinVar variant_summary.txt
+        #[arg(long)]
+        clinvar: Option<PathBuf>,
+        /// Genome build of the files, keeping ClinVar records on this build
+        #[arg(long, default_value_t = acmg::GenomeBuild::Grch38)]
+        build: acmg::GenomeBuild,
+        /// Index file to write
+        #[arg(short, long)]
+        output: PathBuf,
+    },
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
-enum EvidenceStrength {
-    StandAlone,
-    VeryStrong,
-    Strong,
-    Moderate,
-    Supporting,
+#[derive(Debug, Subcommand)]
+enum ExportTarget {
+    /// Writes a ClinVar submission record with the classification, assertion criteria and a summary of the evidence
+    #[command(arg_required_else_help = true, name = "clinvar")]
+    Clinvar {
+        /// HGVS description of the variant against a versioned reference sequence, e.g. 'NM_000257.4:c.1208G>A'
+        #[arg(long, required_unless_present = "variant")]
+        hgvs: Option<String>,
+        /// ACMG evidence string, e.g 'PS3(PMID:12345), PM2, PP3'
+        #[arg(short, long)]
+        evidence: String,
+        /// Condition as an ontology identifier, e.g. MONDO:0007254 or OMIM:114480
+        #[arg(long)]
+        condition_id: Option<String>,
+        /// Preferred name of the condition
+        #[arg(long, default_value = "not provided")]
+        condition: String,
+        /// Date the variant was last evaluated, as YYYY-MM-DD
+        #[arg(long)]
+        date_evaluated: Option<String>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Tsv)]
+        format: ExportFormat,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
+    /// Writes a FHIR Bundle of a variant Observation and a diagnostic implication carrying the classification
+    #[command(arg_required_else_help = true, name = "fhir")]
+    Fhir {
+        /// HGVS description of the variant against a versioned reference sequence, e.g. 'NM_000257.4:c.1208G>A'
+        #[arg(long, required_unless_present = "variant")]
+        hgvs: Option<String>,
+        /// ACMG evidence string, e.g 'PS3(PMID:12345), PM2, PP3'
+        #[arg(short, long)]
+        evidence: String,
+        #[command(flatten)]
+        scoring: ScoringArgs,
+    },
 }
 
-impl EvidenceStrength {
-    fn points(&self) -> i32 {
-        match self {
-            StandAlone | VeryStrong => 8,
-            Strong => 4,
-            Moderate => 2,
-            Supporting => 1,
+fn main() {
+    let (command, registry) = configure(Cli::command(), &env::args_os().collect::<Vec<_>>()).unwrap_or_else(|error| exit_with_error(error));
+    let args = Cli::from_arg_matches(&command.get_matches()).unwrap_or_else(|error| error.exit());
+    init_logging(args.verbose, args.log_format);
+    match args.command {
+        Commands::Info { acmg_evidence, format, plot, exit_on, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            if acmg_evidence == "-" && plot.is_some() {
+                exit_with_error(CliError::Acmg(AcmgError::InvalidParameter("--plot takes a single evidence string, not stdin".to_string())));
+            } else if acmg_evidence == "-" && !exit_on.is_empty() {
+                exit_with_error(CliError::Acmg(AcmgError::InvalidParameter("--exit-on takes a single evidence string, not stdin".to_string())));
+            } else if acmg_evidence == "-" {
+                match run_info_stream(format, &scoring) {
+                    Ok(0) => {}
+                    Ok(errors) => {
+                        tracing::error!("{} lines failed to parse", errors);
+                        process::exit(1);
+                    }
+                    Err(error) => exit_with_error(error),
+                }
+            } else {
+                match run_info(&acmg_evidence, format, plot, &scoring) {
+                    Ok(assessment) => exit_on_assessment(&assessment, &exit_on),
+                    Err(error) => exit_with_error(error),
+                }
+            }
         }
-    }
-}
-
-impl FromStr for EvidenceStrength {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<EvidenceStrength, Self::Err> {
-        match s.to_uppercase().as_str() {
-            "A" | "STANDALONE" => Ok(StandAlone),
-            "VS" | "VERYSTRONG" => Ok(VeryStrong),
-            "S" | "STRONG" => Ok(Strong),
-            "M" | "MODERATE" => Ok(Moderate),
-            "P" | "SUPPORTING" => Ok(Supporting),
-            _ => Err(format!("Invalid strength value: {}", s)),
+        Commands::Classify { hgvs, evidence, format, plot, exit_on, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            match run_classify(hgvs.as_deref(), &evidence, format, plot, &scoring) {
+                Ok(assessment) => exit_on_assessment(&assessment, &exit_on),
+                Err(error) => exit_with_error(error),
+            }
         }
-    }
-}
-
-#[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
-struct EvidenceCode {
-    category: Category,
-    strength: EvidenceStrength,
-    code: i32,
-    description: &'static str,
-}
-
-impl Display for EvidenceCode {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let category = match self.category {
-            Pathogenic => "P",
-            Benign => "B",
-        };
-        let strength = match self.strength {
-            StandAlone => "A",
-            VeryStrong => "VS",
-            Strong => "S",
-            Moderate => "M",
-            Supporting => "P",
-        };
-        f.pad(&format!("{}{}{}", category, strength, self.code))
-    }
-}
-
-#[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
-struct Evidence {
-    evidence_code: &'static EvidenceCode,
-    modifier: Option<EvidenceStrength>,
-}
-
-impl Display for Evidence {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match &self.modifier {
-            Some(modifier) => f.pad(&format!("{}_{:?}", self.evidence_code, modifier)),
-            None => f.pad(&format!("{}", self.evidence_code)),
+        Commands::Diff { old, new, db, format, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            if let Err(error) = run_diff(&old, &new, &db, format, &scoring) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Compare { intervar, format, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            if let Err(error) = run_compare_intervar(&intervar, format, &scoring) {
+                exit_with_error(error);
+            }
+        }
+        Commands::CompareReviewers { a, b, variant_column, evidence_column, format, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            let columns = ReviewerColumns { variant_column: &variant_column, evidence_column: &evidence_column };
+            if let Err(error) = run_compare_reviewers(&a, &b, &columns, format, &scoring) {
+                exit_with_error(error);
+            }
+        }
+        #[cfg(feature = "tui")]
+        Commands::Interactive { file, evidence, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            if let Err(error) = run_interactive(file.as_deref(), evidence.as_deref(), &scoring) {
+                exit_with_error(error);
+            }
+        }
+        Commands::WhatIf { acmg_evidence, format, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            if let Err(error) = run_what_if(&acmg_evidence, format, &scoring) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Sensitivity { acmg_evidence, prior_range, odds_vs_range, exponent_range, format, scoring } => {
+            let priors = prior_range.unwrap_or(Sweep::single(scoring.prior));
+            let odds_paths = odds_vs_range.unwrap_or(Sweep::single(scoring.odds_vs));
+            let exponents = exponent_range.unwrap_or(Sweep::single(scoring.exponent));
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            if let Err(error) = run_sensitivity(&acmg_evidence, priors, odds_paths, exponents, format, &scoring) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Report { hgvs, evidence, format, template, output, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            if let Err(error) = run_report(hgvs.as_deref(), &evidence, format, template.as_deref(), output.as_deref(), &scoring) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Export { target: ExportTarget::Clinvar { hgvs, evidence, condition_id, condition, date_evaluated, format, scoring } } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            let options = ClinVarOptions {
+                hgvs: hgvs.as_deref(),
+                evidence: &evidence,
+                condition_id: condition_id.as_deref(),
+                condition: &condition,
+                date_evaluated: date_evaluated.as_deref(),
+                format,
+                scoring: &scoring,
+            };
+            if let Err(error) = run_export_clinvar(&options) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Phenopacket { input, evidence, output, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            if let Err(error) = run_phenopacket(&input, evidence.as_deref(), output.as_deref(), &scoring) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Export { target: ExportTarget::Fhir { hgvs, evidence, scoring } } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            if let Err(error) = run_export_fhir(hgvs.as_deref(), &evidence, &scoring) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Batch { input, sheet, evidence_column, variant_column, spliceai_column, output, format, threads, rejects, max_error_rate, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            let options = BatchOptions {
+                input: &input,
+                output: output.as_deref(),
+                evidence_column: &evidence_column,
+                variant_column: variant_column.as_deref(),
+                spliceai_column: spliceai_column.as_deref(),
+                sheet: sheet.as_deref(),
+                format,
+                threads,
+                rejects: rejects.as_deref(),
+                max_error_rate,
+                scoring: &scoring,
+            };
+            match run_batch(&options) {
+                Ok(summary) => match &rejects {
+                    Some(rejects) if summary.errors > 0 => tracing::info!("Classified {} records, {} errors written to {}", summary.records - summary.errors, summary.errors, rejects.display()),
+                    _ => tracing::info!("Classified {} records, {} errors", summary.records - summary.errors, summary.errors),
+                },
+                Err(error) => exit_with_error(error),
+            }
+        }
+        Commands::List { category, strength, format } => {
+            if let Err(error) = run_list(category, strength, format, &registry) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Combos { target, guideline, classifier, max_criteria, all, format } => {
+            if let Err(error) = run_combos(target, guideline, classifier, max_criteria, all, format) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Describe { code } => {
+            if let Err(error) = run_describe(code) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Pvs1 { variant_type, answers, format } => {
+            if let Err(error) = run_pvs1(variant_type, &answers, format) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Bp7 { spliceai, phylop, gerp, phylop_bedgraph, gerp_bedgraph, variant, build, spliceai_threshold, phylop_threshold, gerp_threshold, format } => {
+            let thresholds = Bp7Thresholds { spliceai: spliceai_threshold, phylop: phylop_threshold, gerp: gerp_threshold };
+            let result = bp7_input(Bp7Input { spliceai, phylop, gerp }, phylop_bedgraph.as_deref(), gerp_bedgraph.as_deref(), variant.as_deref(), build)
+                .and_then(|input| run_bp7(input, &thresholds, format));
+            if let Err(error) = result {
+                exit_with_error(error);
+            }
+        }
+        Commands::Pm3 { pathogenic_in_trans, pathogenic_phase_unknown, vus_in_trans, homozygous, format } => {
+            let observations = Pm3Observations { pathogenic_in_trans, pathogenic_phase_unknown, vus_in_trans, homozygous };
+            if let Err(error) = run_pm3(observations, format) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Ps4 { cases, controls, format } => {
+            if let Err(error) = run_ps4(CaseControl { cases, controls }, format) {
+                exit_with_error(error);
+            }
+        }
+        Commands::DeNovo { confirmed, assumed, phenotype, format } => {
+            if let Err(error) = run_de_novo(DeNovoObservations { confirmed, assumed, phenotype }, format) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Trio { proband, mother, father, vcf, variant, build, samples, parentage_confirmed, mother_affected, father_affected, phenotype, format } => {
+            let genotypes = match (&vcf, &variant, &samples) {
+                (Some(vcf), Some(variant), Some(samples)) => read_trio_vcf(vcf, variant, build, samples.each_ref().map(String::as_str)),
+                _ => Ok([proband, mother, father].map(|genotype| genotype.unwrap_or(Genotype::NoCall))),
+            };
+            let result = genotypes.and_then(|[proband, mother, father]| {
+                run_trio(Trio { proband, mother, father, parentage_confirmed, mother_affected, father_affected, phenotype }, format)
+            });
+            if let Err(error) = result {
+                exit_with_error(error);
+            }
+        }
+        Commands::Segregation { affected_carriers, unaffected_noncarriers, affected_noncarriers, ped, carriers, noncarriers, probands, format } => {
+            let result = match ped {
+                Some(ped) => read_ped_counts(&ped, &carriers, &noncarriers, &probands).and_then(|families| {
+                    let counts = families.values().fold(SegregationCounts::default(), |total, counts| total + *counts);
+                    run_segregation(counts, Some(&families), format)
+                }),
+                None => run_segregation(SegregationCounts { affected_carriers, unaffected_noncarriers, affected_noncarriers }, None, format),
+            };
+            if let Err(error) = result {
+                exit_with_error(error);
+            }
+        }
+        Commands::Clinvar { variant, index, assembly, format } => {
+            if let Err(error) = run_clinvar(&index, &assembly, &variant, format) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Suggest { vep_vcf, build, format, constraint } => {
+            let result = constraint.resolve()
+                .and_then(|constraint| run_suggest(&vep_vcf, build, &constraint, format));
+            if let Err(error) = result {
+                exit_with_error(error);
+            }
+        }
+        #[cfg(feature = "db")]
+        Commands::Db { db, command } => {
+            if let Err(error) = run_db(&db, command, &registry) {
+                exit_with_error(error);
+            }
+        }
+        #[cfg(feature = "index")]
+        Commands::Index { command: IndexCommand::Build { gnomad, clinvar, build, output } } => {
+            if let Err(error) = run_index_build(&gnomad, clinvar.as_deref(), build, &output) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Cnv { cnv_type, criteria, genes, section, interval, clingen_dosage, format } => {
+            let dosage = interval.as_ref().zip(clingen_dosage.as_deref());
+            if let Err(error) = run_cnv(cnv_type, &criteria, genes, &section, dosage, format) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Oncogenicity { evidence, format } => {
+            if let Err(error) = run_oncogenicity(&evidence, format) {
+                exit_with_error(error);
+            }
+        }
+        Commands::SomaticTier { therapeutic, diagnostic, prognostic, benign, format } => {
+            if let Err(error) = run_somatic_tier(SomaticEvidence { therapeutic, diagnostic, prognostic, benign }, format) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Stats { input, top, format } => {
+            if let Err(error) = run_stats(&input, top, format) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Schema => {
+            if let Err(error) = run_schema() {
+                exit_with_error(error);
+            }
+        }
+        Commands::Completions { shell } => {
+            if let Err(error) = run_completions(shell, &mut Cli::command()) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Daemon { scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            if let Err(error) = run_daemon(&scoring) {
+                exit_with_error(error);
+            }
+        }
+        #[cfg(feature = "server")]
+        Commands::Serve { host, port, access, scoring } => {
+            let scoring = scoring.resolve(&registry).unwrap_or_else(|error| exit_with_error(error));
+            let access = access.resolve().unwrap_or_else(|error| exit_with_error(error));
+            if let Err(error) = run_serve(&host, port, scoring, access) {
+                exit_with_error(error);
+            }
+        }
+        Commands::Validate { acmg_evidence, inheritance, format } => {
+            match run_validate(&acmg_evidence, inheritance, format, &registry) {
+                Ok(true) => {}
+                Ok(false) => process::exit(1),
+                Err(error) => exit_with_error(error),
+            }
+        }
+        Commands::Fmt { acmg_evidence, check } => {
+            match run_fmt(&acmg_evidence, check, &registry) {
+                Ok(0) => {}
+                Ok(_) => process::exit(1),
+                Err(error) => exit_with_error(error),
+            }
+        }
+        Commands::Convert { acmg_evidence, to } => {
+            match run_convert(&acmg_evidence, to) {
+                Ok(0) => {}
+                Ok(_) => process::exit(1),
+                Err(error) => exit_with_error(error),
+            }
+        }
+        Commands::Extract { text, format } => {
+            match run_extract(&text, format) {
+                Ok(true) => {}
+                Ok(false) => process::exit(1),
+                Err(error) => exit_with_error(error),
+            }
         }
     }
 }
 
-impl Evidence {
-    fn points(&self) -> i32 {
-        let points = self.modifier.as_ref().unwrap_or(&self.evidence_code.strength).points();
-        if self.evidence_code.category == Pathogenic { points } else { -points }
+/// Exits with 1 if the assessment has any error diagnostics, or the code of its classification if it's one of those to
+/// exit on.
+fn exit_on_assessment(assessment: &AcmgAssessment, exit_on: &[ExitOn]) {
+    if let Some(code) = assessment_exit_code(assessment, exit_on) {
+        process::exit(code);
     }
 }
 
-const PRIOR_PROB: f64 = 0.1;
-const ODDS_PATH_VERY_STRONG: f64 = 350.0;
-const EXPONENTIAL_PROGRESSION: f64 = 2.0;
-lazy_static! {
-    static ref RE: Regex = Regex::new(r"([BP][AVSMP]{1,2}\d{1})(_([A-Z]+))?").unwrap();
-    static ref SUPPORTING_EVIDENCE_EXPONENT: f64 = EXPONENTIAL_PROGRESSION.powf(-3.0); // 0.125
-    static ref ODDS_PATH_SUPPORTING: f64 = ODDS_PATH_VERY_STRONG.powf(*SUPPORTING_EVIDENCE_EXPONENT); // 2.08
-    static ref EVIDENCE_CODES: HashMap<&'static str, EvidenceCode> = HashMap::from([
-        // Path VeryStrong
-        ("PVS1", EvidenceCode{category: Pathogenic, strength: VeryStrong, code: 1, description: "Null variant (nonsense, frameshift, canonical ±1 or 2 splice sites, initiation codon, single or multiexon deletion) in a gene where LOF is a known mechanism of disease"}),
-        // Path Strong
-        ("PS1", EvidenceCode{category: Pathogenic, strength: Strong, code: 1, description: "Same amino acid change as a previously established pathogenic variant regardless of nucleotide change"}),
-        ("PS2", EvidenceCode{category: Pathogenic, strength: Strong, code: 2, description: "De novo (both maternity and paternity confirmed) in a patient with the disease and no family history"}),
-        ("PS3", EvidenceCode{category: Pathogenic, strength: Strong, code: 3, description: "Well-established in vitro or in vivo functional studies supportive of a damaging effect on the gene or gene product"}),
-        ("PS4", EvidenceCode{category: Pathogenic, strength: Strong, code: 4, description: "The prevalence of the variant in affected individuals is significantly increased compared with the prevalence in controls"}),
-        // Path Moderate
-        ("PM1", EvidenceCode{category: Pathogenic, strength: Moderate, code: 1, description: "Located in a mutational hot spot and/or critical and well-established functional domain (e.g., active site of an enzyme) without benign variation"}),
-        ("PM2", EvidenceCode{category: Pathogenic, strength: Moderate, code: 2, description: "Absent from controls (or at extremely low frequency if recessive) in Exome Sequencing Project, 1000 Genomes Project, or Exome Aggregation Consortium"}),
-        ("PM3", EvidenceCode{category: Pathogenic, strength: Moderate, code: 3, description: "For recessive disorders, detected in trans with a pathogenic variant"}),
-        ("PM4", EvidenceCode{category: Pathogenic, strength: Moderate, code: 4, description: "Protein length changes as a result of in-frame deletions/insertions in a nonrepeat region or stop-loss variants"}),
-        ("PM5", EvidenceCode{category: Pathogenic, strength: Moderate, code: 5, description: "Novel missense change at an amino acid residue where a different missense change determined to be pathogenic has been seen before"}),
-        ("PM6", EvidenceCode{category: Pathogenic, strength: Moderate, code: 6, description: "Assumed de novo, but without confirmation of paternity and maternity"}),
-        // Path Supporting
-        ("PP1", EvidenceCode{category: Pathogenic, strength: Supporting, code: 1, description: "Cosegregation with disease in multiple affected family members in a gene definitively known to cause the disease"}),
-        ("PP2", EvidenceCode{category: Pathogenic, strength: Supporting, code: 2, description: "Missense variant in a gene that has a low rate of benign missense variation and in which missense variants are a common mechanism of disease"}),
-        ("PP3", EvidenceCode{category: Pathogenic, strength: Supporting, code: 3, description: "Multiple lines of computational evidence support a deleterious effect on the gene or gene product (conservation, evolutionary, splicing impact, etc.)"}),
-        ("PP4", EvidenceCode{category: Pathogenic, strength: Supporting, code: 4, description: "Patient’s phenotype or family history is highly specific for a disease with a single genetic etiology"}),
-        ("PP5", EvidenceCode{category: Pathogenic, strength: Supporting, code: 5, description: "Reputable source recently reports variant as pathogenic, but the evidence is not available to the laboratory to perform an independent evaluation"}),
-        // BENIGN - Table 4 of https://www.acmg.net/docs/Standards_Guidelines_for_the_Interpretation_of_Sequence_Variants.pdf
-        // Benign StandAlone
-        ("BA1", EvidenceCode{category: Benign, strength: StandAlone, code: 1, description: "Allele frequency is >5% in Exome Sequencing Project, 1000 Genomes Project, or Exome Aggregation Consortium"}),
-        // Benign Strong
-        ("BS1", EvidenceCode{category: Benign, strength: Strong, code: 1, description: "Allele frequency is greater than expected for disorder"}),
-        ("BS2", EvidenceCode{category: Benign, strength: Strong, code: 2, description: "Observed in a healthy adult individual for a recessive (homozygous), dominant (heterozygous), or X-linked (hemizygous) disorder, with full penetrance expected at an early age"}),
-        ("BS3", EvidenceCode{category: Benign, strength: Strong, code: 3, description: "Well-established in vitro or in vivo functional studies show no damaging effect on protein function or splicing"}),
-        ("BS4", EvidenceCode{category: Benign, strength: Strong, code: 4, description: "Lack of segregation in affected members of a family"}),
-        // Benign Supporting
-        ("BP1", EvidenceCode{category: Benign, strength: Supporting, code: 1, description: "Missense variant in a gene for which primarily truncating variants are known to cause disease"}),
-        ("BP2", EvidenceCode{category: Benign, strength: Supporting, code: 2, description: "Observed in trans with a pathogenic variant for a fully penetrant dominant gene/disorder or observed in cis with a pathogenic variant in any inheritance pattern"}),
-        ("BP3", EvidenceCode{category: Benign, strength: Supporting, code: 3, description: "In-frame deletions/insertions in a repetitive region without a known function"}),
-        ("BP4", EvidenceCode{category: Benign, strength: Supporting, code: 4, description: "Multiple lines of computational evidence suggest no impact on gene or gene product (conservation, evolutionary, splicing impact, etc.)"}),
-        ("BP5", EvidenceCode{category: Benign, strength: Supporting, code: 5, description: "Variant found in a case with an alternate molecular basis for disease"}),
-        ("BP6", EvidenceCode{category: Benign, strength: Supporting, code: 6, description: "Reputable source recently reports variant as benign, but the evidence is not available to the laboratory to perform an independent evaluation"}),
-        ("BP7", EvidenceCode{category: Benign, strength: Supporting, code: 7, description: "A synonymous (silent) variant for which splicing prediction algorithms predict no impact to the splice consensus sequence nor the creation of a new splice site AND the nucleotide is not highly conserved"}),
-        ]);
+/// Reports the error, logged once logging is set up and otherwise on stderr, and exits with its exit code.
+fn exit_with_error(error: CliError) -> ! {
+    if tracing::dispatcher::has_been_set() {
+        tracing::error!("{}", error);
+    } else {
+        eprintln!("error: {}", error);
+    }
+    process::exit(error.exit_code())
 }
-
-
-
@@ -0,0 +1,362 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+use crate::parser::format_evidence_set;
+use crate::EvidenceStrength::{Moderate, StandAlone, Strong, Supporting, VeryStrong};
+use crate::{parse_evidence, parse_evidence_set, AcmgError, Evidence, EvidenceSet, EvidenceStrength};
+
+/// The words and abbreviations strengths are written as after a code.
+const STRENGTH_WORDS: &str = "very[ _-]?strong|stand[ _-]?alone|strong|moderate|supporting|vstr|str|mod|sup";
+
+/// A strength written after a code in one of the notations in use, e.g. `PM2 Supporting`, `PVS1 (Moderate)`,
+/// `PVS1-strong` or the ClinGen shorthand `PM2_P`.
+static STRENGTH: LazyLock<Regex> = LazyLock::new(|| Regex::new(&format!(
+    r"(?i)\b([BP](?:VS|S|M|P|A)\d)(?:\s*\(\s*({words})\s*\)|[ _-]+({words})\b|_(VS|S|M|P)\b)",
+    words = STRENGTH_WORDS,
+)).unwrap());
+
+/// A code in prose with any strength written after it, e.g. `PM2 (supporting)`. Codes must be upper case, so words
+/// like `bp1` in the text aren't taken for them.
+static PROSE_CODE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&format!(
+    r"\b((?:PVS|PS|PM|PP|BA|BS|BP)\d+)(?i:\s*\(\s*({words})\s*\)|[ _-]+({words})\b|_(VS|S|M|P)\b)?",
+    words = STRENGTH_WORDS,
+)).unwrap());
+
+/// A note or citations written after a space, e.g. `PS3 (MAVE assay)` or `PS3 [PMID:30311383]`.
+static SPACED_NOTE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\b([BP][AVSMP]{1,2}\d(?:_[A-Z]+)?)\s+([({\[])").unwrap());
+
+/// The flags of InterVar's output, e.g. `PVS1=1` or `PM=[0, 1, 0, 0, 0, 0, 0]`.
+static INTERVAR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\b(PVS1|BA1)\s*=\s*(\d)|\b(PS|PM|PP|BS|BP)\s*=\s*\[([\d,\s]*)\]").unwrap());
+
+/// The groups of InterVar's output in order, with the number of criteria of each.
+const INTERVAR_GROUPS: [(&str, usize); 7] = [("PVS", 1), ("PS", 5), ("PM", 7), ("PP", 6), ("BA", 1), ("BS", 5), ("BP", 8)];
+
+/// A notation evidence is written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Notation {
+    /// The canonical notation of the points-based strengths, e.g. `PVS1, PM2_Supporting(PMID:12345)`.
+    #[default]
+    Acmg,
+    /// Strengths after a space, e.g. `PVS1, PM2 Supporting`.
+    Spaced,
+    /// Strengths in parentheses, e.g. `PVS1 (Moderate), PM2`.
+    Parenthesized,
+    /// The flags of InterVar's output, e.g. `PVS1=1 PS=[0, 0, 0, 0, 0] PM=[0, 1, 0, 0, 0, 0, 0] ...`.
+    Intervar,
+}
+
+impl Notation {
+    pub const ALL: [Notation; 4] = [Notation::Acmg, Notation::Spaced, Notation::Parenthesized, Notation::Intervar];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Notation::Acmg => "acmg",
+            Notation::Spaced => "spaced",
+            Notation::Parenthesized => "parenthesized",
+            Notation::Intervar => "intervar",
+        }
+    }
+}
+
+impl Display for Notation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Notation {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<Notation, Self::Err> {
+        Notation::ALL.iter()
+            .find(|notation| notation.name().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| AcmgError::InvalidParameter(format!("unknown notation '{}', expected acmg, spaced, parenthesized or intervar", s)))
+    }
+}
+
+/// The strength of a word or abbreviation written after a code, e.g. `supporting`, `Mod` or `P`.
+fn strength_name(name: &str) -> Option<EvidenceStrength> {
+    match name.to_ascii_lowercase().replace([' ', '_', '-'], "").as_str() {
+        "standalone" => Some(StandAlone),
+        "verystrong" | "vstr" | "vs" => Some(VeryStrong),
+        "strong" | "str" | "s" => Some(Strong),
+        "moderate" | "mod" | "m" => Some(Moderate),
+        "supporting" | "sup" | "p" => Some(Supporting),
+        _ => None,
+    }
+}
+
+/// The strength written after the code of a match of [`STRENGTH`] or [`PROSE_CODE`].
+fn written_strength(caps: &Captures) -> Option<EvidenceStrength> {
+    strength_name(caps.get(2).or(caps.get(3)).or(caps.get(4))?.as_str())
+}
+
+/// Parses evidence in any of the notations, detecting InterVar's flags and otherwise reading strengths written in
+/// the other notations as the modifiers they stand for, e.g. `PVS1 (Moderate), PM2 Supporting` as
+/// `PVS1_Moderate, PM2_Supporting`.
+pub fn parse_notation(evidence: &str) -> Result<EvidenceSet, AcmgError> {
+    if INTERVAR.is_match(evidence) {
+        return parse_intervar(evidence);
+    }
+    let canonical = STRENGTH.replace_all(evidence, |caps: &Captures| {
+        match written_strength(caps) {
+            Some(strength) => format!("{}_{:?}", &caps[1], strength),
+            None => caps[0].to_string(),
+        }
+    });
+    parse_evidence_set(&SPACED_NOTE.replace_all(&canonical, "$1$2"))
+}
+
+/// Parses the flags of InterVar's output, where each criterion set is 1 and the positions of each list are
+/// numbered from 1, e.g. `PM=[0, 1, 0, 0, 0, 0, 0]` for PM2. No flags set is an empty set of evidence, as InterVar
+/// writes for variants it found no evidence for.
+fn parse_intervar(evidence: &str) -> Result<EvidenceSet, AcmgError> {
+    let mut codes = vec![];
+    for caps in INTERVAR.captures_iter(evidence) {
+        if let (Some(code), Some(flag)) = (caps.get(1), caps.get(2)) {
+            if flag.as_str() != "0" {
+                codes.push(code.as_str().to_ascii_uppercase());
+            }
+            continue;
+        }
+        let group = caps[3].to_ascii_uppercase();
+        for (index, flag) in caps[4].split(',').map(str::trim).enumerate() {
+            match flag {
+                "0" => {}
+                "" => return Err(AcmgError::InvalidSyntax(caps[0].to_string())),
+                flag if flag.chars().all(|c| c.is_ascii_digit()) => codes.push(format!("{}{}", group, index + 1)),
+                _ => return Err(AcmgError::InvalidSyntax(caps[0].to_string())),
+            }
+        }
+    }
+    if codes.is_empty() {
+        return Ok(EvidenceSet::new());
+    }
+    parse_evidence_set(&codes.join(", "))
+}
+
+/// A code found in free text by [`extract_evidence`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EvidenceSpan {
+    pub evidence: Evidence,
+    /// The byte offsets of the code and its strength in the text.
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+/// The evidence found in free text by [`extract_evidence`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Extraction {
+    /// The canonical evidence string, e.g. `PVS1, PM2_Supporting`.
+    pub acmg_evidence: String,
+    pub evidence: EvidenceSet,
+    /// Every mention of a code in order, including repeated mentions.
+    pub spans: Vec<EvidenceSpan>,
+    pub warnings: Vec<String>,
+}
+
+/// Pulls the codes out of report prose or ClinVar assertion criteria comments, with the strengths written after
+/// them in any of the lenient notations, e.g. `PM2 (supporting)` or `PVS1-strong`. A code mentioned again keeps the
+/// first strength written for it, with a warning if another strength is written, and unknown codes such as `PM8` are
+/// skipped with a warning. Negations such as `BP4 not met` aren't recognized, so the spans are for review.
+pub fn extract_evidence(text: &str) -> Extraction {
+    let mut extraction = Extraction::default();
+    // The evidence applied, with whether its strength was written.
+    let mut applied: Vec<(Evidence, bool)> = vec![];
+    for caps in PROSE_CODE.captures_iter(text) {
+        let found = caps.get(0).expect("the whole match");
+        let strength = written_strength(&caps);
+        let evidence = match strength {
+            Some(strength) => parse_evidence(&format!("{}_{:?}", &caps[1], strength)),
+            None => parse_evidence(&caps[1]),
+        };
+        let evidence = match evidence {
+            Ok(evidence) => evidence,
+            Err(error) => {
+                extraction.warnings.push(format!("'{}' at {} is skipped: {}", found.as_str(), found.start(), error));
+                continue;
+            }
+        };
+        match applied.iter_mut().find(|(other, _)| other.evidence_code == evidence.evidence_code) {
+            Some((other, written @ false)) if strength.is_some() => {
+                *other = evidence.clone();
+                *written = true;
+            }
+            Some((other, true)) if strength.is_some() && other.strength() != evidence.strength() => extraction.warnings.push(format!(
+                "'{}' at {} conflicts with the earlier {}, which is kept", found.as_str(), found.start(), other)),
+            Some(_) => {}
+            None => applied.push((evidence.clone(), strength.is_some())),
+        }
+        extraction.spans.push(EvidenceSpan { evidence, start: found.start(), end: found.end(), text: found.as_str().to_string() });
+    }
+    for (evidence, _) in applied {
+        extraction.evidence.insert(evidence);
+    }
+    extraction.acmg_evidence = format_evidence_set(&extraction.evidence);
+    extraction
+}
+
+/// Writes evidence in the notation, with a warning for each modifier, note or citation it can't express, which are
+/// dropped.
+pub fn write_notation(evidence: &EvidenceSet, notation: Notation) -> Result<(String, Vec<String>), AcmgError> {
+    let mut warnings = vec![];
+    if notation != Notation::Acmg {
+        for evidence in evidence.iter() {
+            if let Some(note) = &evidence.note {
+                warnings.push(format!("the note of {} '{}' isn't written in {} notation", evidence, note, notation));
+            }
+            if !evidence.citations.is_empty() {
+                warnings.push(format!("the citations of {} aren't written in {} notation", evidence, notation));
+            }
+        }
+    }
+    let written = match notation {
+        Notation::Acmg => format_evidence_set(evidence),
+        Notation::Spaced | Notation::Parenthesized => {
+            let written: Vec<String> = evidence.iter()
+                .map(|evidence| match (evidence.modifier, notation) {
+                    (Some(modifier), Notation::Spaced) => format!("{} {:?}", evidence.evidence_code, modifier),
+                    (Some(modifier), _) => format!("{} ({:?})", evidence.evidence_code, modifier),
+                    (None, _) => evidence.evidence_code.to_string(),
+                })
+                .collect();
+            written.join(", ")
+        }
+        Notation::Intervar => {
+            let mut flags: Vec<Vec<u8>> = INTERVAR_GROUPS.iter().map(|(_, size)| vec![0; *size]).collect();
+            for evidence in evidence.iter() {
+                let name = &evidence.evidence_code.name;
+                let split = name.find(|c: char| c.is_ascii_digit()).unwrap_or(name.len());
+                let (group, number) = (&name[..split], name[split..].parse::<usize>().unwrap_or_default());
+                let index = INTERVAR_GROUPS.iter().position(|(prefix, size)| *prefix == group && (1..=*size).contains(&number))
+                    .ok_or_else(|| AcmgError::InvalidParameter(format!("{} can't be written in InterVar notation", name)))?;
+                flags[index][number - 1] = 1;
+                if evidence.modifier.is_some() {
+                    warnings.push(format!("{} is written as {}, as InterVar has no strength modifiers", evidence, name));
+                }
+            }
+            let groups: Vec<String> = INTERVAR_GROUPS.iter().zip(&flags)
+                .map(|((prefix, size), flags)| match size {
+                    1 => format!("{}1={}", prefix, flags[0]),
+                    _ => format!("{}=[{}]", prefix, flags.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")),
+                })
+                .collect();
+            groups.join(" ")
+        }
+    };
+    Ok((written, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evidence_written_in_each_notation_is_parsed_back() {
+        let evidence = parse_evidence_set("PVS1, PS3_Moderate, PM2_Supporting, PP3, BS1_Supporting, BP4").unwrap();
+        for notation in Notation::ALL {
+            let (written, warnings) = write_notation(&evidence, notation).unwrap();
+            let parsed = parse_notation(&written).unwrap();
+            match notation {
+                Notation::Intervar => {
+                    assert_eq!(format_evidence_set(&parsed), "PVS1, PS3, PM2, PP3, BS1, BP4", "{}", written);
+                    assert_eq!(warnings.len(), 3);
+                }
+                _ => {
+                    assert_eq!(parsed, evidence, "{}", written);
+                    assert!(warnings.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn each_notation_is_written_as_documented() {
+        let evidence = parse_evidence_set("PVS1, PM2_Supporting").unwrap();
+        let cases = [
+            (Notation::Acmg, "PVS1, PM2_Supporting"),
+            (Notation::Spaced, "PVS1, PM2 Supporting"),
+            (Notation::Parenthesized, "PVS1, PM2 (Supporting)"),
+            (Notation::Intervar, "PVS1=1 PS=[0, 0, 0, 0, 0] PM=[0, 1, 0, 0, 0, 0, 0] PP=[0, 0, 0, 0, 0, 0] BA1=0 BS=[0, 0, 0, 0, 0] BP=[0, 0, 0, 0, 0, 0, 0, 0]"),
+        ];
+        for (notation, expected) in cases {
+            assert_eq!(write_notation(&evidence, notation).unwrap().0, expected);
+            assert_eq!(notation.name().parse::<Notation>().unwrap(), notation);
+        }
+    }
+
+    #[test]
+    fn intervar_output_is_parsed() {
+        let output = "PVS1=1 PS=[0, 0, 1, 0, 0] PM=[0, 1, 0, 0, 0, 0, 0] PP=[0, 0, 1, 0, 0, 0] BA1=0 BS=[0, 0, 0, 0, 0] BP=[0, 0, 0, 0, 0, 0, 0, 0]";
+        assert_eq!(format_evidence_set(&parse_notation(output).unwrap()), "PVS1, PS3, PM2, PP3");
+        let nothing = "PVS1=0 PS=[0, 0, 0, 0, 0] PM=[0, 0, 0, 0, 0, 0, 0] PP=[0, 0, 0, 0, 0, 0] BA1=0 BS=[0, 0, 0, 0, 0] BP=[0, 0, 0, 0, 0, 0, 0, 0]";
+        assert!(parse_notation(nothing).unwrap().is_empty());
+        assert!(parse_notation("PVS1=1 PM=[0, , 0]").is_err());
+    }
+
+    #[test]
+    fn strengths_in_parentheses_are_told_apart_from_notes() {
+        let parsed = parse_notation("PVS1 (Moderate), PS3 (MAVE assay)").unwrap();
+        assert_eq!(format_evidence_set(&parsed), "PVS1_Moderate, PS3(MAVE assay)");
+        let ps3 = parsed.iter().find(|evidence| evidence.evidence_code.name == "PS3").unwrap();
+        assert_eq!(ps3.modifier, None);
+        assert_eq!(ps3.note.as_deref(), Some("MAVE assay"));
+    }
+
+    #[test]
+    fn strengths_are_read_in_each_lenient_notation() {
+        let cases = [
+            ("PM2_P, PS3_M, PS4_S, PM1_VS", "PS3_Moderate, PS4_Strong, PM1_VeryStrong, PM2_Supporting"),
+            ("PM2 Supporting", "PM2_Supporting"),
+            ("PM2 (sup)", "PM2_Supporting"),
+            ("PVS1-strong", "PVS1_Strong"),
+            ("PS3 very strong", "PS3_VeryStrong"),
+            ("BA1 stand-alone", "BA1_StandAlone"),
+            ("pm2_p", "PM2_Supporting"),
+        ];
+        for (evidence, expected) in cases {
+            assert_eq!(format_evidence_set(&parse_notation(evidence).unwrap()), expected, "{}", evidence);
+        }
+    }
+
+    #[test]
+    fn evidence_is_extracted_from_prose_with_the_strengths_written_after_it() {
+        let extraction = extract_evidence("Absent from gnomAD, PM2 (supporting). A nonsense variant, PVS1-strong. PP3 met.");
+        assert_eq!(extraction.acmg_evidence, "PVS1_Strong, PM2_Supporting, PP3");
+        assert!(extraction.warnings.is_empty());
+    }
+
+    #[test]
+    fn spans_are_the_byte_offsets_of_each_mention() {
+        let text = "Über PM2 (supporting) und PVS1-strong; PM2 again";
+        let extraction = extract_evidence(text);
+        let spans: Vec<(&str, usize, usize)> = extraction.spans.iter().map(|span| (span.text.as_str(), span.start, span.end)).collect();
+        assert_eq!(spans, vec![("PM2 (supporting)", 6, 22), ("PVS1-strong", 27, 38), ("PM2", 40, 43)]);
+        for span in &extraction.spans {
+            assert_eq!(&text[span.start..span.end], span.text);
+        }
+        assert_eq!(extraction.spans[2].evidence.to_string(), "PM2");
+    }
+
+    #[test]
+    fn the_first_strength_written_for_a_code_is_kept() {
+        let extraction = extract_evidence("PM2 is met. PM2_Supporting as it is absent. PM2 moderate was considered.");
+        assert_eq!(extraction.acmg_evidence, "PM2_Supporting");
+        assert_eq!(extraction.warnings, vec!["'PM2 moderate' at 44 conflicts with the earlier PM2_Supporting, which is kept"]);
+    }
+
+    #[test]
+    fn unknown_and_lower_case_codes_are_not_extracted() {
+        let extraction = extract_evidence("PM8 and bp1 were not applied, PS1 was");
+        assert_eq!(extraction.acmg_evidence, "PS1");
+        assert_eq!(extraction.warnings.len(), 1);
+        assert!(extraction.warnings[0].starts_with("'PM8' at 0 is skipped"));
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::code::unknown_code;
+use crate::parser::{normalize_input, parse_modifier};
+use crate::Category::{self, Benign, Pathogenic};
+use crate::EvidenceStrength::{self, Moderate, StandAlone, Strong, Supporting, VeryStrong};
+use crate::AcmgError;
+
+static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(O(?:VS|S|M|P)\d|SB(?:VS|S|P)\d)(?:_([A-Z]+))?$").unwrap());
+
+/// The somatic oncogenicity codes of the ClinGen/CGC/VICC SOP, Horak et al. 2022.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OncogenicityCode {
+    OVS1,
+    OS1,
+    OS2,
+    OS3,
+    OM1,
+    OM2,
+    OM3,
+    OM4,
+    OP1,
+    OP2,
+    OP3,
+    OP4,
+    SBVS1,
+    SBS1,
+    SBS2,
+    SBP1,
+    SBP2,
+}
+
+impl OncogenicityCode {
+    pub const ALL: [OncogenicityCode; 17] = [
+        OncogenicityCode::OVS1, OncogenicityCode::OS1, OncogenicityCode::OS2, OncogenicityCode::OS3,
+        OncogenicityCode::OM1, OncogenicityCode::OM2, OncogenicityCode::OM3, OncogenicityCode::OM4,
+        OncogenicityCode::OP1, OncogenicityCode::OP2, OncogenicityCode::OP3, OncogenicityCode::OP4,
+        OncogenicityCode::SBVS1, OncogenicityCode::SBS1, OncogenicityCode::SBS2, OncogenicityCode::SBP1, OncogenicityCode::SBP2,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OncogenicityCode::OVS1 => "OVS1",
+            OncogenicityCode::OS1 => "OS1",
+            OncogenicityCode::OS2 => "OS2",
+            OncogenicityCode::OS3 => "OS3",
+            OncogenicityCode::OM1 => "OM1",
+            OncogenicityCode::OM2 => "OM2",
+            OncogenicityCode::OM3 => "OM3",
+            OncogenicityCode::OM4 => "OM4",
+            OncogenicityCode::OP1 => "OP1",
+            OncogenicityCode::OP2 => "OP2",
+            OncogenicityCode::OP3 => "OP3",
+            OncogenicityCode::OP4 => "OP4",
+            OncogenicityCode::SBVS1 => "SBVS1",
+            OncogenicityCode::SBS1 => "SBS1",
+            OncogenicityCode::SBS2 => "SBS2",
+            OncogenicityCode::SBP1 => "SBP1",
+            OncogenicityCode::SBP2 => "SBP2",
+        }
+    }
+
+    /// Oncogenic codes count towards [`Category::Pathogenic`] and somatic benign codes towards [`Category::Benign`].
+    pub fn category(&self) -> Category {
+        if self.as_str().starts_with('O') { Pathogenic } else { Benign }
+    }
+
+    pub fn strength(&self) -> EvidenceStrength {
+        match self {
+            OncogenicityCode::SBVS1 => StandAlone,
+            OncogenicityCode::OVS1 => VeryStrong,
+            OncogenicityCode::OS1 | OncogenicityCode::OS2 | OncogenicityCode::OS3 | OncogenicityCode::SBS1 | OncogenicityCode::SBS2 => Strong,
+            OncogenicityCode::OM1 | OncogenicityCode::OM2 | OncogenicityCode::OM3 | OncogenicityCode::OM4 => Moderate,
+            _ => Supporting,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            OncogenicityCode::OVS1 => "Null variant (nonsense, frameshift, canonical ±1 or 2 splice sites, initiation codon, single-exon or multiexon deletion) in a bona fide tumor suppressor gene",
+            OncogenicityCode::OS1 => "Same amino acid change as a previously established oncogenic variant regardless of nucleotide change",
+            OncogenicityCode::OS2 => "Well-established in vitro or in vivo functional studies supportive of an oncogenic effect of the variant",
+            OncogenicityCode::OS3 => "Located in a cancerhotspots.org hotspot with at least 50 samples with a somatic variant at the same amino acid position, and the same amino acid change in at least 10 samples",
+            OncogenicityCode::OM1 => "Located in a critical and well-established part of a functional domain (e.g., active site of an enzyme)",
+            OncogenicityCode::OM2 => "Protein length changes from in-frame deletions/insertions in a known oncogene or tumor suppressor gene, or stop-loss variants in a known tumor suppressor gene",
+            OncogenicityCode::OM3 => "Missense variant at an amino acid residue where a different missense variant determined to be oncogenic has been documented",
+            OncogenicityCode::OM4 => "Located in a cancerhotspots.org hotspot with fewer than 50 samples with a somatic variant at the same amino acid position, and the same amino acid change in at least 10 samples",
+            OncogenicityCode::OP1 => "All used lines of computational evidence support an oncogenic effect of the variant (conservation/evolutionary, splicing effect, etc.)",
+            OncogenicityCode::OP2 => "Somatic variant in a gene in a malignancy with a single genetic etiology",
+            OncogenicityCode::OP3 => "Located in a cancerhotspots.org hotspot, with the same amino acid change in fewer than 10 samples",
+            OncogenicityCode::OP4 => "Absent from controls (or at an extremely low frequency) in gnomAD",
+            OncogenicityCode::SBVS1 => "Minor allele frequency is above 5% in gnomAD in any of 5 general continental populations",
+            OncogenicityCode::SBS1 => "Minor allele frequency is above 1% in gnomAD in any of 5 general continental populations",
+            OncogenicityCode::SBS2 => "Well-established in vitro or in vivo functional studies show no oncogenic effects",
+            OncogenicityCode::SBP1 => "All used lines of computational evidence suggest no effect of the variant (conservation/evolutionary, splicing effect, etc.)",
+            OncogenicityCode::SBP2 => "A synonymous (silent) variant for which splicing prediction algorithms predict no effect on the splice consensus sequence nor the creation of a new splice site and the nucleotide is not highly conserved",
+        }
+    }
+}
+
+impl Display for OncogenicityCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for OncogenicityCode {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<OncogenicityCode, Self::Err> {
+        let upper = s.to_uppercase();
+        OncogenicityCode::ALL.iter()
+            .find(|code| code.as_str() == upper)
+            .copied()
+            .ok_or_else(|| unknown_code(s, OncogenicityCode::ALL.iter().map(OncogenicityCode::as_str)))
+    }
+}
+
+/// An applied oncogenicity code, optionally with a modified strength e.g. OS2_Moderate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct OncogenicityEvidence {
+    pub code: OncogenicityCode,
+    pub modifier: Option<EvidenceStrength>,
+}
+
+impl OncogenicityEvidence {
+    pub fn strength(&self) -> EvidenceStrength {
+        self.modifier.unwrap_or(self.code.strength())
+    }
+
+    /// Points contributed by this evidence, from the fixed Horak et al. 2022 scale of 8 for very strong (or stand-alone)
+    /// evidence, 4 for strong, 2 for moderate and 1 for supporting - positive for oncogenic, negative for benign.
+    pub fn points(&self) -> i32 {
+        let points = match self.strength() {
+            StandAlone | VeryStrong => 8,
+            Strong => 4,
+            Moderate => 2,
+            Supporting => 1,
+        };
+        if self.code.category() == Pathogenic { points } else { -points }
+    }
+}
+
+impl From<OncogenicityCode> for OncogenicityEvidence {
+    fn from(code: OncogenicityCode) -> OncogenicityEvidence {
+        OncogenicityEvidence { code, modifier: None }
+    }
+}
+
+impl Display for OncogenicityEvidence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.modifier {
+            Some(modifier) => f.pad(&format!("{}_{:?}", self.code, modifier)),
+            None => f.pad(self.code.as_str()),
+        }
+    }
+}
+
+impl FromStr for OncogenicityEvidence {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<OncogenicityEvidence, Self::Err> {
+        let upper = s.to_uppercase();
+        let caps = RE.captures(&upper).ok_or_else(|| AcmgError::InvalidSyntax(s.to_string()))?;
+        let code: OncogenicityCode = caps[1].parse()?;
+        let modifier = parse_modifier(code.as_str(), caps.get(2).map_or("", |m| m.as_str()))?;
+        Ok(OncogenicityEvidence { code, modifier })
+    }
+}
+
+/// Evidence is serialized along with its applied strength and points, which are ignored when deserializing.
+#[cfg(feature = "serde")]
+impl serde::Serialize for OncogenicityEvidence {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("OncogenicityEvidence", 4)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("modifier", &self.modifier)?;
+        state.serialize_field("strength", &self.strength())?;
+        state.serialize_field("points", &self.points())?;
+        state.end()
+    }
+}
+
+/// The oncogenicity classification of a somatic variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OncogenicityClassification {
+    Oncogenic,
+    LikelyOncogenic,
+    UncertainSignificance,
+    LikelyBenign,
+    Benign,
+}
+
+/// Classifies a total oncogenicity score using the point thresholds from Horak et al. 2022.
+pub fn oncogenicity_classification(points: i32) -> OncogenicityClassification {
+    match points {
+        p if p >= 10 => OncogenicityClassification::Oncogenic,
+        p if p >= 6 => OncogenicityClassification::LikelyOncogenic,
+        p if p >= 0 => OncogenicityClassification::UncertainSignificance,
+        p if p >= -6 => OncogenicityClassification::LikelyBenign,
+        _ => OncogenicityClassification::Benign,
+    }
+}
+
+/// The oncogenicity evidence for a somatic variant, parsed from a string such as `OS3, OM1, OP4`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OncogenicityAssessment {
+    evidence: Vec<OncogenicityEvidence>,
+}
+
+impl OncogenicityAssessment {
+    pub fn evidence(&self) -> &[OncogenicityEvidence] {
+        &self.evidence
+    }
+
+    pub fn points(&self) -> i32 {
+        self.evidence.iter().map(OncogenicityEvidence::points).sum()
+    }
+
+    pub fn classify(&self) -> OncogenicityClassification {
+        oncogenicity_classification(self.points())
+    }
+}
+
+impl FromStr for OncogenicityAssessment {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<OncogenicityAssessment, Self::Err> {
+        let codes = normalize_input(s);
+        if codes.clone().all(str::is_empty) {
+            return Err(AcmgError::EmptyInput);
+        }
+        let mut evidence: Vec<OncogenicityEvidence> = vec![];
+        for code in codes {
+            let parsed: OncogenicityEvidence = code.parse()?;
+            if evidence.iter().any(|existing| existing.code == parsed.code) {
+                return Err(AcmgError::DuplicateCode(parsed.code.to_string()));
+            }
+            evidence.push(parsed);
+        }
+        evidence.sort();
+        Ok(OncogenicityAssessment { evidence })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(evidence: &str) -> i32 {
+        evidence.parse::<OncogenicityAssessment>().unwrap().points()
+    }
+
+    #[test]
+    fn evidence_scores_on_the_horak_point_scale() {
+        let cases = [
+            ("OVS1", 8), ("OS1", 4), ("OM1", 2), ("OP1", 1),
+            ("SBVS1", -8), ("SBS1", -4), ("SBP1", -1),
+            ("OS2_Moderate", 2), ("OM1_Strong", 4), ("OS3_VeryStrong", 8), ("SBS2_Supporting", -1),
+        ];
+        for (evidence, expected) in cases {
+            assert_eq!(points(evidence), expected, "{}", evidence);
+        }
+    }
+
+    #[test]
+    fn classification_thresholds_follow_horak() {
+        use OncogenicityClassification::*;
+        let cases = [
+            (12, Oncogenic), (10, Oncogenic), (9, LikelyOncogenic), (6, LikelyOncogenic),
+            (5, UncertainSignificance), (0, UncertainSignificance),
+            (-1, LikelyBenign), (-6, LikelyBenign), (-7, Benign), (-12, Benign),
+        ];
+        for (points, expected) in cases {
+            assert_eq!(oncogenicity_classification(points), expected, "{}", points);
+        }
+    }
+
+    #[test]
+    fn assessments_classify_by_total_points() {
+        use OncogenicityClassification::*;
+        let cases = [
+            ("OS3, OM1, OP4, OP1", 8, LikelyOncogenic),
+            ("OVS1, OS2", 12, Oncogenic),
+            ("OP4", 1, UncertainSignificance),
+            ("SBS1, SBP1", -5, LikelyBenign),
+            ("SBVS1", -8, Benign),
+            ("OS2, SBS2", 0, UncertainSignificance),
+        ];
+        for (evidence, total, expected) in cases {
+            let assessment: OncogenicityAssessment = evidence.parse().unwrap();
+            assert_eq!((assessment.points(), assessment.classify()), (total, expected), "{}", evidence);
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::code::unknown_code;
+use crate::evidence::evidence_code;
+use crate::{AcmgError, Citation, CodeRegistry, Diagnostic, Evidence, EvidenceSet, EvidenceStrength, Rule};
+use crate::EvidenceStrength::{Moderate, StandAlone, Strong, Supporting, VeryStrong};
+
+static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i-u)^([BP][AVSMP]{1,2}\d{1})(_([A-Z]+))?$").unwrap());
+
+/// Splits an evidence string such as `[PVS1, PM2_Supporting]` or `PVS1; PM2_Supporting` into the individual evidence
+/// codes, keeping any notes such as `PS3(PMID:12345; MAVE assay)` and citations such as `PS3[PMID:30311383]` intact.
+/// The codes borrow from the evidence string, and a leading or trailing comma gives an empty code.
+pub fn normalize_input(acmg_evidence: &str) -> Tokens<'_> {
+    let trimmed = acmg_evidence.trim_start_matches(|c: char| c.is_whitespace() || c == '[');
+    let brackets = acmg_evidence[..acmg_evidence.len() - trimmed.len()].matches('[').count();
+    let mut rest = trimmed.trim_end();
+    for _ in 0..brackets {
+        rest = rest.strip_suffix(']').unwrap_or(rest).trim_end();
+    }
+    Tokens { rest: Some(rest) }
+}
+
+/// The evidence codes of an evidence string, from [`normalize_input`].
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+    rest: Option<&'a str>,
+}
+
+/// Whether a character outside of a note separates evidence codes.
+fn is_separator(c: char) -> bool {
+    matches!(c, ' ' | ',' | ';' | '[' | ']')
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.rest?;
+        let mut depth = 0usize;
+        let end = rest.char_indices().find_map(|(i, c)| {
+            match c {
+                '(' | '{' => depth += 1,
+                '[' if i > 0 => depth += 1,
+                ')' | '}' => depth = depth.saturating_sub(1),
+                ']' if depth > 0 => depth -= 1,
+                c if depth == 0 && is_separator(c) => return Some(i),
+                _ => {}
+            }
+            None
+        });
+        let Some(end) = end else {
+            self.rest = None;
+            return Some(rest);
+        };
+        self.rest = Some(rest[end..].trim_start_matches(is_separator));
+        Some(&rest[..end])
+    }
+}
+
+/// Splits the note off evidence such as `PS3(PMID:12345)` or `PM2{gnomAD v4 AF=0}`. Empty notes are dropped.
+fn split_note(evidence: &str) -> Result<(&str, Option<&str>), AcmgError> {
+    let Some(start) = evidence.find(['(', '{']) else {
+        return Ok((evidence, None));
+    };
+    let close = if evidence[start..].starts_with('(') { ')' } else { '}' };
+    match evidence[start + 1..].strip_suffix(close) {
+        Some(note) => Ok((&evidence[..start], Some(note.trim()).filter(|note| !note.is_empty()))),
+        None => Err(AcmgError::InvalidSyntax(evidence.to_string())),
+    }
+}
+
+/// Splits the citations off evidence such as `PS3[PMID:30311383; doi:10.1016/j.ajhg.2018.09.012]`, separated by
+/// semicolons or commas, checking each is a valid PMID or DOI.
+fn split_citations(evidence: &str) -> Result<(Cow<'_, str>, Vec<Citation>), AcmgError> {
+    let mut depth = 0usize;
+    let start = evidence.char_indices().find_map(|(i, c)| {
+        match c {
+            '(' | '{' => depth += 1,
+            ')' | '}' => depth = depth.saturating_sub(1),
+            '[' if depth == 0 => return Some(i),
+            _ => {}
+        }
+        None
+    });
+    let Some(start) = start else {
+        return Ok((Cow::Borrowed(evidence), vec![]));
+    };
+    let end = evidence[start..].find(']').map(|end| start + end).ok_or_else(|| AcmgError::InvalidSyntax(evidence.to_string()))?;
+    let citations = evidence[start + 1..end].split([';', ','])
+        .map(str::trim)
+        .filter(|citation| !citation.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<Citation>, AcmgError>>()?;
+    Ok((Cow::Owned(format!("{}{}", &evidence[..start], &evidence[end + 1..])), citations))
+}
+
+/// Parses a single evidence code with an optional strength modifier, note and citations, e.g. `PM2_Supporting`,
+/// `PS3(MAVE assay)` or `PS3[PMID:30311383]`. Use [`CodeRegistry::parse_evidence`] for evidence applying registered
+/// codes or aliases.
+pub fn parse_evidence(evidence: &str) -> Result<Evidence, AcmgError> {
+    parse_evidence_in(evidence, &CodeRegistry::new())
+}
+
+/// Parses a single evidence code as [`parse_evidence`], or a code or alias registered in the registry.
+pub(crate) fn parse_evidence_in(evidence: &str, registry: &CodeRegistry) -> Result<Evidence, AcmgError> {
+    let (uncited, citations) = split_citations(evidence)?;
+    let (code, note) = split_note(&uncited)?;
+    if let Some((evidence_code, modifier)) = registry.parse_alias(code) {
+        return Ok(Evidence { evidence_code, modifier, note: note.map(str::to_string), citations });
+    }
+    if let Some(registered) = registry.parse_registered(code) {
+        let (evidence_code, modifier) = registered?;
+        return Ok(Evidence { evidence_code, modifier, note: note.map(str::to_string), citations });
+    }
+    if let Some(caps) = RE.captures(code) {
+        let mut upper = [0u8; 4];
+        let ev_code_str = ascii_uppercase(caps.get(1).map_or("", |m| m.as_str()), &mut upper);
+        let evidence_code = match evidence_code(ev_code_str) {
+            Some(ev) => ev.into(),
+            None => return Err(unknown_code(ev_code_str, registry.code_names())),
+        };
+
+        let modifier = parse_modifier(ev_code_str, caps.get(3).map_or("", |m| m.as_str()))?;
+        return Ok(Evidence { evidence_code, modifier, note: note.map(str::to_string), citations });
+    }
+    Err(AcmgError::InvalidSyntax(evidence.to_string()))
+}
+
+/// Uppercases a code of at most four ASCII characters, as matched by `RE`, into `buf`.
+fn ascii_uppercase<'a>(code: &str, buf: &'a mut [u8; 4]) -> &'a str {
+    let buf = &mut buf[..code.len()];
+    buf.copy_from_slice(code.as_bytes());
+    buf.make_ascii_uppercase();
+    std::str::from_utf8(buf).expect("codes are ASCII")
+}
+
+const MODIFIERS: [(&str, EvidenceStrength); 5] =
+    [("STANDALONE", StandAlone), ("VERYSTRONG", VeryStrong), ("STRONG", Strong), ("MODERATE", Moderate), ("SUPPORTING", Supporting)];
+
+/// Parses the strength modifier of an evidence code, e.g. `Supporting`, or None if empty.
+pub(crate) fn parse_modifier(code: &str, modifier: &str) -> Result<Option<EvidenceStrength>, AcmgError> {
+    if modifier.is_empty() {
+        return Ok(None);
+    }
+    MODIFIERS.iter()
+        .find(|(name, _)| modifier.eq_ignore_ascii_case(name))
+        .map(|(_, strength)| Some(*strength))
+        .ok_or_else(|| AcmgError::InvalidModifier { code: code.to_string(), modifier: modifier.to_uppercase() })
+}
+
+/// Parses a full evidence string, e.g. `PVS1, PS1, PM2_Supporting`, into a set of evidence. The same
+/// evidence appearing twice is reported as an [`AcmgError::DuplicateCode`].
+pub fn parse_evidence_set(acmg_evidence: &str) -> Result<EvidenceSet, AcmgError> {
+    parse_evidence_set_in(acmg_evidence, &CodeRegistry::new())
+}
+
+/// Parses a full evidence string as [`parse_evidence_set`], including the codes registered in the registry.
+pub(crate) fn parse_evidence_set_in(acmg_evidence: &str, registry: &CodeRegistry) -> Result<EvidenceSet, AcmgError> {
+    let evidence_list = normalize_input(acmg_evidence);
+    if evidence_list.clone().all(str::is_empty) {
+        return Err(AcmgError::EmptyInput);
+    }
+    let mut set = EvidenceSet::new();
+    for evidence_code in evidence_list {
+        let evidence = parse_evidence_in(evidence_code, registry)?;
+        if set.contains(&evidence) {
+            return Err(AcmgError::DuplicateCode(evidence.to_string()));
+        }
+        set.insert(evidence);
+    }
+    Ok(set)
+}
+
+/// Rewrites an evidence string in canonical form, e.g. `pvs1 , pm2_supporting; pp3` as `PVS1, PM2_Supporting, PP3`,
+/// for storing and diffing evidence. The evidence is sorted and cased as it's displayed, aliases are replaced by the
+/// evidence they stand for and notes are kept.
+pub fn format_evidence(acmg_evidence: &str) -> Result<String, AcmgError> {
+    Ok(format_evidence_set(&parse_evidence_set(acmg_evidence)?))
+}
+
+/// Writes a set of evidence in the canonical form of [`format_evidence`].
+pub(crate) fn format_evidence_set(evidence: &EvidenceSet) -> String {
+    let evidence: Vec<String> = evidence.iter()
+        .map(|evidence| {
+            let written = match &evidence.note {
+                Some(note) if note.contains(['(', ')']) => format!("{}{{{}}}", evidence, note),
+                Some(note) => format!("{}({})", evidence, note),
+                None => evidence.to_string(),
+            };
+            if evidence.citations.is_empty() {
+                return written;
+            }
+            let citations: Vec<String> = evidence.citations.iter().map(ToString::to_string).collect();
+            format!("{}[{}]", written, citations.join("; "))
+        })
+        .collect();
+    evidence.join(", ")
+}
+
+/// How to resolve the same criterion being given more than once at different strengths, e.g. `PM2, PM2_Supporting`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum DuplicatePolicy {
+    /// Fail with an [`AcmgError::RepeatedCriterion`].
+    Error,
+    /// Keep the strongest of the strengths given.
+    KeepStrongest,
+    /// Keep the strength given first.
+    KeepFirst,
+}
+
+impl DuplicatePolicy {
+    pub const ALL: [DuplicatePolicy; 3] = [DuplicatePolicy::Error, DuplicatePolicy::KeepStrongest, DuplicatePolicy::KeepFirst];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DuplicatePolicy::Error => "error",
+            DuplicatePolicy::KeepStrongest => "keep-strongest",
+            DuplicatePolicy::KeepFirst => "keep-first",
+        }
+    }
+}
+
+impl Display for DuplicatePolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for DuplicatePolicy {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<DuplicatePolicy, Self::Err> {
+        DuplicatePolicy::ALL.iter()
+            .find(|policy| policy.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| AcmgError::UnknownDuplicatePolicy(s.to_string()))
+    }
+}
+
+/// Parses a full evidence string like [`parse_evidence_set`], resolving a criterion given at different strengths
+/// with the policy rather than keeping each of them. Every resolution is explained by a
+/// [`Rule::RepeatedCriterion`] warning.
+pub fn parse_evidence_set_with_policy(acmg_evidence: &str, policy: DuplicatePolicy) -> Result<(EvidenceSet, Vec<Diagnostic>), AcmgError> {
+    parse_evidence_set_with_policy_in(acmg_evidence, policy, &CodeRegistry::new())
+}
+
+/// Parses a full evidence string as [`parse_evidence_set_with_policy`], including the codes registered in the
+/// registry.
+pub(crate) fn parse_evidence_set_with_policy_in(acmg_evidence: &str, policy: DuplicatePolicy, registry: &CodeRegistry) -> Result<(EvidenceSet, Vec<Diagnostic>), AcmgError> {
+    let evidence_list = normalize_input(acmg_evidence);
+    if evidence_list.clone().all(str::is_empty) {
+        return Err(AcmgError::EmptyInput);
+    }
+    let mut kept: Vec<Evidence> = vec![];
+    let mut diagnostics = vec![];
+    for evidence_code in evidence_list {
+        let evidence = parse_evidence_in(evidence_code, registry)?;
+        let Some(existing) = kept.iter_mut().find(|existing| existing.evidence_code == evidence.evidence_code) else {
+            kept.push(evidence);
+            continue;
+        };
+        if *existing == evidence {
+            return Err(AcmgError::DuplicateCode(evidence.to_string()));
+        }
+        let applied = vec![existing.to_string(), evidence.to_string()];
+        let code = evidence.evidence_code.to_string();
+        match policy {
+            DuplicatePolicy::Error => return Err(AcmgError::RepeatedCriterion { code, applied: applied.join(", ") }),
+            DuplicatePolicy::KeepStrongest if evidence.strength().points() > existing.strength().points() => *existing = evidence,
+            DuplicatePolicy::KeepStrongest | DuplicatePolicy::KeepFirst => {}
+        }
+        let reason = if policy == DuplicatePolicy::KeepFirst { "the first given" } else { "the strongest" };
+        diagnostics.push(Diagnostic::new(Rule::RepeatedCriterion,
+                                         format!("{} was given as both {}, keeping {} as {}", code, applied.join(" and "), existing, reason),
+                                         applied));
+    }
+    Ok((kept.into_iter().collect(), diagnostics))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(acmg_evidence: &str) -> Vec<&str> {
+        normalize_input(acmg_evidence).collect()
+    }
+
+    #[test]
+    fn tokens_split_on_mixed_separators() {
+        let cases = [
+            ("PVS1, PM2_Supporting", vec!["PVS1", "PM2_Supporting"]),
+            ("PVS1;PM2_Supporting PP3", vec!["PVS1", "PM2_Supporting", "PP3"]),
+            ("PVS1 ,; PM2  ,PP3", vec!["PVS1", "PM2", "PP3"]),
+            ("  PVS1  ", vec!["PVS1"]),
+            (",PVS1", vec!["", "PVS1"]),
+            ("", vec![""]),
+        ];
+        for (acmg_evidence, expected) in cases {
+            assert_eq!(tokens(acmg_evidence), expected, "{}", acmg_evidence);
+        }
+    }
+
+    #[test]
+    fn tokens_strip_enclosing_brackets_but_keep_citations() {
+        let cases = [
+            ("[PVS1, PM2]", vec!["PVS1", "PM2"]),
+            ("[[PVS1, PM2]]", vec!["PVS1", "PM2"]),
+            (" [ PVS1 ] ", vec!["PVS1"]),
+            ("[PS3[PMID:30311383; doi:10.1016/j.ajhg.2018.09.012], PM2]", vec!["PS3[PMID:30311383; doi:10.1016/j.ajhg.2018.09.012]", "PM2"]),
+            ("PS3[PMID:30311383], PM2", vec!["PS3[PMID:30311383]", "PM2"]),
+        ];
+        for (acmg_evidence, expected) in cases {
+            assert_eq!(tokens(acmg_evidence), expected, "{}", acmg_evidence);
+        }
+    }
+
+    #[test]
+    fn tokens_keep_notes_with_separators_intact() {
+        let cases = [
+            ("PS3(PMID:12345, MAVE assay), PM2", vec!["PS3(PMID:12345, MAVE assay)", "PM2"]),
+            ("PM2{gnomAD v4; AF=0} PP3", vec!["PM2{gnomAD v4; AF=0}", "PP3"]),
+            ("PS3(assay (in vitro), [fig 2]); PM2", vec!["PS3(assay (in vitro), [fig 2])", "PM2"]),
+        ];
+        for (acmg_evidence, expected) in cases {
+            assert_eq!(tokens(acmg_evidence), expected, "{}", acmg_evidence);
+        }
+    }
+
+    #[test]
+    fn unterminated_note_runs_to_the_end() {
+        assert_eq!(tokens("PVS1, PS3(PMID:12345, PM2"), vec!["PVS1", "PS3(PMID:12345, PM2"]);
+        assert!(matches!(split_note("PS3(PMID:12345, PM2"), Err(AcmgError::InvalidSyntax(_))));
+        assert!(matches!(parse_evidence_set("PVS1, PS3(PMID:12345, PM2"), Err(AcmgError::InvalidSyntax(_))));
+    }
+
+    #[test]
+    fn notes_are_split_off() {
+        let cases = [
+            ("PS3", ("PS3", None)),
+            ("PS3(PMID:12345, MAVE assay)", ("PS3", Some("PMID:12345, MAVE assay"))),
+            ("PM2{ gnomAD v4 AF=0 }", ("PM2", Some("gnomAD v4 AF=0"))),
+            ("PS3()", ("PS3", None)),
+            ("PS3(  )", ("PS3", None)),
+        ];
+        for (evidence, expected) in cases {
+            assert_eq!(split_note(evidence).unwrap(), expected, "{}", evidence);
+        }
+        for evidence in ["PS3(MAVE", "PS3{MAVE)", "PS3(MAVE) trailing"] {
+            assert!(matches!(split_note(evidence), Err(AcmgError::InvalidSyntax(_))), "{}", evidence);
+        }
+    }
+
+    #[test]
+    fn citations_are_split_off() {
+        let (rest, citations) = split_citations("PS3[PMID:30311383; doi:10.1016/j.ajhg.2018.09.012, PMID 123]").unwrap();
+        assert_eq!(rest, "PS3");
+        let citations: Vec<String> = citations.iter().map(ToString::to_string).collect();
+        assert_eq!(citations, vec!["PMID:30311383", "doi:10.1016/j.ajhg.2018.09.012", "PMID:123"]);
+
+        let (rest, citations) = split_citations("PS3(see [fig 2])[PMID:30311383]").unwrap();
+        assert_eq!((rest.as_ref(), citations.len()), ("PS3(see [fig 2])", 1));
+        let (rest, citations) = split_citations("PS3[]").unwrap();
+        assert_eq!((rest.as_ref(), citations.len()), ("PS3", 0));
+        let (rest, citations) = split_citations("PS3(no citations)").unwrap();
+        assert_eq!((rest.as_ref(), citations.len()), ("PS3(no citations)", 0));
+    }
+
+    #[test]
+    fn invalid_citations_are_errors() {
+        assert!(matches!(split_citations("PS3[PMID:30311383"), Err(AcmgError::InvalidSyntax(_))));
+        assert!(matches!(split_citations("PS3[PMID:0123]"), Err(AcmgError::InvalidCitation(_))));
+        assert!(matches!(split_citations("PS3[a paper]"), Err(AcmgError::InvalidCitation(_))));
+    }
+}
@@ -0,0 +1,86 @@
+use crate::EvidenceStrength::{Moderate, Strong, Supporting, VeryStrong};
+use crate::{Code, Evidence, EvidenceStrength};
+
+/// Counts of in trans observations of a variant in affected individuals with a recessive disorder, tabulated
+/// according to the ClinGen SVI recommendation for PM3 (version 1.0).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pm3Observations {
+    /// Observations confirmed in trans with a pathogenic or likely pathogenic variant.
+    pub pathogenic_in_trans: u32,
+    /// Observations with a pathogenic or likely pathogenic variant, phase unknown.
+    pub pathogenic_phase_unknown: u32,
+    /// Observations confirmed in trans with a variant of uncertain significance.
+    pub vus_in_trans: u32,
+    /// Homozygous occurrences.
+    pub homozygous: u32,
+}
+
+/// The most points homozygous occurrences can contribute.
+const MAX_HOMOZYGOUS_POINTS: f64 = 1.0;
+
+impl Pm3Observations {
+    /// The total PM3 points - 1 per pathogenic partner in trans, 0.5 per pathogenic partner of unknown phase,
+    /// 0.25 per VUS partner in trans and 0.5 per homozygous occurrence up to a maximum of 1.
+    pub fn points(&self) -> f64 {
+        let homozygous = (0.5 * self.homozygous as f64).min(MAX_HOMOZYGOUS_POINTS);
+        self.pathogenic_in_trans as f64 + 0.5 * self.pathogenic_phase_unknown as f64 + 0.25 * self.vus_in_trans as f64 + homozygous
+    }
+
+    /// The recommended PM3 strength - Supporting from 0.5 points, Moderate from 1, Strong from 2 and Very Strong
+    /// from 4, or None if there are too few points to apply PM3.
+    pub fn strength(&self) -> Option<EvidenceStrength> {
+        match self.points() {
+            points if points >= 4.0 => Some(VeryStrong),
+            points if points >= 2.0 => Some(Strong),
+            points if points >= 1.0 => Some(Moderate),
+            points if points >= 0.5 => Some(Supporting),
+            _ => None,
+        }
+    }
+
+    /// PM3 at the recommended strength, ready to be added to an assessment.
+    pub fn evidence(&self) -> Option<Evidence> {
+        self.strength().map(|strength| Evidence::at_strength(Code::PM3, strength))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observations(pathogenic_in_trans: u32, pathogenic_phase_unknown: u32, vus_in_trans: u32, homozygous: u32) -> Pm3Observations {
+        Pm3Observations { pathogenic_in_trans, pathogenic_phase_unknown, vus_in_trans, homozygous }
+    }
+
+    #[test]
+    fn points_follow_the_svi_table() {
+        assert_eq!(observations(1, 0, 0, 0).points(), 1.0);
+        assert_eq!(observations(0, 1, 0, 0).points(), 0.5);
+        assert_eq!(observations(0, 0, 1, 0).points(), 0.25);
+        assert_eq!(observations(0, 0, 0, 1).points(), 0.5);
+        assert_eq!(observations(0, 0, 0, 2).points(), 1.0);
+        assert_eq!(observations(0, 0, 0, 5).points(), 1.0);
+        assert_eq!(observations(2, 1, 2, 3).points(), 4.0);
+    }
+
+    #[test]
+    fn strength_rises_at_half_one_two_and_four_points() {
+        let cases = [
+            (observations(0, 0, 1, 0), None),
+            (observations(0, 0, 2, 0), Some(Supporting)),
+            (observations(0, 1, 1, 0), Some(Supporting)),
+            (observations(1, 0, 0, 0), Some(Moderate)),
+            (observations(1, 1, 1, 0), Some(Moderate)),
+            (observations(2, 0, 0, 0), Some(Strong)),
+            (observations(1, 1, 1, 2), Some(Strong)),
+            (observations(4, 0, 0, 0), Some(VeryStrong)),
+        ];
+        for (observations, expected) in cases {
+            assert_eq!(observations.strength(), expected, "{:?}", observations);
+        }
+        let evidence = observations(2, 0, 0, 0).evidence().unwrap();
+        assert_eq!((evidence.evidence_code.name.as_ref(), evidence.strength()), ("PM3", Strong));
+        assert!(Pm3Observations::default().evidence().is_none());
+    }
+}
@@ -0,0 +1,298 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::EvidenceStrength::{Moderate, Strong, Supporting, VeryStrong};
+use crate::{AcmgError, Code, Evidence, EvidenceStrength};
+
+/// The type of a putative loss of function variant, as distinguished by the PVS1 decision tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum VariantType {
+    Nonsense,
+    Frameshift,
+    /// A variant at the canonical ±1 or ±2 splice sites.
+    CanonicalSplice,
+    InitiationCodon,
+    /// Deletion of the full gene.
+    GeneDeletion,
+    /// Deletion of one or more exons.
+    ExonDeletion,
+    /// Duplication of one or more exons, proven to be in tandem.
+    TandemDuplication,
+    /// Duplication of one or more exons, presumed to be in tandem.
+    PresumedTandemDuplication,
+    /// Duplication of one or more exons, not in tandem or of unknown location.
+    Duplication,
+}
+
+impl VariantType {
+    pub const ALL: [VariantType; 9] = [
+        VariantType::Nonsense, VariantType::Frameshift, VariantType::CanonicalSplice, VariantType::InitiationCodon,
+        VariantType::GeneDeletion, VariantType::ExonDeletion, VariantType::TandemDuplication,
+        VariantType::PresumedTandemDuplication, VariantType::Duplication,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            VariantType::Nonsense => "nonsense",
+            VariantType::Frameshift => "frameshift",
+            VariantType::CanonicalSplice => "canonical-splice",
+            VariantType::InitiationCodon => "initiation-codon",
+            VariantType::GeneDeletion => "gene-deletion",
+            VariantType::ExonDeletion => "exon-deletion",
+            VariantType::TandemDuplication => "tandem-duplication",
+            VariantType::PresumedTandemDuplication => "presumed-tandem-duplication",
+            VariantType::Duplication => "duplication",
+        }
+    }
+}
+
+impl Display for VariantType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for VariantType {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<VariantType, Self::Err> {
+        VariantType::ALL.iter()
+            .find(|variant_type| variant_type.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| AcmgError::UnknownVariantType(s.to_string()))
+    }
+}
+
+/// The answers to the questions of the PVS1 decision tree for a single variant. Questions which don't apply
+/// to the variant type are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pvs1Input {
+    pub variant_type: VariantType,
+    /// Loss of function is a known mechanism of disease for the gene.
+    pub lof_mechanism: bool,
+    /// The reading frame is preserved, e.g. by an in-frame exon skip or deletion.
+    pub in_frame: bool,
+    /// The variant is predicted to undergo nonsense mediated decay.
+    pub nmd: bool,
+    /// The truncated or altered region is critical to protein function.
+    pub critical_region: bool,
+    /// Loss of function variants in the exon are frequent in the general population.
+    pub frequent_lof: bool,
+    /// The exon is absent from biologically relevant transcripts.
+    pub absent_from_relevant_transcripts: bool,
+    /// The variant removes more than 10% of the protein.
+    pub removes_over_10_percent: bool,
+    /// A different functional transcript uses an alternative start codon.
+    pub alternative_start_codon: bool,
+    /// One or more pathogenic variants are upstream of the closest potential in-frame start codon.
+    pub upstream_pathogenic: bool,
+}
+
+impl Pvs1Input {
+    /// Answers for a variant of the given type in a gene where loss of function is a mechanism of disease, with
+    /// every other question answered no.
+    pub fn new(variant_type: VariantType) -> Pvs1Input {
+        Pvs1Input {
+            variant_type,
+            lof_mechanism: true,
+            in_frame: false,
+            nmd: false,
+            critical_region: false,
+            frequent_lof: false,
+            absent_from_relevant_transcripts: false,
+            removes_over_10_percent: false,
+            alternative_start_codon: false,
+            upstream_pathogenic: false,
+        }
+    }
+}
+
+/// The PVS1 strength recommended by the decision tree, with the reason for the recommendation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Pvs1Outcome {
+    /// The strength PVS1 should be applied at, None if it is not applicable.
+    pub strength: Option<EvidenceStrength>,
+    pub reason: &'static str,
+}
+
+impl Pvs1Outcome {
+    fn new(strength: EvidenceStrength, reason: &'static str) -> Pvs1Outcome {
+        Pvs1Outcome { strength: Some(strength), reason }
+    }
+
+    fn not_applicable(reason: &'static str) -> Pvs1Outcome {
+        Pvs1Outcome { strength: None, reason }
+    }
+
+    /// The PVS1 evidence at the recommended strength, ready to be added to an assessment.
+    pub fn evidence(&self) -> Option<Evidence> {
+        self.strength.map(|strength| Evidence::at_strength(Code::PVS1, strength))
+    }
+}
+
+impl Display for Pvs1Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.evidence() {
+            Some(evidence) => write!(f, "{}", evidence),
+            None => write!(f, "N/A"),
+        }
+    }
+}
+
+/// Walks the PVS1 decision tree of Abou Tayoun et al. 2018 to recommend the strength PVS1 should be applied at.
+pub fn assess_pvs1(input: &Pvs1Input) -> Pvs1Outcome {
+    if !input.lof_mechanism {
+        return Pvs1Outcome::not_applicable("Loss of function is not a known mechanism of disease");
+    }
+    match input.variant_type {
+        VariantType::Nonsense | VariantType::Frameshift => truncating(input),
+        VariantType::CanonicalSplice | VariantType::ExonDeletion if input.in_frame => in_frame(input),
+        VariantType::CanonicalSplice | VariantType::ExonDeletion => truncating(input),
+        VariantType::GeneDeletion => Pvs1Outcome::new(VeryStrong, "Full gene deletion"),
+        VariantType::TandemDuplication if !input.in_frame && input.nmd => {
+            Pvs1Outcome::new(VeryStrong, "Proven tandem duplication disrupting the reading frame and predicted to undergo NMD")
+        }
+        VariantType::PresumedTandemDuplication if !input.in_frame && input.nmd => {
+            Pvs1Outcome::new(Strong, "Presumed tandem duplication disrupting the reading frame and predicted to undergo NMD")
+        }
+        VariantType::TandemDuplication | VariantType::PresumedTandemDuplication => {
+            Pvs1Outcome::not_applicable("Tandem duplication preserving the reading frame or not predicted to undergo NMD")
+        }
+        VariantType::Duplication => Pvs1Outcome::not_applicable("Duplication not in tandem or of unknown location"),
+        VariantType::InitiationCodon if input.alternative_start_codon => {
+            Pvs1Outcome::not_applicable("A different functional transcript uses an alternative start codon")
+        }
+        VariantType::InitiationCodon if input.upstream_pathogenic => {
+            Pvs1Outcome::new(Moderate, "Pathogenic variants upstream of the closest potential in-frame start codon")
+        }
+        VariantType::InitiationCodon => {
+            Pvs1Outcome::new(Supporting, "No pathogenic variants upstream of the closest potential in-frame start codon")
+        }
+    }
+}
+
+/// Variants disrupting the reading frame, which may be predicted to undergo NMD.
+fn truncating(input: &Pvs1Input) -> Pvs1Outcome {
+    if input.nmd {
+        return if input.absent_from_relevant_transcripts {
+            Pvs1Outcome::not_applicable("Predicted to undergo NMD, but the exon is absent from biologically relevant transcripts")
+        } else {
+            Pvs1Outcome::new(VeryStrong, "Predicted to undergo NMD in biologically relevant transcripts")
+        };
+    }
+    if input.critical_region {
+        return Pvs1Outcome::new(Strong, "Escapes NMD, but the truncated region is critical to protein function");
+    }
+    unknown_region(input)
+}
+
+/// Variants preserving the reading frame.
+fn in_frame(input: &Pvs1Input) -> Pvs1Outcome {
+    if input.critical_region {
+        return Pvs1Outcome::new(Strong, "Preserves the reading frame, but the altered region is critical to protein function");
+    }
+    unknown_region(input)
+}
+
+/// Variants altering a region with no known role in protein function.
+fn unknown_region(input: &Pvs1Input) -> Pvs1Outcome {
+    if input.frequent_lof || input.absent_from_relevant_transcripts {
+        Pvs1Outcome::not_applicable("Loss of function variants in the exon are frequent or the exon is absent from biologically relevant transcripts")
+    } else if input.removes_over_10_percent {
+        Pvs1Outcome::new(Strong, "Removes more than 10% of the protein")
+    } else {
+        Pvs1Outcome::new(Moderate, "Removes less than 10% of the protein")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EvidenceStrength::StandAlone;
+    use VariantType::*;
+
+    fn input(variant_type: VariantType) -> Pvs1Input {
+        Pvs1Input::new(variant_type)
+    }
+
+    #[test]
+    fn each_branch_of_the_decision_tree_recommends_its_strength() {
+        let cases = [
+            (Pvs1Input { lof_mechanism: false, nmd: true, ..input(Nonsense) }, None),
+            (Pvs1Input { nmd: true, ..input(Nonsense) }, Some(VeryStrong)),
+            (Pvs1Input { nmd: true, absent_from_relevant_transcripts: true, ..input(Frameshift) }, None),
+            (Pvs1Input { critical_region: true, ..input(Frameshift) }, Some(Strong)),
+            (Pvs1Input { removes_over_10_percent: true, ..input(Nonsense) }, Some(Strong)),
+            (input(Nonsense), Some(Moderate)),
+            (Pvs1Input { frequent_lof: true, removes_over_10_percent: true, ..input(Frameshift) }, None),
+            (Pvs1Input { nmd: true, ..input(CanonicalSplice) }, Some(VeryStrong)),
+            (Pvs1Input { in_frame: true, nmd: true, ..input(CanonicalSplice) }, Some(Moderate)),
+            (Pvs1Input { in_frame: true, critical_region: true, ..input(CanonicalSplice) }, Some(Strong)),
+            (Pvs1Input { in_frame: true, removes_over_10_percent: true, ..input(ExonDeletion) }, Some(Strong)),
+            (Pvs1Input { in_frame: true, absent_from_relevant_transcripts: true, ..input(ExonDeletion) }, None),
+            (Pvs1Input { nmd: true, ..input(ExonDeletion) }, Some(VeryStrong)),
+            (input(GeneDeletion), Some(VeryStrong)),
+            (Pvs1Input { nmd: true, ..input(TandemDuplication) }, Some(VeryStrong)),
+            (Pvs1Input { in_frame: true, nmd: true, ..input(TandemDuplication) }, None),
+            (Pvs1Input { nmd: true, ..input(PresumedTandemDuplication) }, Some(Strong)),
+            (input(PresumedTandemDuplication), None),
+            (Pvs1Input { nmd: true, ..input(Duplication) }, None),
+            (Pvs1Input { alternative_start_codon: true, upstream_pathogenic: true, ..input(InitiationCodon) }, None),
+            (Pvs1Input { upstream_pathogenic: true, ..input(InitiationCodon) }, Some(Moderate)),
+            (input(InitiationCodon), Some(Supporting)),
+        ];
+        for (input, strength) in cases {
+            assert_eq!(assess_pvs1(&input).strength, strength, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn every_terminal_strength_is_reachable() {
+        let mut reached = vec![];
+        for variant_type in VariantType::ALL {
+            for answers in 0..1 << 8 {
+                let answer = |bit: u32| answers & (1 << bit) != 0;
+                let input = Pvs1Input {
+                    variant_type,
+                    lof_mechanism: true,
+                    in_frame: answer(0),
+                    nmd: answer(1),
+                    critical_region: answer(2),
+                    frequent_lof: answer(3),
+                    absent_from_relevant_transcripts: answer(4),
+                    removes_over_10_percent: answer(5),
+                    alternative_start_codon: answer(6),
+                    upstream_pathogenic: answer(7),
+                };
+                let strength = assess_pvs1(&input).strength;
+                assert_ne!(strength, Some(StandAlone), "{:?}", input);
+                if !reached.contains(&strength) {
+                    reached.push(strength);
+                }
+            }
+        }
+        for strength in [Some(VeryStrong), Some(Strong), Some(Moderate), Some(Supporting), None] {
+            assert!(reached.contains(&strength), "{:?}", strength);
+        }
+    }
+
+    #[test]
+    fn outcomes_are_displayed_as_the_evidence_applied() {
+        assert_eq!(assess_pvs1(&Pvs1Input { critical_region: true, ..input(Frameshift) }).to_string(), "PVS1_Strong");
+        assert_eq!(assess_pvs1(&Pvs1Input { nmd: true, ..input(Nonsense) }).to_string(), "PVS1");
+        assert_eq!(assess_pvs1(&input(Duplication)).to_string(), "N/A");
+        assert_eq!(assess_pvs1(&input(Duplication)).evidence(), None);
+    }
+
+    #[test]
+    fn variant_types_are_parsed_from_their_names() {
+        for variant_type in VariantType::ALL {
+            assert_eq!(variant_type.name().parse::<VariantType>().unwrap(), variant_type);
+        }
+        assert!("missense".parse::<VariantType>().is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::BufRead;
+
+use crate::{AcmgError, EvidenceStrength, GenomicVariant};
+
+/// A region of a BED file, with 0-based half-open coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Region {
+    /// The chromosome without a `chr` prefix, e.g. 7.
+    pub chromosome: String,
+    pub start: u64,
+    pub end: u64,
+    /// The name column, e.g. a hotspot or domain.
+    pub name: Option<String>,
+    /// The strength of the evidence the region supports, from a strength in the score column, e.g. `Supporting`.
+    pub strength: Option<EvidenceStrength>,
+}
+
+impl Region {
+    /// Whether the region overlaps the reference bases of the variant.
+    pub fn overlaps(&self, variant: &GenomicVariant) -> bool {
+        let start = variant.position - 1;
+        let end = start + variant.reference.len() as u64;
+        self.chromosome == variant.chromosome && self.start < end && start < self.end
+    }
+
+    /// Whether the region contains all the reference bases of the variant.
+    pub fn contains(&self, variant: &GenomicVariant) -> bool {
+        let start = variant.position - 1;
+        self.chromosome == variant.chromosome && self.start <= start && start + variant.reference.len() as u64 <= self.end
+    }
+
+    /// The name if given, otherwise the coordinates, e.g. `7:117559590-117559600`.
+    pub fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| format!("{}:{}-{}", self.chromosome, self.start + 1, self.end))
+    }
+}
+
+/// The regions of a BED file, such as curated mutational hotspots.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Regions {
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Reads a BED file of at least the chromosome, start and end columns, skipping `#` comments and `track` and
+    /// `browser` lines. A strength in place of the numeric score, e.g. `Supporting`, gives the strength the region
+    /// supports.
+    pub fn from_bed(reader: impl BufRead) -> Result<Regions, AcmgError> {
+        let mut regions = vec![];
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|error| AcmgError::InvalidBed(error.to_string()))?;
+            let invalid = |reason: &str| AcmgError::InvalidBed(format!("line {}: {}", number + 1, reason));
+            if line.trim().is_empty() || line.starts_with('#') || line.starts_with("track") || line.starts_with("browser") {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            if fields.len() < 3 {
+                return Err(invalid("expected chromosome, start and end columns"));
+            }
+            let (Ok(start), Ok(end)) = (fields[1].parse::<u64>(), fields[2].parse::<u64>()) else {
+                return Err(invalid("invalid start or end"));
+            };
+            if start >= end {
+                return Err(invalid("the start must be before the end"));
+            }
+            regions.push(Region {
+                chromosome: fields[0].trim_start_matches("chr").trim_start_matches("CHR").to_uppercase(),
+                start,
+                end,
+                name: fields.get(3).filter(|name| !name.is_empty() && **name != ".").map(|name| name.to_string()),
+                strength: fields.get(4).and_then(|score| score.parse().ok()),
+            });
+        }
+        Ok(Regions { regions })
+    }
+
+    /// The first region overlapping the variant.
+    pub fn overlapping(&self, variant: &GenomicVariant) -> Option<&Region> {
+        self.regions.iter().find(|region| region.overlaps(variant))
+    }
+
+    /// The first region containing the variant entirely.
+    pub fn containing(&self, variant: &GenomicVariant) -> Option<&Region> {
+        self.regions.iter().find(|region| region.contains(variant))
+    }
+}
+
+/// The score of a bedGraph track, e.g. phyloP or GERP++ converted from bigWig with `bigWigToBedGraph`, at the first
+/// reference base of the variant, or None if the track has no score there.
+pub fn bedgraph_score(reader: impl BufRead, variant: &GenomicVariant) -> Result<Option<f64>, AcmgError> {
+    let position = variant.position - 1;
+    for (number, line) in reader.lines().enumerate() {
+        let line = line.map_err(|error| AcmgError::InvalidBed(error.to_string()))?;
+        if line.trim().is_empty() || line.starts_with('#') || line.starts_with("track") || line.starts_with("browser") {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let invalid = || AcmgError::InvalidBed(format!("line {}: expected chromosome, start, end and score columns", number + 1));
+        let [chromosome, start, end, score] = fields[..] else {
+            return Err(invalid());
+        };
+        if !chromosome.trim_start_matches("chr").eq_ignore_ascii_case(&variant.chromosome) {
+            continue;
+        }
+        let (Ok(start), Ok(end)) = (start.parse::<u64>(), end.parse::<u64>()) else {
+            return Err(invalid());
+        };
+        if start <= position && position < end {
+            return score.parse().map(Some).map_err(|_| invalid());
+        }
+    }
+    Ok(None)
+}
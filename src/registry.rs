@@ -0,0 +1,281 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::parser::{format_evidence_set, parse_evidence_in, parse_evidence_set_in, parse_evidence_set_with_policy_in, parse_modifier};
+use crate::validation::validate_evidence;
+use crate::{evidence_codes, AcmgError, Category, Code, CodeRef, Diagnostic, DuplicatePolicy, Evidence, EvidenceCode, EvidenceSet, EvidenceStrength, Inheritance};
+
+/// The definition of an additional evidence code, e.g. an in-house code or a VCEP-specific split criterion such as
+/// PM2_1, or of a replacement for the built-in code of the same name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CodeDefinition {
+    pub name: String,
+    pub category: Category,
+    /// The strength the code is applied at without a modifier.
+    pub strength: EvidenceStrength,
+    pub description: String,
+    /// Strengths the code may plausibly be applied at, any strength if empty.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allowed_strengths: Vec<EvidenceStrength>,
+}
+
+/// A registered code, shared by the evidence applying it.
+#[derive(Debug)]
+pub(crate) struct RegisteredCode {
+    pub(crate) evidence_code: EvidenceCode,
+    pub(crate) allowed_strengths: Vec<EvidenceStrength>,
+}
+
+/// Evidence codes defined in addition to the built-in codes, e.g. an in-house code or a VCEP-specific split criterion
+/// such as PM2_1, or in place of them, and aliases for lab-internal notations. Evidence parsed with the registry can
+/// apply its codes, which parse, score and display like the built-in codes, e.g. `PM2_1` or `PM2_1_Strong`, and its
+/// aliases.
+#[derive(Debug, Clone, Default)]
+pub struct CodeRegistry {
+    codes: Vec<Arc<RegisteredCode>>,
+    /// Aliases and the code and strength modifier they stand for, keyed by the uppercased alias.
+    aliases: Vec<(String, CodeRef, Option<EvidenceStrength>)>,
+}
+
+impl CodeRegistry {
+    /// A registry of the built-in codes alone.
+    pub const fn new() -> CodeRegistry {
+        CodeRegistry { codes: Vec::new(), aliases: Vec::new() }
+    }
+
+    /// Registers an evidence code. A code named as a built-in code replaces it, and registering a name again replaces
+    /// the earlier definition for evidence parsed afterwards. Names are case-insensitive.
+    pub fn register_code(&mut self, definition: CodeDefinition) -> Result<CodeRef, AcmgError> {
+        let name = definition.name.trim().to_uppercase();
+        let invalid = |reason: &str| AcmgError::InvalidCodeDefinition { code: definition.name.clone(), reason: reason.to_string() };
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic()) || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(invalid("names start with a letter followed by letters, digits or underscores"));
+        }
+        if let Some((_, modifier)) = name.rsplit_once('_') {
+            if parse_modifier(&name, modifier).is_ok() {
+                return Err(invalid("names can't end in a strength modifier"));
+            }
+        }
+        if definition.description.trim().is_empty() {
+            return Err(invalid("a description is required"));
+        }
+        let number = name.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+        let number = number[..number.find(|c: char| !c.is_ascii_digit()).unwrap_or(number.len())].parse().unwrap_or_default();
+        let registered = Arc::new(RegisteredCode {
+            evidence_code: EvidenceCode {
+                category: definition.category,
+                strength: definition.strength,
+                code: number,
+                name: Cow::Owned(name),
+                description: Cow::Owned(definition.description),
+            },
+            allowed_strengths: definition.allowed_strengths,
+        });
+        match self.codes.iter_mut().find(|other| other.evidence_code.name == registered.evidence_code.name) {
+            Some(other) => *other = registered.clone(),
+            None => self.codes.push(registered.clone()),
+        }
+        Ok(CodeRef::registered(registered))
+    }
+
+    /// Registers an alias for a code and strength, e.g. `PM2sup` for `PM2_Supporting` or `PVS1_RNA` for
+    /// `PVS1_Strong`, so lab-internal or legacy notations parse as the evidence they stand for. The evidence may apply
+    /// a code registered beforehand. Aliases are case-insensitive, take precedence over codes of the same name and may
+    /// be followed by a note. Returns the evidence the alias stands for.
+    pub fn register_alias(&mut self, alias: &str, evidence: &str) -> Result<Evidence, AcmgError> {
+        let invalid = |reason: String| AcmgError::InvalidAlias { alias: alias.to_string(), reason };
+        let name = alias.trim().to_uppercase();
+        if name.is_empty() || name.contains(|c: char| c.is_whitespace() || ",;[](){}".contains(c)) {
+            return Err(invalid("aliases can't be empty or contain spaces, commas, semicolons, brackets or braces".to_string()));
+        }
+        let target = self.parse_evidence(evidence.trim()).map_err(|error| invalid(error.to_string()))?;
+        if target.note.is_some() || !target.citations.is_empty() {
+            return Err(invalid("aliases stand for a code and strength, without a note or citations".to_string()));
+        }
+        self.aliases.retain(|(other, _, _)| *other != name);
+        self.aliases.push((name, target.evidence_code.clone(), target.modifier));
+        Ok(target)
+    }
+
+    /// The registered evidence codes, in the order they were first registered.
+    pub fn registered_codes(&self) -> impl Iterator<Item = &EvidenceCode> {
+        self.codes.iter().map(|registered| &registered.evidence_code)
+    }
+
+    /// All evidence codes in display order, pathogenic codes first, including the registered codes in place of the
+    /// built-in codes they replace.
+    pub fn evidence_codes(&self) -> Vec<&EvidenceCode> {
+        let mut codes: Vec<&EvidenceCode> = evidence_codes().into_iter()
+            .filter(|code| self.registered_codes().all(|other| other.name != code.name))
+            .chain(self.registered_codes())
+            .collect();
+        codes.sort();
+        codes
+    }
+
+    /// Parses a single evidence code as [`parse_evidence`](crate::parse_evidence), including the registered codes.
+    pub fn parse_evidence(&self, evidence: &str) -> Result<Evidence, AcmgError> {
+        parse_evidence_in(evidence, self)
+    }
+
+    /// Parses a full evidence string as [`parse_evidence_set`](crate::parse_evidence_set), including the registered
+    /// codes.
+    pub fn parse_evidence_set(&self, acmg_evidence: &str) -> Result<EvidenceSet, AcmgError> {
+        parse_evidence_set_in(acmg_evidence, self)
+    }
+
+    /// Parses a full evidence string as [`parse_evidence_set_with_policy`](crate::parse_evidence_set_with_policy),
+    /// including the registered codes.
+    pub fn parse_evidence_set_with_policy(&self, acmg_evidence: &str, policy: DuplicatePolicy) -> Result<(EvidenceSet, Vec<Diagnostic>), AcmgError> {
+        parse_evidence_set_with_policy_in(acmg_evidence, policy, self)
+    }
+
+    /// Rewrites an evidence string in canonical form as [`format_evidence`](crate::format_evidence), including the
+    /// registered codes.
+    pub fn format_evidence(&self, acmg_evidence: &str) -> Result<String, AcmgError> {
+        Ok(format_evidence_set(&self.parse_evidence_set(acmg_evidence)?))
+    }
+
+    /// Checks an evidence string as [`validate`](crate::validate), including the registered codes, and against the
+    /// mode of inheritance of the disorder if given.
+    pub fn validate(&self, acmg_evidence: &str, inheritance: Option<Inheritance>) -> Vec<Diagnostic> {
+        validate_evidence(acmg_evidence, inheritance, self)
+    }
+
+    /// The code and strength modifier of an alias, if `code` is one, ignoring case.
+    pub(crate) fn parse_alias(&self, code: &str) -> Option<(CodeRef, Option<EvidenceStrength>)> {
+        self.aliases.iter()
+            .find(|(alias, _, _)| alias.eq_ignore_ascii_case(code))
+            .map(|(_, evidence_code, modifier)| (evidence_code.clone(), *modifier))
+    }
+
+    /// Parses a registered code with an optional strength modifier, e.g. `PM2_1_Strong`, or None if it doesn't start
+    /// with a registered code. The longest registered code matching is taken.
+    pub(crate) fn parse_registered(&self, code: &str) -> Option<Result<(CodeRef, Option<EvidenceStrength>), AcmgError>> {
+        self.codes.iter()
+            .filter_map(|registered| {
+                let name = registered.evidence_code.name.as_ref();
+                match code.get(..name.len()) {
+                    Some(prefix) if prefix.eq_ignore_ascii_case(name) => match &code[name.len()..] {
+                        "" => Some((registered, None)),
+                        rest => rest.strip_prefix('_').map(|modifier| (registered, Some(modifier))),
+                    },
+                    _ => None,
+                }
+            })
+            .max_by_key(|(registered, _)| registered.evidence_code.name.len())
+            .map(|(registered, modifier)| {
+                let modifier = parse_modifier(&registered.evidence_code.name, modifier.unwrap_or_default())?;
+                Ok((CodeRef::registered(registered.clone()), modifier))
+            })
+    }
+
+    /// The names of the built-in and registered codes, for suggesting the closest to an unknown code.
+    pub(crate) fn code_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Code::ALL.iter().map(Code::as_str).collect();
+        names.extend(self.registered_codes().map(|evidence_code| evidence_code.name.as_ref()));
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_evidence, parse_evidence_set, AcmgAssessment, AcmgClassification, Category::Pathogenic};
+    use crate::EvidenceStrength::{Moderate, Strong, Supporting, VeryStrong};
+
+    fn definition(name: &str, strength: EvidenceStrength, allowed_strengths: Vec<EvidenceStrength>) -> CodeDefinition {
+        CodeDefinition { name: name.to_string(), category: Pathogenic, strength, description: format!("{} as defined by the lab", name), allowed_strengths }
+    }
+
+    #[test]
+    fn registered_codes_parse_and_score_like_built_in_codes() {
+        let mut registry = CodeRegistry::new();
+        registry.register_code(definition("PM2_1", Supporting, vec![Supporting, Moderate])).unwrap();
+        let evidence = registry.parse_evidence_set("PVS1, pm2_1_strong, PM2_1(gnomAD v4)").unwrap();
+        let applied: Vec<String> = evidence.iter().map(ToString::to_string).collect();
+        assert_eq!(applied, ["PVS1", "PM2_1", "PM2_1_Strong"]);
+        assert_eq!(evidence.total_points(), 13);
+        let assessment = AcmgAssessment::parse_with_registry("PVS1, PM2_1", &registry, None).unwrap();
+        assert_eq!((assessment.points(), assessment.classify()), (9, AcmgClassification::LikelyPathogenic));
+        assert_eq!(registry.format_evidence("pm2_1, pvs1").unwrap(), "PVS1, PM2_1");
+    }
+
+    #[test]
+    fn registered_codes_are_only_known_to_their_registry() {
+        let mut registry = CodeRegistry::new();
+        registry.register_code(definition("PM2_1", Supporting, vec![])).unwrap();
+        assert!(matches!(parse_evidence_set("PM2_1"), Err(AcmgError::InvalidSyntax(_))));
+        assert!(matches!(CodeRegistry::new().parse_evidence("PM2_1"), Err(AcmgError::InvalidSyntax(_))));
+        assert!(registry.parse_evidence("PM2_1").is_ok());
+    }
+
+    #[test]
+    fn a_registered_code_replaces_the_built_in_code_of_its_name() {
+        let mut registry = CodeRegistry::new();
+        registry.register_code(definition("PS3", Moderate, vec![])).unwrap();
+        let evidence = registry.parse_evidence("PS3").unwrap();
+        assert_eq!((evidence.strength(), evidence.points()), (Moderate, 2));
+        assert_eq!(evidence.evidence_code.description, "PS3 as defined by the lab");
+        assert_eq!(parse_evidence("PS3").unwrap().strength(), Strong);
+        let codes = registry.evidence_codes();
+        assert_eq!(codes.len(), crate::EVIDENCE_CODES.len());
+        assert_eq!(codes.iter().find(|code| code.name == "PS3").unwrap().strength, Moderate);
+
+        registry.register_code(definition("PS3", VeryStrong, vec![])).unwrap();
+        assert_eq!(registry.parse_evidence("PS3").unwrap().strength(), VeryStrong);
+        assert_eq!(evidence.strength(), Moderate);
+    }
+
+    #[test]
+    fn registered_allowed_strengths_are_checked() {
+        let mut registry = CodeRegistry::new();
+        registry.register_code(definition("PM2_1", Supporting, vec![Supporting, Moderate])).unwrap();
+        let rules = |evidence: &str| registry.validate(evidence, None).into_iter().map(|diagnostic| diagnostic.rule).collect::<Vec<_>>();
+        assert_eq!(rules("PVS1, PM2_1_Moderate"), []);
+        assert_eq!(rules("PVS1, PM2_1_Strong"), [crate::Rule::ImplausibleStrength]);
+    }
+
+    #[test]
+    fn invalid_definitions_are_rejected() {
+        let mut registry = CodeRegistry::new();
+        for name in ["2PM", "PM-2", "PM2_Strong", ""] {
+            assert!(matches!(registry.register_code(definition(name, Supporting, vec![])), Err(AcmgError::InvalidCodeDefinition { .. })), "{}", name);
+        }
+        let undescribed = CodeDefinition { description: " ".to_string(), ..definition("PM2_1", Supporting, vec![]) };
+        assert!(matches!(registry.register_code(undescribed), Err(AcmgError::InvalidCodeDefinition { .. })));
+        assert_eq!(registry.registered_codes().count(), 0);
+    }
+
+    #[test]
+    fn aliases_parse_as_the_evidence_they_stand_for() {
+        let mut registry = CodeRegistry::new();
+        for (alias, evidence) in [("PVS1_RNA", "PVS1_Strong"), ("PM2sup", "PM2_Supporting"), ("PS3-mod", "PS3_Moderate")] {
+            assert_eq!(registry.register_alias(alias, evidence).unwrap().to_string(), evidence);
+        }
+        let evidence = registry.parse_evidence_set("pvs1_rna, PM2SUP, PS3-mod(MAVE assay)").unwrap();
+        assert_eq!(registry.format_evidence("pvs1_rna, PM2SUP, PS3-mod(MAVE assay)").unwrap(), "PVS1_Strong, PS3_Moderate(MAVE assay), PM2_Supporting");
+        assert_eq!(evidence.total_points(), 7);
+        assert!(matches!(parse_evidence_set("PM2sup"), Err(AcmgError::InvalidSyntax(_))));
+        assert!(matches!(CodeRegistry::new().parse_evidence("PS3-mod"), Err(AcmgError::InvalidSyntax(_))));
+    }
+
+    #[test]
+    fn aliases_may_stand_for_registered_codes_and_be_replaced() {
+        let mut registry = CodeRegistry::new();
+        registry.register_code(definition("PM2_1", Supporting, vec![])).unwrap();
+        registry.register_alias("absent", "PM2_1_Moderate").unwrap();
+        assert_eq!(registry.parse_evidence("ABSENT").unwrap().to_string(), "PM2_1_Moderate");
+        registry.register_alias("absent", "PM2").unwrap();
+        assert_eq!(registry.parse_evidence("absent").unwrap().to_string(), "PM2");
+    }
+
+    #[test]
+    fn invalid_aliases_are_rejected() {
+        let mut registry = CodeRegistry::new();
+        for (alias, evidence) in [("PM2 sup", "PM2_Supporting"), ("", "PM2"), ("PM2sup", "PM2_Weak"), ("PS3x", "PS3(MAVE assay)")] {
+            assert!(matches!(registry.register_alias(alias, evidence), Err(AcmgError::InvalidAlias { .. })), "{}", alias);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::{AcmgClassification, AcmgError, CaseControl};
+
+/// How the variant is expected to cause disease, deciding the categories it's classified into following the ClinGen
+/// Low Penetrance/Risk Allele Working Group (Schmidt et al. 2024).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum AlleleModel {
+    /// A variant of a Mendelian disorder, on the P/LP/VUS/LB/B scale.
+    #[default]
+    Mendelian,
+    /// A variant of a Mendelian disorder with low penetrance, e.g. HFE p.C282Y, whose P and LP classifications are
+    /// qualified as low penetrance.
+    LowPenetrance,
+    /// A variant associated with a multifactorial disorder, classified as a risk allele from its case-control
+    /// association rather than on the P/LP/VUS scale.
+    RiskAllele,
+}
+
+impl AlleleModel {
+    pub const ALL: [AlleleModel; 3] = [AlleleModel::Mendelian, AlleleModel::LowPenetrance, AlleleModel::RiskAllele];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlleleModel::Mendelian => "mendelian",
+            AlleleModel::LowPenetrance => "low-penetrance",
+            AlleleModel::RiskAllele => "risk-allele",
+        }
+    }
+}
+
+impl Display for AlleleModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for AlleleModel {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<AlleleModel, Self::Err> {
+        AlleleModel::ALL.iter()
+            .find(|model| model.name().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| AcmgError::InvalidParameter(format!("unknown allele model '{}', expected mendelian, low-penetrance or risk-allele", s)))
+    }
+}
+
+/// A classification of a low penetrance variant or a risk allele, as ClinVar accepts them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum AlleleClassification {
+    PathogenicLowPenetrance,
+    LikelyPathogenicLowPenetrance,
+    EstablishedRiskAllele,
+    LikelyRiskAllele,
+    UncertainRiskAllele,
+}
+
+impl AlleleClassification {
+    /// The term, e.g. `Established risk allele`.
+    pub fn term(&self) -> &'static str {
+        match self {
+            AlleleClassification::PathogenicLowPenetrance => "Pathogenic, low penetrance",
+            AlleleClassification::LikelyPathogenicLowPenetrance => "Likely pathogenic, low penetrance",
+            AlleleClassification::EstablishedRiskAllele => "Established risk allele",
+            AlleleClassification::LikelyRiskAllele => "Likely risk allele",
+            AlleleClassification::UncertainRiskAllele => "Uncertain risk allele",
+        }
+    }
+
+    /// The low penetrance qualification of a P or LP classification, or None for the others, which stand as they are.
+    pub fn low_penetrance(classification: AcmgClassification) -> Option<AlleleClassification> {
+        match classification {
+            AcmgClassification::Pathogenic => Some(AlleleClassification::PathogenicLowPenetrance),
+            AcmgClassification::LikelyPathogenic => Some(AlleleClassification::LikelyPathogenicLowPenetrance),
+            _ => None,
+        }
+    }
+
+    /// Classifies a risk allele from its case-control association: Established if significantly more prevalent in
+    /// cases (see [`CaseControl::significant`]) and replicated by at least one independent study, Likely if the
+    /// association isn't replicated, and Uncertain without a significant association.
+    pub fn risk_allele(case_control: Option<&CaseControl>, replications: u32) -> AlleleClassification {
+        match case_control.filter(|case_control| case_control.significant()) {
+            Some(_) if replications > 0 => AlleleClassification::EstablishedRiskAllele,
+            Some(_) => AlleleClassification::LikelyRiskAllele,
+            None => AlleleClassification::UncertainRiskAllele,
+        }
+    }
+}
+
+impl Display for AlleleClassification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.term())
+    }
+}
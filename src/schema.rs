@@ -0,0 +1,7 @@
+/// The version of the JSON schema of assessment results, incremented when a field is removed or changes meaning.
+/// Fields may be added within a version.
+pub const RESULT_SCHEMA_VERSION: u32 = 1;
+
+/// The JSON Schema (draft 2020-12) of assessment results serialized with the `serde` feature, with the run metadata
+/// the CLI stamps them with.
+pub const RESULT_SCHEMA: &str = include_str!("../data/schemas/result-v1.schema.json");
@@ -0,0 +1,162 @@
+use std::fmt::{Display, Formatter};
+
+use crate::AcmgClassification;
+
+/// The version of the ACMG secondary findings gene list bundled, Miller et al. 2023.
+pub const SF_VERSION: &str = "ACMG SF v3.2";
+
+/// The phenotype category of a secondary findings gene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum SfCategory {
+    Cancer,
+    Cardiovascular,
+    Metabolism,
+    Miscellaneous,
+}
+
+impl Display for SfCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            SfCategory::Cancer => "cancer",
+            SfCategory::Cardiovascular => "cardiovascular",
+            SfCategory::Metabolism => "inborn errors of metabolism",
+            SfCategory::Miscellaneous => "miscellaneous",
+        })
+    }
+}
+
+use SfCategory::{Cancer, Cardiovascular, Metabolism, Miscellaneous};
+
+const BIALLELIC: Option<&str> = Some("report biallelic P/LP variants");
+
+/// The genes of ACMG SF v3.2, with how P/LP variants are reported where not all of them are.
+const SF_GENES: [(&str, SfCategory, Option<&str>); 81] = [
+    ("APC", Cancer, None),
+    ("BMPR1A", Cancer, None),
+    ("BRCA1", Cancer, None),
+    ("BRCA2", Cancer, None),
+    ("MAX", Cancer, None),
+    ("MEN1", Cancer, None),
+    ("MLH1", Cancer, None),
+    ("MSH2", Cancer, None),
+    ("MSH6", Cancer, None),
+    ("MUTYH", Cancer, BIALLELIC),
+    ("NF2", Cancer, None),
+    ("PALB2", Cancer, None),
+    ("PMS2", Cancer, None),
+    ("PTEN", Cancer, None),
+    ("RB1", Cancer, None),
+    ("RET", Cancer, None),
+    ("SDHAF2", Cancer, None),
+    ("SDHB", Cancer, None),
+    ("SDHC", Cancer, None),
+    ("SDHD", Cancer, None),
+    ("SMAD4", Cancer, None),
+    ("STK11", Cancer, None),
+    ("TMEM127", Cancer, None),
+    ("TP53", Cancer, None),
+    ("TSC1", Cancer, None),
+    ("TSC2", Cancer, None),
+    ("VHL", Cancer, None),
+    ("WT1", Cancer, None),
+    ("ACTA2", Cardiovascular, None),
+    ("ACTC1", Cardiovascular, None),
+    ("APOB", Cardiovascular, None),
+    ("BAG3", Cardiovascular, None),
+    ("CALM1", Cardiovascular, None),
+    ("CALM2", Cardiovascular, None),
+    ("CALM3", Cardiovascular, None),
+    ("CASQ2", Cardiovascular, BIALLELIC),
+    ("COL3A1", Cardiovascular, None),
+    ("DES", Cardiovascular, None),
+    ("DSC2", Cardiovascular, None),
+    ("DSG2", Cardiovascular, None),
+    ("DSP", Cardiovascular, None),
+    ("FBN1", Cardiovascular, None),
+    ("FLNC", Cardiovascular, None),
+    ("KCNH2", Cardiovascular, None),
+    ("KCNQ1", Cardiovascular, None),
+    ("LDLR", Cardiovascular, None),
+    ("LMNA", Cardiovascular, None),
+    ("MYBPC3", Cardiovascular, None),
+    ("MYH11", Cardiovascular, None),
+    ("MYH7", Cardiovascular, None),
+    ("MYL2", Cardiovascular, None),
+    ("MYL3", Cardiovascular, None),
+    ("PCSK9", Cardiovascular, None),
+    ("PKP2", Cardiovascular, None),
+    ("PRKAG2", Cardiovascular, None),
+    ("RBM20", Cardiovascular, None),
+    ("RYR2", Cardiovascular, None),
+    ("SCN5A", Cardiovascular, None),
+    ("SMAD3", Cardiovascular, None),
+    ("TGFBR1", Cardiovascular, None),
+    ("TGFBR2", Cardiovascular, None),
+    ("TMEM43", Cardiovascular, None),
+    ("TNNC1", Cardiovascular, None),
+    ("TNNI3", Cardiovascular, None),
+    ("TNNT2", Cardiovascular, None),
+    ("TPM1", Cardiovascular, None),
+    ("TRDN", Cardiovascular, BIALLELIC),
+    ("TTN", Cardiovascular, Some("report truncating P/LP variants only")),
+    ("BTD", Metabolism, BIALLELIC),
+    ("GAA", Metabolism, BIALLELIC),
+    ("GLA", Metabolism, None),
+    ("OTC", Metabolism, None),
+    ("ACVRL1", Miscellaneous, None),
+    ("ATP7B", Miscellaneous, BIALLELIC),
+    ("CACNA1S", Miscellaneous, None),
+    ("ENG", Miscellaneous, None),
+    ("HFE", Miscellaneous, Some("report p.C282Y homozygotes only")),
+    ("HNF1A", Miscellaneous, None),
+    ("RPE65", Miscellaneous, BIALLELIC),
+    ("RYR1", Miscellaneous, None),
+    ("TTR", Miscellaneous, None),
+];
+
+/// A gene of the ACMG secondary findings list, in which P/LP variants are reported as secondary findings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SecondaryFindingsGene {
+    pub gene: String,
+    pub category: SfCategory,
+    /// Which P/LP variants are reported, where not all of them are, e.g. only biallelic ones.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub note: Option<String>,
+    /// The version of the list, e.g. ACMG SF v3.2.
+    pub version: String,
+}
+
+impl SecondaryFindingsGene {
+    /// Whether a variant of the classification is reported as a secondary finding, if Pathogenic or Likely Pathogenic.
+    pub fn is_reportable(&self, classification: AcmgClassification) -> bool {
+        matches!(classification, AcmgClassification::Pathogenic | AcmgClassification::LikelyPathogenic)
+    }
+}
+
+/// E.g. `BRCA1 is an ACMG SF v3.2 gene (cancer)`.
+impl Display for SecondaryFindingsGene {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is an {} gene ({})", self.gene, self.version, self.category)?;
+        match &self.note {
+            Some(note) => write!(f, ", {}", note),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The gene of the bundled ACMG secondary findings list with the symbol, ignoring case.
+pub fn secondary_findings_gene(gene: &str) -> Option<SecondaryFindingsGene> {
+    SF_GENES.iter()
+        .find(|(symbol, _, _)| symbol.eq_ignore_ascii_case(gene.trim()))
+        .map(|(symbol, category, note)| SecondaryFindingsGene {
+            gene: symbol.to_string(),
+            category: *category,
+            note: note.map(str::to_string),
+            version: SF_VERSION.to_string(),
+        })
+}
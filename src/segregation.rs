@@ -0,0 +1,207 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::BufRead;
+
+use crate::EvidenceStrength::{Moderate, Strong, Supporting};
+use crate::{AcmgError, Code, Evidence};
+
+/// Counts of informative meioses across the families segregating a variant with a dominant disorder, for the
+/// counting method of Jarvik & Browning 2016.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SegregationCounts {
+    /// Affected relatives carrying the variant, excluding the probands.
+    pub affected_carriers: u32,
+    /// Unaffected relatives not carrying the variant.
+    pub unaffected_noncarriers: u32,
+    /// Affected relatives not carrying the variant, i.e. observations of non-segregation.
+    pub affected_noncarriers: u32,
+}
+
+impl SegregationCounts {
+    /// The LOD score, log10 of 1 / (0.5^affected carriers × 0.75^unaffected non-carriers).
+    pub fn lod(&self) -> f64 {
+        -(self.affected_carriers as f64 * 0.5_f64.log10() + self.unaffected_noncarriers as f64 * 0.75_f64.log10())
+    }
+
+    /// BS4 if the variant fails to segregate in any affected relative, otherwise PP1 at Supporting from a LOD
+    /// of 0.9, Moderate from 1.5 and Strong from 3, or None if the LOD is too low to apply PP1.
+    pub fn evidence(&self) -> Option<Evidence> {
+        if self.affected_noncarriers > 0 {
+            return Some(Evidence::from(Code::BS4));
+        }
+        let strength = match self.lod() {
+            lod if lod >= 3.0 => Strong,
+            lod if lod >= 1.5 => Moderate,
+            lod if lod >= 0.9 => Supporting,
+            _ => return None,
+        };
+        Some(Evidence::at_strength(Code::PP1, strength))
+    }
+}
+
+impl std::ops::Add for SegregationCounts {
+    type Output = SegregationCounts;
+
+    fn add(self, other: SegregationCounts) -> SegregationCounts {
+        SegregationCounts {
+            affected_carriers: self.affected_carriers + other.affected_carriers,
+            unaffected_noncarriers: self.unaffected_noncarriers + other.unaffected_noncarriers,
+            affected_noncarriers: self.affected_noncarriers + other.affected_noncarriers,
+        }
+    }
+}
+
+/// An individual of a PED file. Parents are None for founders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PedIndividual {
+    pub family: String,
+    pub id: String,
+    pub father: Option<String>,
+    pub mother: Option<String>,
+    /// Whether the individual is affected, None if the phenotype is unknown.
+    pub affected: Option<bool>,
+}
+
+/// The families of a PED file, the six whitespace-separated columns of family, individual, father, mother, sex and
+/// phenotype, with 1 for unaffected and 2 for affected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pedigree {
+    pub individuals: Vec<PedIndividual>,
+}
+
+impl Pedigree {
+    /// Reads a PED file, skipping blank lines and `#` comments.
+    pub fn from_ped(reader: impl BufRead) -> Result<Pedigree, AcmgError> {
+        let mut individuals = vec![];
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|error| AcmgError::InvalidPed(error.to_string()))?;
+            let invalid = |reason: &str| AcmgError::InvalidPed(format!("line {}: {}", number + 1, reason));
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 6 {
+                return Err(invalid("expected family, individual, father, mother, sex and phenotype columns"));
+            }
+            let parent = |id: &str| (id != "0").then(|| id.to_string());
+            let affected = match fields[5] {
+                "1" => Some(false),
+                "2" => Some(true),
+                "0" | "-9" => None,
+                _ => return Err(invalid("expected a phenotype of 1 for unaffected, 2 for affected or 0 for unknown")),
+            };
+            if individuals.iter().any(|individual: &PedIndividual| individual.family == fields[0] && individual.id == fields[1]) {
+                return Err(invalid(&format!("individual {} is repeated", fields[1])));
+            }
+            individuals.push(PedIndividual {
+                family: fields[0].to_string(),
+                id: fields[1].to_string(),
+                father: parent(fields[2]),
+                mother: parent(fields[3]),
+                affected,
+            });
+        }
+        Ok(Pedigree { individuals })
+    }
+
+    /// The individual given as `FAMILY:ID`, or by a bare ID found in only one family, as PED IDs are only unique
+    /// within a family.
+    fn individual(&self, given: &str) -> Result<&PedIndividual, AcmgError> {
+        if let Some(individual) = given.split_once(':').and_then(|(family, id)| self.individuals.iter().find(|individual| individual.family == family && individual.id == id)) {
+            return Ok(individual);
+        }
+        let mut matching = self.individuals.iter().filter(|individual| individual.id == given);
+        match (matching.next(), matching.next()) {
+            (Some(individual), None) => Ok(individual),
+            (Some(first), Some(second)) => Err(AcmgError::InvalidPed(format!(
+                "individual {} is in families {} and {}, give it as FAMILY:ID e.g. {}:{}", given, first.family, second.family, first.family, given))),
+            (None, _) => Err(AcmgError::InvalidPed(format!("no individual {}", given))),
+        }
+    }
+
+    /// The family and ID of each individual given, by [`Pedigree::individual`].
+    fn resolve(&self, given: &[&str]) -> Result<HashSet<(&str, &str)>, AcmgError> {
+        given.iter()
+            .map(|given| self.individual(given).map(|individual| (individual.family.as_str(), individual.id.as_str())))
+            .collect()
+    }
+
+    /// Counts the informative meioses of each family from the individuals tested for the variant, given as
+    /// `FAMILY:ID` or by an ID found in only one family. Probands aren't counted as affected carriers, and a family
+    /// with no proband given has its first affected carrier taken as its proband. Individuals of unknown phenotype,
+    /// unaffected carriers and untested individuals aren't informative.
+    pub fn segregation_counts(&self, carriers: &[&str], noncarriers: &[&str], probands: &[&str]) -> Result<BTreeMap<String, SegregationCounts>, AcmgError> {
+        let (carriers, noncarriers, probands) = (self.resolve(carriers)?, self.resolve(noncarriers)?, self.resolve(probands)?);
+        if let Some((family, id)) = carriers.iter().find(|individual| noncarriers.contains(*individual)) {
+            return Err(AcmgError::InvalidPed(format!("{}:{} is given as both a carrier and a non-carrier", family, id)));
+        }
+        let mut families: BTreeMap<String, SegregationCounts> = BTreeMap::new();
+        let mut with_proband: HashSet<&str> = probands.iter().map(|(family, _)| *family).collect();
+        for individual in &self.individuals {
+            let counts = families.entry(individual.family.clone()).or_default();
+            let key = (individual.family.as_str(), individual.id.as_str());
+            let (carrier, noncarrier) = (carriers.contains(&key), noncarriers.contains(&key));
+            match individual.affected {
+                Some(true) if carrier && probands.contains(&key) => {}
+                Some(true) if carrier && with_proband.insert(individual.family.as_str()) => {}
+                Some(true) if carrier => counts.affected_carriers += 1,
+                Some(true) if noncarrier => counts.affected_noncarriers += 1,
+                Some(false) if noncarrier => counts.unaffected_noncarriers += 1,
+                _ => {}
+            }
+        }
+        Ok(families)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PED: &str = "F1 1 0 0 1 2\nF1 2 0 0 2 1\nF1 3 1 2 1 2\nF2 1 0 0 1 2\nF2 2 0 0 2 2\nF2 3 1 2 1 2\n";
+
+    fn applied(affected_carriers: u32, unaffected_noncarriers: u32, affected_noncarriers: u32) -> Option<String> {
+        SegregationCounts { affected_carriers, unaffected_noncarriers, affected_noncarriers }.evidence().map(|evidence| evidence.to_string())
+    }
+
+    #[test]
+    fn lod_counts_half_per_affected_carrier_and_three_quarters_per_unaffected_noncarrier() {
+        let counts = SegregationCounts { affected_carriers: 3, unaffected_noncarriers: 2, affected_noncarriers: 0 };
+        assert!((counts.lod() - (8.0f64.log10() + (16.0f64 / 9.0).log10())).abs() < 1e-12);
+        assert_eq!(SegregationCounts::default().lod(), 0.0);
+    }
+
+    #[test]
+    fn pp1_strength_rises_at_lods_of_0_9_1_5_and_3() {
+        assert_eq!(applied(2, 0, 0), None);
+        assert_eq!(applied(2, 1, 0), None);
+        assert_eq!(applied(3, 0, 0).as_deref(), Some("PP1"));
+        assert_eq!(applied(2, 3, 0).as_deref(), Some("PP1"));
+        assert_eq!(applied(4, 0, 0).as_deref(), Some("PP1"));
+        assert_eq!(applied(5, 0, 0).as_deref(), Some("PP1_Moderate"));
+        assert_eq!(applied(9, 0, 0).as_deref(), Some("PP1_Moderate"));
+        assert_eq!(applied(10, 0, 0).as_deref(), Some("PP1_Strong"));
+        assert_eq!(applied(0, 24, 0).as_deref(), Some("PP1_Moderate"));
+        assert_eq!(applied(0, 25, 0).as_deref(), Some("PP1_Strong"));
+    }
+
+    #[test]
+    fn an_affected_noncarrier_is_bs4_whatever_the_lod() {
+        assert_eq!(applied(0, 0, 1).as_deref(), Some("BS4"));
+        assert_eq!(applied(10, 5, 1).as_deref(), Some("BS4"));
+        let pedigree = Pedigree::from_ped(PED.as_bytes()).unwrap();
+        let families = pedigree.segregation_counts(&["F2:3"], &["F2:2"], &["F2:3"]).unwrap();
+        assert_eq!(families["F2"], SegregationCounts { affected_carriers: 0, unaffected_noncarriers: 0, affected_noncarriers: 1 });
+        assert_eq!(families["F2"].evidence().map(|evidence| evidence.to_string()).as_deref(), Some("BS4"));
+    }
+
+    #[test]
+    fn ids_repeated_across_families_must_be_qualified() {
+        let pedigree = Pedigree::from_ped(PED.as_bytes()).unwrap();
+        assert!(pedigree.segregation_counts(&["1", "3"], &["2"], &[]).is_err());
+        let families = pedigree.segregation_counts(&["F1:1", "F1:3"], &["F1:2"], &[]).unwrap();
+        assert_eq!(families["F1"], SegregationCounts { affected_carriers: 1, unaffected_noncarriers: 1, affected_noncarriers: 0 });
+        assert_eq!(families["F2"], SegregationCounts::default());
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::AcmgError;
+
+/// The level of evidence for the clinical significance of a somatic variant, from Li et al. 2017.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EvidenceLevel {
+    /// FDA-approved therapy or included in professional guidelines.
+    A,
+    /// Well-powered studies with consensus from experts in the field.
+    B,
+    /// FDA-approved therapies for different tumor types or investigational therapies, or multiple small published
+    /// studies with some consensus.
+    C,
+    /// Preclinical trials or a few case reports without consensus.
+    D,
+}
+
+impl EvidenceLevel {
+    pub const ALL: [EvidenceLevel; 4] = [EvidenceLevel::A, EvidenceLevel::B, EvidenceLevel::C, EvidenceLevel::D];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EvidenceLevel::A => "A",
+            EvidenceLevel::B => "B",
+            EvidenceLevel::C => "C",
+            EvidenceLevel::D => "D",
+        }
+    }
+}
+
+impl Display for EvidenceLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for EvidenceLevel {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<EvidenceLevel, Self::Err> {
+        EvidenceLevel::ALL.iter()
+            .find(|level| level.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| AcmgError::UnknownEvidenceLevel(s.to_string()))
+    }
+}
+
+/// The AMP/ASCO/CAP tier of a somatic variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SomaticTier {
+    /// Variants of strong clinical significance.
+    TierI,
+    /// Variants of potential clinical significance.
+    TierII,
+    /// Variants of unknown clinical significance.
+    TierIII,
+    /// Benign or likely benign variants.
+    TierIV,
+}
+
+impl SomaticTier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SomaticTier::TierI => "Tier I",
+            SomaticTier::TierII => "Tier II",
+            SomaticTier::TierIII => "Tier III",
+            SomaticTier::TierIV => "Tier IV",
+        }
+    }
+}
+
+impl Display for SomaticTier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// The evidence for the clinical significance of a somatic variant in a tumor, tiered following the AMP/ASCO/CAP
+/// standards of Li et al. 2017.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SomaticEvidence {
+    /// The strongest evidence the variant predicts response or resistance to a therapy.
+    pub therapeutic: Option<EvidenceLevel>,
+    /// The strongest evidence the variant is diagnostic of a tumor type.
+    pub diagnostic: Option<EvidenceLevel>,
+    /// The strongest evidence the variant is prognostic.
+    pub prognostic: Option<EvidenceLevel>,
+    /// The variant is observed at a significant allele frequency in the general population or is known to be
+    /// benign.
+    pub benign: bool,
+}
+
+impl SomaticEvidence {
+    /// The strongest level of therapeutic, diagnostic or prognostic evidence.
+    pub fn level(&self) -> Option<EvidenceLevel> {
+        [self.therapeutic, self.diagnostic, self.prognostic].into_iter().flatten().min()
+    }
+
+    /// Tier I for level A or B evidence, Tier II for level C or D, Tier III without any and Tier IV for benign
+    /// variants.
+    pub fn tier(&self) -> SomaticTier {
+        if self.benign {
+            return SomaticTier::TierIV;
+        }
+        match self.level() {
+            Some(EvidenceLevel::A | EvidenceLevel::B) => SomaticTier::TierI,
+            Some(EvidenceLevel::C | EvidenceLevel::D) => SomaticTier::TierII,
+            None => SomaticTier::TierIII,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use crate::{AcmgError, Code, Evidence, EvidenceSet, EvidenceStrength};
+
+/// How a gene-specific specification modifies a single criterion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CriterionSpec {
+    /// Whether the criterion may be applied at all for this gene.
+    #[cfg_attr(feature = "serde", serde(default = "applicable"))]
+    pub applicable: bool,
+    /// Strength used when the code is applied without a modifier.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub default_strength: Option<EvidenceStrength>,
+    /// Strengths the criterion may be applied at, any strength if empty.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allowed_strengths: Vec<EvidenceStrength>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub notes: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+fn applicable() -> bool {
+    true
+}
+
+impl Default for CriterionSpec {
+    fn default() -> CriterionSpec {
+        CriterionSpec { applicable: true, default_strength: None, allowed_strengths: vec![], notes: None }
+    }
+}
+
+/// Gene-specific allele frequency thresholds for the frequency criteria.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrequencyThresholds {
+    pub ba1: Option<f64>,
+    pub bs1: Option<f64>,
+    pub pm2: Option<f64>,
+}
+
+/// A gene-specific criteria specification, such as those defined by ClinGen Variant Curation Expert Panels
+/// (VCEPs), which adjusts the built-in criteria before scoring.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CriteriaSpec {
+    pub gene: String,
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub version: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub criteria: BTreeMap<Code, CriterionSpec>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub frequency_thresholds: FrequencyThresholds,
+}
+
+#[cfg(feature = "serde")]
+const BUILTIN_SPECS: &[(&str, &str)] = &[
+    ("MT", include_str!("../data/specs/MT.json")),
+    ("MYH7", include_str!("../data/specs/MYH7.json")),
+];
+
+impl CriteriaSpec {
+    /// Parses a specification from JSON, e.g. a file exported from the ClinGen Criteria Specification Registry
+    /// and converted to this format.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<CriteriaSpec, AcmgError> {
+        serde_json::from_str(json).map_err(|error| AcmgError::InvalidSpec(error.to_string()))
+    }
+
+    /// One of the specifications bundled with the crate, by gene symbol.
+    #[cfg(feature = "serde")]
+    pub fn builtin(gene: &str) -> Result<CriteriaSpec, AcmgError> {
+        BUILTIN_SPECS.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(gene))
+            .ok_or_else(|| AcmgError::UnknownSpec(gene.to_string()))
+            .and_then(|(_, json)| CriteriaSpec::from_json(json))
+    }
+
+    /// Genes with a bundled specification.
+    #[cfg(feature = "serde")]
+    pub fn builtin_genes() -> impl Iterator<Item = &'static str> {
+        BUILTIN_SPECS.iter().map(|(gene, _)| *gene)
+    }
+
+    /// A short label identifying the specification, e.g. `MYH7 v1.0`.
+    pub fn label(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{} v{}", self.gene, version),
+            None => self.gene.clone(),
+        }
+    }
+
+    pub fn criterion(&self, code: Code) -> Option<&CriterionSpec> {
+        self.criteria.get(&code)
+    }
+
+    /// Applies the specification to the evidence - codes applied without a modifier are given the specified
+    /// default strength, and criteria which are not applicable or applied at a disallowed strength are errors.
+    /// Registered codes other than replacements of built-in codes are left as they are.
+    pub fn apply(&self, evidence_set: &EvidenceSet) -> Result<EvidenceSet, AcmgError> {
+        evidence_set.iter()
+            .map(|evidence| self.apply_evidence(evidence))
+            .collect()
+    }
+
+    fn apply_evidence(&self, evidence: &Evidence) -> Result<Evidence, AcmgError> {
+        let Ok(code) = evidence.evidence_code.to_string().parse::<Code>() else {
+            return Ok(evidence.clone());
+        };
+        let criterion = match self.criterion(code) {
+            Some(criterion) => criterion,
+            None => return Ok(evidence.clone()),
+        };
+        if !criterion.applicable {
+            return Err(AcmgError::DisallowedCode { code: code.to_string(), spec: self.label() });
+        }
+        let modifier = evidence.modifier.or(criterion.default_strength.filter(|strength| *strength != evidence.evidence_code.strength));
+        let applied = Evidence { note: evidence.note.clone(), citations: evidence.citations.clone(), ..Evidence::new(evidence.evidence_code.clone(), modifier) };
+        if !criterion.allowed_strengths.is_empty() && !criterion.allowed_strengths.contains(&applied.strength()) {
+            return Err(AcmgError::DisallowedStrength { evidence: applied.to_string(), spec: self.label() });
+        }
+        Ok(applied)
+    }
+}
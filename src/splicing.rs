@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use crate::{AcmgError, Code, Evidence, GenomeBuild, GenomicVariant};
+
+/// SpliceAI delta scores from which PP3 applies, following the ClinGen SVI splicing recommendations (Walker et al.
+/// 2023).
+pub const SPLICEAI_PP3: f64 = 0.2;
+/// SpliceAI delta scores up to which BP4 applies, or which predict no impact on splicing.
+pub const SPLICEAI_BP4: f64 = 0.1;
+
+/// PP3 for a SpliceAI delta score of at least 0.2, BP4 for one of at most 0.1, or None in between.
+pub fn spliceai_evidence(delta: f64) -> Result<Option<Evidence>, AcmgError> {
+    if !(0.0..=1.0).contains(&delta) {
+        return Err(AcmgError::InvalidParameter(format!("SpliceAI delta scores must be between 0 and 1, got {}", delta)));
+    }
+    Ok(match delta {
+        delta if delta >= SPLICEAI_PP3 => Some(Evidence::from(Code::PP3)),
+        delta if delta <= SPLICEAI_BP4 => Some(Evidence::from(Code::BP4)),
+        _ => None,
+    })
+}
+
+/// The largest SpliceAI delta score of each variant of a precomputed SpliceAI VCF, e.g. an extract of the Illumina
+/// masked scores.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpliceAiScores {
+    scores: HashMap<GenomicVariant, f64>,
+}
+
+impl SpliceAiScores {
+    /// Reads the `SpliceAI` INFO field of a VCF on the given build, `ALLELE|SYMBOL|DS_AG|DS_AL|DS_DG|DS_DL|...` for
+    /// each allele and gene, keeping the largest delta score of each allele across genes.
+    pub fn from_vcf(reader: impl BufRead, build: GenomeBuild) -> Result<SpliceAiScores, AcmgError> {
+        let mut scores: HashMap<GenomicVariant, f64> = HashMap::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|error| AcmgError::InvalidVcf(error.to_string()))?;
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            let invalid = |reason: &str| AcmgError::InvalidVcf(format!("line {}: {}", number + 1, reason));
+            let fields: Vec<&str> = line.splitn(9, '\t').collect();
+            if fields.len() < 8 {
+                return Err(invalid("expected at least 8 columns"));
+            }
+            let position: u64 = fields[1].parse().map_err(|_| invalid("invalid position"))?;
+            let Some(predictions) = fields[7].split(';').find_map(|entry| entry.strip_prefix("SpliceAI=")) else {
+                continue;
+            };
+            for prediction in predictions.split(',') {
+                let values: Vec<&str> = prediction.split('|').collect();
+                if values.len() < 6 {
+                    return Err(invalid("expected ALLELE|SYMBOL|DS_AG|DS_AL|DS_DG|DS_DL in the SpliceAI field"));
+                }
+                let Ok(variant) = GenomicVariant::new(build, fields[0], position, fields[3], values[0]) else {
+                    continue;
+                };
+                let delta = values[2..6].iter()
+                    .map(|score| score.parse::<f64>().map_err(|_| invalid("invalid SpliceAI delta score")))
+                    .try_fold(0.0_f64, |max, score| score.map(|score| max.max(score)))?;
+                let max = scores.entry(variant).or_insert(delta);
+                *max = max.max(delta);
+            }
+        }
+        Ok(SpliceAiScores { scores })
+    }
+
+    /// The largest delta score of the variant, if it was scored.
+    pub fn get(&self, variant: &GenomicVariant) -> Option<f64> {
+        self.scores.get(variant).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+}
+
+/// Thresholds of the splicing and conservation scores for BP7.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bp7Thresholds {
+    /// The largest SpliceAI delta score predicting no impact on splicing.
+    pub spliceai: f64,
+    /// phyloP scores at or above this are highly conserved, e.g. of phyloP100way.
+    pub phylop: f64,
+    /// GERP++ RS scores at or above this are highly conserved.
+    pub gerp: f64,
+}
+
+impl Default for Bp7Thresholds {
+    fn default() -> Bp7Thresholds {
+        Bp7Thresholds { spliceai: 0.1, phylop: 0.1, gerp: 2.0 }
+    }
+}
+
+/// The scores of a synonymous variant for deciding BP7.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bp7Input {
+    /// The largest of the SpliceAI acceptor and donor gain and loss delta scores.
+    pub spliceai: Option<f64>,
+    pub phylop: Option<f64>,
+    pub gerp: Option<f64>,
+}
+
+/// Whether BP7 applies to a synonymous variant and why.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Bp7Outcome {
+    pub applies: bool,
+    pub reasons: Vec<String>,
+}
+
+impl Bp7Outcome {
+    pub fn evidence(&self) -> Option<Evidence> {
+        self.applies.then(|| Evidence::from(Code::BP7))
+    }
+}
+
+/// Decides BP7 for a synonymous variant, which applies if SpliceAI predicts no impact on splicing and the nucleotide
+/// isn't highly conserved. At least one of the phyloP and GERP++ scores is needed, and neither may reach its
+/// threshold.
+pub fn assess_bp7(input: &Bp7Input, thresholds: &Bp7Thresholds) -> Result<Bp7Outcome, AcmgError> {
+    if let Some(spliceai) = input.spliceai.filter(|spliceai| !(0.0..=1.0).contains(spliceai)) {
+        return Err(AcmgError::InvalidParameter(format!("SpliceAI delta scores must be between 0 and 1, got {}", spliceai)));
+    }
+    let mut applies = true;
+    let mut reasons = vec![];
+    match input.spliceai {
+        Some(spliceai) if spliceai <= thresholds.spliceai => {
+            reasons.push(format!("SpliceAI {} predicts no impact on splicing (at most {})", spliceai, thresholds.spliceai));
+        }
+        Some(spliceai) => {
+            applies = false;
+            reasons.push(format!("SpliceAI {} predicts an impact on splicing (above {})", spliceai, thresholds.spliceai));
+        }
+        None => {
+            applies = false;
+            reasons.push("No SpliceAI score to rule out an impact on splicing".to_string());
+        }
+    }
+    let conservation = [("phyloP", input.phylop, thresholds.phylop), ("GERP++", input.gerp, thresholds.gerp)];
+    if conservation.iter().all(|(_, score, _)| score.is_none()) {
+        applies = false;
+        reasons.push("No phyloP or GERP++ score to rule out high conservation".to_string());
+    }
+    for (name, score, threshold) in conservation {
+        match score {
+            Some(score) if score >= threshold => {
+                applies = false;
+                reasons.push(format!("{} {} is highly conserved (at least {})", name, score, threshold));
+            }
+            Some(score) => reasons.push(format!("{} {} is not highly conserved (below {})", name, score, threshold)),
+            None => {}
+        }
+    }
+    Ok(Bp7Outcome { applies, reasons })
+}
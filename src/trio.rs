@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{AcmgError, Code, DeNovoObservations, Evidence, GenomicVariant, PhenotypeConsistency};
+
+/// The genotype of an individual at a variant, by the number of copies of the alternate allele.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Genotype {
+    HomRef,
+    Het,
+    /// Homozygous, or hemizygous for a haploid call such as on the X chromosome of a male.
+    HomAlt,
+    NoCall,
+}
+
+impl Genotype {
+    /// The genotype of a VCF GT field, e.g. `0/1` or `1|1`, counting copies of the alternate allele with the given
+    /// 1-based index. Calls with any missing allele are no calls.
+    pub fn from_gt(gt: &str, allele: usize) -> Result<Genotype, AcmgError> {
+        let alleles: Vec<&str> = gt.split(['/', '|']).collect();
+        if alleles.len() > 2 || alleles.iter().any(|allele| allele.is_empty()) {
+            return Err(AcmgError::InvalidGenotype(gt.to_string()));
+        }
+        if alleles.contains(&".") {
+            return Ok(Genotype::NoCall);
+        }
+        let mut copies = 0;
+        for called in &alleles {
+            let called: usize = called.parse().map_err(|_| AcmgError::InvalidGenotype(gt.to_string()))?;
+            copies += usize::from(called == allele);
+        }
+        Ok(match (copies, alleles.len()) {
+            (0, _) => Genotype::HomRef,
+            (1, 2) => Genotype::Het,
+            _ => Genotype::HomAlt,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Genotype::HomRef => "hom-ref",
+            Genotype::Het => "het",
+            Genotype::HomAlt => "hom-alt",
+            Genotype::NoCall => "no-call",
+        }
+    }
+
+    /// Whether the individual carries the alternate allele, None for a no call.
+    pub fn carrier(&self) -> Option<bool> {
+        match self {
+            Genotype::HomRef => Some(false),
+            Genotype::Het | Genotype::HomAlt => Some(true),
+            Genotype::NoCall => None,
+        }
+    }
+}
+
+impl Display for Genotype {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// Parses a genotype by name, e.g. `het`, or as a biallelic VCF GT field, e.g. `0/1`.
+impl FromStr for Genotype {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<Genotype, Self::Err> {
+        let named = [Genotype::HomRef, Genotype::Het, Genotype::HomAlt, Genotype::NoCall].into_iter()
+            .find(|genotype| genotype.name().eq_ignore_ascii_case(s.trim()));
+        match named {
+            Some(genotype) => Ok(genotype),
+            None => Genotype::from_gt(s.trim(), 1),
+        }
+    }
+}
+
+/// The genotypes of a proband and their parents at a variant, with whether the parents are affected with the
+/// proband's disorder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trio {
+    pub proband: Genotype,
+    pub mother: Genotype,
+    pub father: Genotype,
+    /// Whether maternity and paternity are confirmed, e.g. by the trio's genotypes genome-wide.
+    pub parentage_confirmed: bool,
+    pub mother_affected: bool,
+    pub father_affected: bool,
+    pub phenotype: PhenotypeConsistency,
+}
+
+/// How the proband came to carry a variant, from the genotypes of a [`Trio`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum TrioOrigin {
+    /// Neither parent carries the variant.
+    DeNovo,
+    Maternal,
+    Paternal,
+    /// Both parents carry the variant.
+    Biparental,
+    /// The proband doesn't carry the variant.
+    NotCarried,
+    /// A genotype needed to tell is a no call.
+    Unknown,
+}
+
+impl Display for TrioOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            TrioOrigin::DeNovo => "de novo",
+            TrioOrigin::Maternal => "maternally inherited",
+            TrioOrigin::Paternal => "paternally inherited",
+            TrioOrigin::Biparental => "inherited from both parents",
+            TrioOrigin::NotCarried => "not carried by the proband",
+            TrioOrigin::Unknown => "unknown, a genotype is a no call",
+        })
+    }
+}
+
+impl Trio {
+    pub fn origin(&self) -> TrioOrigin {
+        match (self.proband.carrier(), self.mother.carrier(), self.father.carrier()) {
+            (Some(false), _, _) => TrioOrigin::NotCarried,
+            (Some(true), Some(false), Some(false)) => TrioOrigin::DeNovo,
+            (Some(true), Some(true), Some(true)) => TrioOrigin::Biparental,
+            (Some(true), Some(true), Some(false)) => TrioOrigin::Maternal,
+            (Some(true), Some(false), Some(true)) => TrioOrigin::Paternal,
+            _ => TrioOrigin::Unknown,
+        }
+    }
+
+    /// Whether a parent affected with the proband's disorder doesn't carry the variant the proband carries.
+    pub fn non_segregating(&self) -> bool {
+        self.proband.carrier() == Some(true)
+            && ((self.mother_affected && self.mother.carrier() == Some(false))
+                || (self.father_affected && self.father.carrier() == Some(false)))
+    }
+
+    /// The variant as a de novo observation, confirmed or assumed by the parentage, if it arose de novo in the
+    /// proband of unaffected parents.
+    pub fn de_novo_observations(&self) -> Option<DeNovoObservations> {
+        (self.origin() == TrioOrigin::DeNovo && !self.non_segregating()).then(|| DeNovoObservations {
+            confirmed: u32::from(self.parentage_confirmed),
+            assumed: u32::from(!self.parentage_confirmed),
+            phenotype: self.phenotype,
+        })
+    }
+
+    /// BS4 if an affected parent doesn't carry the variant, otherwise PS2 for a de novo variant with confirmed
+    /// parentage or PM6 with assumed parentage, at the strength of [`DeNovoObservations::strength`]. None if the
+    /// variant is inherited from unaffected parents or its origin is unknown.
+    pub fn evidence(&self) -> Option<Evidence> {
+        if self.non_segregating() {
+            return Some(Evidence::from(Code::BS4));
+        }
+        self.de_novo_observations().and_then(|observations| observations.evidence())
+    }
+}
+
+/// Reads the genotypes of the proband, mother and father samples, in that order, at a variant from a multi-sample
+/// VCF. Multiallelic records are matched by the alternate allele and records are matched after normalization, so
+/// the variant can be given in any equivalent representation.
+pub fn read_trio_genotypes(reader: impl BufRead, variant: &GenomicVariant, samples: [&str; 3]) -> Result<[Genotype; 3], AcmgError> {
+    let mut columns: Option<[usize; 3]> = None;
+    for (number, line) in reader.lines().enumerate() {
+        let line = line.map_err(|error| AcmgError::InvalidVcf(error.to_string()))?;
+        let invalid = |reason: String| AcmgError::InvalidVcf(format!("line {}: {}", number + 1, reason));
+        if let Some(header) = line.strip_prefix("#CHROM") {
+            let names: HashMap<&str, usize> = header.split('\t').enumerate().skip(9).map(|(column, name)| (name, column)).collect();
+            let column = |sample: &str| names.get(sample).copied().ok_or_else(|| invalid(format!("no sample {}", sample)));
+            columns = Some([column(samples[0])?, column(samples[1])?, column(samples[2])?]);
+            continue;
+        }
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+        let columns = columns.ok_or_else(|| invalid("expected a #CHROM header line before the records".to_string()))?;
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() < 10 {
+            return Err(invalid("expected at least 10 columns".to_string()));
+        }
+        let Ok(position) = fields[1].parse::<u64>() else {
+            return Err(invalid("invalid position".to_string()));
+        };
+        let allele = fields[4].split(',').position(|alternate| {
+            GenomicVariant::new(variant.build, fields[0], position, fields[3], alternate).is_ok_and(|record| record == *variant)
+        });
+        let Some(allele) = allele else {
+            continue;
+        };
+        let gt = fields[8].split(':').position(|key| key == "GT").ok_or_else(|| invalid("no GT field".to_string()))?;
+        let genotype = |column: usize| -> Result<Genotype, AcmgError> {
+            let sample = fields.get(column).ok_or_else(|| invalid(format!("no genotype in column {}", column + 1)))?;
+            Genotype::from_gt(sample.split(':').nth(gt).unwrap_or("."), allele + 1)
+        };
+        return Ok([genotype(columns[0])?, genotype(columns[1])?, genotype(columns[2])?]);
+    }
+    Err(AcmgError::InvalidVcf(format!("{} not found", variant)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trio(proband: &str, mother: &str, father: &str) -> Trio {
+        Trio {
+            proband: proband.parse().unwrap(),
+            mother: mother.parse().unwrap(),
+            father: father.parse().unwrap(),
+            parentage_confirmed: true,
+            mother_affected: false,
+            father_affected: false,
+            phenotype: PhenotypeConsistency::HighlySpecific,
+        }
+    }
+
+    fn evidence(trio: Trio) -> Option<String> {
+        trio.evidence().map(|evidence| evidence.to_string())
+    }
+
+    #[test]
+    fn genotypes_give_the_origin() {
+        let cases = [
+            (("0/1", "0/0", "0/0"), TrioOrigin::DeNovo),
+            (("0/1", "0/1", "0/0"), TrioOrigin::Maternal),
+            (("1/1", "0/0", "1"), TrioOrigin::Paternal),
+            (("1/1", "0/1", "0/1"), TrioOrigin::Biparental),
+            (("0/0", "0/1", "0/0"), TrioOrigin::NotCarried),
+            (("0/1", "./.", "0/0"), TrioOrigin::Unknown),
+        ];
+        for ((proband, mother, father), origin) in cases {
+            assert_eq!(trio(proband, mother, father).origin(), origin, "{} {} {}", proband, mother, father);
+        }
+    }
+
+    #[test]
+    fn de_novo_is_ps2_with_confirmed_parentage_and_pm6_with_assumed() {
+        let confirmed = trio("het", "hom-ref", "hom-ref");
+        let assumed = Trio { parentage_confirmed: false, ..confirmed };
+        assert_eq!(evidence(confirmed).as_deref(), Some("PS2"));
+        assert_eq!(evidence(assumed).as_deref(), Some("PM6"));
+
+        let consistent = Trio { phenotype: PhenotypeConsistency::Consistent, ..confirmed };
+        assert_eq!(evidence(consistent).as_deref(), Some("PS2_Moderate"));
+        assert_eq!(evidence(Trio { parentage_confirmed: false, ..consistent }).as_deref(), Some("PM6_Supporting"));
+        assert_eq!(evidence(Trio { phenotype: PhenotypeConsistency::Inconsistent, ..confirmed }), None);
+    }
+
+    #[test]
+    fn affected_parent_without_the_variant_is_bs4() {
+        let de_novo = trio("het", "hom-ref", "hom-ref");
+        let mother_affected = Trio { mother_affected: true, ..de_novo };
+        assert!(mother_affected.non_segregating());
+        assert_eq!(mother_affected.de_novo_observations(), None);
+        assert_eq!(evidence(mother_affected).as_deref(), Some("BS4"));
+
+        let paternal = trio("het", "hom-ref", "het");
+        assert_eq!(evidence(Trio { mother_affected: true, ..paternal }).as_deref(), Some("BS4"));
+    }
+
+    #[test]
+    fn affected_parent_carrying_the_variant_gives_no_evidence() {
+        let maternal = Trio { mother_affected: true, ..trio("het", "het", "hom-ref") };
+        assert!(!maternal.non_segregating());
+        assert_eq!(evidence(maternal), None);
+        let unaffected = trio("het", "het", "hom-ref");
+        assert_eq!(evidence(unaffected), None);
+        let no_call = Trio { father_affected: true, ..trio("het", "hom-ref", "./.") };
+        assert_eq!(evidence(no_call), None);
+    }
+}
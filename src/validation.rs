@@ -0,0 +1,368 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::Category::{Benign, Pathogenic};
+use crate::EvidenceStrength::StandAlone;
+use crate::conflicts::check_conflicts;
+use crate::parser::parse_evidence_in;
+use crate::{check_inheritance, guidance, normalize_input, AcmgError, CodeRegistry, Code, CriteriaSpec, EvidenceSet, EvidenceStrength, Inheritance};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A validation rule. The ids are stable so they can be referred to from curation pipelines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Rule {
+    InvalidSyntax,
+    UnknownCode,
+    InvalidModifier,
+    EmptyInput,
+    DuplicateEvidence,
+    StandAloneConflict,
+    RepeatedCriterion,
+    DoubleCounting,
+    ContradictoryEvidence,
+    ImplausibleStrength,
+    DisabledCode,
+    InapplicableInheritance,
+    InheritanceRequirement,
+    Ba1Exception,
+    SplicePredictionConflict,
+    UnestablishedLofMechanism,
+    LimitedGeneValidity,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 17] = [
+        Rule::InvalidSyntax, Rule::UnknownCode, Rule::InvalidModifier, Rule::EmptyInput, Rule::DuplicateEvidence,
+        Rule::StandAloneConflict, Rule::InapplicableInheritance, Rule::RepeatedCriterion, Rule::DoubleCounting,
+        Rule::ContradictoryEvidence, Rule::ImplausibleStrength, Rule::DisabledCode, Rule::InheritanceRequirement,
+        Rule::Ba1Exception, Rule::SplicePredictionConflict, Rule::UnestablishedLofMechanism, Rule::LimitedGeneValidity,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::InvalidSyntax => "E001",
+            Rule::UnknownCode => "E002",
+            Rule::InvalidModifier => "E003",
+            Rule::EmptyInput => "E004",
+            Rule::DuplicateEvidence => "E005",
+            Rule::StandAloneConflict => "E006",
+            Rule::InapplicableInheritance => "E007",
+            Rule::RepeatedCriterion => "W001",
+            Rule::DoubleCounting => "W002",
+            Rule::ContradictoryEvidence => "W003",
+            Rule::ImplausibleStrength => "W004",
+            Rule::DisabledCode => "W005",
+            Rule::InheritanceRequirement => "W006",
+            Rule::Ba1Exception => "W007",
+            Rule::SplicePredictionConflict => "W008",
+            Rule::UnestablishedLofMechanism => "W009",
+            Rule::LimitedGeneValidity => "W010",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::InvalidSyntax => "invalid-syntax",
+            Rule::UnknownCode => "unknown-code",
+            Rule::InvalidModifier => "invalid-modifier",
+            Rule::EmptyInput => "empty-input",
+            Rule::DuplicateEvidence => "duplicate-evidence",
+            Rule::StandAloneConflict => "stand-alone-conflict",
+            Rule::RepeatedCriterion => "repeated-criterion",
+            Rule::DoubleCounting => "double-counting",
+            Rule::ContradictoryEvidence => "contradictory-evidence",
+            Rule::ImplausibleStrength => "implausible-strength",
+            Rule::DisabledCode => "disabled-code",
+            Rule::InapplicableInheritance => "inapplicable-inheritance",
+            Rule::InheritanceRequirement => "inheritance-requirement",
+            Rule::Ba1Exception => "ba1-exception",
+            Rule::SplicePredictionConflict => "splice-prediction-conflict",
+            Rule::UnestablishedLofMechanism => "unestablished-lof-mechanism",
+            Rule::LimitedGeneValidity => "limited-gene-validity",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Rule::RepeatedCriterion | Rule::DoubleCounting | Rule::ContradictoryEvidence | Rule::ImplausibleStrength | Rule::DisabledCode
+            | Rule::InheritanceRequirement | Rule::Ba1Exception | Rule::SplicePredictionConflict
+            | Rule::UnestablishedLofMechanism | Rule::LimitedGeneValidity => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// Parses a rule from either its id or its name, e.g. `W002` or `double-counting`.
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Rule, Self::Err> {
+        Rule::ALL.iter()
+            .find(|rule| rule.id().eq_ignore_ascii_case(s) || rule.name() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown rule {}", s))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Rule {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Rule {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Rule, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        id.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Rules are serialized as their id.
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for Rule {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        utoipa::openapi::ObjectBuilder::new()
+            .schema_type(utoipa::openapi::schema::Type::String)
+            .enum_values(Some(Rule::ALL.iter().map(Rule::id)))
+            .description(Some("The id of a validation rule, e.g. W002"))
+            .into()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::ToSchema for Rule {}
+
+/// A problem found with an evidence string, e.g. an unknown code or a contradictory combination of codes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub message: String,
+    /// The evidence codes the diagnostic refers to.
+    pub codes: Vec<String>,
+    /// The known code closest to an unknown one.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(rule: Rule, message: String, codes: Vec<String>) -> Diagnostic {
+        Diagnostic { rule, severity: rule.severity(), message, codes, suggestion: None }
+    }
+
+    pub fn with_suggestion(mut self, suggestion: Option<String>) -> Diagnostic {
+        self.suggestion = suggestion;
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}[{}]: {}", severity, self.rule.id(), self.message)
+    }
+}
+
+impl From<&AcmgError> for Rule {
+    fn from(error: &AcmgError) -> Rule {
+        match error {
+            AcmgError::EmptyInput => Rule::EmptyInput,
+            AcmgError::UnknownCode { .. } => Rule::UnknownCode,
+            AcmgError::InvalidModifier { .. } => Rule::InvalidModifier,
+            AcmgError::DuplicateCode(_) | AcmgError::RepeatedCriterion { .. } => Rule::DuplicateEvidence,
+            _ => Rule::InvalidSyntax,
+        }
+    }
+}
+
+/// Checks the syntax and semantics of an evidence string, reporting every problem found rather than stopping
+/// at the first one.
+pub fn validate(acmg_evidence: &str) -> Vec<Diagnostic> {
+    validate_evidence(acmg_evidence, None, &CodeRegistry::new())
+}
+
+/// Validates an evidence string as [`validate`] does, also checking the evidence against the mode of inheritance of
+/// the disorder with [`check_inheritance`].
+pub fn validate_with_inheritance(acmg_evidence: &str, inheritance: Inheritance) -> Vec<Diagnostic> {
+    validate_evidence(acmg_evidence, Some(inheritance), &CodeRegistry::new())
+}
+
+pub(crate) fn validate_evidence(acmg_evidence: &str, inheritance: Option<Inheritance>, registry: &CodeRegistry) -> Vec<Diagnostic> {
+    let tokens = normalize_input(acmg_evidence);
+    if tokens.clone().all(str::is_empty) {
+        let error = AcmgError::EmptyInput;
+        return vec![Diagnostic::new(Rule::from(&error), error.to_string(), vec![])];
+    }
+    let mut diagnostics = vec![];
+    let mut set = EvidenceSet::new();
+    for token in tokens {
+        let error = match parse_evidence_in(token, registry) {
+            Ok(evidence) if set.contains(&evidence) => AcmgError::DuplicateCode(evidence.to_string()),
+            Ok(evidence) => {
+                set.insert(evidence);
+                continue;
+            }
+            Err(error) => error,
+        };
+        let suggestion = match &error {
+            AcmgError::UnknownCode { suggestion, .. } => suggestion.clone(),
+            _ => None,
+        };
+        diagnostics.push(Diagnostic::new(Rule::from(&error), error.to_string(), vec![token.to_string()]).with_suggestion(suggestion));
+    }
+    diagnostics.extend(check_evidence(&set));
+    if let Some(inheritance) = inheritance {
+        diagnostics.extend(check_inheritance(&set, inheritance));
+    }
+    diagnostics
+}
+
+/// Semantic checks of a set of evidence which has already been parsed.
+pub fn check_evidence(evidence_set: &EvidenceSet) -> Vec<Diagnostic> {
+    check_evidence_with_spec(evidence_set, None)
+}
+
+/// Semantic checks of a set of evidence, with the strengths allowed by a criteria specification taking precedence
+/// over the plausible strengths of each code.
+pub fn check_evidence_with_spec(evidence_set: &EvidenceSet, spec: Option<&CriteriaSpec>) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    let mut by_code: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for evidence in evidence_set {
+        by_code.entry(evidence.evidence_code.to_string()).or_default().push(evidence.to_string());
+    }
+    for (code, applied) in by_code.into_iter().filter(|(_, applied)| applied.len() > 1) {
+        diagnostics.push(Diagnostic::new(Rule::RepeatedCriterion,
+                                         format!("{} is applied more than once: {}", code, applied.join(", ")),
+                                         applied));
+    }
+
+    let stand_alone: Vec<String> = evidence_set.iter()
+        .filter(|evidence| evidence.evidence_code.category == Benign && evidence.strength() == StandAlone)
+        .map(|evidence| evidence.to_string())
+        .collect();
+    let pathogenic: Vec<String> = evidence_set.iter()
+        .filter(|evidence| evidence.evidence_code.category == Pathogenic)
+        .map(|evidence| evidence.to_string())
+        .collect();
+    if !stand_alone.is_empty() && !pathogenic.is_empty() {
+        diagnostics.push(Diagnostic::new(Rule::StandAloneConflict,
+                                         format!("Stand-alone benign evidence {} contradicts pathogenic evidence {}", stand_alone.join(", "), pathogenic.join(", ")),
+                                         stand_alone.into_iter().chain(pathogenic).collect()));
+    }
+    diagnostics.extend(check_conflicts(evidence_set));
+    diagnostics.extend(check_strengths(evidence_set, spec));
+    diagnostics
+}
+
+/// Checks each code is applied at a strength it can plausibly reach, e.g. not BA1_Supporting or PP5_Strong,
+/// according to the [`guidance`] unless the specification allows otherwise.
+fn check_strengths(evidence_set: &EvidenceSet, spec: Option<&CriteriaSpec>) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for evidence in evidence_set {
+        let code: Option<Code> = evidence.evidence_code.to_string().parse().ok();
+        let allowed: &[EvidenceStrength] = code.and_then(|code| spec.and_then(|spec| spec.criterion(code)))
+            .map(|criterion| criterion.allowed_strengths.as_slice())
+            .filter(|allowed| !allowed.is_empty())
+            .or_else(|| evidence.evidence_code.allowed_strengths())
+            .or_else(|| code.map(|code| guidance(code).allowed_strengths))
+            .unwrap_or_default();
+        if !allowed.is_empty() && !allowed.contains(&evidence.strength()) {
+            let allowed: Vec<String> = allowed.iter().map(|strength| format!("{:?}", strength)).collect();
+            diagnostics.push(Diagnostic::new(Rule::ImplausibleStrength,
+                                             format!("{} is not a plausible strength for {}, which may be applied at {}", evidence, evidence.evidence_code, allowed.join(", ")),
+                                             vec![evidence.to_string()]));
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(diagnostics: Vec<Diagnostic>) -> Vec<&'static str> {
+        diagnostics.iter().map(|diagnostic| diagnostic.rule.id()).collect()
+    }
+
+    #[test]
+    fn each_rule_is_reported_for_the_evidence_breaking_it() {
+        let cases = [
+            ("PVS1, ((", vec!["E001"]),
+            ("PVS1, BP8", vec!["E002"]),
+            ("PVS1_Foo", vec!["E003"]),
+            (" , ", vec!["E004"]),
+            ("PVS1, PVS1", vec!["E005"]),
+            ("BA1, PVS1", vec!["E006"]),
+            ("PS3, PS3_Moderate", vec!["W001"]),
+            ("PVS1, PM4", vec!["W002"]),
+            ("PM2, BS1", vec!["W003"]),
+            ("PP5_Strong", vec!["W004"]),
+            ("PVS1, PM2, PP3, BP4, BA1", vec!["E006", "W002", "W003", "W003"]),
+            ("PVS1, PM2", vec![]),
+        ];
+        for (evidence, expected) in cases {
+            assert_eq!(rules(validate(evidence)), expected, "{}", evidence);
+        }
+    }
+
+    #[test]
+    fn stand_alone_conflicts_are_only_between_benign_and_pathogenic_evidence() {
+        assert_eq!(rules(validate("BA1, BS1")), Vec::<&str>::new());
+        assert_eq!(rules(validate("PVS1_StandAlone, PM2")), vec!["W004"]);
+        let diagnostics = validate("BA1, PM2_Supporting");
+        assert_eq!(diagnostics[0].rule, Rule::StandAloneConflict);
+        assert_eq!(diagnostics[0].codes, vec!["BA1", "PM2_Supporting"]);
+    }
+
+    #[test]
+    fn evidence_is_checked_against_the_mode_of_inheritance() {
+        let cases = [
+            ("PM3", Inheritance::AutosomalDominant, vec!["E007"]),
+            ("PM3", Inheritance::AutosomalRecessive, vec![]),
+            ("BS2", Inheritance::AutosomalRecessive, vec!["W006"]),
+            ("BS2", Inheritance::AutosomalDominant, vec![]),
+        ];
+        for (evidence, inheritance, expected) in cases {
+            assert_eq!(rules(validate_with_inheritance(evidence, inheritance)), expected, "{} {}", evidence, inheritance);
+        }
+    }
+
+    #[test]
+    fn unknown_codes_are_reported_with_the_closest_known_code() {
+        let diagnostics = validate("PVS1, BP8");
+        assert_eq!(diagnostics[0].suggestion.as_deref(), Some("BP7"));
+        assert!(diagnostics[0].is_error());
+    }
+
+    #[test]
+    fn rules_are_parsed_from_their_id_or_name() {
+        for rule in Rule::ALL {
+            assert_eq!(rule.id().parse::<Rule>(), Ok(rule));
+            assert_eq!(rule.id().to_lowercase().parse::<Rule>(), Ok(rule));
+            assert_eq!(rule.name().parse::<Rule>(), Ok(rule));
+            assert_eq!(rule.severity() == Severity::Error, rule.id().starts_with('E'), "{}", rule.id());
+        }
+        assert!("E999".parse::<Rule>().is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::AcmgError;
+
+static COORDINATES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?i:chr)?([0-9]{1,2}|[XYxy]|(?i:MT?))[-:](\d+)[-:]([ACGTNacgtn]+)[-:>]([ACGTNacgtn]+)$").unwrap());
+
+/// The reference genome assembly coordinates are given on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum GenomeBuild {
+    #[cfg_attr(feature = "serde", serde(rename = "GRCh37"))]
+    Grch37,
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "GRCh38"))]
+    Grch38,
+}
+
+impl GenomeBuild {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GenomeBuild::Grch37 => "GRCh37",
+            GenomeBuild::Grch38 => "GRCh38",
+        }
+    }
+}
+
+impl Display for GenomeBuild {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for GenomeBuild {
+    type Err = AcmgError;
+
+    fn from_str(s: &str) -> Result<GenomeBuild, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "grch37" | "hg19" | "b37" => Ok(GenomeBuild::Grch37),
+            "grch38" | "hg38" | "b38" => Ok(GenomeBuild::Grch38),
+            _ => Err(AcmgError::UnknownBuild(s.to_string())),
+        }
+    }
+}
+
+/// A variant given by VCF-style coordinates on a genome build, normalized so that equivalent representations
+/// compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GenomicVariant {
+    pub build: GenomeBuild,
+    /// The chromosome without a `chr` prefix, e.g. 7.
+    pub chromosome: String,
+    /// The 1-based position of the first reference base.
+    pub position: u64,
+    pub reference: String,
+    pub alternate: String,
+}
+
+impl GenomicVariant {
+    /// Creates a normalized variant, trimming bases shared by the alleles. Bases shared at the end are trimmed
+    /// first so indels are left-aligned within the given alleles, keeping the leading base VCF requires. Shifting
+    /// an indel further left through the surrounding reference sequence isn't possible without the reference
+    /// genome. The mitochondrial chromosome is named MT, as in gnomAD, whether given as M or chrM.
+    pub fn new(build: GenomeBuild, chromosome: &str, position: u64, reference: &str, alternate: &str) -> Result<GenomicVariant, AcmgError> {
+        let invalid = || AcmgError::InvalidVariant(format!("{}-{}-{}-{}", chromosome, position, reference, alternate));
+        let valid_allele = |allele: &str| !allele.is_empty() && allele.chars().all(|base| "ACGTN".contains(base));
+        let (mut reference, mut alternate) = (reference.to_uppercase(), alternate.to_uppercase());
+        if position == 0 || !valid_allele(&reference) || !valid_allele(&alternate) || reference == alternate {
+            return Err(invalid());
+        }
+        while reference.len() > 1 && alternate.len() > 1 && reference.ends_with(|base| alternate.ends_with(base)) {
+            reference.pop();
+            alternate.pop();
+        }
+        let mut position = position;
+        while reference.len() > 1 && alternate.len() > 1 && reference[..1] == alternate[..1] {
+            reference.remove(0);
+            alternate.remove(0);
+            position += 1;
+        }
+        let chromosome = chromosome.to_uppercase();
+        let chromosome = match chromosome.strip_prefix("CHR").unwrap_or(&chromosome) {
+            "M" => "MT".to_string(),
+            chromosome => chromosome.to_string(),
+        };
+        Ok(GenomicVariant { build, chromosome, position, reference, alternate })
+    }
+
+    /// Whether the variant inserts or deletes a multiple of three bases, e.g. `7-117559590-ATCT-A`.
+    pub fn is_in_frame_indel(&self) -> bool {
+        self.reference.len() != self.alternate.len() && self.reference.len().abs_diff(self.alternate.len()).is_multiple_of(3)
+    }
+
+    /// Parses coordinates such as `7-117559590-G-A` or `chr7:117559590:G:A` on the given build.
+    pub fn parse(s: &str, build: GenomeBuild) -> Result<GenomicVariant, AcmgError> {
+        let caps = COORDINATES.captures(s.trim()).ok_or_else(|| AcmgError::InvalidVariant(s.to_string()))?;
+        let position = caps[2].parse().map_err(|_| AcmgError::InvalidVariant(s.to_string()))?;
+        GenomicVariant::new(build, &caps[1], position, &caps[3], &caps[4])
+    }
+}
+
+/// Displayed as chromosome-position-reference-alternate, the variant id format used by gnomAD.
+impl Display for GenomicVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("{}-{}-{}-{}", self.chromosome, self.position, self.reference, self.alternate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(chromosome: &str, position: u64, reference: &str, alternate: &str) -> GenomicVariant {
+        GenomicVariant::new(GenomeBuild::Grch38, chromosome, position, reference, alternate).unwrap()
+    }
+
+    #[test]
+    fn shared_bases_are_trimmed_from_the_end_then_the_start() {
+        let cases = [
+            (("7", 100, "G", "A"), "7-100-G-A"),
+            (("7", 100, "GCA", "GTA"), "7-101-C-T"),
+            (("7", 100, "ATCTC", "ATC"), "7-100-ATC-A"),
+            (("7", 100, "AT", "ATCT"), "7-100-A-ATC"),
+            (("7", 100, "CAGT", "CAT"), "7-101-AG-A"),
+            (("7", 100, "TTAAG", "TTCCG"), "7-102-AA-CC"),
+            (("7", 100, "ACGTACGT", "ACGT"), "7-100-ACGTA-A"),
+        ];
+        for ((chromosome, position, reference, alternate), expected) in cases {
+            assert_eq!(variant(chromosome, position, reference, alternate).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn equivalent_representations_are_equal() {
+        let expected = variant("7", 117559590, "ATCT", "A");
+        for s in ["7-117559590-ATCT-A", "chr7:117559590:atct:a", "CHR7-117559590-ATCTG-AG", "7:117559590:ATCT>A"] {
+            assert_eq!(GenomicVariant::parse(s, GenomeBuild::Grch38).unwrap(), expected, "{}", s);
+        }
+        assert_ne!(GenomicVariant::parse("7-117559590-ATCT-A", GenomeBuild::Grch37).unwrap(), expected);
+        assert!(expected.is_in_frame_indel());
+    }
+
+    #[test]
+    fn mitochondrial_variants_are_on_chromosome_mt() {
+        for s in ["M-3243-A-G", "chrM-3243-A-G", "MT-3243-A-G", "chrMT:3243:A:G", "m-3243-a-g"] {
+            assert_eq!(GenomicVariant::parse(s, GenomeBuild::Grch38).unwrap().to_string(), "MT-3243-A-G", "{}", s);
+        }
+    }
+
+    #[test]
+    fn invalid_variants_are_rejected() {
+        for s in ["7-0-G-A", "7-100-G-G", "7-100-G-", "7-100-G-R", "7-G-A", "GRCh38:7-100-G-A"] {
+            assert!(GenomicVariant::parse(s, GenomeBuild::Grch38).is_err(), "{}", s);
+        }
+        assert!(GenomicVariant::new(GenomeBuild::Grch38, "7", 100, "GA", "GA").is_err());
+    }
+}
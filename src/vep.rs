@@ -0,0 +1,159 @@
+use std::io::BufRead;
+
+use crate::{AcmgError, Code, Evidence, EvidenceSet, GeneConstraint, GenomeBuild, GenomicVariant};
+
+/// The consequences of a null variant, suggesting PVS1 in a gene where loss of function causes disease.
+const NULL_CONSEQUENCES: [&str; 4] = ["stop_gained", "frameshift_variant", "splice_donor_variant", "splice_acceptor_variant"];
+/// The consequences changing the length of the protein, suggesting PM4.
+const LENGTH_CHANGING_CONSEQUENCES: [&str; 3] = ["inframe_insertion", "inframe_deletion", "stop_lost"];
+
+/// One VEP CSQ annotation of an allele, for a transcript or other feature.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VepAnnotation {
+    /// The Sequence Ontology consequences, e.g. stop_gained.
+    pub consequences: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub gene: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub feature: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub hgvsc: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub hgvsp: Option<String>,
+    /// Whether VEP flagged the annotation with `--pick` or as the canonical transcript.
+    pub picked: bool,
+}
+
+impl VepAnnotation {
+    pub fn has_consequence(&self, consequence: &str) -> bool {
+        self.consequences.iter().any(|c| c == consequence)
+    }
+}
+
+/// A variant of a VEP annotated VCF with the annotations of its allele.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VepRecord {
+    pub variant: GenomicVariant,
+    pub annotations: Vec<VepAnnotation>,
+}
+
+impl VepRecord {
+    /// The annotation codes are suggested from, the one VEP picked or of the canonical transcript, otherwise the first.
+    pub fn annotation(&self) -> Option<&VepAnnotation> {
+        self.annotations.iter().find(|annotation| annotation.picked).or_else(|| self.annotations.first())
+    }
+}
+
+/// Reads the variants of a VCF annotated by VEP with the CSQ INFO field, whose fields are named in its `##INFO` header,
+/// one record per alternate allele. Symbolic alleles are skipped.
+pub fn read_vep_vcf(reader: impl BufRead, build: GenomeBuild) -> Result<Vec<VepRecord>, AcmgError> {
+    let mut format: Option<Vec<String>> = None;
+    let mut records = vec![];
+    for (number, line) in reader.lines().enumerate() {
+        let line = line.map_err(|error| AcmgError::InvalidVcf(error.to_string()))?;
+        let invalid = |reason: &str| AcmgError::InvalidVcf(format!("line {}: {}", number + 1, reason));
+        if let Some(header) = line.strip_prefix("##INFO=<ID=CSQ,") {
+            let fields = header.split_once("Format: ").map(|(_, fields)| fields.trim_end_matches('>').trim_end_matches('"'))
+                .ok_or_else(|| invalid("expected the CSQ header to describe its format"))?;
+            format = Some(fields.split('|').map(str::to_string).collect());
+            continue;
+        }
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+        let format = format.as_ref().ok_or_else(|| invalid("expected a CSQ header before the records, is the VCF annotated by VEP?"))?;
+        let fields: Vec<&str> = line.splitn(9, '\t').collect();
+        if fields.len() < 8 {
+            return Err(invalid("expected at least 8 columns"));
+        }
+        let position: u64 = fields[1].parse().map_err(|_| invalid("invalid position"))?;
+        let alternates: Vec<&str> = fields[4].split(',').collect();
+        let annotations: Vec<Vec<&str>> = fields[7].split(';')
+            .find_map(|entry| entry.strip_prefix("CSQ="))
+            .map(|csq| csq.split(',').map(|annotation| annotation.split('|').collect()).collect())
+            .unwrap_or_default();
+        for (allele_number, alternate) in alternates.iter().enumerate() {
+            let Ok(variant) = GenomicVariant::new(build, fields[0], position, fields[3], alternate) else {
+                continue;
+            };
+            let allele = vep_allele(fields[3], &alternates, alternate);
+            let annotations = annotations.iter()
+                .filter(|annotation| match csq_value(format, annotation, "ALLELE_NUM") {
+                    Some(number) => number == (allele_number + 1).to_string(),
+                    None => csq_value(format, annotation, "Allele") == Some(allele.as_str()),
+                })
+                .map(|annotation| VepAnnotation {
+                    consequences: csq_value(format, annotation, "Consequence").map(|consequences| consequences.split('&').map(str::to_string).collect()).unwrap_or_default(),
+                    gene: csq_value(format, annotation, "SYMBOL").map(str::to_string),
+                    feature: csq_value(format, annotation, "Feature").map(str::to_string),
+                    hgvsc: csq_value(format, annotation, "HGVSc").map(str::to_string),
+                    hgvsp: csq_value(format, annotation, "HGVSp").map(str::to_string),
+                    picked: csq_value(format, annotation, "PICK") == Some("1") || csq_value(format, annotation, "CANONICAL") == Some("YES"),
+                })
+                .collect();
+            records.push(VepRecord { variant, annotations });
+        }
+    }
+    Ok(records)
+}
+
+/// The value of a field of a CSQ annotation by its name in the format, unless empty.
+fn csq_value<'a>(format: &[String], annotation: &[&'a str], name: &str) -> Option<&'a str> {
+    format.iter().position(|field| field == name)
+        .and_then(|index| annotation.get(index).copied())
+        .filter(|value| !value.is_empty())
+}
+
+/// The allele as VEP writes it, without the first base if all the alleles share it, or `-` for a deletion.
+fn vep_allele(reference: &str, alternates: &[&str], alternate: &str) -> String {
+    let first = reference.chars().next();
+    if alternates.iter().all(|alternate| alternate.chars().next() == first) {
+        let allele = &alternate[1..];
+        return if allele.is_empty() { "-".to_string() } else { allele.to_string() };
+    }
+    alternate.to_string()
+}
+
+/// A code suggested by the consequence of a variant, for a curator to review.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConsequenceSuggestion {
+    pub evidence: Evidence,
+    pub reason: String,
+}
+
+/// Suggests PVS1 for a null variant in a gene where loss of function is a mechanism of disease, PM4 for an in-frame
+/// indel or stop-loss, and for a missense variant PP2 in a gene constrained for missense variation or BP1 in an
+/// unconstrained gene where loss of function is the mechanism, see [`GeneConstraint`].
+pub fn suggest_from_consequence(annotation: &VepAnnotation, constraint: &GeneConstraint) -> Vec<ConsequenceSuggestion> {
+    let mut suggestions = vec![];
+    let gene = annotation.gene.as_deref().unwrap_or_default();
+    let lof = constraint.lof_constrained(gene).filter(|lof| lof.constrained);
+    let suggest = |code: Code, reason: String| ConsequenceSuggestion { evidence: Evidence::from(code), reason };
+    if let Some(consequence) = NULL_CONSEQUENCES.iter().find(|consequence| annotation.has_consequence(consequence)) {
+        if let Some(lof) = lof {
+            suggestions.push(suggest(Code::PVS1, format!("{} in {}, {}", consequence, gene, lof)));
+        }
+    } else if let Some(consequence) = LENGTH_CHANGING_CONSEQUENCES.iter().find(|consequence| annotation.has_consequence(consequence)) {
+        suggestions.push(suggest(Code::PM4, format!("{} changes the length of the protein", consequence)));
+    } else if annotation.has_consequence("missense_variant") {
+        match (constraint.missense_constrained(gene), lof) {
+            (Some(missense), _) if missense.constrained => {
+                suggestions.push(suggest(Code::PP2, format!("missense_variant in {}, {}", gene, missense)));
+            }
+            (Some(missense), Some(lof)) => {
+                suggestions.push(suggest(Code::BP1, format!("missense_variant in {}, {} and {}", gene, lof, missense)));
+            }
+            _ => {}
+        }
+    }
+    suggestions
+}
+
+/// The suggested codes as an evidence string, e.g. `PVS1`, for a curator to complete.
+pub fn suggested_evidence(suggestions: &[ConsequenceSuggestion]) -> String {
+    let evidence: EvidenceSet = suggestions.iter().map(|suggestion| suggestion.evidence.clone()).collect();
+    evidence.iter().map(ToString::to_string).collect::<Vec<_>>().join(",")
+}
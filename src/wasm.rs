@@ -0,0 +1,101 @@
+//! The JavaScript API of the WebAssembly build, for classifying evidence client-side.
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::bayes::{EXPONENTIAL_PROGRESSION, ODDS_PATH_VERY_STRONG, PRIOR_PROB};
+use crate::{AcmgAssessment, AcmgError, AlleleFrequency, BayesianModel, Classifier, CriteriaSpec, DuplicatePolicy, FrequencyCriteria, GenomeBuild, GenomicVariant, Guideline, HgvsVariant, Predictor};
+
+/// The options of `classify`, any of which may be left out.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ClassifyOptions {
+    /// e.g. `"acmg2015"`, defaulting to the points-based guideline.
+    guideline: Option<String>,
+    /// `"points"` or `"posterior"`.
+    classifier: Option<String>,
+    /// e.g. `"keep-strongest"`.
+    duplicates: Option<String>,
+    strict: bool,
+    /// The built-in criteria specification for a gene, e.g. `"MYH7"`.
+    gene: Option<String>,
+    /// A criteria specification, as in its JSON.
+    spec: Option<serde_json::Value>,
+    prior: Option<f64>,
+    odds_vs: Option<f64>,
+    exponent: Option<f64>,
+    revel: Option<f64>,
+    cadd: Option<f64>,
+    bayesdel: Option<f64>,
+    alphamissense: Option<f64>,
+    /// The allele frequency, applying BA1, BS1 or PM2 at the specification's or SVI thresholds.
+    af: Option<f64>,
+    /// VCF coordinates, e.g. `"7-117559590-G-A"`.
+    variant: Option<String>,
+    build: Option<String>,
+    hgvs: Option<String>,
+}
+
+impl ClassifyOptions {
+    fn assess(&self, acmg_evidence: &str) -> Result<AcmgAssessment, AcmgError> {
+        let guideline = self.guideline.as_deref().map(str::parse::<Guideline>).transpose()?.unwrap_or_default();
+        let spec = match (&self.spec, &self.gene) {
+            (Some(spec), _) => Some(CriteriaSpec::from_json(&spec.to_string())?),
+            (None, Some(gene)) => Some(CriteriaSpec::builtin(gene)?),
+            (None, None) => guideline.spec_name().map(CriteriaSpec::builtin).transpose()?,
+        };
+        let mut assessment = match self.duplicates.as_deref() {
+            Some(policy) => AcmgAssessment::parse_with_policy(acmg_evidence, policy.parse::<DuplicatePolicy>()?)?,
+            None => acmg_evidence.parse::<AcmgAssessment>()?,
+        };
+        let model = BayesianModel::new(
+            self.prior.unwrap_or(PRIOR_PROB),
+            self.odds_vs.unwrap_or(ODDS_PATH_VERY_STRONG),
+            self.exponent.unwrap_or(EXPONENTIAL_PROGRESSION),
+        )?;
+        assessment = assessment
+            .with_guideline(guideline)
+            .with_classifier(self.classifier.as_deref().map(str::parse::<Classifier>).transpose()?.unwrap_or_default())
+            .with_model(model);
+        let predictor_score = [
+            (Predictor::Revel, self.revel),
+            (Predictor::Cadd, self.cadd),
+            (Predictor::BayesDel, self.bayesdel),
+            (Predictor::AlphaMissense, self.alphamissense),
+        ].into_iter().find_map(|(predictor, score)| score.map(|score| (predictor, score)));
+        if let Some((predictor, score)) = predictor_score {
+            assessment = assessment.with_predictor_score(predictor, score)?;
+        }
+        if let Some(af) = self.af {
+            let criteria = match &spec {
+                Some(spec) => FrequencyCriteria::default().with_thresholds(&spec.frequency_thresholds),
+                None => FrequencyCriteria::default(),
+            };
+            assessment = assessment.with_frequency(AlleleFrequency { af, ..AlleleFrequency::default() }, &criteria)?;
+        }
+        if let Some(spec) = &spec {
+            assessment = assessment.apply_spec(spec)?;
+        }
+        if let Some(variant) = &self.variant {
+            let build = self.build.as_deref().map(str::parse::<GenomeBuild>).transpose()?.unwrap_or(GenomeBuild::Grch38);
+            assessment = assessment.with_variant(GenomicVariant::parse(variant, build)?);
+        }
+        if let Some(hgvs) = &self.hgvs {
+            assessment = assessment.with_hgvs(hgvs.parse::<HgvsVariant>()?);
+        }
+        if self.strict { assessment.strict() } else { Ok(assessment) }
+    }
+}
+
+/// Classifies an evidence string, e.g. `classify("PVS1, PM2_Supporting", { guideline: "acmg2015" })`, returning the
+/// assessment as an object as in the JSON output of the CLI. Throws an `Error` for invalid evidence or options.
+#[wasm_bindgen]
+pub fn classify(evidence: &str, options: JsValue) -> Result<JsValue, JsError> {
+    let options: ClassifyOptions = if options.is_undefined() || options.is_null() {
+        ClassifyOptions::default()
+    } else {
+        serde_wasm_bindgen::from_value(options).map_err(|error| JsError::new(&format!("Invalid options: {}", error)))?
+    };
+    let result = options.assess(evidence).map_err(|error| JsError::new(&error.to_string()))?.result();
+    result.serialize(&serde_wasm_bindgen::Serializer::json_compatible()).map_err(|error| JsError::new(&error.to_string()))
+}
@@ -0,0 +1,260 @@
+use crate::Category::{self, Benign, Pathogenic};
+use crate::EvidenceStrength::{self, Moderate, StandAlone, Strong, Supporting, VeryStrong};
+use crate::{AcmgAssessment, AcmgClassification, Code, Evidence, EvidenceSet};
+
+/// The strengths pathogenic evidence can be added at, weakest first.
+const PATHOGENIC_STRENGTHS: [EvidenceStrength; 4] = [Supporting, Moderate, Strong, VeryStrong];
+
+/// The default strengths of the benign codes, weakest first.
+const BENIGN_STRENGTHS: [EvidenceStrength; 3] = [Supporting, Strong, StandAlone];
+
+/// The most criteria considered in addition to the applied evidence.
+pub const MAX_ADDITIONS: usize = 4;
+
+/// How the classification of an assessment would change with more evidence or without each criterion, for
+/// prioritizing follow-up.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WhatIf {
+    pub classification: AcmgClassification,
+    pub points: i32,
+    /// What it would take to reach Likely pathogenic and Pathogenic.
+    pub targets: Vec<WhatIfTarget>,
+    /// The applied criteria whose removal alone would change the classification.
+    pub removals: Vec<WhatIfRemoval>,
+}
+
+/// The smallest combinations of additional pathogenic evidence which would reach a classification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WhatIfTarget {
+    pub target: AcmgClassification,
+    /// True if the evidence already reaches the target, so no combinations are needed.
+    pub reached: bool,
+    /// The combinations, see [`evidence_combinations`]. Empty if the target can't be reached with up to
+    /// [`MAX_ADDITIONS`] criteria.
+    pub combinations: Vec<EvidenceCombination>,
+}
+
+/// Criteria to add, by strength, and the resulting score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EvidenceCombination {
+    /// The strengths of the criteria, strongest first.
+    pub strengths: Vec<EvidenceStrength>,
+    pub points: i32,
+    pub classification: AcmgClassification,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WhatIfRemoval {
+    pub evidence: Evidence,
+    pub classification: AcmgClassification,
+}
+
+/// Explores the evidence of an assessment with its guideline, classifier and model. Additional evidence is
+/// counted by strength alone, as the guidelines combine it.
+pub fn what_if(assessment: &AcmgAssessment) -> WhatIf {
+    let classification = assessment.classify();
+    let targets = [AcmgClassification::LikelyPathogenic, AcmgClassification::Pathogenic].into_iter()
+        .map(|target| {
+            let reached = rank(classification) >= rank(target);
+            let combinations = if reached { vec![] } else { evidence_combinations(assessment, target, MAX_ADDITIONS) };
+            WhatIfTarget { target, reached, combinations }
+        })
+        .collect();
+
+    let applied = assessment.evidence_set();
+    let removals = applied.iter()
+        .filter_map(|removed| {
+            let remaining = applied.iter().filter(|evidence| *evidence != removed).cloned().collect();
+            let without = reclassify(assessment, remaining).classify();
+            (without != classification).then(|| WhatIfRemoval { evidence: removed.clone(), classification: without })
+        })
+        .collect();
+
+    WhatIf { classification, points: assessment.points(), targets, removals }
+}
+
+/// The smallest combinations of up to `max_criteria` more criteria which would bring an assessment to at least the
+/// target classification, or at most for benign targets, fewest criteria first. Pathogenic evidence is added for
+/// pathogenic targets and benign evidence otherwise, counted by strength alone as the guidelines combine it. No
+/// criterion of a combination could be removed or weakened without falling short.
+pub fn evidence_combinations(assessment: &AcmgAssessment, target: AcmgClassification, max_criteria: usize) -> Vec<EvidenceCombination> {
+    let combiner = Combiner::new(assessment, target);
+    let reaches = |combination: &[EvidenceStrength]| {
+        combiner.classify(combination).is_some_and(|classification| match combiner.category {
+            Pathogenic => rank(classification) >= rank(target),
+            Benign => rank(classification) <= rank(target),
+        })
+    };
+    combinations(combiner.strengths, max_criteria).into_iter()
+        .filter(|combination| reaches(combination) && weakened(combiner.strengths, combination).iter().all(|weaker| !reaches(weaker)))
+        .map(|combination| combiner.combination(combination))
+        .collect()
+}
+
+/// Every combination of up to `max_criteria` more criteria, as [`evidence_combinations`], with which the assessment
+/// would be classified as the target.
+pub fn classified_combinations(assessment: &AcmgAssessment, target: AcmgClassification, max_criteria: usize) -> Vec<EvidenceCombination> {
+    let combiner = Combiner::new(assessment, target);
+    combinations(combiner.strengths, max_criteria).into_iter()
+        .filter(|combination| combiner.classify(combination) == Some(target))
+        .map(|combination| combiner.combination(combination))
+        .collect()
+}
+
+/// Adds evidence by strength to an assessment, using distinct codes of the category not already applied.
+struct Combiner<'a> {
+    assessment: &'a AcmgAssessment,
+    category: Category,
+    strengths: &'static [EvidenceStrength],
+    unused: Vec<Code>,
+}
+
+impl Combiner<'_> {
+    fn new(assessment: &AcmgAssessment, target: AcmgClassification) -> Combiner<'_> {
+        let (category, strengths): (Category, &[EvidenceStrength]) = if rank(target) > rank(AcmgClassification::UncertainSignificance) {
+            (Pathogenic, &PATHOGENIC_STRENGTHS)
+        } else {
+            (Benign, &BENIGN_STRENGTHS)
+        };
+        let applied = assessment.evidence_set();
+        let unused = Code::ALL.into_iter()
+            .filter(|code| code.evidence_code().category == category)
+            .filter(|code| applied.iter().all(|evidence| evidence.evidence_code != code.evidence_code()))
+            .collect();
+        Combiner { assessment, category, strengths, unused }
+    }
+
+    /// The assessment with the combination added, unless there aren't enough unused codes.
+    fn combine(&self, combination: &[EvidenceStrength]) -> Option<AcmgAssessment> {
+        if combination.len() > self.unused.len() {
+            return None;
+        }
+        let mut evidence = self.assessment.evidence_set().clone();
+        evidence.extend(combination.iter().zip(&self.unused).map(|(strength, code)| Evidence::at_strength(*code, *strength)));
+        Some(reclassify(self.assessment, evidence))
+    }
+
+    fn classify(&self, combination: &[EvidenceStrength]) -> Option<AcmgClassification> {
+        self.combine(combination).map(|assessment| assessment.classify())
+    }
+
+    fn combination(&self, mut strengths: Vec<EvidenceStrength>) -> EvidenceCombination {
+        let assessment = self.combine(&strengths).expect("combinations are of unused codes");
+        strengths.reverse();
+        EvidenceCombination { strengths, points: assessment.points(), classification: assessment.classify() }
+    }
+}
+
+/// The evidence classified as the assessment is.
+fn reclassify(assessment: &AcmgAssessment, evidence: EvidenceSet) -> AcmgAssessment {
+    AcmgAssessment::new(evidence)
+        .with_guideline(assessment.guideline())
+        .with_classifier(assessment.classifier())
+        .with_model(*assessment.model())
+}
+
+/// How pathogenic a classification is, Benign being the least.
+fn rank(classification: AcmgClassification) -> usize {
+    AcmgClassification::ALL.len() - AcmgClassification::ALL.iter().position(|other| *other == classification).unwrap_or_default()
+}
+
+/// Every combination of one to `max_criteria` of the strengths, weakest first within each, fewest criteria first.
+fn combinations(strengths: &[EvidenceStrength], max_criteria: usize) -> Vec<Vec<EvidenceStrength>> {
+    let mut combinations: Vec<Vec<EvidenceStrength>> = vec![vec![]];
+    let mut all = vec![];
+    for _ in 0..max_criteria {
+        combinations = combinations.iter()
+            .flat_map(|combination| {
+                let from = combination.last().map_or(0, |last| strengths.iter().position(|strength| strength == last).unwrap_or_default());
+                strengths[from..].iter().map(move |strength| {
+                    let mut longer = combination.clone();
+                    longer.push(*strength);
+                    longer
+                })
+            })
+            .collect();
+        all.extend(combinations.iter().cloned());
+    }
+    all
+}
+
+/// The combination with each criterion in turn weakened by a step, or removed if it's the weakest strength.
+fn weakened(strengths: &[EvidenceStrength], combination: &[EvidenceStrength]) -> Vec<Vec<EvidenceStrength>> {
+    (0..combination.len())
+        .map(|index| {
+            let mut weaker = combination.to_vec();
+            match strengths.iter().position(|strength| *strength == weaker[index]) {
+                Some(0) | None => {
+                    weaker.remove(index);
+                }
+                Some(position) => weaker[index] = strengths[position - 1],
+            }
+            weaker
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Guideline;
+
+    const PATHOGENIC_CODES: [Code; 4] = [Code::PS1, Code::PS2, Code::PM1, Code::PM4];
+    const BENIGN_CODES: [Code; 4] = [Code::BS1, Code::BS2, Code::BP1, Code::BP3];
+
+    fn is_benign(target: AcmgClassification) -> bool {
+        rank(target) < rank(AcmgClassification::UncertainSignificance)
+    }
+
+    /// The assessment's evidence string with the combination written out as codes, classified afresh.
+    fn with_combination(assessment: &AcmgAssessment, target: AcmgClassification, combination: &EvidenceCombination) -> AcmgAssessment {
+        let codes = if is_benign(target) { BENIGN_CODES } else { PATHOGENIC_CODES };
+        let mut evidence = vec![assessment.evidence_set().to_string()];
+        evidence.extend(combination.strengths.iter().zip(codes).map(|(strength, code)| Evidence::at_strength(code, *strength).to_string()));
+        let evidence: AcmgAssessment = evidence.join(", ").parse().unwrap();
+        evidence.with_guideline(assessment.guideline())
+    }
+
+    #[test]
+    fn suggested_evidence_reaches_each_target() {
+        let cases = [
+            ("PM2_Supporting", Guideline::Points2020, AcmgClassification::LikelyPathogenic),
+            ("PM2_Supporting, PP3", Guideline::Points2020, AcmgClassification::Pathogenic),
+            ("PVS1", Guideline::Acmg2015, AcmgClassification::Pathogenic),
+            ("PM2_Supporting", Guideline::Points2020, AcmgClassification::LikelyBenign),
+            ("PM2_Supporting", Guideline::Points2020, AcmgClassification::Benign),
+        ];
+        for (evidence, guideline, target) in cases {
+            let assessment = evidence.parse::<AcmgAssessment>().unwrap().with_guideline(guideline);
+            let combinations = evidence_combinations(&assessment, target, MAX_ADDITIONS);
+            assert!(!combinations.is_empty(), "{} to {:?}", evidence, target);
+            for combination in &combinations {
+                let reached = with_combination(&assessment, target, combination);
+                let reaches = match is_benign(target) {
+                    true => rank(reached.classify()) <= rank(target),
+                    false => rank(reached.classify()) >= rank(target),
+                };
+                assert!(reaches, "{} with {:?} to {:?} is {:?}", evidence, combination.strengths, target, reached.classify());
+                assert_eq!((reached.points(), reached.classify()), (combination.points, combination.classification), "{} with {:?}", evidence, combination.strengths);
+            }
+        }
+    }
+
+    #[test]
+    fn what_if_targets_are_reached_by_their_combinations() {
+        let assessment: AcmgAssessment = "PVS1".parse().unwrap();
+        let what_if = what_if(&assessment);
+        let targets: Vec<(AcmgClassification, bool)> = what_if.targets.iter().map(|target| (target.target, target.reached)).collect();
+        assert_eq!(targets, vec![(AcmgClassification::LikelyPathogenic, true), (AcmgClassification::Pathogenic, false)]);
+        let pathogenic = &what_if.targets[1].combinations;
+        let strengths: Vec<&[EvidenceStrength]> = pathogenic.iter().map(|combination| combination.strengths.as_slice()).collect();
+        assert_eq!(strengths, vec![&[Moderate][..], &[Supporting, Supporting][..]]);
+        for combination in pathogenic {
+            assert_eq!(with_combination(&assessment, AcmgClassification::Pathogenic, combination).classify(), AcmgClassification::Pathogenic, "{:?}", combination.strengths);
+        }
+    }
+}